@@ -0,0 +1,31 @@
+//! Cancelling slow work
+//!
+//! Building documentation and searching every crate can take tens of seconds. A [`CancelToken`]
+//! shared between whoever asked for that work and the [`Navigator`](crate::Navigator) doing it
+//! lets the work stop early: builds in progress are killed, and crates not yet loaded or indexed
+//! are skipped. Work stopped this way isn't remembered as a failure, so it's tried again the
+//! next time it's asked for.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A flag, shared between clones, for stopping work in progress
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Ask work watching this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether work should stop
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Let work go ahead again, before starting something new
+    pub fn clear(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,295 @@
+//! Platform and feature availability from `#[doc(cfg(…))]`
+//!
+//! Items limited to some platforms or features carry a `#[doc(cfg(…))]` attribute in rustdoc
+//! JSON, written by hand or added by `doc_auto_cfg` (as on docs.rs). This parses those
+//! predicates, describes them the way rustdoc's HTML does ("Available on Linux only"), and
+//! evaluates them against the cfg options of a target.
+
+use rustc_hash::FxHashSet;
+use std::fmt::{self, Display, Formatter};
+#[cfg(not(target_family = "wasm"))]
+use std::process::Command;
+
+/// A cfg predicate, such as `all(unix, feature = "fs")`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cfg {
+    /// A bare option, such as `unix` or `test`
+    Name(String),
+    /// A key-value option, such as `target_os = "linux"` or `feature = "serde"`
+    KeyValue(String, String),
+    All(Vec<Cfg>),
+    Any(Vec<Cfg>),
+    Not(Box<Cfg>),
+}
+
+impl Cfg {
+    /// Parse a cfg predicate, such as `all(unix, feature = "fs")`
+    pub fn parse(predicate: &str) -> Option<Self> {
+        let mut parser = Parser { rest: predicate };
+        let cfg = parser.predicate()?;
+        parser.rest.trim().is_empty().then_some(cfg)
+    }
+
+    /// The predicate of a `#[doc(cfg(…))]` attribute, as rustdoc JSON renders attributes
+    pub fn from_attribute(attribute: &str) -> Option<Self> {
+        const PREFIX: &str = "doc(cfg(";
+        let start = attribute.find(PREFIX)? + PREFIX.len();
+        let mut parser = Parser {
+            rest: &attribute[start..],
+        };
+        let mut cfgs = parser.list()?;
+        match cfgs.len() {
+            0 => None,
+            1 => cfgs.pop(),
+            _ => Some(Self::All(cfgs)),
+        }
+    }
+
+    fn is_compound(&self) -> bool {
+        matches!(self, Self::All(cfgs) | Self::Any(cfgs) if cfgs.len() > 1)
+    }
+}
+
+/// Describes the predicate in words, for "Available on … only"
+impl Display for Cfg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(name) => match name.as_str() {
+                "unix" => f.write_str("Unix"),
+                "windows" => f.write_str("Windows"),
+                "debug_assertions" => f.write_str("debug-assertions enabled"),
+                "test" => f.write_str("testing"),
+                "doc" => f.write_str("documentation"),
+                name => f.write_str(name),
+            },
+            Self::KeyValue(key, value) => match (key.as_str(), value.as_str()) {
+                ("feature", feature) => write!(f, "crate feature {feature}"),
+                ("target_os" | "target_family", value) => f.write_str(match value {
+                    "linux" => "Linux",
+                    "macos" => "macOS",
+                    "ios" => "iOS",
+                    "android" => "Android",
+                    "windows" => "Windows",
+                    "unix" => "Unix",
+                    "freebsd" => "FreeBSD",
+                    "netbsd" => "NetBSD",
+                    "openbsd" => "OpenBSD",
+                    "wasi" => "WASI",
+                    "wasm" => "WebAssembly",
+                    "none" => "bare metal",
+                    other => other,
+                }),
+                ("target_arch", arch) => f.write_str(match arch {
+                    "x86_64" => "x86-64",
+                    "aarch64" => "AArch64",
+                    "arm" => "ARM",
+                    "wasm32" => "WebAssembly",
+                    "riscv64" => "RISC-V RV64",
+                    other => other,
+                }),
+                ("target_env", "gnu") => f.write_str("GNU"),
+                ("target_env", "msvc") => f.write_str("MSVC"),
+                ("target_pointer_width", width) => write!(f, "{width}-bit"),
+                ("target_endian", endian) => write!(f, "{endian}-endian"),
+                (key, value) => write!(f, "{key}=\"{value}\""),
+            },
+            Self::All(cfgs) => write_joined(f, cfgs, " and "),
+            Self::Any(cfgs) => write_joined(f, cfgs, " or "),
+            Self::Not(cfg) if cfg.is_compound() => write!(f, "non-({cfg})"),
+            Self::Not(cfg) => write!(f, "non-{cfg}"),
+        }
+    }
+}
+
+fn write_joined(f: &mut Formatter<'_>, cfgs: &[Cfg], separator: &str) -> fmt::Result {
+    for (n, cfg) in cfgs.iter().enumerate() {
+        if n != 0 {
+            f.write_str(separator)?;
+        }
+        if cfg.is_compound() {
+            write!(f, "({cfg})")?;
+        } else {
+            write!(f, "{cfg}")?;
+        }
+    }
+    Ok(())
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn eat(&mut self, token: char) -> bool {
+        self.rest = self.rest.trim_start();
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn ident(&mut self) -> Option<&'a str> {
+        self.rest = self.rest.trim_start();
+        let end = self
+            .rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(self.rest.len());
+        if end == 0 {
+            return None;
+        }
+        let (ident, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(ident)
+    }
+
+    fn string(&mut self) -> Option<&'a str> {
+        if !self.eat('"') {
+            return None;
+        }
+        let (string, rest) = self.rest.split_once('"')?;
+        self.rest = rest;
+        Some(string)
+    }
+
+    fn predicate(&mut self) -> Option<Cfg> {
+        let name = self.ident()?;
+        if self.eat('=') {
+            return Some(Cfg::KeyValue(name.to_string(), self.string()?.to_string()));
+        }
+        if !self.eat('(') {
+            return Some(Cfg::Name(name.to_string()));
+        }
+        let mut cfgs = self.list()?;
+        match name {
+            "all" => Some(Cfg::All(cfgs)),
+            "any" => Some(Cfg::Any(cfgs)),
+            "not" if cfgs.len() == 1 => cfgs.pop().map(|cfg| Cfg::Not(Box::new(cfg))),
+            _ => None,
+        }
+    }
+
+    /// Comma-separated predicates, up to and including the closing parenthesis
+    fn list(&mut self) -> Option<Vec<Cfg>> {
+        let mut cfgs = vec![];
+        loop {
+            if self.eat(')') {
+                return Some(cfgs);
+            }
+            cfgs.push(self.predicate()?);
+            if !self.eat(',') {
+                return self.eat(')').then_some(cfgs);
+            }
+        }
+    }
+}
+
+/// The cfg options set for a target, as printed by `rustc --print cfg`
+#[derive(Debug, Clone, Default)]
+pub struct TargetCfg {
+    options: FxHashSet<(String, Option<String>)>,
+}
+
+impl TargetCfg {
+    /// Ask rustc for the cfg options of a target triple, or of the host
+    #[cfg(not(target_family = "wasm"))]
+    pub fn detect(target: Option<&str>) -> Option<Self> {
+        let mut command = Command::new("rustc");
+        command.args(["--print", "cfg"]);
+        if let Some(target) = target {
+            command.args(["--target", target]);
+        }
+        let output = command.output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(Self::parse(std::str::from_utf8(&output.stdout).ok()?))
+    }
+
+    /// Parse `rustc --print cfg` output: one `name` or `key="value"` option per line
+    pub fn parse(print_cfg: &str) -> Self {
+        let options = print_cfg
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| match line.split_once('=') {
+                Some((key, value)) => (key.to_string(), Some(value.trim_matches('"').to_string())),
+                None => (line.to_string(), None),
+            })
+            .collect();
+        Self { options }
+    }
+
+    /// Whether an item with this cfg exists on the target
+    ///
+    /// Options the target doesn't decide, such as crate features, are assumed to be enabled,
+    /// so only platform mismatches make an item unavailable.
+    pub fn is_available(&self, cfg: &Cfg) -> bool {
+        self.evaluate(cfg) != Some(false)
+    }
+
+    /// Some(answer) when the target decides the predicate, None when it depends on other options
+    fn evaluate(&self, cfg: &Cfg) -> Option<bool> {
+        match cfg {
+            Cfg::Name(name) => {
+                Self::decides(name).then(|| self.options.contains(&(name.clone(), None)))
+            }
+            Cfg::KeyValue(key, value) => Self::decides(key)
+                .then(|| self.options.contains(&(key.clone(), Some(value.clone())))),
+            Cfg::All(cfgs) => {
+                let answers: Vec<_> = cfgs.iter().map(|cfg| self.evaluate(cfg)).collect();
+                if answers.contains(&Some(false)) {
+                    Some(false)
+                } else {
+                    answers.iter().all(Option::is_some).then_some(true)
+                }
+            }
+            Cfg::Any(cfgs) => {
+                let answers: Vec<_> = cfgs.iter().map(|cfg| self.evaluate(cfg)).collect();
+                if answers.contains(&Some(true)) {
+                    Some(true)
+                } else {
+                    answers.iter().all(Option::is_some).then_some(false)
+                }
+            }
+            Cfg::Not(cfg) => self.evaluate(cfg).map(|answer| !answer),
+        }
+    }
+
+    /// Whether an option is set by the target, rather than by features or build flags
+    fn decides(option: &str) -> bool {
+        option.starts_with("target_") || matches!(option, "unix" | "windows" | "panic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_and_evaluates_doc_cfg_attributes() {
+        let linux = Cfg::from_attribute(r#"#[doc(cfg(target_os = "linux"))]"#).unwrap();
+        assert_eq!(linux.to_string(), "Linux");
+
+        let unix_fs = Cfg::from_attribute(r#"#[doc(cfg(all(unix, feature = "fs")))]"#).unwrap();
+        assert_eq!(unix_fs.to_string(), "Unix and crate feature fs");
+
+        let not_wasm = Cfg::parse(r#"not(any(target_arch = "wasm32", windows))"#).unwrap();
+        assert_eq!(not_wasm.to_string(), "non-(WebAssembly or Windows)");
+
+        assert_eq!(Cfg::from_attribute("#[doc(hidden)]"), None);
+        assert_eq!(Cfg::parse("all(unix"), None);
+
+        let linux_host = TargetCfg::parse(
+            "panic=\"unwind\"\ntarget_arch=\"x86_64\"\ntarget_family=\"unix\"\n\
+             target_os=\"linux\"\nunix\n",
+        );
+        assert!(linux_host.is_available(&linux));
+        assert!(linux_host.is_available(&unix_fs));
+        assert!(linux_host.is_available(&not_wasm));
+        assert!(!linux_host.is_available(&Cfg::Name("windows".into())));
+        assert!(linux_host.is_available(&Cfg::parse(r#"feature = "nightly""#).unwrap()));
+    }
+}
@@ -1,8 +1,13 @@
 //! Version conversions for rustdoc-types formats
 //!
 //! Each module (e.g., `v56`) handles conversion from that version to the next (v56 -> v57).
-//! Conversions can be chained: v55 -> v56 -> v57
+//! Conversions can be chained: v55 -> v56 -> v57. Formats 45 through 54 share a schema close
+//! enough to v55 that [`v53`] converts them all to v55 at once.
+//!
+//! Formats newer than the current one are read by [`newer`], without conversion.
 
+pub mod newer;
+pub mod v53;
 pub mod v55;
 pub mod v56;
 
@@ -10,12 +15,30 @@ use anyhow::{Context, Result};
 use rustdoc_types::{Crate, FORMAT_VERSION};
 use sonic_rs::JsonValueTrait;
 
+/// The oldest format version that can be converted
+pub const MIN_FORMAT_VERSION: u32 = 45;
+
+/// The newest format version read, leniently, by the current rustdoc-types
+pub const MAX_FORMAT_VERSION: u32 = FORMAT_VERSION + 2;
+
+/// Whether a format version is parsed with the current rustdoc-types rather than converted from
+/// an older one: the current format, or one of the next few
+pub fn is_parsed_directly(format_version: u32) -> bool {
+    (FORMAT_VERSION..=MAX_FORMAT_VERSION).contains(&format_version)
+}
+
+/// Whether a format version can be read at all, by conversion or directly
+pub fn is_supported(format_version: u32) -> bool {
+    (MIN_FORMAT_VERSION..=MAX_FORMAT_VERSION).contains(&format_version)
+}
+
 /// Load rustdoc JSON and normalize to the current format version
 ///
 /// This function:
 /// 1. Parses the JSON to determine the format version
 /// 2. Parses with the appropriate rustdoc-types version
-/// 3. Converts through intermediate versions to reach FORMAT_VERSION (57)
+/// 3. Converts through intermediate versions to reach FORMAT_VERSION (57), from as far back
+///    as [`MIN_FORMAT_VERSION`]
 pub fn load_and_normalize(json: &[u8], format_version: Option<u32>) -> Result<Crate> {
     // First, peek at the format version without parsing the entire JSON
     let format_version = if let Some(format_version) = format_version {
@@ -47,19 +70,29 @@ pub fn load_and_normalize(json: &[u8], format_version: Option<u32>) -> Result<Cr
             let crate_56 = v55::convert_crate(crate_55).context("Failed to convert v55 to v56")?;
             v56::convert_crate(crate_56)
         }
-        v if v < 55 => {
-            anyhow::bail!(
-                "Format version {} is too old. Minimum supported version: 55, current version: {}",
-                v,
-                FORMAT_VERSION
-            )
+        54 => {
+            // v54 only lacks a v55 attribute variant, so parse as v55, then convert to v56 and v57
+            let crate_54: rustdoc_types_55::Crate = sonic_rs::serde::from_slice(json)
+                .context("Failed to parse as format version 54")?;
+            let crate_56 = v55::convert_crate(crate_54).context("Failed to convert v54 to v56")?;
+            v56::convert_crate(crate_56)
+        }
+        v if (MIN_FORMAT_VERSION..54).contains(&v) => {
+            // Structure attributes as v55, then convert to v56 and v57
+            let crate_55 = v53::convert_json(json, v)
+                .with_context(|| format!("Failed to convert v{v} to v55"))?;
+            let crate_56 = v55::convert_crate(crate_55).context("Failed to convert v55 to v56")?;
+            v56::convert_crate(crate_56)
+        }
+        v if v < MIN_FORMAT_VERSION => {
+            anyhow::bail!(crate::toolchain::describe_format_mismatch(v))
+        }
+        v if is_parsed_directly(v) => {
+            // Newer than the current version, parse leniently
+            newer::parse_leniently(json, v)
         }
         v => {
-            anyhow::bail!(
-                "Format version {} is too new. Maximum supported version: {}",
-                v,
-                FORMAT_VERSION
-            )
+            anyhow::bail!(crate::toolchain::describe_format_mismatch(v))
         }
     }
 }
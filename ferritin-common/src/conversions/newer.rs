@@ -0,0 +1,73 @@
+//! Reading format versions newer than `FORMAT_VERSION`
+//!
+//! Nightly bumps the rustdoc JSON format often, and most bumps add a field or an enum variant that
+//! few items use. Rather than refusing docs from a fresh nightly until ferritin catches up, the
+//! next few formats are parsed with the current types: unknown fields are ignored, and entries
+//! that still fail to parse (usually because of a new variant) are left out instead of failing
+//! the whole crate.
+
+use anyhow::{Context, Result};
+use rustdoc_types::{Crate, FORMAT_VERSION, Item, ItemSummary};
+use serde::de::DeserializeOwned;
+use sonic_rs::{JsonValueMutTrait, Value};
+
+/// Parse a crate in a newer format as the current one, leaving out items that don't fit
+pub fn parse_leniently(json: &[u8], format_version: u32) -> Result<Crate> {
+    if let Ok(crate_data) = sonic_rs::serde::from_slice(json) {
+        log::debug!("Parsed format version {format_version} as {FORMAT_VERSION}");
+        return Ok(crate_data);
+    }
+
+    let mut value: Value = sonic_rs::from_slice(json)
+        .with_context(|| format!("Failed to parse format version {format_version} as JSON"))?;
+
+    let skipped_items = retain_parseable::<Item>(&mut value, "index");
+    let skipped_paths = retain_parseable::<ItemSummary>(&mut value, "paths");
+    if skipped_items + skipped_paths > 0 {
+        log::warn!(
+            "Left out {skipped_items} items and {skipped_paths} paths that format version \
+             {format_version} changed since {FORMAT_VERSION}"
+        );
+    }
+
+    sonic_rs::value::from_value(&value).with_context(|| {
+        format!("Failed to parse format version {format_version} as {FORMAT_VERSION}")
+    })
+}
+
+/// Remove the entries of an object field that don't parse as `T`, returning how many there were
+fn retain_parseable<T: DeserializeOwned>(value: &mut Value, field: &str) -> usize {
+    let Some(entries) = value
+        .get_mut(field)
+        .and_then(|entries| entries.as_object_mut())
+    else {
+        return 0;
+    };
+
+    let unparseable: Vec<String> = entries
+        .iter()
+        .filter(|(_, entry)| sonic_rs::value::from_value::<T>(entry).is_err())
+        .map(|(key, _)| key.to_string())
+        .collect();
+
+    for key in &unparseable {
+        entries.remove(key);
+    }
+    unparseable.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sonic_rs::{JsonContainerTrait, JsonValueTrait};
+
+    #[test]
+    fn retains_only_parseable_entries() {
+        let mut value: Value =
+            sonic_rs::from_str(r#"{"index": {"0": 1, "1": "new", "2": 3}}"#).unwrap();
+        assert_eq!(retain_parseable::<u32>(&mut value, "index"), 1);
+        let index = value.get("index").and_then(|index| index.as_object());
+        assert_eq!(index.map(|index| index.len()), Some(2));
+        assert_eq!(retain_parseable::<u32>(&mut value, "paths"), 0);
+    }
+}
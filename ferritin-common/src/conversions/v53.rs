@@ -0,0 +1,236 @@
+//! Conversion from rustdoc-types format versions 45 through 53 to 55
+//!
+//! Changes up to v55:
+//! - v46, v48–v50, v52 and v53 changed how some attributes are printed, but not the schema
+//!   (v47 was never released)
+//! - v51 made `AssocItemConstraint::args` and `Type::QualifiedPath::args` optional, which older
+//!   JSON already satisfies
+//! - v54 replaced the strings of `Item::attrs` with the structured `Attribute` enum
+//! - v55 added an `Attribute` variant
+//!
+//! Strategy: Parse as JSON, replace each attribute string with the `Attribute` it stands for
+//! (or `Attribute::Other`), and deserialize as v55. Format 54 is parsed as v55 directly.
+
+use anyhow::{Context, Result};
+use rustdoc_types_55 as v55;
+use rustdoc_types_55::{Attribute, AttributeRepr, ReprKind};
+use sonic_rs::{JsonValueMutTrait, JsonValueTrait, Value};
+
+/// Convert a crate in format 45 through 53 to v55
+pub fn convert_json(json: &[u8], format_version: u32) -> Result<v55::Crate> {
+    let mut json_value: Value = sonic_rs::from_slice(json)
+        .with_context(|| format!("Failed to parse format version {format_version} as JSON"))?;
+
+    // Patch: Replace attribute strings with structured attributes
+    if let Some(index) = json_value
+        .get_mut("index")
+        .and_then(|index| index.as_object_mut())
+    {
+        for (_id, item) in index.iter_mut() {
+            let Some(attrs) = item.get_mut("attrs").and_then(|attrs| attrs.as_array_mut()) else {
+                continue;
+            };
+            for attr in attrs.iter_mut() {
+                if let Some(attribute) = attr.as_str().map(convert_attribute) {
+                    *attr = sonic_rs::value::to_value(&attribute)
+                        .context("Failed to serialize attribute")?;
+                }
+            }
+        }
+    }
+
+    // Update format_version in JSON before deserializing
+    if let Some(obj) = json_value.as_object_mut() {
+        obj.insert("format_version", sonic_rs::json!(55));
+    }
+
+    sonic_rs::value::from_value(&json_value).with_context(|| {
+        format!("Failed to deserialize format version {format_version} as v55 crate")
+    })
+}
+
+/// The structured form of an attribute as older formats print it, either as written in source
+/// (`#[must_use = "reason"]`) or as rustc's debug printing (`#[attr = MustUse {reason: "…"}]`)
+fn convert_attribute(attr: &str) -> Attribute {
+    let attr = attr.trim();
+    let Some(inner) = attr
+        .strip_prefix("#[")
+        .and_then(|inner| inner.strip_suffix(']'))
+    else {
+        return Attribute::Other(attr.to_string());
+    };
+    // `#[unsafe(no_mangle)]` and `#[unsafe(export_name = "…")]` mean the same as without
+    let inner = inner
+        .strip_prefix("unsafe(")
+        .and_then(|inner| inner.strip_suffix(')'))
+        .unwrap_or(inner)
+        .trim();
+
+    match inner {
+        "non_exhaustive" | "attr = NonExhaustive" => return Attribute::NonExhaustive,
+        "automatically_derived" | "attr = AutomaticallyDerived" => {
+            return Attribute::AutomaticallyDerived;
+        }
+        "no_mangle" | "attr = NoMangle" => return Attribute::NoMangle,
+        "macro_export" => return Attribute::MacroExport,
+        "must_use" | "attr = MustUse" => return Attribute::MustUse { reason: None },
+        _ => {}
+    }
+
+    if let Some(reason) = inner
+        .strip_prefix("must_use")
+        .and_then(|rest| quoted_value(rest.trim_start().strip_prefix('=')?))
+        .or_else(|| {
+            let rest = inner.strip_prefix("attr = MustUse")?.trim_start();
+            quoted_value(
+                rest.strip_prefix('{')?
+                    .trim_start()
+                    .strip_prefix("reason:")?,
+            )
+        })
+    {
+        return Attribute::MustUse {
+            reason: Some(reason.to_string()),
+        };
+    }
+
+    if let Some(name) = inner
+        .strip_prefix("export_name")
+        .and_then(|rest| quoted_value(rest.trim_start().strip_prefix('=')?))
+    {
+        return Attribute::ExportName(name.to_string());
+    }
+
+    if let Some(section) = inner
+        .strip_prefix("link_section")
+        .and_then(|rest| quoted_value(rest.trim_start().strip_prefix('=')?))
+    {
+        return Attribute::LinkSection(section.to_string());
+    }
+
+    if let Some(args) = inner
+        .strip_prefix("repr(")
+        .and_then(|args| args.strip_suffix(')'))
+        && let Some(repr) = convert_repr(args)
+    {
+        return Attribute::Repr(repr);
+    }
+
+    if let Some(args) = inner
+        .strip_prefix("target_feature(")
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        // Each `enable = "…"` may list several comma-separated features
+        let enable = args
+            .split("enable")
+            .filter_map(quoted_value)
+            .flat_map(|features| features.split(','))
+            .map(|feature| feature.trim().to_string())
+            .collect();
+        return Attribute::TargetFeature { enable };
+    }
+
+    Attribute::Other(attr.to_string())
+}
+
+/// The arguments of `#[repr(…)]`, such as `C, u8` or `align(8)`
+fn convert_repr(args: &str) -> Option<AttributeRepr> {
+    let mut repr = AttributeRepr {
+        kind: ReprKind::Rust,
+        align: None,
+        packed: None,
+        int: None,
+    };
+
+    for arg in args.split(',').map(str::trim).filter(|arg| !arg.is_empty()) {
+        match arg {
+            "C" => repr.kind = ReprKind::C,
+            "Rust" => repr.kind = ReprKind::Rust,
+            "transparent" => repr.kind = ReprKind::Transparent,
+            "simd" => repr.kind = ReprKind::Simd,
+            "packed" => repr.packed = Some(1),
+            _ => {
+                if let Some(align) = parenthesized(arg, "align") {
+                    repr.align = Some(align.parse().ok()?);
+                } else if let Some(packed) = parenthesized(arg, "packed") {
+                    repr.packed = Some(packed.parse().ok()?);
+                } else if arg.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    repr.int = Some(arg.to_string());
+                } else {
+                    return None;
+                }
+            }
+        }
+    }
+
+    Some(repr)
+}
+
+/// `value` from `= "value"` or `"value"`, ignoring anything after the closing quote
+fn quoted_value(rest: &str) -> Option<&str> {
+    let rest = rest.trim();
+    let rest = rest.strip_prefix('=').map_or(rest, str::trim_start);
+    let rest = rest.strip_prefix('"')?;
+    let end = rest.find('"')?;
+    Some(&rest[..end])
+}
+
+/// `8` from `align(8)`
+fn parenthesized<'a>(arg: &'a str, name: &str) -> Option<&'a str> {
+    arg.strip_prefix(name)?
+        .strip_prefix('(')?
+        .strip_suffix(')')
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_attribute_strings() {
+        assert_eq!(
+            convert_attribute("#[non_exhaustive]"),
+            Attribute::NonExhaustive
+        );
+        assert_eq!(
+            convert_attribute(r#"#[must_use = "use it"]"#),
+            Attribute::MustUse {
+                reason: Some("use it".into())
+            }
+        );
+        assert_eq!(
+            convert_attribute(r#"#[attr = MustUse {reason: "use it"}]"#),
+            Attribute::MustUse {
+                reason: Some("use it".into())
+            }
+        );
+        assert_eq!(
+            convert_attribute(r#"#[unsafe(export_name = "ext")]"#),
+            Attribute::ExportName("ext".into())
+        );
+        assert_eq!(
+            convert_attribute("#[repr(C, u8)]"),
+            Attribute::Repr(AttributeRepr {
+                kind: ReprKind::C,
+                align: None,
+                packed: None,
+                int: Some("u8".into()),
+            })
+        );
+        assert_eq!(
+            convert_attribute(r#"#[target_feature(enable = "sse2,avx")]"#),
+            Attribute::TargetFeature {
+                enable: vec!["sse2".into(), "avx".into()]
+            }
+        );
+        assert_eq!(
+            convert_attribute("#[doc(hidden)]"),
+            Attribute::Other("#[doc(hidden)]".into())
+        );
+        assert_eq!(
+            convert_attribute("#[attr = Inline(Hint)]"),
+            Attribute::Other("#[attr = Inline(Hint)]".into())
+        );
+    }
+}
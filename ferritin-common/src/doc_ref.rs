@@ -1,9 +1,14 @@
 use crate::{
-    Navigator, RustdocData, navigator::parse_docsrs_url, rustdoc_data::kind_discriminator,
+    Navigator, RustdocData,
+    cfg::Cfg,
+    navigator::parse_docsrs_url,
+    rustdoc_data::kind_discriminator,
+    string_utils::{doc_aliases_in, is_doc_hidden},
 };
 use fieldwork::Fieldwork;
 use rustdoc_types::{
-    ExternalCrate, Id, Item, ItemEnum, ItemKind, ItemSummary, MacroKind, ProcMacro, Use,
+    Attribute, ExternalCrate, GenericBound, Id, Item, ItemEnum, ItemKind, ItemSummary, MacroKind,
+    ProcMacro, Use,
 };
 
 /// A lightweight, `Copy` reference to a parent item set during tree traversal.
@@ -113,7 +118,148 @@ impl<'a> DocRef<'a, Item> {
     }
 
     pub fn summary(&self) -> Option<&'a ItemSummary> {
-        self.crate_docs().paths.get(&self.id)
+        self.crate_docs().paths().get(&self.id)
+    }
+
+    /// Returns the path this item was re-exported at, if it was reached through a `pub use`.
+    ///
+    /// An item is considered re-exported when its canonical location (from rustdoc's `paths`
+    /// map) differs from the module it was found in during tree traversal. Only available for
+    /// items reached by walking the module tree, such as through `child_items` or
+    /// `Navigator::resolve_path`.
+    pub fn reexport_path(&self) -> Option<String> {
+        let parent = self.parent?;
+        let name = self.name()?;
+        let summary = self.summary()?;
+        let parent_summary = parent.crate_docs.paths().get(&parent.item.id)?;
+
+        let parent_path = &parent_summary.path;
+        let is_canonical = std::ptr::eq(parent.crate_docs, self.crate_docs)
+            && summary.path.len() == parent_path.len() + 1
+            && summary.path.starts_with(parent_path)
+            && summary.path.last().is_some_and(|last| last == name);
+
+        if is_canonical {
+            return None;
+        }
+
+        let crate_name = parent.crate_docs.name();
+        match parent_path.get(1..) {
+            Some(tail) if !tail.is_empty() => {
+                Some(format!("{crate_name}::{}::{name}", tail.join("::")))
+            }
+            _ => Some(format!("{crate_name}::{name}")),
+        }
+    }
+
+    /// Whether this item is marked `#[doc(hidden)]`.
+    ///
+    /// Hidden items only appear in rustdoc JSON generated with `--document-hidden-items`,
+    /// which is how local workspace crates are built.
+    pub fn is_hidden(&self) -> bool {
+        self.item.attrs.iter().any(|attr| match attr {
+            Attribute::Other(attr) => is_doc_hidden(attr),
+            _ => false,
+        })
+    }
+
+    /// Other names this item can be found by in search, from its `#[doc(alias = "…")]`
+    /// attributes
+    pub fn doc_aliases(&self) -> Vec<&'a str> {
+        self.item
+            .attrs
+            .iter()
+            .flat_map(|attr| match attr {
+                Attribute::Other(attr) => doc_aliases_in(attr),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    /// The first of this item's doc aliases that appears in `query`, to explain a search result
+    /// whose name doesn't match
+    pub fn alias_matching(&self, query: &str) -> Option<&'a str> {
+        let query = query.to_lowercase();
+        self.doc_aliases()
+            .into_iter()
+            .find(|alias| query.contains(&alias.to_lowercase()))
+    }
+
+    /// The platforms or features this item is limited to, from its `#[doc(cfg(…))]` attributes
+    pub fn cfg(&self) -> Option<Cfg> {
+        let mut cfgs: Vec<Cfg> = self
+            .item
+            .attrs
+            .iter()
+            .filter_map(|attr| match attr {
+                Attribute::Other(attr) => Cfg::from_attribute(attr),
+                _ => None,
+            })
+            .collect();
+
+        match cfgs.len() {
+            0 => None,
+            1 => cfgs.pop(),
+            _ => Some(Cfg::All(cfgs)),
+        }
+    }
+
+    /// The traits named in this trait's supertrait bounds, e.g. `Clone` for `Copy`.
+    ///
+    /// Returns an empty list for items that are not traits. Bounds that cannot be resolved
+    /// (for example because the defining crate is unavailable) are skipped.
+    pub fn supertraits(&self) -> Vec<DocRef<'a, Item>> {
+        let ItemEnum::Trait(trait_item) = self.inner() else {
+            return vec![];
+        };
+
+        trait_item
+            .bounds
+            .iter()
+            .filter_map(|bound| match bound {
+                GenericBound::TraitBound { trait_, .. } => self.get_path(trait_.id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Traits in already-loaded crates that name this trait as a supertrait.
+    ///
+    /// This never loads additional crates, so the result only covers crates that have been
+    /// visited so far. Results are sorted by path for stable output.
+    pub fn subtraits(&self) -> Vec<DocRef<'a, Item>> {
+        if !matches!(self.inner(), ItemEnum::Trait(_)) {
+            return vec![];
+        }
+        let Some(summary) = self.summary() else {
+            return vec![];
+        };
+
+        let navigator = self.navigator;
+        let mut subtraits = navigator
+            .loaded_crates()
+            .flat_map(|crate_docs| {
+                crate_docs.items().values().filter_map(move |item| {
+                    let ItemEnum::Trait(trait_item) = &item.inner else {
+                        return None;
+                    };
+
+                    // Ids are crate-local, so compare bounds by their fully qualified path
+                    let names_self = trait_item.bounds.iter().any(|bound| match bound {
+                        GenericBound::TraitBound { trait_, .. } => crate_docs
+                            .paths()
+                            .get(&trait_.id)
+                            .is_some_and(|bound_summary| bound_summary.path == summary.path),
+                        _ => false,
+                    });
+
+                    names_self.then(|| DocRef::new(navigator, crate_docs, item))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        subtraits.sort_by_key(|subtrait| subtrait.summary().map(|summary| &summary.path));
+        subtraits
     }
 
     pub fn find_child(&self, child_name: &str) -> Option<DocRef<'a, Item>> {
@@ -183,7 +329,7 @@ impl<'a> DocRef<'a, Item> {
         // Prefer a path without a discriminator on the parent segment (simpler output).
         // The unqualified key is only present in path_to_id when there is no collision at
         // that path, so its presence is a reliable signal that we can omit the discriminator.
-        if let Some(parent_summary) = parent_ref.crate_docs.paths.get(&parent_ref.item.id) {
+        if let Some(parent_summary) = parent_ref.crate_docs.paths().get(&parent_ref.item.id) {
             if let Some(tail) = parent_summary.path.get(1..) {
                 let parent_key = tail.join("::");
                 if parent_ref.crate_docs.path_to_id.contains_key(&parent_key) {
@@ -210,6 +356,10 @@ impl<'a> DocRef<'a, Item> {
 
     pub fn kind(&self) -> ItemKind {
         match self.item.inner {
+            // rustdoc describes keywords as empty modules, so only their summary tells them apart
+            ItemEnum::Module(_) if self.summary().is_some_and(|s| s.kind == ItemKind::Keyword) => {
+                ItemKind::Keyword
+            }
             ItemEnum::Module(_) => ItemKind::Module,
             ItemEnum::ExternCrate { .. } => ItemKind::ExternCrate,
             ItemEnum::Use(_) => ItemKind::Use,
@@ -298,7 +448,7 @@ impl<'a> DocRef<'a, ItemSummary> {
             return None;
         }
 
-        let external = self.crate_docs().external_crates.get(&self.crate_id)?;
+        let external = self.crate_docs().external_crates().get(&self.crate_id)?;
         Some(self.build_ref(external))
     }
 }
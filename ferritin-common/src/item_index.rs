@@ -0,0 +1,390 @@
+//! Lazily parsed rustdoc JSON
+//!
+//! Rustdoc JSON for large crates (std, windows-sys) runs to hundreds of megabytes, nearly all of
+//! it in the `index` of items, and most of those items are never looked at. Files in the current
+//! format are memory-mapped instead of read: everything but the index (including the `paths`
+//! table) is parsed up front, while each item's span in the file is recorded and the item is
+//! parsed the first time it's reached. The next few formats are read the same way, since most of
+//! their items fit the current types. Older formats go through
+//! [`load_and_normalize`](crate::conversions::load_and_normalize) and are parsed eagerly.
+//!
+//! JSON that's already in memory, as on wasm targets where nothing can be mapped, is parsed
+//! lazily the same way.
+
+use anyhow::{Context, Result, anyhow};
+use elsa::sync::FrozenMap;
+#[cfg(not(target_family = "wasm"))]
+use memmap2::Mmap;
+use rustc_hash::FxHashMap;
+use rustdoc_types::{Crate, Id, Item, ItemEnum};
+use semver::Version;
+use serde::de::DeserializeOwned;
+use sonic_rs::JsonValueTrait;
+use std::fmt::{self, Debug, Formatter};
+#[cfg(not(target_family = "wasm"))]
+use std::fs::File;
+use std::ops::{Deref, Range};
+use std::path::Path;
+
+/// The items of a crate, keyed by id
+///
+/// This stands in for [`Crate::index`], which is left empty in [`RustdocData`](crate::RustdocData).
+pub struct ItemIndex(Repr);
+
+enum Repr {
+    /// Every item, already parsed
+    Parsed(FxHashMap<Id, Item>),
+    /// Items parsed from a memory map as they're reached
+    Mapped(MappedItems),
+}
+
+struct MappedItems {
+    json: JsonBytes,
+    spans: FxHashMap<Id, ItemSpan>,
+    /// Length of the JSON outside of the index, which is parsed up front
+    unindexed_len: usize,
+    /// Items parsed so far, boxed so that references outlive later insertions
+    parsed: FrozenMap<Id, Box<Item>>,
+}
+
+/// Where an item's JSON is in the memory map
+struct ItemSpan {
+    range: Range<usize>,
+    /// Whether the item is an impl block, so that impls can be found without parsing every item
+    is_impl: bool,
+}
+
+impl ItemIndex {
+    /// Take the items out of an already parsed crate
+    pub(crate) fn from_crate(crate_data: &mut Crate) -> Self {
+        Self(Repr::Parsed(std::mem::take(&mut crate_data.index)))
+    }
+
+    /// Whether items are parsed from a memory map as they're reached, rather than all up front
+    pub fn is_lazy(&self) -> bool {
+        matches!(self.0, Repr::Mapped(_))
+    }
+
+    /// The length of the JSON parsed so far, for current-format crates parsed lazily
+    ///
+    /// Parsed data takes roughly as much memory as the JSON it came from, so this estimates what
+    /// a crate holds beyond its memory map.
+    pub fn parsed_json_len(&self) -> Option<usize> {
+        let Repr::Mapped(mapped) = &self.0 else {
+            return None;
+        };
+        let parsed_items_len: usize = mapped
+            .parsed
+            .keys_cloned()
+            .iter()
+            .filter_map(|id| mapped.spans.get(id))
+            .map(|span| span.range.len())
+            .sum();
+        Some(mapped.unindexed_len + parsed_items_len)
+    }
+
+    /// Look up an item, parsing it if this is the first time it's been reached
+    pub fn get(&self, id: &Id) -> Option<&Item> {
+        match &self.0 {
+            Repr::Parsed(items) => items.get(id),
+            Repr::Mapped(mapped) => mapped.get(id),
+        }
+    }
+
+    /// Whether an item with this id exists, without parsing it
+    pub fn contains(&self, id: &Id) -> bool {
+        match &self.0 {
+            Repr::Parsed(items) => items.contains_key(id),
+            Repr::Mapped(mapped) => mapped.spans.contains_key(id),
+        }
+    }
+
+    /// The ids of every item, in no particular order
+    pub fn ids(&self) -> Box<dyn Iterator<Item = &Id> + '_> {
+        match &self.0 {
+            Repr::Parsed(items) => Box::new(items.keys()),
+            Repr::Mapped(mapped) => Box::new(mapped.spans.keys()),
+        }
+    }
+
+    /// Every item, in no particular order
+    ///
+    /// This parses every item of a memory-mapped crate, so prefer [`ItemIndex::get`] and
+    /// [`ItemIndex::impls`] where they suffice.
+    pub fn values(&self) -> Box<dyn Iterator<Item = &Item> + '_> {
+        match &self.0 {
+            Repr::Parsed(items) => Box::new(items.values()),
+            Repr::Mapped(mapped) => Box::new(mapped.spans.keys().filter_map(|id| mapped.get(id))),
+        }
+    }
+
+    /// The impl blocks of the crate, in no particular order
+    pub fn impls(&self) -> Box<dyn Iterator<Item = &Item> + '_> {
+        match &self.0 {
+            Repr::Parsed(items) => Box::new(
+                items
+                    .values()
+                    .filter(|item| matches!(item.inner, ItemEnum::Impl(_))),
+            ),
+            Repr::Mapped(mapped) => Box::new(
+                mapped
+                    .spans
+                    .iter()
+                    .filter(|(_, span)| span.is_impl)
+                    .filter_map(|(id, _)| mapped.get(id)),
+            ),
+        }
+    }
+}
+
+impl ItemIndex {
+    /// The ids of the crate's impl blocks, in the order [`ItemIndex::impls`] finds them, without
+    /// parsing them
+    pub(crate) fn impl_ids(&self) -> Box<dyn Iterator<Item = &Id> + '_> {
+        match &self.0 {
+            Repr::Parsed(items) => Box::new(
+                items
+                    .iter()
+                    .filter(|(_, item)| matches!(item.inner, ItemEnum::Impl(_)))
+                    .map(|(id, _)| id),
+            ),
+            Repr::Mapped(mapped) => Box::new(
+                mapped
+                    .spans
+                    .iter()
+                    .filter(|(_, span)| span.is_impl)
+                    .map(|(id, _)| id),
+            ),
+        }
+    }
+
+    /// Parse the parts of an item that `T` picks out of its JSON, without keeping them
+    ///
+    /// This suits reading a few fields of nearly every item, as indexing does, which would
+    /// otherwise leave every item parsed. Only memory-mapped crates have JSON to read, so this is
+    /// `None` for crates parsed up front.
+    pub(crate) fn read_partial<T: DeserializeOwned>(&self, id: &Id) -> Option<T> {
+        let Repr::Mapped(mapped) = &self.0 else {
+            return None;
+        };
+        let span = mapped.spans.get(id)?;
+        sonic_rs::serde::from_slice(&mapped.json[span.range.clone()])
+            .inspect_err(|error| log::error!("Failed to read item {}: {error}", id.0))
+            .ok()
+    }
+}
+
+impl MappedItems {
+    fn get(&self, id: &Id) -> Option<&Item> {
+        if let Some(item) = self.parsed.get(id) {
+            return Some(item);
+        }
+
+        let span = self.spans.get(id)?;
+        match sonic_rs::serde::from_slice::<Item>(&self.json[span.range.clone()]) {
+            Ok(item) => Some(self.parsed.insert(*id, Box::new(item))),
+            Err(error) => {
+                log::error!("Failed to parse item {}: {error}", id.0);
+                None
+            }
+        }
+    }
+}
+
+/// Cloning parses every item of a memory-mapped crate, since the map itself can't be shared
+impl Clone for ItemIndex {
+    fn clone(&self) -> Self {
+        Self(Repr::Parsed(
+            self.ids()
+                .filter_map(|id| Some((*id, self.get(id)?.clone())))
+                .collect(),
+        ))
+    }
+}
+
+impl PartialEq for ItemIndex {
+    fn eq(&self, other: &Self) -> bool {
+        self.ids().count() == other.ids().count()
+            && self.ids().all(|id| self.get(id) == other.get(id))
+    }
+}
+
+impl Eq for ItemIndex {}
+
+impl Debug for ItemIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (kind, len) = match &self.0 {
+            Repr::Parsed(items) => ("parsed", items.len()),
+            Repr::Mapped(mapped) => ("mapped", mapped.spans.len()),
+        };
+        f.debug_struct("ItemIndex")
+            .field("kind", &kind)
+            .field("len", &len)
+            .finish()
+    }
+}
+
+/// The bytes of a rustdoc JSON file
+enum JsonBytes {
+    #[cfg(not(target_family = "wasm"))]
+    Mapped(Mmap),
+    Owned(Box<[u8]>),
+}
+
+impl Deref for JsonBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            #[cfg(not(target_family = "wasm"))]
+            JsonBytes::Mapped(mmap) => mmap,
+            JsonBytes::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// A memory-mapped rustdoc JSON file
+pub(crate) struct MappedJson {
+    json: JsonBytes,
+}
+
+impl MappedJson {
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        // SAFETY: truncating a file while it is mapped would fault on the next read of an
+        // unparsed item. Files that ferritin itself rewrites while they may be in use are read
+        // with `MappedJson::read` instead: out-of-date workspace docs, which are rebuilt in the
+        // background, and dependency docs shared by several versions of a crate, where loading
+        // one version rebuilds the other's file. The docs.rs cache and std docs are written
+        // once, and workspace docs are only rebuilt by a fresh request, after the documents
+        // mapping them are dropped.
+        let mmap = unsafe { Mmap::map(&file) }
+            .with_context(|| format!("Failed to map {}", path.display()))?;
+        Ok(Self {
+            json: JsonBytes::Mapped(mmap),
+        })
+    }
+
+    /// Files can't be mapped on wasm targets, so they're read instead
+    #[cfg(target_family = "wasm")]
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        Self::read(path)
+    }
+
+    /// Read a file into memory rather than mapping it, for a file that may be rewritten while
+    /// it's in use
+    pub(crate) fn read(path: &Path) -> Result<Self> {
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Self::from_bytes(bytes))
+    }
+
+    /// JSON that's already in memory, such as a response body
+    pub(crate) fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self {
+            json: JsonBytes::Owned(bytes.into_boxed_slice()),
+        }
+    }
+
+    /// The rustdoc JSON format version, read without parsing the rest of the file
+    pub(crate) fn format_version(&self) -> Option<u32> {
+        let format_version = sonic_rs::get_from_slice(&self.json, &["format_version"]).ok()?;
+        format_version.as_u64()?.try_into().ok()
+    }
+
+    /// The crate's version, read without parsing the rest of the file
+    pub(crate) fn crate_version(&self) -> Option<Version> {
+        let crate_version = sonic_rs::get_from_slice(&self.json, &["crate_version"]).ok()?;
+        Version::parse(crate_version.as_str()?).ok()
+    }
+
+    /// Parse the crate, leaving its items in the map to be parsed as they're reached
+    ///
+    /// Older formats are converted and parsed eagerly. Items of newer formats that don't fit the
+    /// current types are logged and skipped when they're reached.
+    pub(crate) fn parse(self) -> Result<(Crate, ItemIndex)> {
+        let format_version = self
+            .format_version()
+            .ok_or_else(|| anyhow!("Failed to extract format_version"))?;
+
+        // Newer formats parse lazily too, leaving out items that don't fit the current types
+        if crate::conversions::is_parsed_directly(format_version) {
+            match self.parse_skeleton() {
+                Ok((crate_data, spans, unindexed_len)) => {
+                    let items = ItemIndex(Repr::Mapped(MappedItems {
+                        json: self.json,
+                        spans,
+                        unindexed_len,
+                        parsed: FrozenMap::new(),
+                    }));
+                    return Ok((crate_data, items));
+                }
+                Err(error) => log::debug!("Parsing eagerly, since lazy parsing failed: {error}"),
+            }
+        }
+
+        let mut crate_data =
+            crate::conversions::load_and_normalize(&self.json, Some(format_version))?;
+        let items = ItemIndex::from_crate(&mut crate_data);
+        Ok((crate_data, items))
+    }
+
+    /// Parse everything but the index, and find where each item of the index is
+    ///
+    /// Also returns the length of the JSON outside of the index.
+    fn parse_skeleton(&self) -> Result<(Crate, FxHashMap<Id, ItemSpan>, usize)> {
+        let json: &[u8] = &self.json;
+        let index = sonic_rs::get_from_slice(json, &["index"]).context("Failed to find index")?;
+        let index_range = subslice_range(json, index.as_raw_str().as_bytes())
+            .ok_or_else(|| anyhow!("index is not borrowed from the file"))?;
+
+        // The rest of the crate is parsed by splicing an empty index into the index's place
+        let unindexed_len = json.len() - index_range.len();
+        let crate_data: Crate = {
+            let mut skeleton = Vec::with_capacity(unindexed_len + 2);
+            skeleton.extend_from_slice(&json[..index_range.start]);
+            skeleton.extend_from_slice(b"{}");
+            skeleton.extend_from_slice(&json[index_range.end..]);
+            sonic_rs::serde::from_slice(&skeleton).context("Failed to parse as current format")?
+        };
+
+        let mut spans = FxHashMap::default();
+        for entry in sonic_rs::to_object_iter(&json[index_range]) {
+            let (id, item) = entry.context("Failed to read index")?;
+            let id = id
+                .parse()
+                .map(Id)
+                .with_context(|| format!("Invalid item id {id:?}"))?;
+            let item = item.as_raw_str().as_bytes();
+            let range = subslice_range(json, item)
+                .ok_or_else(|| anyhow!("item {} is not borrowed from the file", id.0))?;
+            let is_impl = sonic_rs::get_from_slice(item, &["inner", "impl"]).is_ok();
+            spans.insert(id, ItemSpan { range, is_impl });
+        }
+
+        Ok((crate_data, spans, unindexed_len))
+    }
+}
+
+/// The position of `part` within `whole`, if it's a subslice of it
+fn subslice_range(whole: &[u8], part: &[u8]) -> Option<Range<usize>> {
+    let start = (part.as_ptr() as usize).checked_sub(whole.as_ptr() as usize)?;
+    let end = start + part.len();
+    (end <= whole.len()).then_some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_subslice_ranges() {
+        let whole = b"{\"index\":{\"0\":{}}}";
+        assert_eq!(subslice_range(whole, &whole[9..17]), Some(9..17));
+        // A copy on the heap, since a literal could share the memory of `whole`
+        let elsewhere = b"{}".to_vec();
+        assert_eq!(subslice_range(whole, &elsewhere), None);
+    }
+}
@@ -1,7 +1,6 @@
 use crate::doc_ref::{DocRef, ParentRef};
 use fieldwork::Fieldwork;
 use rustdoc_types::{Id, Item, ItemEnum, Type, Use};
-use std::collections::hash_map::Values;
 
 pub struct MethodIter<'a> {
     item: DocRef<'a, Item>,
@@ -40,13 +39,22 @@ impl<'a, T> DocRef<'a, T> {
     }
 }
 
+/// Whether an impl block's self type `for_` is `item`, a named type or a primitive
+fn is_impl_for(for_: &Type, item: &Item) -> bool {
+    match (for_, &item.inner) {
+        (Type::ResolvedPath(path), _) => path.id == item.id,
+        (Type::Primitive(name), ItemEnum::Primitive(primitive)) => *name == primitive.name,
+        _ => false,
+    }
+}
+
 pub struct TraitIter<'a> {
     item: DocRef<'a, Item>,
-    item_iter: Values<'a, Id, Item>,
+    item_iter: Box<dyn Iterator<Item = &'a Item> + 'a>,
 }
 impl<'a> TraitIter<'a> {
     fn new(item: DocRef<'a, Item>) -> Self {
-        let item_iter = item.crate_docs().index.values();
+        let item_iter = item.crate_docs().items().impls();
         Self { item, item_iter }
     }
 }
@@ -57,8 +65,7 @@ impl<'a> Iterator for TraitIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         for item in &mut self.item_iter {
             if let ItemEnum::Impl(impl_block) = &item.inner
-                && let Type::ResolvedPath(path) = &impl_block.for_
-                && path.id == self.item.id
+                && is_impl_for(&impl_block.for_, self.item.item())
                 && impl_block.trait_.is_some()
             {
                 return Some(self.item.build_ref(item));
@@ -143,19 +150,28 @@ impl<'a, T> Iterator for IdIter<'a, T> {
                             })?;
 
                         if use_item.is_glob {
-                            self.glob_iter = match source_item.inner() {
-                                ItemEnum::Module(module) => {
-                                    Some(Box::new(source_item.id_iter(&module.items)))
-                                }
+                            let glob_iter = match source_item.inner() {
+                                ItemEnum::Module(module) => source_item.id_iter(&module.items),
                                 ItemEnum::Enum(enum_item) => {
-                                    Some(Box::new(source_item.id_iter(&enum_item.variants)))
+                                    source_item.id_iter(&enum_item.variants)
                                 }
-                                _ => None,
+                                _ => break,
                             };
 
+                            // Glob-imported items are attributed to the importing module so
+                            // that they can be recognized as re-exports
+                            self.glob_iter = Some(Box::new(match self.parent {
+                                Some(parent) => glob_iter.with_parent(parent),
+                                None => glob_iter,
+                            }));
+
                             break;
                         } else {
-                            return Some(source_item.with_name(&use_item.name));
+                            let source_item = source_item.with_name(&use_item.name);
+                            return Some(match self.parent {
+                                Some(parent) => source_item.with_parent(parent),
+                                None => source_item,
+                            });
                         }
                     }
                     return Some(match self.parent {
@@ -175,12 +191,12 @@ impl<'a, T> Iterator for IdIter<'a, T> {
 
 pub(crate) struct InherentImplBlockIter<'a> {
     item: DocRef<'a, Item>,
-    item_iter: Values<'a, Id, Item>,
+    item_iter: Box<dyn Iterator<Item = &'a Item> + 'a>,
 }
 
 impl<'a> InherentImplBlockIter<'a> {
     pub(crate) fn new(item: DocRef<'a, Item>) -> Self {
-        let item_iter = item.crate_docs().index.values();
+        let item_iter = item.crate_docs().items().impls();
         Self { item, item_iter }
     }
 }
@@ -191,8 +207,7 @@ impl<'a> Iterator for InherentImplBlockIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         for item in &mut self.item_iter {
             if let ItemEnum::Impl(impl_block) = &item.inner
-                && let Type::ResolvedPath(path) = &impl_block.for_
-                && path.id == self.item.id
+                && is_impl_for(&impl_block.for_, self.item.item())
                 && impl_block.trait_.is_none()
             {
                 return Some(DocRef::new(self.item.navigator(), self.item, item));
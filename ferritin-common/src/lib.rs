@@ -1,21 +1,94 @@
-// Core library for rustdoc navigation and search
+//! Rustdoc JSON navigation and search
+//!
+//! This is the library behind the [ferritin](https://github.com/jbr/ferritin) CLI and the
+//! rustdoc-mcp server, for other tools (editor plugins, doc linters) that want to look up and
+//! search Rust documentation the same way.
+//!
+//! # Public API
+//!
+//! These items follow semver: while ferritin-common is 0.x, breaking changes to them bump the
+//! minor version, and patch releases only add to them.
+//!
+//! - [`Navigator`], built with `Navigator::default().with_*_source(…)`, loads crates from its
+//!   sources and resolves paths like `std::vec::Vec::push` to items
+//! - [`sources`]: [`StdSource`](sources::StdSource), [`LocalSource`](sources::LocalSource)
+//!   with its [`BuildOptions`](sources::BuildOptions),
+//!   [`JsonFileSource`](sources::JsonFileSource) and `DocsRsSource`, and the
+//!   [`DocSource`](sources::DocSource) trait they implement, for sources of your own added with
+//!   [`Navigator::with_source`]
+//! - [`DocRef`], an item borrowed from the navigator along with the crate it came from
+//! - [`search`]: [`Navigator::search`] and its [`ScoredResult`](search::ScoredResult)s
+//! - [`RustdocData`], [`CrateInfo`], [`CrateName`], [`CrateProvenance`], [`Suggestion`] and
+//!   [`CancelToken`]
+//! - [`rustdoc_types`], re-exported so that items can be matched on without depending on the
+//!   same version separately
+//!
+//! Everything else, including [`conversions`], [`item_index`], [`iterators`], [`cfg`] and
+//! [`toolchain`], is public so that ferritin's binaries can use it, and may change in any
+//! release.
+//!
+//! # Features
+//!
+//! - `network` (default): [`DocsRsSource`](sources::DocsRsSource) for crates that aren't local,
+//!   and [`StdSource::download_prebuilt`](sources::StdSource::download_prebuilt) for std docs
+//!   without a nightly toolchain. Without it, ferritin-common doesn't make network requests and
+//!   doesn't depend on an HTTP client.
+//!
+//! # WebAssembly
+//!
+//! ferritin-common builds for wasm targets with default features disabled, for viewers that run
+//! in a browser. There, [`StdSource`](sources::StdSource) and
+//! [`LocalSource`](sources::LocalSource) don't exist, since they run rustup and cargo; crates
+//! are loaded from JSON fetched by the page with [`RustdocData::from_json_bytes`], through a
+//! [`DocSource`](sources::DocSource) added with [`Navigator::with_source`]. Search runs on the
+//! calling thread.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ferritin_common::Navigator;
+//! use ferritin_common::sources::{LocalSource, StdSource};
+//! use std::path::Path;
+//!
+//! let navigator = Navigator::default()
+//!     .with_std_source(StdSource::from_rustup())
+//!     .with_local_source(LocalSource::load(Path::new(".")).ok());
+//!
+//! let mut suggestions = vec![];
+//! if let Some(item) = navigator.resolve_path("std::vec::Vec", &mut suggestions) {
+//!     println!("{:?}: {:?}", item.kind(), item.docs);
+//! }
+//!
+//! if let Ok(results) = navigator.search("push onto a vector", &["std"]) {
+//!     for result in results.iter().take(5) {
+//!         println!("{} {:?}", result.crate_name, result.id_path);
+//!     }
+//! }
+//! ```
+
 // Re-export rustdoc_types for convenience
 pub use rustdoc_types;
 
+pub mod cancel;
+pub mod cfg;
 pub mod conversions;
 pub mod crate_name;
 pub mod doc_ref;
+pub mod item_index;
 pub mod iterators;
 mod navigator;
 mod rustdoc_data;
 pub mod search;
 pub mod sources;
+pub mod std_builtins;
 pub mod string_utils;
+pub mod toolchain;
 
 // Re-export commonly used types
+pub use cancel::CancelToken;
 pub use crate_name::CrateName;
 pub use doc_ref::DocRef;
-pub use navigator::{CrateInfo, Navigator};
+pub use navigator::{CrateInfo, Navigator, Suggestion, parse_version_req};
 pub use rustdoc_data::RustdocData;
 pub use sources::CrateProvenance;
 
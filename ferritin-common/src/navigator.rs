@@ -1,13 +1,20 @@
 //! Navigator - orchestrates documentation lookup across multiple sources
 
+use crate::CancelToken;
 use crate::CrateName;
 use crate::DocRef;
 use crate::RustdocData;
-use crate::search::SearchIndex;
-use crate::sources::{CrateProvenance, DocsRsSource, LocalSource, Source, StdSource};
+use crate::search::{Ranking, SearchIndex};
+#[cfg(feature = "network")]
+use crate::sources::DocsRsSource;
+use crate::sources::{CrateProvenance, DocSource, JsonFileSource, PackageMetadata};
+#[cfg(not(target_family = "wasm"))]
+use crate::sources::{LocalSource, StdSource};
+use crate::std_builtins::std_builtin_path;
 use crate::string_utils::case_aware_jaro_winkler;
 use elsa::sync::FrozenMap;
 use fieldwork::Fieldwork;
+use rustc_hash::FxHashMap;
 use rustdoc_types::{Id, Item, ItemEnum, ItemKind};
 use semver::Version;
 use semver::VersionReq;
@@ -15,12 +22,14 @@ use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Debug;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 // /// Key for identifying crates in the working set
 // /// Version is None for workspace/local crates, Some(semver) for published crates
 // type CrateKey = (String, Option<String>);
 
-#[derive(Fieldwork)]
+#[derive(Debug, Fieldwork)]
 #[fieldwork(get)]
 pub struct Suggestion<'a> {
     path: String,
@@ -47,6 +56,40 @@ pub(crate) fn parse_docsrs_url(url: &str) -> Option<(&str, &str)> {
     }
 }
 
+/// Parse the version in a `crate@version` specifier
+///
+/// A complete version such as `1.0.190` selects exactly that version rather than the newest
+/// compatible one, while partial versions and requirements (`1.2`, `^1.2`, `>=1.1, <1.5`) select
+/// the newest matching version. Anything unparseable matches every version.
+pub fn parse_version_req(version: &str) -> VersionReq {
+    let version = version.trim();
+    let version_req = if Version::parse(version).is_ok() {
+        VersionReq::parse(&format!("={version}"))
+    } else {
+        VersionReq::parse(version)
+    };
+    version_req.unwrap_or(VersionReq::STAR)
+}
+
+/// Split a `crate@version` specifier into the crate name and its version requirement
+///
+/// A bare crate name accepts any version.
+pub(crate) fn parse_crate_specifier(specifier: &str) -> (&str, VersionReq) {
+    match specifier.split_once('@') {
+        Some((crate_name, version)) => (crate_name, parse_version_req(version)),
+        None => (specifier, VersionReq::STAR),
+    }
+}
+
+/// Whether a working set entry satisfies a version requirement, so that no other version needs
+/// to be loaded
+fn accepts(data: &Option<RustdocData>, version_req: &VersionReq) -> bool {
+    *version_req == VersionReq::STAR
+        || data
+            .as_ref()
+            .is_some_and(|data| data.version().is_none_or(|v| version_req.matches(v)))
+}
+
 /// External crate info extracted from html_root_url
 #[derive(Debug, Clone)]
 struct ExternalCrateInfo {
@@ -67,23 +110,74 @@ pub struct CrateInfo {
     pub(crate) default_crate: bool,
     pub(crate) used_by: Vec<String>,
     pub(crate) json_path: Option<PathBuf>,
+    /// Names the crate is imported under through `package = "…"` renames in Cargo.toml
+    pub(crate) aliases: Vec<String>,
+    /// The package's README file, for crates with a checkout on disk
+    pub(crate) readme: Option<PathBuf>,
+}
+
+impl CrateInfo {
+    /// Describe a crate found by a [`DocSource`] added with [`Navigator::with_source`]
+    pub fn new(name: impl Into<String>, version: Option<Version>) -> Self {
+        Self {
+            provenance: CrateProvenance::Custom,
+            version,
+            description: None,
+            name: name.into(),
+            default_crate: false,
+            used_by: vec![],
+            json_path: None,
+            aliases: vec![],
+            readme: None,
+        }
+    }
+
+    /// Add a description, shown when listing crates
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add the path of the package's README, shown on the crate's root page
+    pub fn with_readme(mut self, readme: impl Into<PathBuf>) -> Self {
+        self.readme = Some(readme.into());
+        self
+    }
 }
 
 /// Navigator orchestrates documentation lookup across multiple sources
 ///
 /// Sources are checked in this order:
-/// 1. std (if crate name matches RUST_CRATES)
-/// 2. local (if LocalSource is present and has the crate)
-/// 3. docs.rs (if DocsRsSource is present)
+/// 1. JSON files (if JsonFileSource is present and has a file named after the crate)
+/// 2. std (if crate name matches RUST_CRATES)
+/// 3. local (if LocalSource is present and has the crate)
+/// 4. sources added with [`Navigator::with_source`], in the order they were added
+/// 5. docs.rs (if DocsRsSource is present)
 #[derive(Fieldwork, Default)]
 #[fieldwork(get, opt_in, with)]
 pub struct Navigator {
+    #[cfg(not(target_family = "wasm"))]
     #[field]
     std_source: Option<StdSource>,
+    #[cfg(feature = "network")]
     #[field]
     docsrs_source: Option<DocsRsSource>,
+    #[cfg(not(target_family = "wasm"))]
     #[field]
     local_source: Option<LocalSource>,
+    #[field]
+    json_file_source: Option<JsonFileSource>,
+    /// Sources added with [`Navigator::with_source`]
+    custom_sources: Vec<Box<dyn DocSource>>,
+
+    /// Stops loading and searching when cancelled, without caching the crates that were
+    /// skipped as failures
+    #[field]
+    cancel_token: CancelToken,
+
+    /// How search results are ranked
+    #[field]
+    ranking: Ranking,
 
     /// Cached docs.
     ///
@@ -91,8 +185,22 @@ pub struct Navigator {
     /// all references to &'a RustdocData or DocRef<'a> are borrowing from this map.
     ///
     /// A None value indicates permanent failure.
+    ///
+    /// Each crate name holds the first version loaded under that name, which is the one bare
+    /// paths like `syn::Ident` refer to.
     working_set: FrozenMap<CrateName<'static>, Box<Option<RustdocData>>>,
 
+    /// Versions of crates requested with a specifier like `syn@1` that differ from the version
+    /// held in the working set, as when a workspace resolves both `syn 1.x` and `syn 2.x`.
+    ///
+    /// A None value indicates permanent failure.
+    other_versions: FrozenMap<(CrateName<'static>, Version), Box<Option<RustdocData>>>,
+
+    /// When each loaded crate was last asked for, as a tick of `use_clock`, keyed by the
+    /// address of its RustdocData (which is boxed, so it never moves)
+    last_used: Mutex<FxHashMap<usize, u64>>,
+    use_clock: AtomicU64,
+
     /// Map from internal name (underscores) to real name/version from external_crates
     external_crate_names: FrozenMap<CrateName<'static>, Box<ExternalCrateInfo>>,
 
@@ -104,43 +212,87 @@ pub struct Navigator {
 
 impl Debug for Navigator {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Navigator")
-            .field("std_source", &self.std_source)
-            .field("docsrs_source", &self.docsrs_source)
-            .field("local_source", &self.local_source)
+        let mut debug = f.debug_struct("Navigator");
+        #[cfg(not(target_family = "wasm"))]
+        debug.field("std_source", &self.std_source);
+        #[cfg(feature = "network")]
+        debug.field("docsrs_source", &self.docsrs_source);
+        #[cfg(not(target_family = "wasm"))]
+        debug.field("local_source", &self.local_source);
+        debug
+            .field("json_file_source", &self.json_file_source)
+            .field("custom_sources", &self.custom_sources.len())
             .finish()
     }
 }
 impl Navigator {
+    /// Add a source of documentation beyond the built-in ones, such as an internal docs
+    /// artifact store
+    ///
+    /// Added sources are tried after local crates and before docs.rs, in the order they were
+    /// added. The crates they look up should have [`CrateProvenance::Custom`].
+    pub fn with_source(mut self, source: Box<dyn DocSource>) -> Self {
+        self.custom_sources.push(source);
+        self
+    }
+
     /// List all available crate names from all sources
-    /// Returns crate names from std library and local workspace/dependencies
+    /// Returns crate names from JSON files, std library, local workspace/dependencies and added
+    /// sources
     pub fn list_available_crates(&self) -> impl Iterator<Item = &CrateInfo> {
         std::iter::empty()
-            .chain(self.std_source.iter().flat_map(|x| x.list_available()))
-            .chain(self.local_source.iter().flat_map(|x| x.list_available()))
+            .chain(
+                self.json_file_source
+                    .iter()
+                    .flat_map(|x| x.list_available()),
+            )
+            .chain(self.std_lib().into_iter().flat_map(|x| x.list_available()))
+            .chain(self.local().into_iter().flat_map(|x| x.list_available()))
+            .chain(self.custom_sources.iter().flat_map(|x| x.list_available()))
     }
 
     /// Look up a crate by name, returning canonical name and metadata
-    /// Tries sources in priority order: std, local, docs.rs
+    /// Tries sources in priority order: JSON files, std, local, docs.rs
     pub fn lookup_crate<'a>(
         &'a self,
         name: &str,
         version: &VersionReq,
     ) -> Option<Cow<'a, CrateInfo>> {
         log::info!("Resolving {name:?}, version {version}");
-        self.std_source()
-            .and_then(|s| s.lookup(name, version))
-            .or_else(|| self.local_source().and_then(|s| s.lookup(name, version)))
-            .or_else(|| self.docsrs_source().and_then(|s| s.lookup(name, version)))
+        self.sources()
+            .find_map(|source| source.lookup(name, version))
+    }
+
+    /// Look up a crate like [`Navigator::lookup_crate`], awaiting docs.rs instead of blocking
+    pub async fn lookup_crate_async<'a>(
+        &'a self,
+        name: &str,
+        version: &VersionReq,
+    ) -> Option<Cow<'a, CrateInfo>> {
+        log::info!("Resolving {name:?}, version {version}");
+        for source in self.sources() {
+            if let Some(crate_info) = source.lookup_async(name, version).await {
+                return Some(crate_info);
+            }
+        }
+        None
     }
 
     /// Get the project root path if a local context exists
+    #[cfg(not(target_family = "wasm"))]
     pub fn project_root(&self) -> Option<&std::path::Path> {
         self.local_source.as_ref().map(|p| p.project_root())
     }
 
+    /// On wasm targets there's no local project
+    #[cfg(target_family = "wasm")]
+    pub fn project_root(&self) -> Option<&std::path::Path> {
+        None
+    }
+
     /// Resolve a path like "std::vec::Vec" or "tokio::runtime::Runtime"
-    /// or (custom format for this crate) "tokio@1::runtime::Runtime" or "serde@1.0.228::de"
+    /// or (custom format for this crate) "tokio@1::runtime::Runtime", "serde@1.0.228::de" or
+    /// "serde@^1.0.200::de" (see [`parse_version_req`])
     ///
     /// This is the primary string entrypoint for any user-generated crate or type specification
     pub fn resolve_path<'a>(
@@ -158,14 +310,17 @@ impl Navigator {
             (path, None)
         };
 
-        let (crate_name, version_req) = if let Some(at) = crate_specifier.find("@") {
-            (
-                &crate_specifier[..at],
-                VersionReq::parse(&crate_specifier[at + 1..]).unwrap_or(VersionReq::STAR),
-            )
-        } else {
-            (crate_specifier, VersionReq::STAR)
-        };
+        let (crate_name, version_req) = parse_crate_specifier(crate_specifier);
+
+        // `u32`, `str::len` and `match` name std's pages for primitive types and keywords,
+        // unless a crate in the workspace goes by the same name
+        if let Some(builtin_path) = std_builtin_path(path)
+            && !self
+                .list_available_crates()
+                .any(|crate_info| crate_info.name == crate_name)
+        {
+            return self.resolve_path(&builtin_path, suggestions);
+        }
 
         let Some(crate_data) = self.load_crate(crate_name, &version_req) else {
             suggestions.extend(self.list_available_crates().map(|crate_info| Suggestion {
@@ -177,7 +332,7 @@ impl Navigator {
         };
 
         // Start from crate root
-        let item = crate_data.get(self, &crate_data.root)?;
+        let item = crate_data.get(self, &crate_data.root())?;
         if let Some(path_start_index) = path_start_index {
             // Try tree traversal first: this returns the canonical public item (e.g. the
             // re-exported module, not the primitive of the same name).
@@ -195,7 +350,7 @@ impl Navigator {
             if let Some(item) = crate_data
                 .path_to_id
                 .get(suffix)
-                .and_then(|&id| crate_data.index.get(&id))
+                .and_then(|id| crate_data.items().get(id))
                 .map(|item| DocRef::new(self, crate_data, item))
             {
                 return Some(item);
@@ -210,7 +365,7 @@ impl Navigator {
                 let parent_suffix = &suffix[..sep];
                 let child_start = path_start_index + sep + 2;
                 if let Some(&parent_id) = crate_data.path_to_id.get(parent_suffix)
-                    && let Some(parent_item) = crate_data.index.get(&parent_id)
+                    && let Some(parent_item) = crate_data.items().get(&parent_id)
                 {
                     let parent_ref = DocRef::new(self, crate_data, parent_item);
                     return self.find_children_recursive(
@@ -229,13 +384,28 @@ impl Navigator {
     }
 
     pub fn canonicalize(&self, name: &str) -> CrateName<'static> {
-        self.std_source()
-            .and_then(|s| s.canonicalize(name))
-            .or_else(|| self.local_source().and_then(|s| s.canonicalize(name)))
-            .or_else(|| self.docsrs_source().and_then(|s| s.canonicalize(name)))
+        self.sources()
+            .find_map(|source| source.canonicalize(name))
             .unwrap_or_else(|| CrateName::from(String::from(name)))
     }
 
+    /// Iterate over crates that have already been loaded into the working set.
+    ///
+    /// This never triggers loading, and crates that failed to load are skipped.
+    pub fn loaded_crates(&self) -> impl Iterator<Item = &RustdocData> {
+        let other_versions = self
+            .other_versions
+            .keys_cloned()
+            .into_iter()
+            .filter_map(move |key| self.other_versions.get(&key)?.as_ref());
+
+        self.working_set
+            .keys_cloned()
+            .into_iter()
+            .filter_map(move |name| self.working_set.get(&name)?.as_ref())
+            .chain(other_versions)
+    }
+
     /// Load a crate by name and optional version
     ///
     /// If version is None:
@@ -245,9 +415,82 @@ impl Navigator {
     ///
     /// Returns None if the crate cannot be found in any source
     pub fn load_crate(&self, name: &str, version_req: &VersionReq) -> Option<&RustdocData> {
+        let data = self.find_or_load_crate(name, version_req)?;
+        self.mark_used(data);
+        Some(data)
+    }
+
+    /// Load a crate like [`Navigator::load_crate`], awaiting docs.rs instead of blocking on it
+    ///
+    /// Crates from docs.rs are resolved, fetched and cached without blocking. Std, local and JSON
+    /// file crates are read from disk (and local ones built if needed) on the polling thread, as
+    /// `load_crate` does.
+    pub async fn load_crate_async(
+        &self,
+        name: &str,
+        version_req: &VersionReq,
+    ) -> Option<&RustdocData> {
+        let data = self.find_or_load_crate_async(name, version_req).await?;
+        self.mark_used(data);
+        Some(data)
+    }
+
+    /// Count a loaded crate as just used, as loading it does, for a crate reached through an
+    /// item already in hand
+    pub fn mark_used(&self, data: &RustdocData) {
+        let tick = self.use_clock.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut last_used) = self.last_used.lock() {
+            last_used.insert(std::ptr::from_ref(data) as usize, tick);
+        }
+    }
+
+    /// A point in the order crates are used, for finding the crates used after it with
+    /// [`Navigator::used_since`]
+    pub fn use_mark(&self) -> u64 {
+        self.use_clock.load(Ordering::Relaxed)
+    }
+
+    /// Whether a loaded crate has been used since `mark` was taken with [`Navigator::use_mark`]
+    pub fn used_since(&self, data: &RustdocData, mark: u64) -> bool {
+        self.last_used.lock().is_ok_and(|last_used| {
+            last_used
+                .get(&(std::ptr::from_ref(data) as usize))
+                .is_some_and(|&tick| tick >= mark)
+        })
+    }
+
+    /// Loaded crates, most recently used first
+    pub fn recently_used_crates(&self) -> Vec<&RustdocData> {
+        let mut crates: Vec<_> = self.loaded_crates().collect();
+        if let Ok(last_used) = self.last_used.lock() {
+            crates.sort_by_key(|data| {
+                std::cmp::Reverse(
+                    last_used
+                        .get(&(std::ptr::from_ref(*data) as usize))
+                        .copied(),
+                )
+            });
+        }
+        crates
+    }
+
+    /// A rough estimate of the memory held by loaded crates
+    ///
+    /// See [`RustdocData::estimated_memory`].
+    pub fn estimated_memory(&self) -> usize {
+        self.loaded_crates()
+            .map(RustdocData::estimated_memory)
+            .sum()
+    }
+
+    fn find_or_load_crate(&self, name: &str, version_req: &VersionReq) -> Option<&RustdocData> {
         let crate_name = self.canonicalize(name);
         if let Some(data) = self.working_set.get(&crate_name) {
-            return data.as_ref();
+            return if accepts(data, version_req) {
+                data.as_ref()
+            } else {
+                self.load_other_version(name, version_req)
+            };
         }
 
         log::info!("Loading {name}@{version_req}");
@@ -275,11 +518,64 @@ impl Navigator {
         } else {
             log::info!("Resolved {resolved_name}");
         }
+        // There's no clock to time loads with on wasm targets
+        #[cfg(not(target_family = "wasm"))]
         let start = std::time::Instant::now();
         let result = self.load(&resolved_name, resolved_version.as_ref(), provenance_hint);
-        let elapsed = start.elapsed();
-        log::debug!("⏱️ Total load time for {}: {:?}", resolved_name, elapsed);
+        #[cfg(not(target_family = "wasm"))]
+        log::debug!(
+            "⏱️ Total load time for {}: {:?}",
+            resolved_name,
+            start.elapsed()
+        );
 
+        self.insert_loaded(resolved_name, result)
+    }
+
+    async fn find_or_load_crate_async(
+        &self,
+        name: &str,
+        version_req: &VersionReq,
+    ) -> Option<&RustdocData> {
+        let crate_name = self.canonicalize(name);
+        if let Some(data) = self.working_set.get(&crate_name) {
+            return if accepts(data, version_req) {
+                data.as_ref()
+            } else {
+                self.load_other_version_async(name, version_req).await
+            };
+        }
+
+        log::info!("Loading {name}@{version_req} asynchronously");
+
+        let (resolved_name, resolved_version, provenance_hint) =
+            if let Some(external_crate) = self.external_crate_names.get(&crate_name) {
+                (
+                    external_crate.name.to_string(),
+                    Some(external_crate.version.clone()),
+                    None,
+                )
+            } else {
+                let lookup_result = self.lookup_crate_async(name, version_req).await?;
+                (
+                    lookup_result.name.to_string(),
+                    lookup_result.version.clone(),
+                    Some(lookup_result.provenance),
+                )
+            };
+
+        let result = self
+            .load_async(&resolved_name, resolved_version.as_ref(), provenance_hint)
+            .await;
+        self.insert_loaded(resolved_name, result)
+    }
+
+    /// Add a freshly loaded crate to the working set, or remember that it failed to load
+    fn insert_loaded(
+        &self,
+        resolved_name: String,
+        result: Option<RustdocData>,
+    ) -> Option<&RustdocData> {
         match result {
             Some(mut data) => {
                 // Index external crates for future lookups
@@ -293,6 +589,10 @@ impl Navigator {
                     .insert(CrateName::from(resolved_name), Box::new(Some(data)))
                     .as_ref()
             }
+            None if self.cancel_token.is_cancelled() => {
+                log::info!("Loading {resolved_name} was cancelled");
+                None
+            }
             None => {
                 // // Mark as failed
                 self.working_set
@@ -302,6 +602,194 @@ impl Navigator {
         }
     }
 
+    /// Load a version of a crate other than the one in the working set
+    ///
+    /// These are cached by name and resolved version, so `syn@1` and `syn@1.0` share docs.
+    fn load_other_version(&self, name: &str, version_req: &VersionReq) -> Option<&RustdocData> {
+        let crate_info = self.lookup_crate(name, version_req)?;
+        let version = crate_info.version.clone()?;
+        let key = (CrateName::from(crate_info.name.clone()), version);
+        if let Some(data) = self.other_versions.get(&key) {
+            return data.as_ref();
+        }
+
+        log::info!("Loading {}@{} alongside the working set", key.0, key.1);
+        let data = self.load(&crate_info.name, Some(&key.1), Some(crate_info.provenance));
+        self.insert_other_version(key, data)
+    }
+
+    async fn load_other_version_async(
+        &self,
+        name: &str,
+        version_req: &VersionReq,
+    ) -> Option<&RustdocData> {
+        let crate_info = self.lookup_crate_async(name, version_req).await?;
+        let version = crate_info.version.clone()?;
+        let key = (CrateName::from(crate_info.name.clone()), version);
+        if let Some(data) = self.other_versions.get(&key) {
+            return data.as_ref();
+        }
+
+        log::info!("Loading {}@{} alongside the working set", key.0, key.1);
+        let data = self
+            .load_async(&crate_info.name, Some(&key.1), Some(crate_info.provenance))
+            .await;
+        self.insert_other_version(key, data)
+    }
+
+    fn insert_other_version(
+        &self,
+        key: (CrateName<'static>, Version),
+        data: Option<RustdocData>,
+    ) -> Option<&RustdocData> {
+        let data = data.map(|mut data| {
+            self.index_external_crates(&data);
+            data.build_path_index();
+            data
+        });
+
+        if data.is_none() && self.cancel_token.is_cancelled() {
+            return None;
+        }
+        self.other_versions.insert(key, Box::new(data)).as_ref()
+    }
+
+    /// Load a crate without adding it to the working set
+    ///
+    /// Unlike [`Navigator::load_crate`], the data is owned by the caller and dropped with it,
+    /// which suits one-off comparisons. Items in the returned data are reached through
+    /// [`RustdocData::root_item`].
+    pub fn load_detached(&self, name: &str, version_req: &VersionReq) -> Option<RustdocData> {
+        let crate_info = self.lookup_crate(name, version_req)?;
+        let mut data = self.load(
+            &crate_info.name,
+            crate_info.version.as_ref(),
+            Some(crate_info.provenance),
+        )?;
+        data.build_path_index();
+        Some(data)
+    }
+
+    /// Load a version of a crate published on docs.rs without adding it to the working set,
+    /// even when a workspace crate has the same name
+    pub fn load_published(&self, name: &str, version_req: &VersionReq) -> Option<RustdocData> {
+        let docsrs_source = self.docs_rs()?;
+        let crate_info = docsrs_source.lookup(name, version_req)?;
+        let mut data = docsrs_source.load(&crate_info.name, crate_info.version.as_ref())?;
+        data.build_path_index();
+        Some(data)
+    }
+
+    /// The README of a loaded crate's package, if its source can find one
+    ///
+    /// Dependencies without a README on disk fall back to the one published on crates.io.
+    pub fn readme(&self, crate_docs: &RustdocData) -> Option<String> {
+        let (name, version) = (crate_docs.name(), crate_docs.version());
+        self.sources_for(*crate_docs.provenance())
+            .into_iter()
+            .find_map(|source| source.readme(name, version))
+            .or_else(|| {
+                crate_docs
+                    .provenance()
+                    .is_local_dependency()
+                    .then(|| self.docs_rs()?.readme(name, version))
+                    .flatten()
+            })
+    }
+
+    /// The manifest metadata of a crate's package, from cargo metadata for local crates and from
+    /// crates.io for published ones
+    ///
+    /// Unlike [`Navigator::load_crate`], this doesn't need the crate's documentation.
+    pub fn package_metadata(
+        &self,
+        name: &str,
+        version_req: &VersionReq,
+    ) -> Option<PackageMetadata> {
+        let crate_info = self.lookup_crate(name, version_req)?;
+        self.sources_for(crate_info.provenance)
+            .into_iter()
+            .find_map(|source| {
+                source.package_metadata(&crate_info.name, crate_info.version.as_ref())
+            })
+    }
+
+    /// The docs.rs source, if there is one
+    #[cfg(feature = "network")]
+    fn docs_rs(&self) -> Option<&dyn DocSource> {
+        self.docsrs_source
+            .as_ref()
+            .map(|source| source as &dyn DocSource)
+    }
+
+    /// Without the `network` feature, nothing comes from docs.rs
+    #[cfg(not(feature = "network"))]
+    fn docs_rs(&self) -> Option<&dyn DocSource> {
+        None
+    }
+
+    /// The std source, if there is one
+    #[cfg(not(target_family = "wasm"))]
+    fn std_lib(&self) -> Option<&dyn DocSource> {
+        self.std_source.as_ref().map(|s| s as &dyn DocSource)
+    }
+
+    /// On wasm targets, std isn't documented by rustup
+    #[cfg(target_family = "wasm")]
+    fn std_lib(&self) -> Option<&dyn DocSource> {
+        None
+    }
+
+    /// The local workspace source, if there is one
+    #[cfg(not(target_family = "wasm"))]
+    fn local(&self) -> Option<&dyn DocSource> {
+        self.local_source.as_ref().map(|s| s as &dyn DocSource)
+    }
+
+    /// On wasm targets, there's no cargo to build local documentation with
+    #[cfg(target_family = "wasm")]
+    fn local(&self) -> Option<&dyn DocSource> {
+        None
+    }
+
+    /// The sources, in the order they're tried
+    fn sources(&self) -> impl Iterator<Item = &dyn DocSource> {
+        [
+            self.json_file_source.as_ref().map(|s| s as &dyn DocSource),
+            self.std_lib(),
+            self.local(),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(
+            self.custom_sources
+                .iter()
+                .map(|s| s.as_ref() as &dyn DocSource),
+        )
+        .chain(self.docs_rs())
+    }
+
+    /// The sources that crates of this provenance come from, in the order they're tried
+    fn sources_for(&self, provenance: CrateProvenance) -> Vec<&dyn DocSource> {
+        match provenance {
+            CrateProvenance::Std => self.std_lib().into_iter().collect(),
+            CrateProvenance::Workspace | CrateProvenance::LocalDependency => {
+                self.local().into_iter().collect()
+            }
+            CrateProvenance::DocsRs => self.docs_rs().into_iter().collect(),
+            CrateProvenance::JsonFile => self
+                .json_file_source
+                .iter()
+                .map(|s| s as &dyn DocSource)
+                .collect(),
+            CrateProvenance::Custom => self
+                .custom_sources
+                .iter()
+                .map(|s| s.as_ref() as &dyn DocSource)
+                .collect(),
+        }
+    }
+
     /// Try loading from the appropriate source based on lookup result
     fn load(
         &self,
@@ -310,38 +798,44 @@ impl Navigator {
         provenance_hint: Option<CrateProvenance>,
     ) -> Option<RustdocData> {
         match provenance_hint {
-            Some(CrateProvenance::Std) => {
-                log::debug!("loading from std");
-                self.std_source()?.load(crate_name, version)
-            }
-            Some(CrateProvenance::Workspace | CrateProvenance::LocalDependency) => {
-                log::debug!("loading from local");
-                self.local_source()?.load(crate_name, version)
-            }
-            Some(CrateProvenance::DocsRs) => {
-                log::debug!("loading from docs.rs");
-                self.docsrs_source()?.load(crate_name, version)
+            Some(provenance) => {
+                log::debug!("loading from {provenance:?}");
+                self.sources_for(provenance)
+                    .into_iter()
+                    .find_map(|source| source.load(crate_name, version))
             }
             None => {
                 log::debug!("No provenance hint available, cascading lookup for {crate_name}");
-                self.std_source()
-                    .and_then(|s| s.load(crate_name, version))
-                    .or_else(|| {
-                        self.local_source()
-                            .and_then(|s| s.load(crate_name, version))
-                    })
-                    .or_else(|| {
-                        self.docsrs_source()
-                            .and_then(|s| s.load(crate_name, version))
-                    })
+                self.sources()
+                    .find_map(|source| source.load(crate_name, version))
             }
         }
     }
 
+    /// Load like [`Navigator::load`], awaiting docs.rs instead of blocking on it
+    async fn load_async(
+        &self,
+        crate_name: &str,
+        version: Option<&Version>,
+        provenance_hint: Option<CrateProvenance>,
+    ) -> Option<RustdocData> {
+        let sources: Vec<_> = match provenance_hint {
+            Some(provenance) => self.sources_for(provenance),
+            None => self.sources().collect(),
+        };
+
+        for source in sources {
+            if let Some(data) = source.load_async(crate_name, version).await {
+                return Some(data);
+            }
+        }
+        None
+    }
+
     /// Index external crates from a loaded crate
     fn index_external_crates(&self, crate_data: &RustdocData) {
         log::debug!("Indexing external crates from {}", crate_data.name());
-        for external in crate_data.external_crates.values() {
+        for external in crate_data.external_crates().values() {
             if let Some(url) = &external.html_root_url
                 && let Some((real_name, version)) = parse_docsrs_url(url)
                 && let Ok(version) = Version::parse(version)
@@ -358,14 +852,18 @@ impl Navigator {
     }
 
     /// Get item from ID path
+    ///
+    /// `crate_name` may carry a version, as in the `syn@1` that search results report for
+    /// indexes built from a versioned specifier.
     pub fn get_item_from_id_path<'a>(
         &'a self,
         crate_name: &str,
         ids: &[u32],
     ) -> Option<(DocRef<'a, Item>, Vec<&'a str>)> {
         let mut path = vec![];
-        let crate_docs = self.load_crate(crate_name, &VersionReq::STAR)?;
-        let mut item = crate_docs.get(self, &crate_docs.root)?;
+        let (crate_name, version_req) = parse_crate_specifier(crate_name);
+        let crate_docs = self.load_crate(crate_name, &version_req)?;
+        let mut item = crate_docs.get(self, &crate_docs.root())?;
         path.push(item.crate_docs().name());
         for id in ids {
             item = item.get(&Id(*id))?;
@@ -468,30 +966,42 @@ fn parse_discriminated_segment(segment: &str) -> (Option<ItemKind>, &str) {
     };
     let (disc, name) = (&segment[..at], &segment[at + 1..]);
     match disc {
-        "mod" | "module" => (Some(ItemKind::Module), name),
-        "struct" => (Some(ItemKind::Struct), name),
-        "enum" => (Some(ItemKind::Enum), name),
-        "union" => (Some(ItemKind::Union), name),
-        "trait" => (Some(ItemKind::Trait), name),
-        "traitalias" => (Some(ItemKind::TraitAlias), name),
-        "fn" | "function" | "method" => (Some(ItemKind::Function), name),
-        "tyalias" | "typealias" => (Some(ItemKind::TypeAlias), name),
-        "type" => (Some(ItemKind::AssocType), name),
-        "const" | "constant" => (Some(ItemKind::Constant), name),
-        "static" => (Some(ItemKind::Static), name),
-        "macro" => (Some(ItemKind::Macro), name),
-        "attr" => (Some(ItemKind::ProcAttribute), name),
-        "derive" => (Some(ItemKind::ProcDerive), name),
-        "prim" | "primitive" => (Some(ItemKind::Primitive), name),
-        "field" => (Some(ItemKind::StructField), name),
-        "variant" => (Some(ItemKind::Variant), name),
         // `value@` matches any value-namespace item — strip prefix, no kind filter.
         "value" => (None, name),
-        // Unrecognised prefix: treat the whole string as the item name.
-        _ => (None, segment),
+        _ => match discriminated_kind(disc) {
+            Some(kind) => (Some(kind), name),
+            // Unrecognised prefix: treat the whole string as the item name.
+            None => (None, segment),
+        },
     }
 }
 
+/// The item kind named by a rustdoc discriminator such as `"fn"` or `"mod"`, accepting the
+/// long forms (`"function"`, `"module"`) too
+pub(crate) fn discriminated_kind(disc: &str) -> Option<ItemKind> {
+    Some(match disc {
+        "mod" | "module" => ItemKind::Module,
+        "struct" => ItemKind::Struct,
+        "enum" => ItemKind::Enum,
+        "union" => ItemKind::Union,
+        "trait" => ItemKind::Trait,
+        "traitalias" => ItemKind::TraitAlias,
+        "fn" | "function" | "method" => ItemKind::Function,
+        "tyalias" | "typealias" => ItemKind::TypeAlias,
+        "type" => ItemKind::AssocType,
+        "const" | "constant" => ItemKind::Constant,
+        "static" => ItemKind::Static,
+        "macro" => ItemKind::Macro,
+        "attr" => ItemKind::ProcAttribute,
+        "derive" => ItemKind::ProcDerive,
+        "prim" | "primitive" => ItemKind::Primitive,
+        "keyword" => ItemKind::Keyword,
+        "field" => ItemKind::StructField,
+        "variant" => ItemKind::Variant,
+        _ => return None,
+    })
+}
+
 // Compile-time assertions that Navigator is thread-safe
 // This is required for multi-threaded interactive TUI
 #[allow(dead_code)]
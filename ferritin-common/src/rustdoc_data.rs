@@ -1,20 +1,28 @@
+use anyhow::Result;
 use fieldwork::Fieldwork;
-use rustdoc_types::{Crate, ExternalCrate, Id, Item, ItemKind};
+use rustc_hash::FxHashMap;
+use rustdoc_types::{Crate, ExternalCrate, Id, Item, ItemKind, ItemSummary, Target};
 use semver::{Version, VersionReq};
 use std::collections::HashMap;
 use std::fmt::{self, Debug, Formatter};
-use std::ops::Deref;
 use std::path::PathBuf;
 
 use crate::CrateProvenance;
 use crate::doc_ref::{self, DocRef};
+use crate::item_index::{ItemIndex, MappedJson};
 use crate::navigator::{Navigator, parse_docsrs_url};
 
 /// Wrapper around rustdoc JSON data that provides convenient query methods
 #[derive(Clone, Fieldwork, PartialEq, Eq)]
 #[fieldwork(get, rename_predicates)]
 pub struct RustdocData {
+    /// Everything but the items, whose `index` is left empty
+    ///
+    /// Not exposed as a whole, so that the empty index can't be mistaken for the crate's items.
+    #[field = false]
     pub(crate) crate_data: Crate,
+    /// The crate's items, parsed as they're reached when the JSON is memory-mapped
+    pub(crate) items: ItemIndex,
     pub(crate) name: String,
     pub(crate) provenance: CrateProvenance,
     pub(crate) fs_path: PathBuf,
@@ -46,26 +54,119 @@ impl Debug for RustdocData {
     }
 }
 
-impl Deref for RustdocData {
-    type Target = Crate;
+impl RustdocData {
+    /// Load a crate from a rustdoc JSON file, for a [`DocSource`](crate::sources::DocSource)
+    /// added with [`Navigator::with_source`]
+    ///
+    /// Formats older than the current one are converted, as they are for the built-in sources.
+    pub fn from_json_file(name: impl Into<String>, json_path: PathBuf) -> Result<Self> {
+        Self::open(name.into(), json_path, CrateProvenance::Custom)
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.crate_data
+    /// Load a crate from rustdoc JSON that's already in memory, as when it's fetched by a
+    /// browser on a wasm target
+    ///
+    /// Items are parsed as they're reached, as they are from a memory-mapped file.
+    pub fn from_json_bytes(name: impl Into<String>, json: Vec<u8>) -> Result<Self> {
+        Self::parse(
+            name.into(),
+            MappedJson::from_bytes(json),
+            PathBuf::new(),
+            CrateProvenance::Custom,
+        )
+    }
+
+    /// Memory-map and parse a rustdoc JSON file
+    pub(crate) fn open(
+        name: String,
+        json_path: PathBuf,
+        provenance: CrateProvenance,
+    ) -> Result<Self> {
+        Self::parse(name, MappedJson::open(&json_path)?, json_path, provenance)
+    }
+
+    fn parse(
+        name: String,
+        json: MappedJson,
+        json_path: PathBuf,
+        provenance: CrateProvenance,
+    ) -> Result<Self> {
+        let (crate_data, items) = json.parse()?;
+        let version = crate_data
+            .crate_version
+            .as_ref()
+            .and_then(|v| Version::parse(v).ok());
+
+        Ok(Self {
+            crate_data,
+            items,
+            name,
+            provenance,
+            fs_path: json_path,
+            version,
+            path_to_id: Default::default(),
+        })
+    }
+
+    /// The id of the crate's root module
+    pub fn root(&self) -> Id {
+        self.crate_data.root
+    }
+
+    /// The path and kind of every item this crate defines or refers to, including those of
+    /// other crates
+    pub fn paths(&self) -> &FxHashMap<Id, ItemSummary> {
+        &self.crate_data.paths
+    }
+
+    /// The crates this crate refers to, keyed by the `crate_id` of [`ItemSummary`]
+    pub fn external_crates(&self) -> &FxHashMap<u32, ExternalCrate> {
+        &self.crate_data.external_crates
+    }
+
+    /// The crate's version as rustdoc recorded it, which for std is a toolchain version such as
+    /// `1.90.0-nightly` rather than a published one
+    pub fn crate_version(&self) -> Option<&str> {
+        self.crate_data.crate_version.as_deref()
+    }
+
+    /// The platform the crate was documented for
+    pub fn target(&self) -> &Target {
+        &self.crate_data.target
+    }
+
+    /// The rustdoc JSON format version, after any conversion from an older one
+    pub fn format_version(&self) -> u32 {
+        self.crate_data.format_version
     }
-}
 
-impl RustdocData {
     pub(crate) fn get<'a>(&'a self, navigator: &'a Navigator, id: &Id) -> Option<DocRef<'a, Item>> {
-        let item = self.crate_data.index.get(id)?;
+        let item = self.items.get(id)?;
         Some(DocRef::new(navigator, self, item))
     }
 
+    /// A rough estimate of the memory held by this crate's parsed data, which takes about as
+    /// much as the JSON it was parsed from
+    pub fn estimated_memory(&self) -> usize {
+        self.items.parsed_json_len().unwrap_or_else(|| {
+            self.fs_path
+                .metadata()
+                .map_or(0, |metadata| metadata.len() as usize)
+        })
+    }
+
     pub fn path<'a>(&'a self, id: &Id) -> Option<doc_ref::Path<'a>> {
-        self.paths.get(id).map(|summary| summary.into())
+        self.paths().get(id).map(|summary| summary.into())
     }
 
     pub fn root_item<'a>(&'a self, navigator: &'a Navigator) -> DocRef<'a, Item> {
-        DocRef::new(navigator, self, &self.index[&self.root])
+        DocRef::new(
+            navigator,
+            self,
+            self.items
+                .get(&self.root())
+                .expect("the root item is in the index"),
+        )
     }
 
     pub fn traverse_to_crate_by_id<'a>(
@@ -82,7 +183,7 @@ impl RustdocData {
             name,
             html_root_url,
             ..
-        } = self.external_crates.get(&id)?;
+        } = self.external_crates().get(&id)?;
 
         let (name, version_req) = html_root_url.as_deref().and_then(parse_docsrs_url).map_or(
             (&**name, VersionReq::STAR),
@@ -102,7 +203,7 @@ impl RustdocData {
         navigator: &'a Navigator,
         id: Id,
     ) -> Option<DocRef<'a, Item>> {
-        let item_summary = self.paths.get(&id)?;
+        let item_summary = self.paths().get(&id)?;
         let crate_ = self.traverse_to_crate_by_id(navigator, item_summary.crate_id)?;
         crate_
             .root_item(navigator)
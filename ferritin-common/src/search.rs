@@ -1,9 +1,20 @@
 pub mod indexer;
+mod query;
 
-use crate::{Navigator, navigator::Suggestion};
+use crate::CrateName;
+use crate::Navigator;
+use crate::navigator::{Suggestion, parse_crate_specifier};
+#[cfg(not(target_family = "wasm"))]
 use rayon::prelude::*;
+#[cfg(not(target_family = "wasm"))]
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
 pub use indexer::*;
+pub use query::{DeprecatedItems, Query};
+
+/// How often [`Navigator::search_with_progress`] reports results while crates remain
+pub const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
 
 impl Navigator {
     /// Search across multiple crates with BM25 scoring
@@ -17,53 +28,186 @@ impl Navigator {
         query: &'query str,
         crate_names: &'query [&'query str],
     ) -> Result<Vec<ScoredResult<'query>>, Vec<Suggestion<'nav>>> {
+        self.search_with_progress(query, crate_names, |_, _| {})
+    }
+
+    /// Search across multiple crates, reporting results from the crates searched so far
+    ///
+    /// Crates are loaded and indexed on a thread pool, and their results merged as they arrive.
+    /// While crates remain, `on_progress` is called with the results so far and the number of
+    /// crates still to be searched: as soon as the first crate's results are in, and then at
+    /// most every [`PROGRESS_INTERVAL`]. Scores shift as crates arrive, since term rarity is
+    /// measured across every crate searched.
+    ///
+    /// Once the navigator's [`CancelToken`](crate::CancelToken) is cancelled, crates not yet
+    /// started are skipped and the results so far are returned.
+    pub fn search_with_progress<'nav, 'query>(
+        &'nav self,
+        query: &'query str,
+        crate_names: &'query [&'query str],
+        on_progress: impl FnMut(&[ScoredResult<'query>], usize),
+    ) -> Result<Vec<ScoredResult<'query>>, Vec<Suggestion<'nav>>> {
+        self.search_streaming(query, crate_names, usize::MAX, on_progress)
+    }
+
+    /// Like [`search_with_progress`](Self::search_with_progress), but `on_progress` is only
+    /// given the top `k` results so far
+    ///
+    /// Selecting the top results from each update rather than sorting every match keeps updates
+    /// cheap on large workspaces, where a common term can match hundreds of thousands of items.
+    /// The returned results are all of them, sorted.
+    pub fn search_streaming<'nav, 'query>(
+        &'nav self,
+        query: &'query str,
+        crate_names: &'query [&'query str],
+        k: usize,
+        mut on_progress: impl FnMut(&[ScoredResult<'query>], usize),
+    ) -> Result<Vec<ScoredResult<'query>>, Vec<Suggestion<'nav>>> {
+        let query = Query::parse(query);
+        let crate_names = if query.crates().is_empty() {
+            crate_names
+        } else {
+            query.crates()
+        };
         if crate_names.is_empty() {
             return Ok(vec![]);
         }
 
-        // Load indexes and search in parallel
-        let results: Vec<_> = crate_names
-            .par_iter()
-            .map(|&crate_name| {
-                self.get_or_build_search_index(crate_name)
-                    .map(|index| (crate_name, index.search(query)))
-            })
-            .collect();
-
-        // Separate successes from failures
-        let mut crate_results = Vec::new();
+        let mut scorer = BM25Scorer::with_ranking(*self.ranking())
+            .with_deprecated_demoted(query.deprecated() == DeprecatedItems::Demote);
         let mut first_error = None;
+        let mut remaining = crate_names.len();
+        let mut last_progress = None;
 
-        for result in results {
+        self.search_each(&query, crate_names, |crate_name, result| {
+            remaining -= 1;
             match result {
-                Ok(data) => crate_results.push(data),
+                Ok(results) => scorer.add(crate_name, results),
                 Err(suggestions) if first_error.is_none() => first_error = Some(suggestions),
                 Err(_) => {}
             }
-        }
+
+            if remaining > 0
+                && !scorer.is_empty()
+                && !self.cancel_token().is_cancelled()
+                && progress_due(&mut last_progress)
+            {
+                on_progress(&scorer.top(k), remaining);
+            }
+        });
 
         // If no crates succeeded, return the first error
-        if crate_results.is_empty() && first_error.is_some() {
-            return Err(first_error.unwrap());
+        if scorer.is_empty()
+            && let Some(suggestions) = first_error
+        {
+            return Err(suggestions);
+        }
+
+        Ok(scorer.score())
+    }
+
+    /// Search each crate, passing `on_crate` each crate's results as they're ready
+    ///
+    /// Crates are loaded and indexed in parallel, while `on_crate` runs on the calling thread.
+    #[cfg(not(target_family = "wasm"))]
+    fn search_each<'nav, 'query>(
+        &'nav self,
+        query: &Query<'query>,
+        crate_names: &[&'query str],
+        mut on_crate: impl FnMut(&'query str, Result<SearchResults<'query>, Vec<Suggestion<'nav>>>),
+    ) {
+        let (sender, receiver) = mpsc::channel();
+
+        std::thread::scope(|scope| {
+            // Load indexes and search in parallel, sending each crate's results when ready
+            scope.spawn(move || {
+                crate_names
+                    .par_iter()
+                    .for_each_with(sender, |sender, &crate_name| {
+                        if self.cancel_token().is_cancelled() {
+                            return;
+                        }
+                        let result = self.search_crate(query, crate_name);
+                        let _ = sender.send((crate_name, result));
+                    });
+            });
+
+            for (crate_name, result) in receiver {
+                on_crate(crate_name, result);
+            }
+        });
+    }
+
+    /// Search each crate in turn, since wasm targets can't spawn threads
+    #[cfg(target_family = "wasm")]
+    fn search_each<'nav, 'query>(
+        &'nav self,
+        query: &Query<'query>,
+        crate_names: &[&'query str],
+        mut on_crate: impl FnMut(&'query str, Result<SearchResults<'query>, Vec<Suggestion<'nav>>>),
+    ) {
+        for &crate_name in crate_names {
+            if self.cancel_token().is_cancelled() {
+                return;
+            }
+            let result = self.search_crate(query, crate_name);
+            on_crate(crate_name, result);
+        }
+    }
+
+    /// Search one crate for the terms of the query, dropping the results its filters reject and
+    /// marking its exact matches
+    fn search_crate<'nav, 'query>(
+        &'nav self,
+        query: &Query<'query>,
+        crate_name: &'query str,
+    ) -> Result<SearchResults<'query>, Vec<Suggestion<'nav>>> {
+        let mut results = self
+            .get_or_build_search_index(crate_name)?
+            .search_terms(query.terms(), query.named_item());
+        if query.unsafe_only() {
+            results.results.retain(|result| result.is_unsafe);
+        }
+        if query.deprecated() == DeprecatedItems::Only {
+            results.results.retain(|result| result.is_deprecated);
         }
 
-        // Aggregate results with BM25 scoring
-        let mut scorer = BM25Scorer::new();
-        for (crate_name, results) in crate_results {
-            scorer.add(crate_name, results);
+        let check_every_result = query.has_exact_operators() || query.has_filters();
+        if check_every_result || query.named_item().is_some() {
+            results.results.retain_mut(|result| {
+                // For a query that's a name or path, only the items with that name can match,
+                // and the index has already found them
+                if !check_every_result && !result.exact {
+                    return true;
+                }
+                let Some((item, path)) = self.get_item_from_id_path(crate_name, &result.id_path)
+                else {
+                    return !query.has_filters();
+                };
+                result.exact = query.ranks_exact_matches() && query.is_exact_match(item, &path);
+                query.passes_filters(item, &path)
+            });
         }
 
-        Ok(scorer.score())
+        Ok(results)
     }
 
     /// Get or build a search index for the given crate
     ///
+    /// The crate may be given as a `crate@version` specifier, which is indexed separately from
+    /// the crate's default version.
+    ///
     /// Returns Err with suggestions if the crate cannot be found
     fn get_or_build_search_index<'nav>(
         &'nav self,
-        crate_name: &str,
+        crate_specifier: &str,
     ) -> Result<&'nav SearchIndex, Vec<Suggestion<'nav>>> {
-        let crate_name = self.canonicalize(crate_name);
+        let (crate_name, version_req) = parse_crate_specifier(crate_specifier);
+        let crate_name = if crate_name.len() == crate_specifier.len() {
+            self.canonicalize(crate_name)
+        } else {
+            CrateName::from(format!("{}@{version_req}", self.canonicalize(crate_name)))
+        };
 
         if let Some(cached) = self.search_indexes.get(&crate_name) {
             if let Some(index) = cached.as_ref() {
@@ -77,7 +221,7 @@ impl Navigator {
         log::info!("Loading search index for {}", crate_name);
 
         // Use existing SearchIndex::load_or_build which handles disk caching
-        let result = SearchIndex::load_or_build(self, crate_name.as_ref());
+        let result = SearchIndex::load_or_build(self, crate_specifier);
 
         match result {
             Ok(index) => {
@@ -88,6 +232,8 @@ impl Navigator {
                     .unwrap();
                 Ok(index_ref)
             }
+            // A cancelled build isn't a permanent failure
+            Err(suggestions) if self.cancel_token().is_cancelled() => Err(suggestions),
             Err(suggestions) => {
                 // Cache the failure
                 self.search_indexes.insert(crate_name, Box::new(None));
@@ -96,3 +242,17 @@ impl Navigator {
         }
     }
 }
+
+/// Whether to report progress again: right away the first time, so that the first results
+/// show while slow crates are still being indexed, then at most every [`PROGRESS_INTERVAL`]
+fn progress_due(last_progress: &mut Option<Instant>) -> bool {
+    // There's no clock on wasm targets, where crates are searched one at a time anyway
+    if cfg!(target_family = "wasm") {
+        return true;
+    }
+    if last_progress.is_some_and(|last| last.elapsed() < PROGRESS_INTERVAL) {
+        return false;
+    }
+    *last_progress = Some(Instant::now());
+    true
+}
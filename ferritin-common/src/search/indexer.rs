@@ -1,15 +1,21 @@
+mod streamed;
 #[cfg(test)]
 mod tests;
 
 use fieldwork::Fieldwork;
 use memchr::memmem;
 use rkyv::rancor::Error;
+use rkyv::util::AlignedVec;
 use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use rustc_hash::FxHashMap;
 use rustc_hash::FxHasher;
-use rustdoc_types::{Item, ItemEnum, ItemSummary, StructKind, Trait};
+use rustdoc_types::{
+    AssocItemConstraintKind, GenericArg, GenericArgs, GenericBound, Id, Item, ItemEnum,
+    ItemSummary, Path as TypePath, StructKind, Term, Type,
+};
 use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, HashSet};
 use std::fs::OpenOptions;
 use std::fs::{self, File};
@@ -19,6 +25,8 @@ use std::ops::AddAssign;
 use std::path::Path;
 use std::time::SystemTime;
 
+use streamed::{IndexedInner, IndexedItem};
+
 use crate::{
     crate_name::CrateName,
     doc_ref::DocRef,
@@ -26,21 +34,23 @@ use crate::{
 };
 
 /// Represents either a resolved Item or an unresolved ItemSummary for link counting
+///
+/// Resolved items are named by their crate and id, so that items of a crate being read from its
+/// JSON needn't be parsed to be counted.
 #[derive(Clone, Copy, Debug)]
 enum ItemOrSummary<'a> {
-    Item(DocRef<'a, Item>),
+    Item(&'a str, Id),
     Summary(DocRef<'a, ItemSummary>),
 }
 
 impl<'a> ItemOrSummary<'a> {
-    /// Try to convert to a resolved Item, filtering by visited crates.
+    /// Try to resolve to an item's crate and id, filtering by visited crates.
     /// Returns None if the item's crate is not in the visited set.
-    fn try_to_item(self, visited_crates: &HashSet<CrateName>) -> Option<DocRef<'a, Item>> {
+    fn try_to_item(self, visited_crates: &HashSet<CrateName>) -> Option<(&'a str, Id)> {
         match self {
-            ItemOrSummary::Item(item) => {
-                let crate_name: CrateName = item.crate_docs().name().into();
-                if visited_crates.contains(&crate_name) {
-                    Some(item)
+            ItemOrSummary::Item(crate_name, id) => {
+                if visited_crates.contains(&CrateName::from(crate_name)) {
+                    Some((crate_name, id))
                 } else {
                     None
                 }
@@ -69,6 +79,7 @@ impl<'a> ItemOrSummary<'a> {
                 target_crate
                     .root_item(summary.navigator())
                     .find_by_path(summary.path.iter().skip(1))
+                    .map(|item| (item.crate_docs().name(), item.id))
             }
         }
     }
@@ -77,8 +88,8 @@ impl<'a> ItemOrSummary<'a> {
 impl PartialEq for ItemOrSummary<'_> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (ItemOrSummary::Item(a), ItemOrSummary::Item(b)) => {
-                a.crate_docs().name() == b.crate_docs().name() && a.id == b.id
+            (ItemOrSummary::Item(a_crate, a_id), ItemOrSummary::Item(b_crate, b_id)) => {
+                a_crate == b_crate && a_id == b_id
             }
             (ItemOrSummary::Summary(a), ItemOrSummary::Summary(b)) => {
                 a.crate_docs().name() == b.crate_docs().name()
@@ -95,10 +106,10 @@ impl Eq for ItemOrSummary<'_> {}
 impl std::hash::Hash for ItemOrSummary<'_> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         match self {
-            ItemOrSummary::Item(item) => {
+            ItemOrSummary::Item(crate_name, id) => {
                 0.hash(state); // discriminant
-                item.crate_docs().name().hash(state);
-                item.id.hash(state);
+                crate_name.hash(state);
+                id.hash(state);
             }
             ItemOrSummary::Summary(summary) => {
                 1.hash(state); // discriminant
@@ -110,6 +121,131 @@ impl std::hash::Hash for ItemOrSummary<'_> {
     }
 }
 
+/// An item reached while indexing, with the name it's re-exported under, if any
+#[derive(Debug)]
+enum Node<'a> {
+    /// An item of the crate being indexed, read from its JSON when it's visited
+    Streamed {
+        /// The crate's root, for resolving the item's links and re-exports
+        root: DocRef<'a, Item>,
+        id: Id,
+        name: Option<String>,
+    },
+    /// An already parsed item: one of another crate, or of a crate that was parsed up front
+    Parsed {
+        item: DocRef<'a, Item>,
+        name: Option<String>,
+    },
+}
+
+impl<'a> From<DocRef<'a, Item>> for Node<'a> {
+    fn from(item: DocRef<'a, Item>) -> Self {
+        Node::Parsed { item, name: None }
+    }
+}
+
+impl<'a> Node<'a> {
+    /// The item's id, and an item of the same crate to resolve its links against
+    fn id_and_context(&self) -> (Id, DocRef<'a, Item>) {
+        match self {
+            Node::Streamed { root, id, .. } => (*id, *root),
+            Node::Parsed { item, .. } => (item.id, *item),
+        }
+    }
+
+    /// The item with id `id` in the same crate as this one
+    fn member(&self, id: Id) -> Option<Node<'a>> {
+        match self {
+            Node::Streamed { root, .. } => {
+                root.crate_docs()
+                    .items()
+                    .contains(&id)
+                    .then_some(Node::Streamed {
+                        root: *root,
+                        id,
+                        name: None,
+                    })
+            }
+            Node::Parsed { item, .. } => item.get(&id).map(Node::from),
+        }
+    }
+
+    fn renamed(self, name: String) -> Self {
+        match self {
+            Node::Streamed { root, id, .. } => Node::Streamed {
+                root,
+                id,
+                name: Some(name),
+            },
+            Node::Parsed { item, .. } => Node::Parsed {
+                item,
+                name: Some(name),
+            },
+        }
+    }
+
+    /// What indexing reads of the item, under the name it was reached by
+    fn read(&self) -> Option<IndexedItem> {
+        match self {
+            Node::Streamed { root, id, name } => {
+                let crate_docs = root.crate_docs();
+                let mut item: IndexedItem = crate_docs.items().read_partial(id)?;
+                item.name = name.clone().or(item.name).or_else(|| {
+                    let summary = crate_docs.paths().get(id)?;
+                    summary.path.last().cloned()
+                });
+                Some(item)
+            }
+            Node::Parsed { item, name } => {
+                let mut indexed = IndexedItem::from_parsed(*item);
+                if name.is_some() {
+                    indexed.name.clone_from(name);
+                }
+                Some(indexed)
+            }
+        }
+    }
+}
+
+/// The crate being indexed, when its items are read from its JSON as they're visited rather
+/// than parsed into the working set
+#[derive(Debug, Clone)]
+struct StreamedCrate<'a> {
+    root: DocRef<'a, Item>,
+    /// The ids of each type's inherent methods, in the order [`DocRef::methods`] finds them
+    methods: FxHashMap<Id, Vec<Id>>,
+}
+
+impl<'a> StreamedCrate<'a> {
+    /// Read `root`'s crate from its JSON, unless it was parsed up front
+    fn new(root: DocRef<'a, Item>) -> Option<Self> {
+        let items = root.crate_docs().items();
+        if !items.is_lazy() {
+            return None;
+        }
+
+        let mut methods: FxHashMap<Id, Vec<Id>> = FxHashMap::default();
+        for impl_id in items.impl_ids() {
+            let Some(IndexedItem {
+                inner:
+                    IndexedInner {
+                        impl_: Some(impl_block),
+                        ..
+                    },
+                ..
+            }) = items.read_partial(impl_id)
+            else {
+                continue;
+            };
+            if let (Type::ResolvedPath(path), None) = (&impl_block.for_, &impl_block.trait_) {
+                methods.entry(path.id).or_default().extend(impl_block.items);
+            }
+        }
+
+        Some(Self { root, methods })
+    }
+}
+
 // Newtypes for clarity
 #[derive(
     Debug,
@@ -179,6 +315,12 @@ struct Posting {
 struct DocumentInfo {
     path: ItemPath,
     length: DocumentLength,
+    /// The item's name, for matching abbreviations such as `HaMa`
+    name: String,
+    /// Whether the item is unsafe to call, implement or use (see [`is_unsafe`])
+    is_unsafe: bool,
+    /// Whether the item is `#[deprecated]`
+    is_deprecated: bool,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -186,11 +328,19 @@ struct Terms<'a> {
     term_docs: BTreeMap<TermHash, BTreeMap<(u64, u32), DocumentTermCount>>,
     shortest_paths: BTreeMap<(u64, u32), Vec<u32>>,
     document_lengths: BTreeMap<(u64, u32), DocumentLength>,
+    names: BTreeMap<(u64, u32), String>,
+    unsafe_items: HashSet<(u64, u32)>,
+    deprecated_items: HashSet<(u64, u32)>,
     crate_hashes: FxHashMap<&'a str, TermHash>,
     // Authority scoring fields
     visited_crates: HashSet<CrateName<'a>>,
     link_counts: HashMap<ItemOrSummary<'a>, usize>,
-    docref_by_id: HashMap<(u64, u32), DocRef<'a, Item>>,
+    /// The crate and id of the item each document was last built from
+    item_by_id: HashMap<(u64, u32), (&'a str, Id)>,
+    /// The crate being indexed, if its items are read from its JSON
+    streamed: Option<StreamedCrate<'a>>,
+    /// Weight of a term in an item's name, from [`Ranking::name_weight`]
+    name_weight: usize,
 }
 
 impl AddAssign for DocumentTermCount {
@@ -220,7 +370,7 @@ impl<'a> Terms<'a> {
         // Two-pass: filter link_counts to only items in visited crates
         let mut filtered_count = 0;
         let mut skipped_count = 0;
-        let filtered_link_counts: HashMap<DocRef<Item>, usize> = self
+        let filtered_link_counts: HashMap<(&str, Id), usize> = self
             .link_counts
             .into_iter()
             .filter_map(|(target, count)| {
@@ -260,6 +410,9 @@ impl<'a> Terms<'a> {
             documents.push(DocumentInfo {
                 path: ItemPath(id_path),
                 length: doc_length,
+                name: self.names.get(&id).cloned().unwrap_or_default(),
+                is_unsafe: self.unsafe_items.contains(&id),
+                is_deprecated: self.deprecated_items.contains(&id),
             });
         }
 
@@ -269,9 +422,9 @@ impl<'a> Terms<'a> {
         let mut authority_count = 0;
 
         for ((crate_hash, item_id), &doc_idx) in &id_set {
-            // Look up the DocRef for this document and then its link count
-            if let Some(docref) = self.docref_by_id.get(&(*crate_hash, *item_id)) {
-                if let Some(&count) = filtered_link_counts.get(docref) {
+            // Look up the item for this document and then its link count
+            if let Some(item) = self.item_by_id.get(&(*crate_hash, *item_id)) {
+                if let Some(&count) = filtered_link_counts.get(item) {
                     authority_scores[doc_idx] = count;
                     max_authority = max_authority.max(count);
                     authority_count += 1;
@@ -307,7 +460,7 @@ impl<'a> Terms<'a> {
             .collect();
 
         SearchableTerms {
-            version: INDEX_FORMAT_VERSION,
+            name_weight: self.name_weight,
             terms,
             documents,
             total_document_length,
@@ -316,19 +469,38 @@ impl<'a> Terms<'a> {
         }
     }
 
-    fn recurse(&mut self, item: DocRef<'a, Item>, ids: &[u32], add_id: bool) {
+    /// Node for an item of the crate being indexed that was reached already parsed, through a
+    /// re-export resolved by path, so that it's read from the crate's JSON like the rest
+    fn stream(&self, node: Node<'a>) -> Node<'a> {
+        match (node, &self.streamed) {
+            (Node::Parsed { item, name }, Some(streamed))
+                if std::ptr::eq(item.crate_docs(), streamed.root.crate_docs()) =>
+            {
+                Node::Streamed {
+                    root: streamed.root,
+                    id: item.id,
+                    name: name.or_else(|| item.name().map(str::to_string)),
+                }
+            }
+            (node, _) => node,
+        }
+    }
+
+    fn recurse(&mut self, node: Node<'a>, ids: &[u32], add_id: bool) {
+        let node = self.stream(node);
+        let (node_id, context) = node.id_and_context();
         let mut ids = ids.to_owned();
         if add_id {
-            ids.push(item.id.0);
+            ids.push(node_id.0);
         }
-        let crate_name = item.crate_docs().name();
+        let crate_name = context.crate_docs().name();
 
         let crate_hash = self
             .crate_hashes
             .entry(crate_name)
             .or_insert_with(|| hash_term(crate_name));
 
-        let id = (crate_hash.0, *ids.last().unwrap_or(&item.id.0));
+        let id = (crate_hash.0, *ids.last().unwrap_or(&node_id.0));
 
         if let Some(existing_path) = self.shortest_paths.get_mut(&id) {
             if ids.len() < existing_path.len() {
@@ -337,50 +509,147 @@ impl<'a> Terms<'a> {
             return;
         }
 
+        let Some(item) = node.read() else {
+            return;
+        };
+
         // Track visited crate
         self.visited_crates.insert(crate_name.into());
 
-        // Store DocRef for later authority score lookup
-        self.docref_by_id.insert(id, item);
+        // Store the item for later authority score lookup
+        self.item_by_id.insert(id, (crate_name, node_id));
+        // Trait items share their trait's document, which describes the trait
+        if let Entry::Vacant(entry) = self.names.entry(id) {
+            entry.insert(item.name.clone().unwrap_or_default());
+            if item.is_unsafe() {
+                self.unsafe_items.insert(id);
+            }
+            if item.is_deprecated() {
+                self.deprecated_items.insert(id);
+            }
+        }
 
-        self.add_for_item(item, id);
+        self.add_for_item(&item, context, id);
 
-        match item.inner() {
-            ItemEnum::Struct(struct_item) => match &struct_item.kind {
-                StructKind::Unit => {}
-                StructKind::Tuple(field_ids) => {
-                    for field in field_ids.iter().flatten().filter_map(|id| item.get(id)) {
-                        self.add_for_item(field, id);
-                    }
-                }
-                StructKind::Plain { fields, .. } => {
-                    for field in item.id_iter(fields) {
-                        self.add_for_item(field, id);
-                    }
-                }
-            },
-            ItemEnum::Trait(Trait { items, .. }) => {
-                for field in item.id_iter(items) {
-                    self.recurse(field, &ids, false);
-                }
+        if let Some(struct_item) = &item.inner.struct_ {
+            let field_ids: Vec<&Id> = match &struct_item.kind {
+                StructKind::Unit => vec![],
+                StructKind::Tuple(field_ids) => field_ids.iter().flatten().collect(),
+                StructKind::Plain { fields, .. } => fields.iter().collect(),
+            };
+            for field in field_ids
+                .into_iter()
+                .filter_map(|field_id| node.member(*field_id)?.read())
+            {
+                self.add_for_item(&field, context, id);
             }
-            _ => {}
-        };
+        }
+
+        if let Some(trait_item) = &item.inner.trait_ {
+            for member in trait_item.items.iter().filter_map(|id| node.member(*id)) {
+                self.recurse(member, &ids, false);
+            }
+        }
 
-        for child in item.child_items().with_use() {
+        // Only one item's JSON is held per level of nesting
+        let children = self.children(&node, &item);
+        drop(item);
+        for child in children {
             self.recurse(child, &ids, true)
         }
 
         self.shortest_paths.insert(id, ids);
     }
 
-    fn add_for_item(&mut self, item: DocRef<'a, Item>, id: (u64, u32)) {
+    /// The items under a node, as [`DocRef::child_items`] finds them with re-exports included
+    fn children(&self, node: &Node<'a>, item: &IndexedItem) -> Vec<Node<'a>> {
+        let (root, id, name) = match node {
+            Node::Parsed { item, .. } => {
+                return item.child_items().with_use().map(Node::from).collect();
+            }
+            Node::Streamed { root, id, name } => (*root, *id, name),
+        };
+
+        let members = |ids: &[Id]| {
+            ids.iter()
+                .filter_map(|id| node.member(*id))
+                .collect::<Vec<_>>()
+        };
+        let methods = || {
+            let method_ids = self
+                .streamed
+                .as_ref()
+                .and_then(|streamed| streamed.methods.get(&id));
+            members(method_ids.map_or(&[], Vec::as_slice))
+        };
+
+        let inner = &item.inner;
+        if let Some(module) = &inner.module {
+            members(&module.items)
+        } else if let Some(enum_item) = &inner.enum_ {
+            let mut children = members(&enum_item.variants);
+            children.extend(methods());
+            children
+        } else if inner.struct_.is_some() {
+            methods()
+        } else if let Some(use_item) = &inner.use_ {
+            let Some(source) = use_item.id.and_then(|id| node.member(id)).or_else(|| {
+                root.navigator()
+                    .resolve_path(&use_item.source, &mut vec![])
+                    .map(Node::from)
+            }) else {
+                return vec![];
+            };
+
+            if !use_item.is_glob {
+                let name = name.clone().unwrap_or_else(|| use_item.name.clone());
+                return vec![source.renamed(name)];
+            }
+
+            // Globs bring in a module's items or an enum's variants
+            match self.stream(source) {
+                Node::Parsed { item, .. } => match item.inner() {
+                    ItemEnum::Module(module) => item
+                        .id_iter(&module.items)
+                        .with_include_use(true)
+                        .map(Node::from)
+                        .collect(),
+                    ItemEnum::Enum(enum_item) => item
+                        .id_iter(&enum_item.variants)
+                        .with_include_use(true)
+                        .map(Node::from)
+                        .collect(),
+                    _ => vec![],
+                },
+                source @ Node::Streamed { .. } => {
+                    let Some(source_item) = source.read() else {
+                        return vec![];
+                    };
+                    let ids = match (source_item.inner.module, source_item.inner.enum_) {
+                        (Some(module), _) => module.items,
+                        (None, Some(enum_item)) => enum_item.variants,
+                        (None, None) => vec![],
+                    };
+                    ids.iter().filter_map(|id| source.member(*id)).collect()
+                }
+            }
+        } else {
+            vec![]
+        }
+    }
+
+    fn add_for_item(&mut self, item: &IndexedItem, context: DocRef<'a, Item>, id: (u64, u32)) {
         let mut doc_length = 0;
 
         // Item name gets very high weight - when someone searches for "vec",
         // they almost certainly want the Vec struct, not its methods
-        if let Some(name) = item.name() {
-            doc_length += self.add_terms(name, id, 20);
+        if let Some(name) = &item.name {
+            doc_length += self.add_terms(name, id, self.name_weight);
+        }
+
+        // `#[doc(alias = "mkdir")]` on `create_dir` is a name as far as searching goes
+        for alias in item.doc_aliases() {
+            doc_length += self.add_terms(alias, id, self.name_weight);
         }
 
         if let Some(docs) = &item.docs {
@@ -404,16 +673,35 @@ impl<'a> Terms<'a> {
             }
         }
 
+        // Types in signatures and fields count a little, so that `Duration timeout` finds
+        // functions taking a `Duration` even when their docs never name it
+        let mut types = vec![];
+        if let Some(function) = &item.inner.function {
+            for (_, input) in &function.sig.inputs {
+                type_names(input, &mut types);
+            }
+            if let Some(output) = &function.sig.output {
+                type_names(output, &mut types);
+            }
+        }
+        if let Some(field) = &item.inner.struct_field {
+            type_names(field, &mut types);
+        }
+        for type_name in types {
+            doc_length += self.add_terms(type_name, id, 1);
+        }
+
         self.document_lengths.insert(id, DocumentLength(doc_length));
 
         // Count outgoing links for authority scoring
+        let crate_docs = context.crate_docs();
         for link_id in item.links.values() {
-            let target = if let Some(item) = item.get(link_id) {
+            let target = if crate_docs.items().contains(link_id) {
                 // Same-crate item
-                ItemOrSummary::Item(item)
-            } else if let Some(summary) = item.crate_docs().paths.get(link_id) {
+                ItemOrSummary::Item(crate_docs.name(), *link_id)
+            } else if let Some(summary) = crate_docs.paths().get(link_id) {
                 // External item summary
-                ItemOrSummary::Summary(item.build_ref(summary))
+                ItemOrSummary::Summary(context.build_ref(summary))
             } else {
                 // Missing link (methods/assoc items) - skip
                 continue;
@@ -425,8 +713,8 @@ impl<'a> Terms<'a> {
         log::trace!(
             "Counted {} links from {} in crate {}",
             item.links.len(),
-            item.name().unwrap_or("<unnamed>"),
-            item.crate_docs().name()
+            item.name.as_deref().unwrap_or("<unnamed>"),
+            crate_docs.name()
         );
     }
 
@@ -451,12 +739,49 @@ impl<'a> Terms<'a> {
 }
 
 /// Index format version - increment to invalidate all cached indexes
-const INDEX_FORMAT_VERSION: u32 = 1;
+const INDEX_FORMAT_VERSION: u32 = 8;
+
+/// The start of every index file, ahead of the format version and the ferritin-common version
+/// that wrote it
+const INDEX_MAGIC: &[u8; 8] = b"FERRIDX\0";
+
+/// The length of an index file's header, a multiple of 16 so that the archive after it is as
+/// aligned as the file's start
+const INDEX_HEADER_LEN: usize = 32;
+
+/// The header that index files written by this build begin with: [`INDEX_MAGIC`], then
+/// [`INDEX_FORMAT_VERSION`] (little-endian), four bytes of padding, and this crate's version,
+/// padded with zeros
+///
+/// The crate version is part of it because a new release can change what goes into an index
+/// without anyone remembering to bump the format version.
+fn index_header() -> [u8; INDEX_HEADER_LEN] {
+    let mut header = [0; INDEX_HEADER_LEN];
+    header[..8].copy_from_slice(INDEX_MAGIC);
+    header[8..12].copy_from_slice(&INDEX_FORMAT_VERSION.to_le_bytes());
+    let version = env!("CARGO_PKG_VERSION").as_bytes();
+    let version_len = version.len().min(INDEX_HEADER_LEN - 16);
+    header[16..16 + version_len].copy_from_slice(&version[..version_len]);
+    header
+}
+
+/// The format version and crate version recorded in an index file's header, for logging why
+/// it's rebuilt, or `None` if it isn't an index header at all
+fn describe_header(header: &[u8; INDEX_HEADER_LEN]) -> Option<(u32, String)> {
+    if &header[..8] != INDEX_MAGIC {
+        return None;
+    }
+    let format_version = u32::from_le_bytes(header[8..12].try_into().ok()?);
+    let crate_version = String::from_utf8_lossy(&header[16..])
+        .trim_end_matches('\0')
+        .to_string();
+    Some((format_version, crate_version))
+}
 
 #[derive(Debug, Clone, Archive, RkyvSerialize, RkyvDeserialize)]
 struct SearchableTerms {
-    /// Format version for cache invalidation
-    version: u32,
+    /// The [`Ranking::name_weight`] the index was built with
+    name_weight: usize,
     terms: BTreeMap<TermHash, Vec<Posting>>,
     documents: Vec<DocumentInfo>,
     total_document_length: usize,
@@ -476,9 +801,7 @@ pub struct SearchIndex {
 }
 
 impl SearchableTerms {
-    fn search<'a>(&self, query: &'a str) -> SearchResults<'a> {
-        let tokens = tokenize(query);
-
+    fn search<'a>(&self, tokens: Vec<&'a str>, exact_name: Option<&str>) -> SearchResults<'a> {
         // Build lookup from hash to original token
         let token_map: HashMap<TermHash, &'a str> = tokens
             .iter()
@@ -494,8 +817,26 @@ impl SearchableTerms {
             }
         }
 
+        // A query term that isn't a word of this crate may abbreviate item names, and counts as
+        // a name match for each item it abbreviates
+        let abbreviations: Vec<(&'a str, Vec<DocumentId>)> = tokens
+            .iter()
+            .filter(|&&token| !self.terms.contains_key(&hash_term(token)))
+            .map(|&token| {
+                let documents = self
+                    .documents
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, doc_info)| abbreviates(token, &doc_info.name))
+                    .map(|(doc_id, _)| DocumentId(doc_id))
+                    .collect::<Vec<_>>();
+                (token, documents)
+            })
+            .filter(|(_, documents)| !documents.is_empty())
+            .collect();
+
         // Build document frequency map (in borrowed strings for public API)
-        let term_doc_freqs: HashMap<&'a str, usize> = term_postings
+        let mut term_doc_freqs: HashMap<&'a str, usize> = term_postings
             .iter()
             .map(|(term_hash, postings)| {
                 let term_str = token_map.get(term_hash).unwrap();
@@ -514,6 +855,15 @@ impl SearchableTerms {
                     .insert(term_str, posting.count.0);
             }
         }
+        for (token, documents) in abbreviations {
+            term_doc_freqs.insert(token, documents.len());
+            for document in documents {
+                doc_term_counts
+                    .entry(document)
+                    .or_default()
+                    .insert(token, self.name_weight);
+            }
+        }
 
         // Convert to results vec
         let results: Vec<SearchResult<'a>> = doc_term_counts
@@ -524,6 +874,9 @@ impl SearchableTerms {
                     doc_length: doc_info.length.0,
                     term_counts,
                     authority: self.authority_scores.get(doc_id.0).copied().unwrap_or(0),
+                    exact: exact_name.is_some_and(|name| doc_info.name.eq_ignore_ascii_case(name)),
+                    is_unsafe: doc_info.is_unsafe,
+                    is_deprecated: doc_info.is_deprecated,
                 })
             })
             .collect();
@@ -561,13 +914,22 @@ impl SearchIndex {
         let mut path = crate_docs.fs_path().to_path_buf();
         path.set_extension("index");
 
-        if let Some(terms) = Self::load(&path, mtime) {
+        let name_weight = navigator.ranking().name_weight;
+        if let Some(terms) = Self::load(&path, mtime, name_weight) {
             log::debug!("Loaded cached index from disk for {crate_name}");
             Ok(Self { crate_name, terms })
         } else {
             log::debug!("Building new index for {crate_name}");
-            let mut terms = Terms::default();
-            terms.recurse(item, &[], false);
+
+            // Indexing reaches nearly every item. The items of lazily parsed crates are read
+            // from their JSON one at a time rather than parsed, so indexing doesn't leave the
+            // whole crate parsed in the working set
+            let mut terms = Terms {
+                name_weight,
+                streamed: StreamedCrate::new(item),
+                ..Terms::default()
+            };
+            terms.recurse(item.into(), &[], false);
             let terms = terms.finalize();
             log::debug!("Finished building index for {crate_name}");
             Self::store(&terms, &path);
@@ -577,50 +939,65 @@ impl SearchIndex {
 
     fn store(terms: &SearchableTerms, path: &Path) {
         if let Ok(mut file) = OpenOptions::new().create_new(true).write(true).open(path) {
-            match rkyv::to_bytes::<Error>(terms) {
-                Ok(bytes) => {
-                    if file.write_all(&bytes).is_err() {
-                        let _ = fs::remove_file(path);
-                    }
-                }
-                Err(_) => {
-                    let _ = fs::remove_file(path);
-                }
+            let written = rkyv::to_bytes::<Error>(terms).is_ok_and(|bytes| {
+                file.write_all(&index_header()).is_ok() && file.write_all(&bytes).is_ok()
+            });
+            if !written {
+                let _ = fs::remove_file(path);
             }
         }
     }
 
-    fn load(path: &Path, mtime: Option<SystemTime>) -> Option<SearchableTerms> {
+    /// Load the index at `path`, unless it's older than the crate's docs (modified at `mtime`),
+    /// was written by another version, or was built with another name weight, in which cases
+    /// it's deleted to be rebuilt
+    fn load(path: &Path, mtime: Option<SystemTime>, name_weight: usize) -> Option<SearchableTerms> {
         let mut file = File::open(path).ok()?;
         let index_mtime = file.metadata().ok().and_then(|m| m.modified().ok())?;
 
         let mtime = mtime?;
-        if index_mtime.duration_since(mtime).is_ok() {
-            let mut bytes = Vec::new();
-            file.read_to_end(&mut bytes).ok()?;
-            match rkyv::from_bytes::<SearchableTerms, Error>(&bytes) {
-                Ok(terms) => {
-                    if terms.version == INDEX_FORMAT_VERSION {
-                        Some(terms)
-                    } else {
-                        log::debug!(
-                            "Index version mismatch at {}: found {}, expected {}",
-                            path.display(),
-                            terms.version,
-                            INDEX_FORMAT_VERSION
-                        );
-                        let _ = fs::remove_file(path);
-                        None
-                    }
-                }
-                Err(_) => {
-                    let _ = fs::remove_file(path);
-                    None
-                }
+        if index_mtime.duration_since(mtime).is_err() {
+            let _ = fs::remove_file(path);
+            return None;
+        }
+
+        // Check the header before reading the archive, which can't be trusted to fail cleanly
+        // when its layout has changed
+        let mut header = [0; INDEX_HEADER_LEN];
+        if file.read_exact(&mut header).is_err() || header != index_header() {
+            match describe_header(&header) {
+                Some((format_version, crate_version)) => log::debug!(
+                    "Rebuilding {}: written in format {format_version} by ferritin-common \
+                     {crate_version}, expected format {INDEX_FORMAT_VERSION} by {}",
+                    path.display(),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                None => log::debug!("Rebuilding {}: not a current index file", path.display()),
             }
-        } else {
             let _ = fs::remove_file(path);
-            None
+            return None;
+        }
+
+        let mut archive = AlignedVec::<16>::new();
+        let terms = archive
+            .extend_from_reader(&mut file)
+            .ok()
+            .and_then(|_| rkyv::from_bytes::<SearchableTerms, Error>(&archive).ok());
+        match terms {
+            Some(terms) if terms.name_weight == name_weight => Some(terms),
+            Some(terms) => {
+                log::debug!(
+                    "Rebuilding {}: built with name weight {}, expected {name_weight}",
+                    path.display(),
+                    terms.name_weight,
+                );
+                let _ = fs::remove_file(path);
+                None
+            }
+            None => {
+                let _ = fs::remove_file(path);
+                None
+            }
         }
     }
 
@@ -635,7 +1012,23 @@ impl SearchIndex {
     /// Search for items containing the given term
     /// Returns components needed for BM25 scoring across multiple crates
     pub fn search<'a>(&self, query: &'a str) -> SearchResults<'a> {
-        self.terms.search(query)
+        self.terms.search(tokenize(query), None)
+    }
+
+    /// Like [`search`](Self::search), for a query already split into terms, such as the plain
+    /// terms of a parsed [`Query`](super::Query)
+    ///
+    /// Results for items named `exact_name` (ignoring case) are marked
+    /// [`exact`](SearchResult::exact).
+    pub fn search_terms<'a>(
+        &self,
+        terms: &[&'a str],
+        exact_name: Option<&str>,
+    ) -> SearchResults<'a> {
+        self.terms.search(
+            terms.iter().flat_map(|term| tokenize(term)).collect(),
+            exact_name,
+        )
     }
 }
 
@@ -665,6 +1058,14 @@ pub struct SearchResult<'a> {
     pub term_counts: HashMap<&'a str, usize>,
     /// Authority score (incoming link count)
     pub authority: usize,
+    /// Whether the item meets every operator of the query, as `=Vec` or `"exact phrase"`, or is
+    /// named by a query that's just a name or path
+    pub exact: bool,
+    /// Whether the item is unsafe to call, implement or use: an unsafe function or trait, or a
+    /// static from an `unsafe extern` block
+    pub is_unsafe: bool,
+    /// Whether the item is `#[deprecated]`
+    pub is_deprecated: bool,
 }
 
 /// A scored search result from BM25 scoring
@@ -679,37 +1080,123 @@ pub struct ScoredResult<'a> {
     pub relevance: f32,
     /// Authority score (normalized 0.0-1.0, based on incoming links)
     pub authority: f32,
+    /// Whether the item is an exact match for the query (see [`SearchResult::exact`]), which
+    /// ranks it above those that aren't, whatever their scores
+    pub exact: bool,
+    /// Whether the item is unsafe to call, implement or use (see [`SearchResult::is_unsafe`])
+    pub is_unsafe: bool,
+    /// Whether the item is `#[deprecated]`, which ranks it below those that aren't unless
+    /// [`BM25Scorer::with_deprecated_demoted`] says otherwise
+    pub is_deprecated: bool,
+}
+
+/// The tunable parts of search ranking
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ranking {
+    /// BM25 term frequency saturation: how quickly repeated mentions of a term stop adding to
+    /// relevance (1.2 is standard)
+    pub k1: f32,
+    /// BM25 document length normalization, from 0 (none) to 1 (full)
+    ///
+    /// Defaults to 0: in documentation, longer documents (like Vec's comprehensive docs) are
+    /// often MORE relevant than short focused docs (like methods).
+    pub b: f32,
+    /// The weight of a term in an item's name or doc alias, where a term in the first paragraph
+    /// of its docs weighs 3 and one elsewhere in its docs 1. Changing it rebuilds search indexes.
+    pub name_weight: usize,
+    /// How much authority (incoming links) boosts relevance: score = relevance × (1 +
+    /// authority_boost × authority), with authority normalized to 0–1 within each crate
+    pub authority_boost: f32,
+}
+
+impl Default for Ranking {
+    fn default() -> Self {
+        Self {
+            k1: 1.2,
+            b: 0.0,
+            name_weight: 20,
+            authority_boost: 1.0,
+        }
+    }
 }
 
 /// BM25 scorer for combining results from multiple crates
 pub struct BM25Scorer<'a> {
-    k1: f32,
-    b: f32,
+    ranking: Ranking,
+    demote_deprecated: bool,
     crate_results: Vec<(&'a str, SearchResults<'a>)>,
 }
 
 impl<'a> BM25Scorer<'a> {
     /// Create a new BM25 scorer with default parameters
     pub fn new() -> Self {
+        Self::with_ranking(Ranking::default())
+    }
+
+    /// Create a BM25 scorer with the given parameters
+    pub fn with_ranking(ranking: Ranking) -> Self {
         Self {
-            // k1 controls term frequency saturation (1.2 is standard)
-            k1: 1.2,
-            // b controls document length normalization
-            // Set to 0 to disable length penalty entirely.
-            // In documentation, longer documents (like Vec's comprehensive docs)
-            // are often MORE relevant than short focused docs (like methods).
-            b: 0.0,
+            ranking,
+            demote_deprecated: true,
             crate_results: Vec::new(),
         }
     }
 
+    /// Whether deprecated items rank below all others (the default), so that old APIs don't
+    /// outrank their replacements
+    pub fn with_deprecated_demoted(mut self, demote_deprecated: bool) -> Self {
+        self.demote_deprecated = demote_deprecated;
+        self
+    }
+
     /// Add search results from a crate
     pub fn add(&mut self, crate_name: &'a str, results: SearchResults<'a>) {
         self.crate_results.push((crate_name, results));
     }
 
-    /// Compute BM25 scores for all results and return them sorted by score
-    pub fn score(self) -> Vec<ScoredResult<'a>> {
+    /// Whether no crate's results have been added yet
+    pub fn is_empty(&self) -> bool {
+        self.crate_results.is_empty()
+    }
+
+    /// Compute BM25 scores for all results added so far and return them sorted by score
+    ///
+    /// More results can be added afterwards and scored again, as when streaming results from
+    /// crates searched in parallel.
+    pub fn score(&self) -> Vec<ScoredResult<'a>> {
+        self.top(usize::MAX)
+    }
+
+    /// The `k` highest scoring results added so far, sorted by score
+    ///
+    /// Only the top `k` are sorted, which is much cheaper than [`score`](Self::score) when
+    /// there are many matches and only a page of them will be shown.
+    pub fn top(&self, k: usize) -> Vec<ScoredResult<'a>> {
+        let mut scored = self.score_unsorted();
+
+        // Exact matches first, then anything not deprecated, then by combined score (descending)
+        let demoted = |result: &ScoredResult<'_>| self.demote_deprecated && result.is_deprecated;
+        let ranking = |a: &ScoredResult<'_>, b: &ScoredResult<'_>| {
+            b.exact
+                .cmp(&a.exact)
+                .then(demoted(a).cmp(&demoted(b)))
+                .then(b.score.total_cmp(&a.score))
+        };
+
+        if scored.len() > k {
+            log::debug!("Selecting the top {k} of {} scored results", scored.len());
+            scored.select_nth_unstable_by(k, ranking);
+            scored.truncate(k);
+        }
+
+        log::debug!("Sorting {} scored results", scored.len());
+
+        scored.sort_by(ranking);
+
+        scored
+    }
+
+    fn score_unsorted(&self) -> Vec<ScoredResult<'a>> {
         log::debug!("Computing global statistics");
 
         // Aggregate global statistics
@@ -760,11 +1247,17 @@ impl<'a> BM25Scorer<'a> {
         log::debug!("Scoring {} results", total_results);
 
         // Score all results
+        let Ranking {
+            k1,
+            b,
+            authority_boost,
+            ..
+        } = self.ranking;
         let mut scored: Vec<ScoredResult<'a>> = Vec::new();
-        for (crate_name, results) in self.crate_results {
+        for (crate_name, results) in &self.crate_results {
             let max_authority = results.max_authority.max(1); // Avoid division by zero
 
-            for result in results.results {
+            for result in &results.results {
                 let doc_len_norm = result.doc_length as f32 / avgdl;
 
                 let relevance: f32 = result
@@ -773,8 +1266,8 @@ impl<'a> BM25Scorer<'a> {
                     .map(|(term, count)| {
                         let idf = global_idf.get(term).copied().unwrap_or(0.0);
                         let tf = *count as f32;
-                        let numerator = tf * (self.k1 + 1.0);
-                        let denominator = tf + self.k1 * (1.0 - self.b + self.b * doc_len_norm);
+                        let numerator = tf * (k1 + 1.0);
+                        let denominator = tf + k1 * (1.0 - b + b * doc_len_norm);
                         idf * (numerator / denominator)
                     })
                     .sum();
@@ -783,24 +1276,22 @@ impl<'a> BM25Scorer<'a> {
                 let authority = result.authority as f32 / max_authority as f32;
 
                 // Combine relevance and authority
-                // Using multiplicative boost: score = relevance * (1.0 + authority)
-                let score = relevance * (1.0 + authority);
+                // Using multiplicative boost: score = relevance * (1.0 + boost * authority)
+                let score = relevance * (1.0 + authority_boost * authority);
 
                 scored.push(ScoredResult {
                     crate_name,
-                    id_path: result.id_path,
+                    id_path: result.id_path.clone(),
                     score,
                     relevance,
                     authority,
+                    exact: result.exact,
+                    is_unsafe: result.is_unsafe,
+                    is_deprecated: result.is_deprecated,
                 });
             }
         }
 
-        log::debug!("Sorting {} scored results", scored.len());
-
-        // Sort by combined score (descending)
-        scored.sort_by(|a, b| b.score.total_cmp(&a.score));
-
         scored
     }
 }
@@ -816,7 +1307,7 @@ fn add_token<'a>(token: &'a str, tokens: &mut Vec<&'a str>) {
 }
 
 /// Simple tokenizer: split on whitespace and punctuation, lowercase, filter short words
-fn tokenize(text: &str) -> Vec<&str> {
+pub(super) fn tokenize(text: &str) -> Vec<&str> {
     let mut tokens = vec![];
     let min_chars = 2;
     let mut last_case = None;
@@ -879,6 +1370,162 @@ fn tokenize(text: &str) -> Vec<&str> {
     tokens
 }
 
+/// The names of the types mentioned in `ty`, without their module paths: `Duration` and `u64` for
+/// `&std::time::Duration` and `Result<u64, io::Error>`. Generic parameters such as `T` are left out.
+fn type_names<'t>(ty: &'t Type, names: &mut Vec<&'t str>) {
+    match ty {
+        Type::ResolvedPath(path) => path_names(path, names),
+        Type::DynTrait(dyn_trait) => {
+            for poly_trait in &dyn_trait.traits {
+                path_names(&poly_trait.trait_, names);
+            }
+        }
+        Type::Primitive(name) => names.push(name),
+        Type::FunctionPointer(function) => {
+            for (_, input) in &function.sig.inputs {
+                type_names(input, names);
+            }
+            if let Some(output) = &function.sig.output {
+                type_names(output, names);
+            }
+        }
+        Type::Tuple(types) => {
+            for ty in types {
+                type_names(ty, names);
+            }
+        }
+        Type::Slice(ty)
+        | Type::Array { type_: ty, .. }
+        | Type::Pat { type_: ty, .. }
+        | Type::RawPointer { type_: ty, .. }
+        | Type::BorrowedRef { type_: ty, .. } => type_names(ty, names),
+        Type::ImplTrait(bounds) => bound_names(bounds, names),
+        Type::QualifiedPath {
+            name,
+            args,
+            self_type,
+            trait_,
+        } => {
+            type_names(self_type, names);
+            if let Some(trait_) = trait_ {
+                path_names(trait_, names);
+            }
+            names.push(name);
+            if let Some(args) = args {
+                args_names(args, names);
+            }
+        }
+        Type::Generic(_) | Type::Infer => {}
+    }
+}
+
+fn path_names<'t>(path: &'t TypePath, names: &mut Vec<&'t str>) {
+    names.push(path.path.rsplit("::").next().unwrap_or(&path.path));
+    if let Some(args) = &path.args {
+        args_names(args, names);
+    }
+}
+
+fn args_names<'t>(args: &'t GenericArgs, names: &mut Vec<&'t str>) {
+    match args {
+        GenericArgs::AngleBracketed { args, constraints } => {
+            for arg in args {
+                if let GenericArg::Type(ty) = arg {
+                    type_names(ty, names);
+                }
+            }
+            for constraint in constraints {
+                match &constraint.binding {
+                    AssocItemConstraintKind::Equality(Term::Type(ty)) => type_names(ty, names),
+                    AssocItemConstraintKind::Equality(Term::Constant(_)) => {}
+                    AssocItemConstraintKind::Constraint(bounds) => bound_names(bounds, names),
+                }
+            }
+        }
+        GenericArgs::Parenthesized { inputs, output } => {
+            for input in inputs {
+                type_names(input, names);
+            }
+            if let Some(output) = output {
+                type_names(output, names);
+            }
+        }
+        GenericArgs::ReturnTypeNotation => {}
+    }
+}
+
+fn bound_names<'t>(bounds: &'t [GenericBound], names: &mut Vec<&'t str>) {
+    for bound in bounds {
+        if let GenericBound::TraitBound { trait_, .. } = bound {
+            path_names(trait_, names);
+        }
+    }
+}
+
+/// Whether `query` abbreviates `name`, as in IDE symbol search: `HaMa` or `hashm` for `HashMap`,
+/// `rtstr` for `read_to_string`
+///
+/// The query is read as pieces, each the start of a word of the name, in order. The first piece
+/// starts the name, later ones may skip words, and an uppercase letter or a `_` in the query
+/// always starts a new piece.
+pub(super) fn abbreviates(query: &str, name: &str) -> bool {
+    let mut query_chars = vec![];
+    let mut after_separator = false;
+    for c in query.chars() {
+        if c == '_' || c == '-' {
+            after_separator = true;
+            continue;
+        }
+        let starts_piece = c.is_uppercase() || after_separator;
+        query_chars.extend(c.to_lowercase().map(|c| (c, starts_piece)));
+        after_separator = false;
+    }
+    let words = name_words(name);
+
+    fn matches(query: &[(char, bool)], words: &[Vec<char>], first: bool) -> bool {
+        if query.is_empty() {
+            return true;
+        }
+        let candidates = if first {
+            &words[..words.len().min(1)]
+        } else {
+            words
+        };
+        candidates.iter().enumerate().any(|(skipped, word)| {
+            (1..=word.len().min(query.len()))
+                .take_while(|&len| {
+                    query[len - 1].0 == word[len - 1] && (len == 1 || !query[len - 1].1)
+                })
+                .any(|len| matches(&query[len..], &words[skipped + 1..], false))
+        })
+    }
+
+    !query_chars.is_empty() && matches(&query_chars, &words, true)
+}
+
+/// The lowercased words of an identifier, split at underscores and at lowercase-to-uppercase
+/// changes: `read_to_string` and `ReadToString` are both `read`, `to`, `string`
+fn name_words(name: &str) -> Vec<Vec<char>> {
+    let mut words: Vec<Vec<char>> = vec![];
+    let mut last_lowercase = false;
+    for c in name.chars() {
+        if c == '_' {
+            last_lowercase = false;
+            words.push(vec![]);
+            continue;
+        }
+        if words.is_empty() || (c.is_uppercase() && last_lowercase) {
+            words.push(vec![]);
+        }
+        last_lowercase = c.is_lowercase() || c.is_ascii_digit();
+        if let Some(word) = words.last_mut() {
+            word.extend(c.to_lowercase());
+        }
+    }
+    words.retain(|word| !word.is_empty());
+    words
+}
+
 /// Hash a term for use as a map key (case-insensitive)
 fn hash_term(term: &str) -> TermHash {
     let mut hasher = FxHasher::default();
@@ -0,0 +1,175 @@
+//! The parts of an item that indexing reads, parsed from its JSON without the rest
+//!
+//! Indexing reaches nearly every item of a crate. Parsing them into [`Item`]s would leave the
+//! whole crate parsed alongside its memory map, so the items of a memory-mapped crate are read
+//! into these smaller types one at a time instead, and dropped once they've been indexed.
+
+use rustc_hash::FxHashMap;
+use rustdoc_types::{Attribute, FunctionSignature, Id, Item, ItemEnum, StructKind, Type, Use};
+use serde::Deserialize;
+use serde::de::IgnoredAny;
+
+use crate::doc_ref::DocRef;
+use crate::string_utils::doc_aliases_in;
+
+#[derive(Debug, Deserialize)]
+pub(super) struct IndexedItem {
+    pub(super) name: Option<String>,
+    pub(super) docs: Option<String>,
+    #[serde(default)]
+    pub(super) links: FxHashMap<String, Id>,
+    #[serde(default)]
+    attrs: Vec<Attribute>,
+    deprecation: Option<IgnoredAny>,
+    pub(super) inner: IndexedInner,
+}
+
+/// The item's kind, with the fields indexing reads
+///
+/// Rustdoc writes `inner` as an object with the kind as its only key, so each kind is an
+/// optional field here and other kinds are left out entirely.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(super) struct IndexedInner {
+    pub(super) module: Option<ItemIds>,
+    #[serde(rename = "struct")]
+    pub(super) struct_: Option<IndexedStruct>,
+    #[serde(rename = "enum")]
+    pub(super) enum_: Option<IndexedEnum>,
+    #[serde(rename = "trait")]
+    pub(super) trait_: Option<IndexedTrait>,
+    pub(super) function: Option<IndexedFunction>,
+    pub(super) struct_field: Option<Type>,
+    #[serde(rename = "use")]
+    pub(super) use_: Option<Use>,
+    #[serde(rename = "impl")]
+    pub(super) impl_: Option<IndexedImpl>,
+    #[serde(rename = "static")]
+    pub(super) static_: Option<Unsafety>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct ItemIds {
+    pub(super) items: Vec<Id>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct IndexedStruct {
+    pub(super) kind: StructKind,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct IndexedEnum {
+    pub(super) variants: Vec<Id>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct IndexedTrait {
+    pub(super) items: Vec<Id>,
+    pub(super) is_unsafe: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct IndexedFunction {
+    pub(super) sig: FunctionSignature,
+    pub(super) header: Unsafety,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct IndexedImpl {
+    pub(super) items: Vec<Id>,
+    #[serde(rename = "trait")]
+    pub(super) trait_: Option<IgnoredAny>,
+    #[serde(rename = "for")]
+    pub(super) for_: Type,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct Unsafety {
+    pub(super) is_unsafe: bool,
+}
+
+impl IndexedItem {
+    /// The fields indexing reads of an item that's already parsed, for items of other crates
+    /// reached through re-exports
+    ///
+    /// Their children are found through the [`DocRef`], so only the kinds that indexing reads
+    /// directly are copied.
+    pub(super) fn from_parsed(item: DocRef<'_, Item>) -> Self {
+        let inner = match item.inner() {
+            ItemEnum::Struct(struct_item) => IndexedInner {
+                struct_: Some(IndexedStruct {
+                    kind: struct_item.kind.clone(),
+                }),
+                ..IndexedInner::default()
+            },
+            ItemEnum::Trait(trait_item) => IndexedInner {
+                trait_: Some(IndexedTrait {
+                    items: trait_item.items.clone(),
+                    is_unsafe: trait_item.is_unsafe,
+                }),
+                ..IndexedInner::default()
+            },
+            ItemEnum::Function(function) => IndexedInner {
+                function: Some(IndexedFunction {
+                    sig: function.sig.clone(),
+                    header: Unsafety {
+                        is_unsafe: function.header.is_unsafe,
+                    },
+                }),
+                ..IndexedInner::default()
+            },
+            ItemEnum::StructField(ty) => IndexedInner {
+                struct_field: Some(ty.clone()),
+                ..IndexedInner::default()
+            },
+            ItemEnum::Static(static_item) => IndexedInner {
+                static_: Some(Unsafety {
+                    is_unsafe: static_item.is_unsafe,
+                }),
+                ..IndexedInner::default()
+            },
+            _ => IndexedInner::default(),
+        };
+
+        Self {
+            name: item.name().map(str::to_string),
+            docs: item.docs.clone(),
+            links: item.links.clone(),
+            attrs: item.attrs.clone(),
+            deprecation: item.deprecation.as_ref().map(|_| IgnoredAny),
+            inner,
+        }
+    }
+
+    /// The item's `#[doc(alias = "…")]` names, as [`DocRef::doc_aliases`] finds them
+    pub(super) fn doc_aliases(&self) -> impl Iterator<Item = &str> {
+        self.attrs.iter().flat_map(|attr| match attr {
+            Attribute::Other(attr) => doc_aliases_in(attr),
+            _ => vec![],
+        })
+    }
+
+    pub(super) fn is_deprecated(&self) -> bool {
+        self.deprecation.is_some()
+    }
+
+    /// Whether calling or implementing the item takes `unsafe`: unsafe functions and methods,
+    /// unsafe traits, and statics from `unsafe extern` blocks
+    pub(super) fn is_unsafe(&self) -> bool {
+        self.inner
+            .function
+            .as_ref()
+            .is_some_and(|function| function.header.is_unsafe)
+            || self
+                .inner
+                .trait_
+                .as_ref()
+                .is_some_and(|trait_item| trait_item.is_unsafe)
+            || self
+                .inner
+                .static_
+                .as_ref()
+                .is_some_and(|static_item| static_item.is_unsafe)
+    }
+}
@@ -22,6 +22,50 @@ fn test_tokenize() {
     );
 }
 
+#[test]
+fn test_abbreviates() {
+    assert!(abbreviates("HaMa", "HashMap"));
+    assert!(abbreviates("hashm", "HashMap"));
+    assert!(abbreviates("rtstr", "read_to_string"));
+    assert!(abbreviates("rstr", "read_to_string"));
+    assert!(abbreviates("ReToS", "ReadToString"));
+
+    // The first piece starts the name, and pieces keep their order
+    assert!(!abbreviates("map", "HashMap"));
+    assert!(!abbreviates("strd", "read_to_string"));
+    // An uppercase letter can't continue a piece
+    assert!(!abbreviates("HAsh", "HashMap"));
+}
+
+#[test]
+fn test_type_names() {
+    let path = |path: &str, args: Vec<Type>| {
+        Type::ResolvedPath(TypePath {
+            path: path.to_string(),
+            id: rustdoc_types::Id(0),
+            args: Some(Box::new(GenericArgs::AngleBracketed {
+                args: args.into_iter().map(GenericArg::Type).collect(),
+                constraints: vec![],
+            })),
+        })
+    };
+    let ty = Type::BorrowedRef {
+        lifetime: None,
+        is_mutable: false,
+        type_: Box::new(path(
+            "std::result::Result",
+            vec![
+                path("std::time::Duration", vec![]),
+                Type::Generic("E".to_string()),
+            ],
+        )),
+    };
+
+    let mut names = vec![];
+    type_names(&ty, &mut names);
+    assert_eq!(names, ["Result", "Duration"]);
+}
+
 #[test]
 fn test_hash_term() {
     // Should be case insensitive
@@ -127,3 +171,178 @@ fn test_prose_slices_matches_pulldown_cmark() {
         );
     }
 }
+
+#[test]
+fn test_top_k_matches_full_scoring() {
+    let results = SearchResults {
+        total_docs: 10,
+        total_doc_length: 100,
+        term_doc_freqs: HashMap::from([("vec", 4)]),
+        results: (1..=4)
+            .map(|count| SearchResult {
+                id_path: vec![count as u32],
+                doc_length: 10,
+                term_counts: HashMap::from([("vec", count)]),
+                authority: 0,
+                exact: false,
+                is_unsafe: false,
+                is_deprecated: false,
+            })
+            .collect(),
+        max_authority: 0,
+    };
+    let mut scorer = BM25Scorer::new();
+    scorer.add("alloc", results);
+
+    let all: Vec<_> = scorer.score().into_iter().map(|r| r.id_path).collect();
+    let top: Vec<_> = scorer.top(2).into_iter().map(|r| r.id_path).collect();
+    assert_eq!(all, vec![vec![4], vec![3], vec![2], vec![1]]);
+    assert_eq!(top, vec![vec![4], vec![3]]);
+    assert!(scorer.top(0).is_empty());
+}
+
+#[test]
+fn test_authority_boost() {
+    let results = || SearchResults {
+        total_docs: 10,
+        total_doc_length: 100,
+        term_doc_freqs: HashMap::from([("vec", 2)]),
+        results: vec![
+            SearchResult {
+                id_path: vec![1],
+                doc_length: 10,
+                term_counts: HashMap::from([("vec", 2)]),
+                authority: 0,
+                exact: false,
+                is_unsafe: false,
+                is_deprecated: false,
+            },
+            SearchResult {
+                id_path: vec![2],
+                doc_length: 10,
+                term_counts: HashMap::from([("vec", 1)]),
+                authority: 10,
+                exact: false,
+                is_unsafe: false,
+                is_deprecated: false,
+            },
+        ],
+        max_authority: 10,
+    };
+    let ranked = |authority_boost| {
+        let mut scorer = BM25Scorer::with_ranking(Ranking {
+            authority_boost,
+            ..Ranking::default()
+        });
+        scorer.add("alloc", results());
+        scorer
+            .score()
+            .into_iter()
+            .map(|r| r.id_path)
+            .collect::<Vec<_>>()
+    };
+
+    // Without the boost, the item mentioning the term more comes first; with a large one, the
+    // linked-to item does
+    assert_eq!(ranked(0.0), vec![vec![1], vec![2]]);
+    assert_eq!(ranked(10.0), vec![vec![2], vec![1]]);
+}
+
+#[test]
+fn test_exact_matches_rank_first() {
+    let result = |id, count, exact| SearchResult {
+        id_path: vec![id],
+        doc_length: 10,
+        term_counts: HashMap::from([("vec", count)]),
+        authority: 0,
+        exact,
+        is_unsafe: false,
+        is_deprecated: false,
+    };
+    let mut scorer = BM25Scorer::new();
+    scorer.add(
+        "alloc",
+        SearchResults {
+            total_docs: 10,
+            total_doc_length: 100,
+            term_doc_freqs: HashMap::from([("vec", 3)]),
+            results: vec![result(1, 5, false), result(2, 1, true), result(3, 3, false)],
+            max_authority: 0,
+        },
+    );
+
+    let ranked: Vec<_> = scorer.score().into_iter().map(|r| r.id_path).collect();
+    assert_eq!(ranked, vec![vec![2], vec![1], vec![3]]);
+    let top: Vec<_> = scorer.top(1).into_iter().map(|r| r.id_path).collect();
+    assert_eq!(top, vec![vec![2]]);
+}
+
+#[test]
+fn test_deprecated_items_rank_last() {
+    let result = |id, count, is_deprecated| SearchResult {
+        id_path: vec![id],
+        doc_length: 10,
+        term_counts: HashMap::from([("vec", count)]),
+        authority: 0,
+        exact: false,
+        is_unsafe: false,
+        is_deprecated,
+    };
+    let ranked = |demote_deprecated| {
+        let mut scorer = BM25Scorer::new().with_deprecated_demoted(demote_deprecated);
+        scorer.add(
+            "alloc",
+            SearchResults {
+                total_docs: 10,
+                total_doc_length: 100,
+                term_doc_freqs: HashMap::from([("vec", 2)]),
+                results: vec![result(1, 5, true), result(2, 1, false)],
+                max_authority: 0,
+            },
+        );
+        scorer
+            .score()
+            .into_iter()
+            .map(|r| r.id_path)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ranked(true), vec![vec![2], vec![1]]);
+    assert_eq!(ranked(false), vec![vec![1], vec![2]]);
+}
+
+#[test]
+fn test_index_header() {
+    let terms = SearchableTerms {
+        name_weight: 3,
+        terms: BTreeMap::new(),
+        documents: vec![],
+        total_document_length: 0,
+        authority_scores: vec![],
+        max_authority: 0,
+    };
+    let path = std::env::temp_dir().join(format!("ferritin-header-{}.index", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let docs_mtime = Some(SystemTime::UNIX_EPOCH);
+
+    SearchIndex::store(&terms, &path);
+    let bytes = fs::read(&path).unwrap();
+    assert_eq!(bytes[..INDEX_HEADER_LEN], index_header());
+    assert_eq!(
+        describe_header(&index_header()),
+        Some((INDEX_FORMAT_VERSION, env!("CARGO_PKG_VERSION").to_string()))
+    );
+    assert!(SearchIndex::load(&path, docs_mtime, 3).is_some());
+
+    // An index written in another format is deleted rather than read
+    let mut old = bytes.clone();
+    old[8..12].copy_from_slice(&(INDEX_FORMAT_VERSION - 1).to_le_bytes());
+    fs::write(&path, &old).unwrap();
+    assert!(SearchIndex::load(&path, docs_mtime, 3).is_none());
+    assert!(!path.exists());
+
+    // As is one from before indexes had headers
+    fs::write(&path, &bytes[INDEX_HEADER_LEN..]).unwrap();
+    assert!(SearchIndex::load(&path, docs_mtime, 3).is_none());
+    assert!(!path.exists());
+}
@@ -0,0 +1,366 @@
+//! Operators in search queries
+//!
+//! Beyond plain terms, a query can hold `"quoted phrases"`, whose words must appear next to each
+//! other in an item's name or docs, and `=Name`, which an item's name must equal (ignoring case).
+//! Their words are looked up in the index like any others; results that meet every operator are
+//! then ranked above those that only share words with the query. A query that's nothing but a
+//! name or path, such as `vec` or `Vec::new`, ranks the items it names above the rest the same way,
+//! however many other items mention it in their docs.
+//!
+//! The remaining operators filter rather than rank, and their words aren't looked up at all:
+//! `-word` drops results with the word in their path, `kind:fn` keeps only items of that kind,
+//! `in:io` keeps only items within a module (or type) of that name, and `crate:tokio` searches
+//! that crate in place of the crates asked for. Giving a filter more than once accepts any of
+//! its values, so `kind:struct kind:enum` finds both. A bare `unsafe:` keeps only items that are
+//! unsafe to call, implement or use.
+//!
+//! Deprecated items rank below the rest unless the query says otherwise: `deprecated:include`
+//! ranks them like any other item, and `deprecated:only` (or a bare `deprecated:`) keeps only
+//! them.
+
+use super::indexer::tokenize;
+use crate::DocRef;
+use crate::navigator::discriminated_kind;
+use crate::rustdoc_data::kind_discriminator;
+use rustdoc_types::Item;
+
+/// A search query, split into the terms to look up and its operators
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query<'a> {
+    /// Plain words, and the text of phrases and `=` names, to look up in the index
+    terms: Vec<&'a str>,
+    /// Quoted phrases, without their quotes
+    phrases: Vec<&'a str>,
+    /// Names given with `=`
+    exact_names: Vec<&'a str>,
+    /// Words given with `-`
+    excluded: Vec<&'a str>,
+    /// Item kinds given with `kind:`
+    kinds: Vec<&'a str>,
+    /// Crates given with `crate:`
+    crates: Vec<&'a str>,
+    /// Paths given with `in:`
+    within: Vec<&'a str>,
+    /// Whether `unsafe:` was given
+    unsafe_only: bool,
+    deprecated: DeprecatedItems,
+}
+
+/// How a search treats `#[deprecated]` items
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeprecatedItems {
+    /// Rank them below all other items
+    #[default]
+    Demote,
+    /// Rank them like any other item
+    Include,
+    /// Leave out everything else
+    Only,
+}
+
+impl<'a> Query<'a> {
+    /// Find the operators in a query string
+    ///
+    /// An unterminated quote runs to the end of the query. An operator with nothing after it,
+    /// such as a lone `-`, is a plain term.
+    pub fn parse(query: &'a str) -> Self {
+        let mut parsed = Self::default();
+        let mut rest = query.trim_start();
+        while !rest.is_empty() {
+            let token_len = if let Some(quoted) = rest.strip_prefix('"') {
+                let phrase_len = quoted.find('"').unwrap_or(quoted.len());
+                let phrase = quoted[..phrase_len].trim();
+                if !phrase.is_empty() {
+                    parsed.phrases.push(phrase);
+                    parsed.terms.push(phrase);
+                }
+                // The opening quote, the phrase and the closing quote, if there is one
+                (1 + phrase_len + 1).min(rest.len())
+            } else {
+                let word_len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                parsed.push_word(&rest[..word_len]);
+                word_len
+            };
+            rest = rest[token_len..].trim_start();
+        }
+        parsed
+    }
+
+    fn push_word(&mut self, word: &'a str) {
+        match word {
+            "unsafe:" => self.unsafe_only = true,
+            "deprecated:" | "deprecated:only" => self.deprecated = DeprecatedItems::Only,
+            "deprecated:include" => self.deprecated = DeprecatedItems::Include,
+            _ => self.push_term(word),
+        }
+    }
+
+    /// A plain term, or an operator with a value, such as `kind:fn`
+    fn push_term(&mut self, word: &'a str) {
+        let operators = [
+            ("=", &mut self.exact_names),
+            ("-", &mut self.excluded),
+            ("kind:", &mut self.kinds),
+            ("crate:", &mut self.crates),
+            ("in:", &mut self.within),
+        ];
+        for (prefix, values) in operators {
+            if let Some(value) = word.strip_prefix(prefix)
+                && !value.is_empty()
+            {
+                values.push(value);
+                // `=Name` still looks for the name
+                if prefix == "=" {
+                    self.terms.push(value);
+                }
+                return;
+            }
+        }
+        self.terms.push(word);
+    }
+
+    /// The words and phrases to look up in the index
+    pub fn terms(&self) -> &[&'a str] {
+        &self.terms
+    }
+
+    /// The crates named with `crate:`, to be searched in place of any others
+    pub fn crates(&self) -> &[&'a str] {
+        &self.crates
+    }
+
+    /// Whether only unsafe items are wanted
+    pub fn unsafe_only(&self) -> bool {
+        self.unsafe_only
+    }
+
+    /// How deprecated items are treated
+    pub fn deprecated(&self) -> DeprecatedItems {
+        self.deprecated
+    }
+
+    /// Whether some results rank above the rest as exact matches: the query has phrases or `=`
+    /// names, or is a name or path
+    pub fn ranks_exact_matches(&self) -> bool {
+        !self.phrases.is_empty() || !self.exact_names.is_empty() || self.named_path().is_some()
+    }
+
+    /// The query's only term, if it's an identifier or a `::` path of identifiers
+    fn named_path(&self) -> Option<&'a str> {
+        let [term] = self.terms[..] else {
+            return None;
+        };
+        let is_path = !self.has_exact_operators()
+            && term.split("::").all(|segment| {
+                !segment.is_empty() && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+            });
+        is_path.then_some(term)
+    }
+
+    /// The name of the item named by a query that's a name or path: `new` for `Vec::new`
+    pub fn named_item(&self) -> Option<&'a str> {
+        self.named_path()?.rsplit("::").next()
+    }
+
+    /// Whether the query has phrases or `=` names
+    pub fn has_exact_operators(&self) -> bool {
+        !self.phrases.is_empty() || !self.exact_names.is_empty()
+    }
+
+    /// Whether the query has `-`, `kind:` or `in:` filters, without which every result passes
+    pub fn has_filters(&self) -> bool {
+        !self.excluded.is_empty() || !self.kinds.is_empty() || !self.within.is_empty()
+    }
+
+    /// Whether `item`, found at `path`, is an exact match: its name equals each `=Name` and each
+    /// phrase appears in its name or docs, or, for a query that's a name or path, its path ends
+    /// with the query
+    pub fn is_exact_match(&self, item: DocRef<'_, Item>, path: &[&str]) -> bool {
+        let name = item.name().unwrap_or_default();
+        if let Some(named_path) = self.named_path() {
+            return ends_with_path(path, name, named_path);
+        }
+
+        if !self
+            .exact_names
+            .iter()
+            .all(|exact_name| name.to_lowercase() == exact_name.to_lowercase())
+        {
+            return false;
+        }
+
+        if self.phrases.is_empty() {
+            return true;
+        }
+        let text = words(&format!(
+            "{name} {}",
+            item.docs.as_deref().unwrap_or_default()
+        ));
+        self.phrases
+            .iter()
+            .all(|phrase| text.contains(&words(phrase)))
+    }
+
+    /// Whether `item`, found at `path`, passes the filters: none of the `-` words are in its
+    /// path, it's one of the `kind:` kinds, and it's within one of the `in:` paths
+    pub fn passes_filters(&self, item: DocRef<'_, Item>, path: &[&str]) -> bool {
+        if self.excluded.iter().any(|word| mentions(path, word)) {
+            return false;
+        }
+
+        let kind = kind_discriminator(item.kind());
+        if !self.kinds.is_empty()
+            && !self.kinds.iter().any(|filter| {
+                discriminated_kind(&filter.to_lowercase())
+                    .is_some_and(|filter| kind_discriminator(filter) == kind)
+            })
+        {
+            return false;
+        }
+
+        // The path ends with the item's own name, unless it was reached through a glob import
+        let parent = match path.split_last() {
+            Some((last, parent)) if item.name() == Some(*last) => parent,
+            _ => path,
+        };
+        self.within.is_empty() || self.within.iter().any(|within| is_within(parent, within))
+    }
+}
+
+/// Text as lowercase words separated by single spaces, with a space at each end, so that
+/// matching one against another only matches whole words
+fn words(text: &str) -> String {
+    let mut words = String::from(" ");
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        words.push_str(&word.to_lowercase());
+        words.push(' ');
+    }
+    words
+}
+
+/// Whether the path of an item named `name`, found at `path`, ends with `suffix`, ignoring case
+fn ends_with_path(path: &[&str], name: &str, suffix: &str) -> bool {
+    // The path ends with the item's own name, unless it was reached through a glob import
+    let mut full_path = path.to_vec();
+    if path.last() != Some(&name) {
+        full_path.push(name);
+    }
+    let suffix: Vec<&str> = suffix.split("::").collect();
+    full_path.len() >= suffix.len()
+        && full_path[full_path.len() - suffix.len()..]
+            .iter()
+            .zip(&suffix)
+            .all(|(segment, suffix)| segment.to_lowercase() == suffix.to_lowercase())
+}
+
+/// Whether every term of `word` is a term of some segment of `path`, as the index would split
+/// them, so that `blocking` is in `spawn_blocking` and `map` in `HashMap`
+fn mentions(path: &[&str], word: &str) -> bool {
+    let path_terms: Vec<String> = path.iter().flat_map(|segment| terms(segment)).collect();
+    terms(word).iter().all(|term| path_terms.contains(term))
+}
+
+/// The lowercased terms of `text`, or all of it if it's too short to have any
+fn terms(text: &str) -> Vec<String> {
+    let tokens = tokenize(text);
+    if tokens.is_empty() {
+        vec![text.to_lowercase()]
+    } else {
+        tokens.into_iter().map(str::to_lowercase).collect()
+    }
+}
+
+/// Whether `parent` contains the segments of `within` (such as `io` or `std::io`) in a row
+fn is_within(parent: &[&str], within: &str) -> bool {
+    let within: Vec<&str> = within.split("::").collect();
+    parent.windows(within.len()).any(|segments| {
+        segments
+            .iter()
+            .zip(&within)
+            .all(|(segment, within)| segment.eq_ignore_ascii_case(within))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_operators() {
+        let query = Query::parse(r#"  "hash  map" =Vec plain "unterminated"#);
+        assert_eq!(query.phrases, ["hash  map", "unterminated"]);
+        assert_eq!(query.exact_names, ["Vec"]);
+        assert_eq!(query.terms, ["hash  map", "Vec", "plain", "unterminated"]);
+        assert!(query.ranks_exact_matches());
+        assert!(!query.has_filters());
+
+        let plain = Query::parse("hash map = x");
+        assert!(!plain.ranks_exact_matches());
+        assert_eq!(plain.terms, ["hash", "map", "=", "x"]);
+    }
+
+    #[test]
+    fn parses_filters() {
+        let query = Query::parse("spawn crate:tokio -blocking kind:fn in:task - unsafe:");
+        assert_eq!(query.terms, ["spawn", "-"]);
+        assert!(query.unsafe_only());
+        assert_eq!(query.deprecated(), DeprecatedItems::Demote);
+        assert_eq!(
+            Query::parse("deprecated:include").deprecated(),
+            DeprecatedItems::Include
+        );
+        assert_eq!(
+            Query::parse("x deprecated:").deprecated(),
+            DeprecatedItems::Only
+        );
+        assert_eq!(query.excluded, ["blocking"]);
+        assert_eq!(query.kinds, ["fn"]);
+        assert_eq!(query.crates(), ["tokio"]);
+        assert_eq!(query.within, ["task"]);
+        assert!(query.has_filters());
+    }
+
+    #[test]
+    fn names_and_paths_match_path_ends() {
+        assert_eq!(Query::parse("Vec::new").named_path(), Some("Vec::new"));
+        assert_eq!(Query::parse("vec kind:struct").named_path(), Some("vec"));
+        assert_eq!(Query::parse("vec push").named_path(), None);
+        assert_eq!(Query::parse("=Vec").named_path(), None);
+        assert_eq!(Query::parse("std::").named_path(), None);
+
+        assert!(ends_with_path(&["std", "vec", "Vec"], "Vec", "vec"));
+        assert!(ends_with_path(&["std", "vec"], "vec", "std::vec"));
+        assert!(ends_with_path(&["std", "prelude"], "Vec", "Vec"));
+        assert!(!ends_with_path(&["std", "vec", "Vec", "new"], "new", "vec"));
+        assert!(!ends_with_path(&["vec"], "vec", "std::vec"));
+    }
+
+    #[test]
+    fn phrases_match_whole_adjacent_words() {
+        assert!(words("Creates a new, empty `HashMap`.").contains(&words("new empty")));
+        assert!(!words("Creates a newer empty map").contains(&words("new empty")));
+        assert!(!words("empty and new").contains(&words("new empty")));
+    }
+
+    #[test]
+    fn exclusions_match_path_terms() {
+        let path = ["tokio", "task", "spawn_blocking"];
+        assert!(mentions(&path, "blocking"));
+        assert!(mentions(&path, "Task"));
+        assert!(!mentions(&path, "block"));
+        assert!(mentions(&["std", "collections", "HashMap"], "map"));
+        assert!(mentions(&["std", "io", "Read"], "io"));
+    }
+
+    #[test]
+    fn within_matches_consecutive_segments() {
+        let parent = ["std", "io", "prelude"];
+        assert!(is_within(&parent, "io"));
+        assert!(is_within(&parent, "std::io"));
+        assert!(!is_within(&parent, "std::prelude"));
+        assert!(!is_within(&parent, "fs"));
+    }
+}
@@ -1,22 +1,48 @@
 //! Documentation sources
 //!
 //! This module defines different sources for rustdoc JSON data:
-//! - StdSource: rustup-managed std library docs
+//! - StdSource: rustup-managed std library docs, or prebuilt ones for stable-only users
 //! - LocalSource: workspace-local crates (built on demand)
-//! - DocsRsSource: fetched from docs.rs and cached
+//! - DocsRsSource: fetched from docs.rs and cached (with the `network` feature)
+//! - Git dependencies, documented from cargo's git checkouts by LocalSource
+//! - Binary and example targets, documented with `cargo rustdoc` by LocalSource
+//! - JsonFileSource: rustdoc JSON files given directly, as produced by other build systems
+//!
+//! Each implements [`DocSource`], which other crates can implement for sources of their own.
+//!
+//! StdSource and LocalSource run rustup and cargo, so they aren't available on wasm targets,
+//! where documentation comes from JSON files and added sources.
 use crate::{CrateName, RustdocData, navigator::CrateInfo};
 use semver::{Version, VersionReq};
+#[cfg(feature = "network")]
 use serde::{Deserialize, Deserializer};
 
+#[cfg(not(target_family = "wasm"))]
+mod binary;
+#[cfg(feature = "network")]
 mod docsrs;
+#[cfg(not(target_family = "wasm"))]
+mod git;
+mod json_files;
+#[cfg(not(target_family = "wasm"))]
 mod local;
+mod package;
+#[cfg(not(target_family = "wasm"))]
 mod std;
 
 use ::std::borrow::Cow;
-pub use docsrs::DocsRsSource;
-pub use local::LocalSource;
+use ::std::future::Future;
+use ::std::pin::Pin;
+#[cfg(feature = "network")]
+pub use docsrs::{DocsRsSource, PublishedVersion};
+pub use json_files::JsonFileSource;
+#[cfg(not(target_family = "wasm"))]
+pub use local::{BuildFailure, BuildOptions, DocCommand, LocalSource};
+pub use package::PackageMetadata;
+#[cfg(not(target_family = "wasm"))]
 pub use std::StdSource;
 
+#[cfg(feature = "network")]
 #[derive(Deserialize, Debug)]
 struct RustdocVersion {
     format_version: u32,
@@ -24,6 +50,7 @@ struct RustdocVersion {
     crate_version: Option<Version>,
 }
 
+#[cfg(feature = "network")]
 fn option_semver_lenient<'de, D>(deserializer: D) -> Result<Option<Version>, D::Error>
 where
     D: Deserializer<'de>,
@@ -38,6 +65,9 @@ pub enum CrateProvenance {
     LocalDependency,
     Std,
     DocsRs,
+    JsonFile,
+    /// From a [`DocSource`] added with [`Navigator::with_source`](crate::Navigator::with_source)
+    Custom,
 }
 impl CrateProvenance {
     pub fn is_workspace(&self) -> bool {
@@ -55,19 +85,34 @@ impl CrateProvenance {
     pub fn is_docs_rs(&self) -> bool {
         matches!(self, Self::DocsRs)
     }
+
+    pub fn is_json_file(&self) -> bool {
+        matches!(self, Self::JsonFile)
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom)
+    }
 }
 
+/// A boxed future, as returned by the async methods of [`DocSource`]
+pub type SourceFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
 /// Trait for documentation sources
 ///
-/// Each source (std, local workspace, docs.rs) implements this trait to provide:
+/// Each source (std, local workspace, docs.rs, JSON files) implements this trait to provide:
 /// - Name lookup/normalization
-/// - Crate loading
+/// - Crate loading, blocking or async
 /// - Available crate listing (where applicable)
-pub trait Source {
+///
+/// Other sources, such as an internal docs artifact store, can implement it too and be added
+/// with [`Navigator::with_source`](crate::Navigator::with_source). Their crates are described
+/// with [`CrateInfo::new`] and loaded with [`RustdocData::from_json_file`].
+pub trait DocSource: Send + Sync {
     /// Transform a crate name into an internal representation
     ///
     /// This should be cheap (local) and based on already-available information.
-    /// Returning None indicates that this Source does not have any information with which to transform the provided name.
+    /// Returning None indicates that this source does not have any information with which to transform the provided name.
     fn canonicalize(&self, input_name: &str) -> Option<CrateName<'static>> {
         let _ = input_name;
         None
@@ -79,6 +124,57 @@ pub trait Source {
     /// Load the rustdoc JSON data for a crate (by canonical name)
     fn load(&self, crate_name: &str, version: Option<&Version>) -> Option<RustdocData>;
 
+    /// Look up a crate like [`DocSource::lookup`], awaiting network I/O instead of blocking on it
+    ///
+    /// Sources that only read local files look up on the polling thread, as `lookup` does.
+    fn lookup_async<'a, 'q>(
+        &'a self,
+        crate_name: &'q str,
+        version: &'q VersionReq,
+    ) -> SourceFuture<'q, Option<Cow<'a, CrateInfo>>>
+    where
+        'a: 'q,
+    {
+        Box::pin(async move { self.lookup(crate_name, version) })
+    }
+
+    /// Load a crate like [`DocSource::load`], awaiting network I/O instead of blocking on it
+    ///
+    /// Sources that only read local files (or build them) load on the polling thread, as `load`
+    /// does.
+    fn load_async<'a>(
+        &'a self,
+        crate_name: &'a str,
+        version: Option<&'a Version>,
+    ) -> SourceFuture<'a, Option<RustdocData>> {
+        Box::pin(async move { self.load(crate_name, version) })
+    }
+
+    /// The README of a crate's package: markdown, or HTML for READMEs rendered by crates.io
+    ///
+    /// By default, this reads the file named by [`CrateInfo::readme`], which is only known for
+    /// crates with a checkout on disk.
+    fn readme(&self, crate_name: &str, version: Option<&Version>) -> Option<String> {
+        let version_req = match version {
+            Some(version) => VersionReq::parse(&format!("={version}")).ok()?,
+            None => VersionReq::STAR,
+        };
+        let path = self.lookup(crate_name, &version_req)?.readme.clone()?;
+        ::std::fs::read_to_string(path).ok()
+    }
+
+    /// The manifest metadata of a crate's package, as found by [`DocSource::lookup`]
+    ///
+    /// Sources that know nothing about packages return None, which is the default.
+    fn package_metadata(
+        &self,
+        crate_name: &str,
+        version: Option<&Version>,
+    ) -> Option<PackageMetadata> {
+        let _ = (crate_name, version);
+        None
+    }
+
     /// List all available crates from this source
     /// Returns None if this source doesn't support listing (e.g., DocsRsSource)
     fn list_available<'a>(&'a self) -> Box<dyn Iterator<Item = &'a CrateInfo> + '_> {
@@ -0,0 +1,130 @@
+//! Binary and example targets
+//!
+//! `cargo doc` skips examples, skips binaries that share a name with their package's library, and
+//! leaves out private items, which is nearly everything in a binary. Workspace binaries and
+//! examples are instead documented one at a time with `cargo rustdoc --bin`/`--example`,
+//! including private items.
+
+use super::local::{BuildFailure, BuildOptions, RUSTDOCFLAGS, output_unless_cancelled};
+use crate::CancelToken;
+use anyhow::Result;
+use cargo_metadata::{Package, Target};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Whether a target is built with `--bin` or `--example`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryKind {
+    Bin,
+    Example,
+}
+
+/// A binary or example target of a workspace package
+#[derive(Debug, Clone)]
+pub(super) struct BinaryTarget {
+    package: String,
+    kind: BinaryKind,
+    name: String,
+    /// The directory holding the target's entry point, checked for changes before loading
+    source_dir: PathBuf,
+}
+
+impl BinaryTarget {
+    /// The binaries and examples of a package that `cargo doc` would not document usefully
+    ///
+    /// A binary named after the package's library is left out, since its docs would overwrite
+    /// the library's.
+    pub(super) fn for_package(package: &Package) -> impl Iterator<Item = Self> + '_ {
+        let lib_name = package
+            .targets
+            .iter()
+            .find(|target| is_library(target))
+            .map(|target| target.name.replace('-', "_"));
+
+        package.targets.iter().filter_map(move |target| {
+            let kind = if target.is_bin() {
+                BinaryKind::Bin
+            } else if target.is_example() {
+                BinaryKind::Example
+            } else {
+                return None;
+            };
+
+            let underscored = target.name.replace('-', "_");
+            if kind == BinaryKind::Bin
+                && lib_name.as_ref().is_some_and(|lib_name| {
+                    *lib_name == underscored || *lib_name == package.name.replace('-', "_")
+                })
+            {
+                return None;
+            }
+
+            Some(Self {
+                package: package.name.to_string(),
+                kind,
+                name: target.name.clone(),
+                source_dir: target
+                    .src_path
+                    .parent()
+                    .map_or_else(PathBuf::new, |dir| dir.as_std_path().to_path_buf()),
+            })
+        })
+    }
+
+    /// The target's crate name, as rustdoc names its JSON output
+    pub(super) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(super) fn source_dir(&self) -> &Path {
+        &self.source_dir
+    }
+
+    /// Build JSON docs for this target into the workspace's target directory, with the
+    /// workspace's target triple, features and rustdoc flags
+    pub(super) fn build(
+        &self,
+        project_root: &Path,
+        target_dir: &Path,
+        build_options: &BuildOptions,
+        toolchain: &str,
+        cancel_token: &CancelToken,
+    ) -> Result<()> {
+        let flag = match self.kind {
+            BinaryKind::Bin => "--bin",
+            BinaryKind::Example => "--example",
+        };
+        log::info!("Documenting {} {flag} {}", self.package, self.name);
+
+        let mut command = Command::new("rustup");
+        command
+            .args(["run", toolchain, "cargo", "rustdoc", "--package"])
+            .args([&self.package, flag, &self.name])
+            .arg("--target-dir")
+            .arg(target_dir);
+        if let Some(target) = build_options.target() {
+            command.args(["--target", target]);
+        }
+        command
+            .args(build_options.feature_args())
+            .args(["--", "--document-private-items"])
+            .args(build_options.rustdoc_flags())
+            .env("RUSTDOCFLAGS", RUSTDOCFLAGS)
+            .current_dir(project_root);
+        let output = output_unless_cancelled(&mut command, cancel_token)?;
+
+        if !output.status.success() {
+            return Err(BuildFailure::new("cargo rustdoc", &output.stderr).into());
+        }
+        Ok(())
+    }
+}
+
+/// Whether a target is the package's library, of whatever crate type
+fn is_library(target: &Target) -> bool {
+    !(target.is_bin()
+        || target.is_example()
+        || target.is_test()
+        || target.is_bench()
+        || target.is_custom_build())
+}
@@ -1,4 +1,4 @@
-use super::{CrateProvenance, Source};
+use super::{CrateProvenance, DocSource, PackageMetadata, SourceFuture};
 use crate::{CrateInfo, RustdocData};
 use anyhow::Result;
 use fieldwork::Fieldwork;
@@ -7,6 +7,7 @@ use std::{borrow::Cow, path::PathBuf};
 use trillium_smol::async_io::block_on;
 
 mod client;
+pub use client::PublishedVersion;
 use client::{DocsRsClient, ResolvedMetadata};
 
 /// Source for docs.rs documentation
@@ -31,9 +32,11 @@ impl DocsRsSource {
             .map(|client| Self { client })
     }
 
-    /// Load a crate from docs.rs
-    async fn load_async(&self, crate_name: &str, version: &Version) -> Result<Option<RustdocData>> {
-        self.client.get_crate(crate_name, version).await
+    /// List every version of a crate published on crates.io, newest first
+    ///
+    /// Returns None if the crate doesn't exist or crates.io couldn't be reached
+    pub fn versions(&self, crate_name: &str) -> Option<Vec<PublishedVersion>> {
+        block_on(self.client.versions(crate_name)).ok().flatten()
     }
 
     /// Docs.rs has unbounded crates, so we don't provide a list
@@ -43,30 +46,76 @@ impl DocsRsSource {
     }
 }
 
-impl Source for DocsRsSource {
+impl DocSource for DocsRsSource {
     fn lookup<'a>(&'a self, name: &str, version_req: &VersionReq) -> Option<Cow<'a, CrateInfo>> {
-        let ResolvedMetadata {
-            name,
-            version,
-            description,
-        } = block_on(self.client.resolve(name, version_req))
-            .ok()
-            .flatten()?;
-
-        Some(Cow::Owned(CrateInfo {
-            provenance: CrateProvenance::DocsRs,
-            version: Some(version),
-            description: Some(description),
-            name,
-            default_crate: false,
-            used_by: vec![],
-            json_path: None,
-        }))
+        block_on(self.lookup_async(name, version_req))
     }
 
     fn load(&self, crate_name: &str, version: Option<&Version>) -> Option<RustdocData> {
-        block_on(self.load_async(crate_name, version?))
+        block_on(self.load_async(crate_name, version))
+    }
+
+    fn readme(&self, crate_name: &str, version: Option<&Version>) -> Option<String> {
+        block_on(self.client.readme(crate_name, version?))
             .ok()
             .flatten()
     }
+
+    fn package_metadata(
+        &self,
+        crate_name: &str,
+        version: Option<&Version>,
+    ) -> Option<PackageMetadata> {
+        block_on(self.client.package_metadata(crate_name, version?))
+            .ok()
+            .flatten()
+    }
+
+    fn lookup_async<'a, 'q>(
+        &'a self,
+        name: &'q str,
+        version_req: &'q VersionReq,
+    ) -> SourceFuture<'q, Option<Cow<'a, CrateInfo>>>
+    where
+        'a: 'q,
+    {
+        Box::pin(async move {
+            let ResolvedMetadata {
+                name,
+                version,
+                description,
+            } = self
+                .client
+                .resolve(name, version_req)
+                .await
+                .ok()
+                .flatten()?;
+
+            Some(Cow::Owned(CrateInfo {
+                provenance: CrateProvenance::DocsRs,
+                version: Some(version),
+                description: Some(description),
+                name,
+                default_crate: false,
+                used_by: vec![],
+                json_path: None,
+                aliases: vec![],
+                readme: None,
+            }))
+        })
+    }
+
+    fn load_async<'a>(
+        &'a self,
+        crate_name: &'a str,
+        version: Option<&'a Version>,
+    ) -> SourceFuture<'a, Option<RustdocData>> {
+        Box::pin(async move {
+            self.client
+                .get_crate(crate_name, version?)
+                .await
+                .ok()
+                .flatten()
+        })
+    }
 }
@@ -1,4 +1,6 @@
-use crate::sources::CrateProvenance;
+use crate::conversions::MIN_FORMAT_VERSION;
+use crate::item_index::{ItemIndex, MappedJson};
+use crate::sources::{CrateProvenance, PackageMetadata};
 use crate::{RustdocData, sources::RustdocVersion};
 use anyhow::{Context, Result, anyhow};
 use fieldwork::Fieldwork;
@@ -9,13 +11,15 @@ use trillium_client::{Client, Status};
 use trillium_rustls::RustlsConfig;
 use trillium_smol::ClientConfig;
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Deserialize)]
 struct CratesIoResponse {
     #[serde(rename = "crate")]
     krate: CrateMetadata,
-    versions: Vec<CrateVersion>,
+    #[serde(default)]
+    versions: Vec<PublishedVersion>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -23,16 +27,41 @@ struct CrateMetadata {
     pub(super) name: String,
     pub(super) default_version: Version,
     pub(super) description: String,
+    #[serde(default)]
+    repository: Option<String>,
+    #[serde(default)]
+    homepage: Option<String>,
+    #[serde(default)]
+    documentation: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
-struct CrateVersion {
-    pub(super) num: Version,
+/// A version of a crate published on crates.io
+#[derive(Deserialize, Debug, Clone, Fieldwork)]
+#[fieldwork(get)]
+pub struct PublishedVersion {
+    #[serde(rename = "num")]
+    version: Version,
+    /// Whether the version was yanked, in which case it is only selected when asked for exactly
+    #[field(copy)]
+    #[serde(default)]
+    yanked: bool,
+    /// Publication timestamp, in RFC 3339 format
+    #[serde(default)]
+    created_at: Option<String>,
+    #[field = false]
+    #[serde(default)]
+    license: Option<String>,
+    #[field = false]
+    #[serde(default)]
+    rust_version: Option<String>,
+    #[field = false]
+    #[serde(default)]
+    edition: Option<String>,
+    #[field = false]
+    #[serde(default)]
+    features: BTreeMap<String, Vec<String>>,
 }
 
-/// Minimum supported format version (inclusive)
-const MIN_FORMAT_VERSION: u32 = 55;
-
 /// Client for fetching rustdoc JSON from docs.rs
 #[derive(Debug, Fieldwork)]
 pub struct DocsRsClient {
@@ -71,6 +100,7 @@ impl DocsRsClient {
                 name,
                 default_version,
                 description,
+                ..
             },
             versions,
         )) = self
@@ -84,10 +114,18 @@ impl DocsRsClient {
         let version = if version_req.matches(&default_version) {
             Some(default_version)
         } else {
-            versions
-                .into_iter()
-                .filter(|version| version_req.matches(version))
+            let matching = versions
+                .iter()
+                .filter(|published| version_req.matches(&published.version));
+            // Yanked versions are still documented, but only chosen when nothing else matches
+            // (such as when a yanked version was asked for by number)
+            matching
+                .clone()
+                .filter(|published| !published.yanked)
+                .map(|published| &published.version)
                 .max()
+                .or_else(|| matching.map(|published| &published.version).max())
+                .cloned()
         };
 
         Ok(version.map(|version| ResolvedMetadata {
@@ -97,6 +135,53 @@ impl DocsRsClient {
         }))
     }
 
+    /// Every published version of a crate, newest first
+    ///
+    /// Returns Ok(None) if the crate is not found
+    pub(super) async fn versions(&self, crate_name: &str) -> Result<Option<Vec<PublishedVersion>>> {
+        Ok(self
+            .metadata(crate_name, true)
+            .await?
+            .map(|(_, mut versions)| {
+                versions.sort_by(|a, b| b.version.cmp(&a.version));
+                versions
+            }))
+    }
+
+    /// The manifest metadata crates.io has for a published version of a crate
+    ///
+    /// Returns Ok(None) if the crate or version is not found
+    pub(super) async fn package_metadata(
+        &self,
+        crate_name: &str,
+        version: &Version,
+    ) -> Result<Option<PackageMetadata>> {
+        let Some((krate, versions)) = self.metadata(crate_name, true).await? else {
+            return Ok(None);
+        };
+        let Some(published) = versions
+            .into_iter()
+            .find(|published| &published.version == version)
+        else {
+            return Ok(None);
+        };
+
+        // crates.io no longer records authors, so those are left empty
+        Ok(Some(PackageMetadata {
+            name: krate.name,
+            version: Some(published.version),
+            description: Some(krate.description).filter(|description| !description.is_empty()),
+            license: published.license,
+            repository: krate.repository,
+            homepage: krate.homepage,
+            documentation: krate.documentation,
+            rust_version: published.rust_version,
+            edition: published.edition,
+            authors: vec![],
+            features: published.features,
+        }))
+    }
+
     /// Fetch rustdoc JSON for a crate, checking cache first
     ///
     /// Returns:
@@ -160,12 +245,14 @@ impl DocsRsClient {
             .await?;
 
         // Normalize to current format version
-        let crate_data = crate::conversions::load_and_normalize(&json, Some(format_version))
+        let mut crate_data = crate::conversions::load_and_normalize(&json, Some(format_version))
             .context("Failed to normalize rustdoc JSON")?;
+        let items = ItemIndex::from_crate(&mut crate_data);
 
         // Build RustdocData
         let data = RustdocData {
             crate_data,
+            items,
             name: crate_name.to_string(),
             provenance: CrateProvenance::DocsRs,
             fs_path,
@@ -176,13 +263,62 @@ impl DocsRsClient {
         Ok(Some(data))
     }
 
+    /// A crate's README as rendered to HTML by crates.io, checking the cache first
+    ///
+    /// Returns Ok(None) if the crate version has no README on crates.io
+    pub(super) async fn readme(
+        &self,
+        crate_name: &str,
+        version: &Version,
+    ) -> Result<Option<String>> {
+        let path = self
+            .cache_dir
+            .join("readmes")
+            .join(crate_name)
+            .join(format!("{version}.html"));
+        if let Ok(readme) = async_fs::read_to_string(&path).await {
+            return Ok(Some(readme));
+        }
+
+        let url =
+            format!("https://static.crates.io/readmes/{crate_name}/{crate_name}-{version}.html");
+        log::debug!("Fetching README from crates.io: {url}");
+
+        let conn = self.http_client.get(url).await?;
+
+        // Missing READMEs are reported as forbidden rather than not found
+        if let Some(Status::NotFound | Status::Forbidden) = conn.status() {
+            return Ok(None);
+        }
+
+        let mut conn = conn
+            .success()
+            .map_err(|e| anyhow!("Failed to fetch README: {}", e))?;
+        let readme = conn
+            .response_body()
+            .read_string()
+            .await
+            .context("Failed to read README")?;
+
+        if let Some(parent) = path.parent() {
+            async_fs::create_dir_all(parent)
+                .await
+                .context("Failed to create cache directory")?;
+        }
+        async_fs::write(&path, &readme)
+            .await
+            .context("Failed to write cache file")?;
+
+        Ok(Some(readme))
+    }
+
     /// Resolve "latest" to a specific version using the crates.io API
     /// Returns Ok(None) if the crate is not found
     async fn metadata(
         &self,
         crate_name: &str,
         include_versions: bool,
-    ) -> Result<Option<(CrateMetadata, Vec<Version>)>> {
+    ) -> Result<Option<(CrateMetadata, Vec<PublishedVersion>)>> {
         let include = if include_versions {
             "versions"
         } else {
@@ -214,7 +350,7 @@ impl DocsRsClient {
         let CratesIoResponse { krate, versions } =
             sonic_rs::serde::from_slice(&bytes).context("Failed to parse crates.io response")?;
 
-        Ok(Some((krate, versions.into_iter().map(|v| v.num).collect())))
+        Ok(Some((krate, versions)))
     }
 
     /// Construct the cache file path for a crate
@@ -256,21 +392,11 @@ impl DocsRsClient {
                 path.display()
             );
 
+            // Normalize to current format version, leaving current-format items to be parsed
+            // as they're reached
             let start = std::time::Instant::now();
-            let json = async_fs::read(&path)
-                .await
-                .context("Failed to read cached file")?;
-            let read_elapsed = start.elapsed();
-            log::debug!(
-                "⏱️ Read {} ({:.2} MB) in {:?}",
-                crate_name,
-                json.len() as f64 / 1_000_000.0,
-                read_elapsed
-            );
-
-            // Normalize to current format version
-            let start = std::time::Instant::now();
-            let crate_data = crate::conversions::load_and_normalize(&json, Some(source_format))
+            let (crate_data, items) = MappedJson::open(&path)
+                .and_then(MappedJson::parse)
                 .context("Failed to normalize cached JSON")?;
             let parse_elapsed = start.elapsed();
             log::debug!("⏱️ Parsed {} in {:?}", crate_name, parse_elapsed);
@@ -282,6 +408,7 @@ impl DocsRsClient {
 
             let data = RustdocData {
                 crate_data,
+                items,
                 name: crate_name.to_string(),
                 provenance: CrateProvenance::LocalDependency,
                 fs_path: path,
@@ -0,0 +1,91 @@
+//! Git dependencies
+//!
+//! Crates pulled from git have no docs.rs JSON. They are documented from their checkout in
+//! cargo's git cache instead, with the output kept in ferritin's cache under the checked-out
+//! revision, so that each revision is built once and shared between projects.
+
+use super::local::{BuildFailure, cargo_doc_json, doc_dir, output_unless_cancelled};
+use crate::CancelToken;
+use anyhow::Result;
+use cargo_metadata::Package;
+use std::path::{Path, PathBuf};
+
+/// A dependency checked out from a git repository
+#[derive(Debug, Clone)]
+pub(super) struct GitCheckout {
+    /// The package's manifest, inside `$CARGO_HOME/git/checkouts`
+    manifest_path: PathBuf,
+    /// Where docs for this revision are built, under `$CARGO_HOME/rustdoc-json/git`
+    target_dir: PathBuf,
+    /// The target triple docs are built for, if not the host
+    target: Option<String>,
+}
+
+impl GitCheckout {
+    /// The checkout a package comes from, if cargo fetched it from git
+    pub(super) fn for_package(package: &Package, target: Option<&str>) -> Option<Self> {
+        let revision = git_revision(&package.source.as_ref()?.repr)?;
+        let target_dir = home::cargo_home()
+            .ok()?
+            .join("rustdoc-json")
+            .join("git")
+            .join(package.name.as_str())
+            .join(revision);
+
+        Some(Self {
+            manifest_path: package.manifest_path.clone().into_std_path_buf(),
+            target_dir,
+            target: target.map(String::from),
+        })
+    }
+
+    /// Where the JSON docs for `crate_name` are written
+    pub(super) fn json_path(&self, crate_name: &str) -> PathBuf {
+        let underscored = crate_name.replace('-', "_");
+        doc_dir(&self.target_dir, self.target.as_deref()).join(format!("{underscored}.json"))
+    }
+
+    /// Build JSON docs from the checkout into the cache
+    pub(super) fn build(&self, toolchain: &str, cancel_token: &CancelToken) -> Result<()> {
+        let checkout_dir = self.manifest_path.parent().unwrap_or(Path::new("."));
+        log::info!("Documenting git checkout {}", checkout_dir.display());
+
+        let mut command = cargo_doc_json(toolchain, self.target.as_deref());
+        command
+            .arg("--manifest-path")
+            .arg(&self.manifest_path)
+            .arg("--target-dir")
+            .arg(&self.target_dir)
+            .current_dir(checkout_dir);
+        let output = output_unless_cancelled(&mut command, cancel_token)?;
+
+        if !output.status.success() {
+            return Err(BuildFailure::new("cargo doc", &output.stderr).into());
+        }
+        Ok(())
+    }
+}
+
+/// The commit of a `git+<url>#<commit>` package source
+fn git_revision(source: &str) -> Option<&str> {
+    let (_, revision) = source.strip_prefix("git+")?.rsplit_once('#')?;
+    Some(revision).filter(|revision| !revision.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn revision_from_package_source() {
+        assert_eq!(
+            git_revision("git+https://github.com/jbr/ferritin?branch=main#0c926d7f"),
+            Some("0c926d7f")
+        );
+        assert_eq!(
+            git_revision("registry+https://github.com/rust-lang/crates.io-index"),
+            None
+        );
+        assert_eq!(git_revision("git+https://github.com/jbr/ferritin"), None);
+    }
+}
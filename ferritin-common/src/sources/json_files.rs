@@ -0,0 +1,124 @@
+//! Rustdoc JSON files given directly
+//!
+//! Build systems other than Cargo (Bazel, Buck) can produce rustdoc JSON too. This source reads
+//! such files, or every `.json` file in a directory, without needing a Cargo project. Rustdoc
+//! names its output after the crate, so each file's stem is taken as its crate name.
+
+use crate::CrateName;
+use crate::RustdocData;
+use crate::navigator::CrateInfo;
+use crate::sources::CrateProvenance;
+use crate::sources::DocSource;
+use anyhow::{Result, anyhow};
+use fieldwork::Fieldwork;
+use rustc_hash::FxHashMap;
+use semver::{Version, VersionReq};
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// Source for rustdoc JSON files named on the command line
+#[derive(Debug, Clone, Fieldwork)]
+#[field(get)]
+pub struct JsonFileSource {
+    /// The files and directories this source was created from
+    paths: Vec<PathBuf>,
+    #[field = false]
+    crates: FxHashMap<CrateName<'static>, CrateInfo>,
+}
+
+impl JsonFileSource {
+    /// Collect the rustdoc JSON files at `paths`, each of which is a `.json` file or a directory
+    /// holding them
+    ///
+    /// Files are only read when their crate is first loaded. When two files share a crate name,
+    /// the one given first wins.
+    pub fn load(paths: &[PathBuf]) -> Result<Self> {
+        let mut crates = FxHashMap::default();
+
+        for path in paths {
+            let json_paths = if path.is_dir() {
+                let mut json_paths = std::fs::read_dir(path)?
+                    .filter_map(|entry| Some(entry.ok()?.path()))
+                    .filter(|path| is_json(path))
+                    .collect::<Vec<_>>();
+                json_paths.sort();
+                json_paths
+            } else if path.is_file() {
+                vec![path.clone()]
+            } else {
+                return Err(anyhow!("{} not found", path.display()));
+            };
+
+            for json_path in json_paths {
+                let Some(name) = json_path.file_stem().and_then(|stem| stem.to_str()) else {
+                    continue;
+                };
+                let crate_name = CrateName::from(name.to_string());
+                crates.entry(crate_name).or_insert_with(|| CrateInfo {
+                    provenance: CrateProvenance::JsonFile,
+                    version: None,
+                    description: Some(format!("from {}", json_path.display())),
+                    name: name.to_string(),
+                    default_crate: false,
+                    used_by: vec![],
+                    json_path: Some(json_path.clone()),
+                    aliases: vec![],
+                    readme: None,
+                });
+            }
+        }
+
+        if crates.is_empty() {
+            return Err(anyhow!(
+                "no rustdoc JSON files found in {}",
+                paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        Ok(Self {
+            paths: paths.to_vec(),
+            crates,
+        })
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+}
+
+impl DocSource for JsonFileSource {
+    fn lookup<'a>(&'a self, name: &str, _version_req: &VersionReq) -> Option<Cow<'a, CrateInfo>> {
+        let canonical = self.canonicalize(name)?;
+        self.crates.get(&canonical).map(Cow::Borrowed)
+    }
+
+    fn load(&self, crate_name: &str, _version: Option<&Version>) -> Option<RustdocData> {
+        let crate_info = self.lookup(crate_name, &VersionReq::STAR)?;
+        let json_path = crate_info.json_path.as_ref()?.to_owned();
+
+        // Other build systems may pin an older toolchain, so older formats are converted
+        RustdocData::open(
+            crate_info.name.clone(),
+            json_path.clone(),
+            CrateProvenance::JsonFile,
+        )
+        .inspect_err(|error| log::error!("Failed to load {}: {error:?}", json_path.display()))
+        .ok()
+    }
+
+    fn list_available<'a>(&'a self) -> Box<dyn Iterator<Item = &'a CrateInfo> + '_> {
+        Box::new(self.crates.values())
+    }
+
+    fn canonicalize(&self, input_name: &str) -> Option<CrateName<'static>> {
+        let (crate_name, _) = self.crates.get_key_value(input_name)?;
+        Some(crate_name.clone())
+    }
+}
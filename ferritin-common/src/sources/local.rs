@@ -1,44 +1,151 @@
 use super::CrateProvenance;
+use super::binary::BinaryTarget;
+use super::git::GitCheckout;
+use crate::CancelToken;
 use crate::RustdocData;
+use crate::conversions::{is_parsed_directly, is_supported};
 use crate::crate_name::CrateName;
+use crate::item_index::MappedJson;
 use crate::navigator::CrateInfo;
-use crate::sources::RustdocVersion;
-use crate::sources::Source;
+use crate::sources::{DocSource, PackageMetadata};
+use crate::toolchain;
 use anyhow::{Result, anyhow};
-use cargo_metadata::MetadataCommand;
+use cargo_metadata::{MetadataCommand, Package};
 use fieldwork::Fieldwork;
 use rustc_hash::FxHashMap;
 use rustc_hash::FxHashSet;
-use rustdoc_types::{Crate, FORMAT_VERSION};
 use semver::Version;
 use semver::VersionReq;
+use serde::Deserialize;
 use std::borrow::Cow;
+use std::fs;
+use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
-use std::process::Command;
-use std::time::SystemTime;
+use std::process::{Command, Output, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
 use walkdir::WalkDir;
 
+/// How workspace documentation is built, beyond what the workspace's own cargo configuration says
+#[derive(Debug, Clone, Default, Fieldwork)]
+#[fieldwork(get, with, option_set_some)]
+pub struct BuildOptions {
+    /// Build directory to use instead of the one cargo would choose
+    ///
+    /// Cargo already honors `CARGO_TARGET_DIR` and `build.target-dir` in `.cargo/config.toml`, so
+    /// this is only needed to override them, as `cargo --target-dir` does.
+    target_dir: Option<PathBuf>,
+
+    /// Target triple to document the workspace for (e.g. `wasm32-unknown-unknown`), so that
+    /// items behind `#[cfg]` for that platform are the ones shown
+    target: Option<String>,
+
+    /// Toolchain to build documentation with, instead of the nightly pinned by the project's
+    /// `rust-toolchain.toml` or `nightly`
+    toolchain: Option<String>,
+
+    /// Kills builds in progress when cancelled
+    cancel_token: CancelToken,
+
+    /// Features to enable when documenting workspace packages, as `cargo doc --features`
+    #[field(with(into))]
+    features: Vec<String>,
+
+    /// Document workspace packages with every feature enabled
+    all_features: bool,
+
+    /// Document workspace packages without their default features
+    no_default_features: bool,
+
+    /// Serve out-of-date workspace documentation rather than rebuilding it while loading, so
+    /// that loading never waits on cargo
+    ///
+    /// The crates served this way are collected by [`LocalSource::take_stale_crates`], to be
+    /// rebuilt in the background with [`LocalSource::rebuild_stale_docs`].
+    defer_rebuilds: bool,
+
+    /// Extra rustdoc flags for workspace builds, such as `--cfg docsrs` for crates that only
+    /// document feature-gated items under `doc_cfg`
+    #[field(with(into))]
+    rustdoc_flags: Vec<String>,
+
+    /// The command each workspace package is documented with, for those not documented with
+    /// `cargo doc`
+    doc_commands: FxHashMap<CrateName<'static>, DocCommand>,
+}
+
+/// How a workspace package's documentation is built
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocCommand {
+    /// `cargo doc`, which hands rustdoc flags to every crate it documents through `RUSTDOCFLAGS`
+    #[default]
+    Doc,
+    /// `cargo rustdoc`, which hands the extra rustdoc flags to the package's library alone
+    Rustdoc,
+}
+
 #[derive(Debug, Fieldwork)]
 #[field(get)]
 pub struct LocalSource {
     manifest_path: PathBuf,
     target_dir: PathBuf,
+    /// Every resolved version of each package, newest first
     #[field = false]
-    crates: FxHashMap<CrateName<'static>, CrateInfo>,
+    crates: FxHashMap<CrateName<'static>, Vec<CrateInfo>>,
     root_crate: Option<CrateName<'static>>,
     can_rebuild: bool,
+    /// Dependencies fetched from git, which are documented from their checkouts
+    #[field = false]
+    git_checkouts: FxHashMap<(CrateName<'static>, Version), GitCheckout>,
+    /// Renamed dependencies (`foo = { package = "bar" }`), from `foo` to `bar`
+    #[field = false]
+    aliases: FxHashMap<CrateName<'static>, CrateName<'static>>,
+    /// Binaries and examples of workspace packages, documented with `cargo rustdoc`
+    #[field = false]
+    binary_targets: FxHashMap<CrateName<'static>, BinaryTarget>,
+    /// Manifest metadata for every resolved package version, including binaries under their own
+    /// names
+    #[field = false]
+    packages: FxHashMap<(CrateName<'static>, Version), PackageMetadata>,
+    build_options: BuildOptions,
+    /// The toolchain chosen with [`BuildOptions::toolchain`] or pinned by the project, if any
+    #[field = false]
+    chosen_toolchain: Option<String>,
+    /// A dated nightly to build with instead of `nightly`, once `nightly` turns out to write a
+    /// format ferritin can't read
+    #[field = false]
+    fallback_toolchain: OnceLock<String>,
+    /// Workspace crates served from out-of-date documentation, with
+    /// [`BuildOptions::defer_rebuilds`], that haven't been taken for rebuilding yet
+    #[field = false]
+    stale_crates: Mutex<Vec<CrateName<'static>>>,
+    /// The most recent failed build of each crate whose last build failed
+    #[field = false]
+    build_failures: Mutex<FxHashMap<CrateName<'static>, BuildFailure>>,
 }
 
 impl LocalSource {
     pub fn load(path: &Path) -> Result<Self> {
+        Self::load_with_options(path, BuildOptions::default())
+    }
+
+    /// Load a workspace whose documentation is built with the given options
+    pub fn load_with_options(path: &Path, build_options: BuildOptions) -> Result<Self> {
+        let mut metadata_command = MetadataCommand::new();
+        if let Some(target_dir) = build_options.target_dir() {
+            // cargo metadata reports the target directory, but has no --target-dir flag
+            metadata_command.env("CARGO_TARGET_DIR", target_dir);
+        }
+
         let metadata = if path.is_dir() {
-            MetadataCommand::new().current_dir(path).exec()?
+            metadata_command.current_dir(path).exec()?
         } else if path.file_name().and_then(|n| n.to_str()) == Some("Cargo.toml") {
             if !path.exists() {
                 return Err(anyhow!("Cargo.toml not found at {}", path.display()));
             }
-            MetadataCommand::new().manifest_path(path).exec()?
+            metadata_command.manifest_path(path).exec()?
         } else {
             return Err(anyhow!(
                 "Path must be a directory or Cargo.toml file, got: {}",
@@ -50,6 +157,7 @@ impl LocalSource {
         let mut reverse_deps: FxHashMap<&str, FxHashSet<&str>> = FxHashMap::default();
 
         let mut workspace_packages: FxHashSet<&str> = FxHashSet::default();
+        let mut aliases: FxHashMap<CrateName<'static>, CrateName<'static>> = FxHashMap::default();
 
         for package in metadata.workspace_packages() {
             workspace_packages.insert(&package.name);
@@ -58,15 +166,21 @@ impl LocalSource {
                     .entry(&dep.name)
                     .or_default()
                     .insert(&package.name);
+                if let Some(rename) = &dep.rename {
+                    aliases.insert(rename.clone().into(), dep.name.clone().into());
+                }
             }
         }
 
         let target_dir = metadata.target_directory.clone().into_std_path_buf();
+        let target = build_options.target.as_deref();
         let root_crate = metadata
             .root_package()
             .map(|p| CrateName::from(p.name.to_string()));
 
-        let mut crates = FxHashMap::default();
+        let mut crates: FxHashMap<CrateName<'static>, Vec<CrateInfo>> = FxHashMap::default();
+        let mut git_checkouts = FxHashMap::default();
+        let mut packages = FxHashMap::default();
         for package in &metadata.packages {
             // let is_crates_io = package
             //     .source
@@ -87,24 +201,93 @@ impl LocalSource {
                 .map(|name| name.to_string())
                 .collect();
 
-            let doc_dir = target_dir.join("doc");
-            let underscored = package.name.replace('-', "_");
-            let json_path = doc_dir.join(format!("{underscored}.json"));
-
-            crates.insert(
-                package.name.to_string().into(),
-                CrateInfo {
-                    provenance,
-                    version: Some(package.version.clone()),
-                    description: package.description.clone(),
-                    name: package.name.to_string(),
-                    default_crate: root_crate
-                        .as_ref()
-                        .is_some_and(|dc| &CrateName::from(&**package.name) == dc),
-                    used_by,
-                    json_path: Some(json_path),
-                },
+            let json_path = match GitCheckout::for_package(package, target) {
+                Some(git_checkout) => {
+                    let json_path = git_checkout.json_path(&package.name);
+                    git_checkouts.insert(
+                        (package.name.to_string().into(), package.version.clone()),
+                        git_checkout,
+                    );
+                    json_path
+                }
+                None => {
+                    let underscored = package.name.replace('-', "_");
+                    doc_dir(&target_dir, target).join(format!("{underscored}.json"))
+                }
+            };
+
+            let crate_name = CrateName::from(package.name.to_string());
+            let mut package_aliases: Vec<String> = aliases
+                .iter()
+                .filter(|(_, real_name)| **real_name == crate_name)
+                .map(|(alias, _)| alias.to_string())
+                .collect();
+            package_aliases.sort();
+
+            packages.insert(
+                (crate_name.clone(), package.version.clone()),
+                package_metadata(package),
             );
+            crates.entry(crate_name).or_default().push(CrateInfo {
+                provenance,
+                version: Some(package.version.clone()),
+                description: package.description.clone(),
+                name: package.name.to_string(),
+                default_crate: root_crate
+                    .as_ref()
+                    .is_some_and(|dc| &CrateName::from(&**package.name) == dc),
+                used_by,
+                json_path: Some(json_path),
+                aliases: package_aliases,
+                readme: readme_path(package),
+            });
+        }
+
+        for versions in crates.values_mut() {
+            versions.sort_by(|a, b| b.version.cmp(&a.version));
+        }
+
+        let mut binary_targets = FxHashMap::default();
+        for package in metadata.workspace_packages() {
+            for binary_target in BinaryTarget::for_package(package) {
+                let target_name = CrateName::from(binary_target.name().to_string());
+                // A binary named after its package, with no library, is already listed under the
+                // package; any other name that's taken belongs to something else
+                if target_name != CrateName::from(&**package.name) {
+                    if crates.contains_key(&target_name) {
+                        continue;
+                    }
+                    let underscored = binary_target.name().replace('-', "_");
+                    packages.insert(
+                        (target_name.clone(), package.version.clone()),
+                        package_metadata(package),
+                    );
+                    crates.insert(
+                        target_name.clone(),
+                        vec![CrateInfo {
+                            provenance: CrateProvenance::Workspace,
+                            version: Some(package.version.clone()),
+                            description: package.description.clone(),
+                            name: binary_target.name().to_string(),
+                            default_crate: false,
+                            used_by: vec![],
+                            json_path: Some(
+                                doc_dir(&target_dir, target).join(format!("{underscored}.json")),
+                            ),
+                            aliases: vec![],
+                            readme: readme_path(package),
+                        }],
+                    );
+                }
+                binary_targets.insert(target_name, binary_target);
+            }
+        }
+
+        let chosen_toolchain = build_options.toolchain.clone().or_else(|| {
+            toolchain::pinned_toolchain(manifest_path.parent().unwrap_or(&manifest_path))
+        });
+        if let Some(chosen_toolchain) = &chosen_toolchain {
+            log::info!("Documenting the workspace with the {chosen_toolchain} toolchain");
         }
 
         Ok(Self {
@@ -113,19 +296,32 @@ impl LocalSource {
             can_rebuild: true,
             crates,
             root_crate,
+            git_checkouts,
+            aliases,
+            binary_targets,
+            packages,
+            build_options,
+            chosen_toolchain,
+            fallback_toolchain: OnceLock::new(),
+            stale_crates: Mutex::default(),
+            build_failures: Mutex::default(),
         })
     }
 
     /// Check if a crate name is a workspace package
     pub fn is_workspace_package(&self, crate_name: &str) -> bool {
         let crate_name = CrateName::from(crate_name);
-        self.crates
-            .get(&crate_name)
-            .is_some_and(|crate_info| crate_info.provenance.is_workspace())
+        self.crates.get(&crate_name).is_some_and(|versions| {
+            versions
+                .iter()
+                .any(|crate_info| crate_info.provenance.is_workspace())
+        })
     }
 
     /// Get the resolved version for a dependency
     /// Returns None if not a dependency or if it's a path/workspace dep
+    ///
+    /// When several versions are resolved, this is the newest.
     pub fn get_dependency_version<'a, 'b: 'a>(
         &'a self,
         crate_name: &'b str,
@@ -133,7 +329,7 @@ impl LocalSource {
         let crate_name = CrateName::from(crate_name);
         self.crates
             .get(&crate_name)
-            .and_then(|lsm| lsm.version.as_ref())
+            .and_then(|versions| versions.first()?.version.as_ref())
     }
 
     /// Get the project root
@@ -148,7 +344,7 @@ impl LocalSource {
 
     /// Get the JSON path for a crate
     fn json_path(&self, crate_name: &str) -> PathBuf {
-        let doc_dir = self.target_dir.join("doc");
+        let doc_dir = doc_dir(&self.target_dir, self.build_options.target.as_deref());
         let underscored = crate_name.replace('-', "_");
         doc_dir.join(format!("{underscored}.json"))
     }
@@ -156,28 +352,61 @@ impl LocalSource {
     /// Load a workspace crate (may rebuild if needed)
     pub fn load_workspace_crate(&self, crate_name: CrateName<'_>) -> Option<RustdocData> {
         let json_path = self.json_path(crate_name.as_ref());
+        let source_dir = self
+            .binary_targets
+            .get(&crate_name.to_static())
+            .map_or_else(
+                || self.project_root().join("src"),
+                |b| b.source_dir().to_owned(),
+            );
+        let flags = self.doc_flags(&crate_name, None);
         let mut tried_rebuilding = false;
 
         loop {
-            let needs_rebuild = json_path
-                .metadata()
-                .ok()
-                .and_then(|m| m.modified().ok())
-                .is_none_or(|docs_updated| {
-                    WalkDir::new(self.project_root().join("src"))
-                        .into_iter()
-                        .filter_map(|entry| -> Option<SystemTime> {
-                            entry.ok()?.metadata().ok()?.modified().ok()
-                        })
-                        .any(|file_updated| file_updated > docs_updated)
-                });
+            // Documentation built with other flags (from before hidden items were documented,
+            // say) is out of date too, unless it can't be rebuilt
+            let built_with_other_flags =
+                self.can_rebuild && !tried_rebuilding && !built_with_flags(&json_path, &flags);
+            let needs_rebuild = built_with_other_flags
+                || json_path
+                    .metadata()
+                    .ok()
+                    .and_then(|m| m.modified().ok())
+                    .is_none_or(|docs_updated| {
+                        WalkDir::new(&source_dir)
+                            .into_iter()
+                            .filter_map(|entry| -> Option<SystemTime> {
+                                entry.ok()?.metadata().ok()?.modified().ok()
+                            })
+                            .any(|file_updated| file_updated > docs_updated)
+                    });
+
+            // Out-of-date docs are read rather than mapped, since the rebuild rewrites the file
+            // while they're in use
+            let serve_stale =
+                needs_rebuild && self.build_options.defer_rebuilds && !tried_rebuilding;
+            let json = if serve_stale {
+                MappedJson::read(&json_path).ok()
+            } else if !needs_rebuild {
+                MappedJson::open(&json_path).ok()
+            } else {
+                None
+            };
 
-            if !needs_rebuild
-                && let Ok(content) = std::fs::read(&json_path)
-                && let Ok(format_version) = sonic_rs::get_from_slice(&content, &["format_version"])
-                && let Ok(FORMAT_VERSION) = format_version.as_raw_str().parse()
+            // Older formats are rebuilt, unless rebuilding wrote one of them again
+            if let Some(json) = json
+                && json
+                    .format_version()
+                    .is_some_and(|v| is_parsed_directly(v) || tried_rebuilding && is_supported(v))
             {
-                let crate_data: Crate = sonic_rs::serde::from_slice(&content).ok()?;
+                if serve_stale {
+                    log::info!("Showing out-of-date documentation for {crate_name}");
+                    if let Ok(mut stale_crates) = self.stale_crates.lock() {
+                        stale_crates.push(crate_name.to_static());
+                    }
+                }
+
+                let (crate_data, items) = json.parse().ok()?;
                 let version = crate_data
                     .crate_version
                     .as_ref()
@@ -185,6 +414,7 @@ impl LocalSource {
 
                 break Some(RustdocData {
                     crate_data,
+                    items,
                     name: crate_name.to_string(),
                     provenance: CrateProvenance::Workspace,
                     fs_path: json_path,
@@ -196,6 +426,10 @@ impl LocalSource {
                 if self.rebuild_docs(&crate_name, None).is_ok() {
                     continue;
                 }
+            } else if tried_rebuilding
+                && self.rebuild_with_compatible_toolchain(&crate_name, None, &json_path)
+            {
+                continue;
             }
             break None;
         }
@@ -207,7 +441,10 @@ impl LocalSource {
         crate_name: CrateName<'_>,
         version: Option<&Version>,
     ) -> Option<RustdocData> {
-        let info = self.lookup(&crate_name, &VersionReq::STAR)?;
+        let versions = self.crates.get(&crate_name)?;
+        let info = versions
+            .iter()
+            .find(|info| version.is_none_or(|v| info.version.as_ref() == Some(v)))?;
         let json_path = info.json_path.as_deref()?;
         let info_version = info.version.as_ref();
 
@@ -218,18 +455,30 @@ impl LocalSource {
             return None;
         }
 
+        // Versions documented at the same path rebuild it out from under each other, so their
+        // docs are read rather than mapped
+        let shares_json_path = versions
+            .iter()
+            .filter(|other| other.json_path.as_deref() == Some(json_path))
+            .nth(1)
+            .is_some();
+        let flags = self.doc_flags(&crate_name, version);
         let mut tried_rebuilding = false;
 
         loop {
-            if let Ok(content) = std::fs::read(json_path)
-                && let Ok(RustdocVersion {
-                    format_version,
-                    crate_version,
-                }) = sonic_rs::serde::from_slice(&content)
-                && format_version == FORMAT_VERSION
-                && crate_version.as_ref() == version
+            let json = if shares_json_path {
+                MappedJson::read(json_path)
+            } else {
+                MappedJson::open(json_path)
+            };
+            if let Ok(json) = json
+                && json
+                    .format_version()
+                    .is_some_and(|v| is_parsed_directly(v) || tried_rebuilding && is_supported(v))
+                && json.crate_version().as_ref() == version
+                && (tried_rebuilding || !self.can_rebuild || built_with_flags(json_path, &flags))
             {
-                let crate_data: Crate = sonic_rs::serde::from_slice(&content).ok()?;
+                let (crate_data, items) = json.parse().ok()?;
                 let version = crate_data
                     .crate_version
                     .as_ref()
@@ -237,6 +486,7 @@ impl LocalSource {
 
                 break Some(RustdocData {
                     crate_data,
+                    items,
                     name: crate_name.to_string(),
                     provenance: CrateProvenance::LocalDependency,
                     fs_path: json_path.to_owned(),
@@ -248,42 +498,462 @@ impl LocalSource {
                 if self.rebuild_docs(&crate_name, version).is_ok() {
                     continue;
                 }
+            } else if tried_rebuilding
+                && self.rebuild_with_compatible_toolchain(&crate_name, version, json_path)
+            {
+                continue;
             }
             break None;
         }
     }
 
+    /// Regenerate rustdoc JSON for every workspace package, for when their sources are known to
+    /// have changed
+    pub fn rebuild_workspace_docs(&self) -> Result<()> {
+        for (crate_name, versions) in &self.crates {
+            if versions.iter().any(|info| info.provenance.is_workspace()) {
+                self.rebuild_docs(crate_name, None)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Workspace crates that have been served from out-of-date documentation since this was last
+    /// called, with [`BuildOptions::defer_rebuilds`]
+    pub fn take_stale_crates(&self) -> Vec<CrateName<'static>> {
+        self.stale_crates
+            .lock()
+            .map(|mut stale_crates| std::mem::take(&mut *stale_crates))
+            .unwrap_or_default()
+    }
+
+    /// Regenerate rustdoc JSON for workspace crates that were served out of date, killing the
+    /// build if `cancel_token` is cancelled
+    ///
+    /// This is independent of the [`BuildOptions::cancel_token`], which cancels the loads that
+    /// are waiting on a build, since nothing waits on this one. Already-loaded documentation
+    /// isn't updated; load it again from a fresh `LocalSource` to see the rebuilt docs.
+    pub fn rebuild_stale_docs(
+        &self,
+        crate_name: &CrateName<'_>,
+        cancel_token: &CancelToken,
+    ) -> Result<()> {
+        self.build_docs(crate_name, None, cancel_token)
+    }
+
+    /// The toolchain documentation is built with: the one chosen with
+    /// [`BuildOptions::toolchain`], the nightly pinned by the project's `rust-toolchain.toml`, or
+    /// `nightly`
+    pub fn toolchain(&self) -> &str {
+        self.chosen_toolchain
+            .as_deref()
+            .or_else(|| self.fallback_toolchain.get().map(String::as_str))
+            .unwrap_or(toolchain::DEFAULT_TOOLCHAIN)
+    }
+
+    /// When a rebuild still leaves JSON in a format that can't be read, explain which toolchain
+    /// wrote it, and rebuild with an installed dated nightly that writes a readable format
+    ///
+    /// Returns whether it rebuilt. This happens at most once, after which every build uses the
+    /// dated nightly, and never when a toolchain was chosen or pinned.
+    fn rebuild_with_compatible_toolchain(
+        &self,
+        crate_name: &CrateName<'_>,
+        version: Option<&Version>,
+        json_path: &Path,
+    ) -> bool {
+        let Some(format_version) = MappedJson::open(json_path)
+            .ok()
+            .and_then(|json| json.format_version())
+        else {
+            return false;
+        };
+        if is_supported(format_version) {
+            // Something other than the format went wrong
+            return false;
+        }
+
+        let built_on = toolchain::toolchain_date(self.toolchain())
+            .map(|date| format!(" ({} is from {date})", self.toolchain()))
+            .unwrap_or_default();
+        log::error!(
+            "Failed to load {crate_name}: {}{built_on}",
+            toolchain::describe_format_mismatch(format_version)
+        );
+        if self.chosen_toolchain.is_some() {
+            return false;
+        }
+
+        let Some(compatible) = toolchain::compatible_nightly() else {
+            log::error!(
+                "No installed nightly writes a format ferritin can read. Install one with \
+                 `rustup toolchain install {}`",
+                toolchain::SUGGESTED_NIGHTLY
+            );
+            return false;
+        };
+        if self.fallback_toolchain.set(compatible.clone()).is_err() {
+            return false;
+        }
+
+        log::info!("Rebuilding {crate_name} with {compatible}");
+        self.rebuild_docs(crate_name, version).is_ok()
+    }
+
     /// Rebuild documentation for a crate
     fn rebuild_docs(&self, crate_name: &CrateName<'_>, version: Option<&Version>) -> Result<()> {
+        self.build_docs(crate_name, version, &self.build_options.cancel_token)
+    }
+
+    /// Regenerate rustdoc JSON for a workspace crate or dependency now, whether or not it's out
+    /// of date
+    ///
+    /// Dependencies are given with their version, since several may be resolved.
+    pub fn rebuild(&self, crate_name: &CrateName<'_>, version: Option<&Version>) -> Result<()> {
+        self.rebuild_docs(crate_name, version)
+    }
+
+    /// Why the last documentation build of a crate failed, if it did
+    ///
+    /// Loading a crate whose build fails finds nothing, and this explains why. A later build that
+    /// succeeds clears it.
+    pub fn build_failure(&self, crate_name: &str) -> Option<BuildFailure> {
+        let crate_name = if crate_name == "crate" {
+            self.root_crate.clone()?
+        } else {
+            self.canonicalize(crate_name)?
+        };
+        self.build_failures.lock().ok()?.get(&crate_name).cloned()
+    }
+
+    /// Rebuild documentation for a crate, unless `cancel_token` is cancelled first, remembering
+    /// whether it failed for [`Self::build_failure`]
+    fn build_docs(
+        &self,
+        crate_name: &CrateName<'_>,
+        version: Option<&Version>,
+        cancel_token: &CancelToken,
+    ) -> Result<()> {
+        let result = self.run_build(crate_name, version, cancel_token);
+        if result.is_ok()
+            && let Some(json_path) = self.built_json_path(crate_name, version)
+        {
+            let _ = fs::write(flags_path(&json_path), self.doc_flags(crate_name, version));
+        }
+        if let Ok(mut build_failures) = self.build_failures.lock() {
+            match &result {
+                Ok(()) => {
+                    build_failures.remove(&crate_name.to_static());
+                }
+                Err(error) => {
+                    if let Some(failure) = error.downcast_ref::<BuildFailure>() {
+                        build_failures.insert(crate_name.to_static(), failure.clone());
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Where [`Self::run_build`] writes a crate's JSON
+    fn built_json_path(
+        &self,
+        crate_name: &CrateName<'_>,
+        version: Option<&Version>,
+    ) -> Option<PathBuf> {
+        let Some(version) = version else {
+            return Some(self.json_path(crate_name));
+        };
+        self.crates
+            .get(&crate_name.to_static())?
+            .iter()
+            .find(|info| info.version.as_ref() == Some(version))?
+            .json_path
+            .clone()
+    }
+
+    /// The rustdoc flags [`Self::run_build`] documents a crate with, recorded beside its JSON so
+    /// that a change of flags rebuilds it
+    fn doc_flags(&self, crate_name: &CrateName<'_>, version: Option<&Version>) -> String {
+        let is_git_checkout = version.is_some_and(|version| {
+            self.git_checkouts
+                .contains_key(&(crate_name.to_static(), version.clone()))
+        });
+        if self.binary_targets.contains_key(&crate_name.to_static()) {
+            format!(
+                "{} --document-private-items",
+                self.build_options.rustdocflags()
+            )
+        } else if is_git_checkout {
+            RUSTDOCFLAGS.to_string()
+        } else {
+            self.build_options.rustdocflags()
+        }
+    }
+
+    /// Run the build that documents a crate: `cargo rustdoc` for binaries and examples, `cargo
+    /// doc` in the checkout for git dependencies, and `cargo doc` in the workspace otherwise
+    fn run_build(
+        &self,
+        crate_name: &CrateName<'_>,
+        version: Option<&Version>,
+        cancel_token: &CancelToken,
+    ) -> Result<()> {
+        if let Some(binary_target) = self.binary_targets.get(&crate_name.to_static()) {
+            return binary_target.build(
+                self.project_root(),
+                &self.target_dir,
+                &self.build_options,
+                self.toolchain(),
+                cancel_token,
+            );
+        }
+
+        if let Some(version) = version
+            && let Some(git_checkout) = self
+                .git_checkouts
+                .get(&(CrateName::from(crate_name.to_string()), version.clone()))
+        {
+            return git_checkout.build(self.toolchain(), cancel_token);
+        }
+
         let package_spec = match version {
             Some(v) => format!("{}@{}", crate_name, v),
             None => crate_name.to_string(),
         };
 
-        let output = Command::new("rustup")
-            .arg("run")
-            .args([
-                "nightly",
-                "cargo",
-                "doc",
-                "--no-deps",
-                "--package",
-                &package_spec,
-            ])
-            .env("RUSTDOCFLAGS", "-Z unstable-options --output-format=json")
-            .current_dir(self.project_root())
-            .output()?;
+        // Cargo only accepts feature flags for workspace packages, and only they are configured
+        // with a doc command
+        let is_workspace_package = self.is_workspace_package(crate_name);
+        let doc_command = if is_workspace_package {
+            self.build_options.doc_command(crate_name)
+        } else {
+            DocCommand::Doc
+        };
+        let target = self.build_options.target.as_deref();
+        let mut command = match doc_command {
+            DocCommand::Doc => cargo_doc_json(self.toolchain(), target),
+            DocCommand::Rustdoc => cargo_rustdoc_json(self.toolchain(), target),
+        };
+        if is_workspace_package {
+            command.args(self.build_options.feature_args());
+        }
+
+        // The target directory is passed on explicitly, since it may have come from --target-dir
+        command
+            .args(["--package", &package_spec])
+            .arg("--target-dir")
+            .arg(&self.target_dir)
+            .current_dir(self.project_root());
+        match doc_command {
+            DocCommand::Doc => {
+                command.env("RUSTDOCFLAGS", self.build_options.rustdocflags());
+            }
+            DocCommand::Rustdoc => {
+                command.arg("--").args(&self.build_options.rustdoc_flags);
+            }
+        }
+        let output = output_unless_cancelled(&mut command, cancel_token)?;
 
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow!("cargo doc failed: {}", stderr));
+            let command_name = match doc_command {
+                DocCommand::Doc => "cargo doc",
+                DocCommand::Rustdoc => "cargo rustdoc",
+            };
+            return Err(BuildFailure::new(command_name, &output.stderr).into());
         }
         Ok(())
     }
 }
 
-impl Source for LocalSource {
-    fn lookup<'a>(&'a self, name: &str, _version: &VersionReq) -> Option<Cow<'a, CrateInfo>> {
+impl BuildOptions {
+    /// The command that documents a package, which is `cargo doc` for all but the workspace
+    /// packages configured otherwise
+    fn doc_command(&self, crate_name: &CrateName<'_>) -> DocCommand {
+        self.doc_commands
+            .get(&crate_name.to_static())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// `RUSTDOCFLAGS` for JSON output, with the extra flags
+    fn rustdocflags(&self) -> String {
+        std::iter::once(RUSTDOCFLAGS)
+            .chain(self.rustdoc_flags.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The cargo arguments that select features for a workspace package
+    pub(super) fn feature_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if !self.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(self.features.join(","));
+        }
+        if self.all_features {
+            args.push("--all-features".to_string());
+        }
+        if self.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+        args
+    }
+}
+
+/// A documentation build that ran and failed, usually because the crate doesn't compile
+#[derive(Debug, Clone, PartialEq, Eq, Fieldwork)]
+#[fieldwork(get)]
+pub struct BuildFailure {
+    /// The command that failed, such as `cargo doc`
+    command: String,
+    /// What cargo wrote to standard error, including the compiler's diagnostics
+    stderr: String,
+}
+
+impl BuildFailure {
+    pub(super) fn new(command: &str, stderr: &[u8]) -> Self {
+        Self {
+            command: command.to_string(),
+            stderr: String::from_utf8_lossy(stderr).into_owned(),
+        }
+    }
+}
+
+impl std::fmt::Display for BuildFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed: {}", self.command, self.stderr)
+    }
+}
+
+impl std::error::Error for BuildFailure {}
+
+/// `cargo doc --no-deps` on a nightly toolchain, configured to write rustdoc JSON, for the given
+/// target triple or the host
+pub(super) fn cargo_doc_json(toolchain: &str, target: Option<&str>) -> Command {
+    let mut command = Command::new("rustup");
+    command
+        .args(["run", toolchain, "cargo", "doc", "--no-deps"])
+        .env("RUSTDOCFLAGS", RUSTDOCFLAGS);
+    if let Some(target) = target {
+        command.args(["--target", target]);
+    }
+    command
+}
+
+/// `cargo rustdoc --lib` on a nightly toolchain, configured to write rustdoc JSON, for the given
+/// target triple or the host
+///
+/// Flags after a following `--` go to the library's rustdoc alone.
+fn cargo_rustdoc_json(toolchain: &str, target: Option<&str>) -> Command {
+    let mut command = Command::new("rustup");
+    command
+        .args(["run", toolchain, "cargo", "rustdoc", "--lib"])
+        .env("RUSTDOCFLAGS", RUSTDOCFLAGS);
+    if let Some(target) = target {
+        command.args(["--target", target]);
+    }
+    command
+}
+
+/// Run a command to completion and collect its output, like [`Command::output`], unless the
+/// token is cancelled first, in which case the command is killed
+pub(super) fn output_unless_cancelled(
+    command: &mut Command,
+    cancel_token: &CancelToken,
+) -> Result<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Pipes are drained on their own threads, so a chatty build can't stall on a full pipe
+    // while it's being polled
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut bytes = vec![];
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut bytes);
+            }
+            bytes
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|pipe| Box::new(pipe) as _));
+    let stderr = drain(child.stderr.take().map(|pipe| Box::new(pipe) as _));
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Output {
+                status,
+                stdout: stdout.join().unwrap_or_default(),
+                stderr: stderr.join().unwrap_or_default(),
+            });
+        }
+        if cancel_token.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow!("cancelled"));
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Where cargo writes docs within a target directory: `doc`, or `{triple}/doc` when building
+/// for an explicit target
+pub(super) fn doc_dir(target_dir: &Path, target: Option<&str>) -> PathBuf {
+    match target {
+        Some(target) => target_dir.join(target).join("doc"),
+        None => target_dir.join("doc"),
+    }
+}
+
+/// Where a package's README is, from the `readme` field cargo fills in from Cargo.toml or by
+/// finding a `README.md` next to it
+fn readme_path(package: &Package) -> Option<PathBuf> {
+    let readme = package.readme.as_ref()?;
+    Some(
+        package
+            .manifest_path
+            .parent()?
+            .join(readme)
+            .into_std_path_buf(),
+    )
+}
+
+/// The manifest metadata cargo reports for a package
+fn package_metadata(package: &Package) -> PackageMetadata {
+    PackageMetadata {
+        name: package.name.to_string(),
+        version: Some(package.version.clone()),
+        description: package.description.clone(),
+        license: package.license.clone(),
+        repository: package.repository.clone(),
+        homepage: package.homepage.clone(),
+        documentation: package.documentation.clone(),
+        rust_version: package.rust_version.as_ref().map(ToString::to_string),
+        edition: Some(package.edition.to_string()),
+        authors: package.authors.clone(),
+        features: package.features.clone(),
+    }
+}
+
+/// The file beside documentation JSON that records the rustdoc flags it was built with
+fn flags_path(json_path: &Path) -> PathBuf {
+    json_path.with_extension("flags")
+}
+
+/// Whether the JSON at `json_path` was recorded as built with `flags`
+fn built_with_flags(json_path: &Path, flags: &str) -> bool {
+    fs::read_to_string(flags_path(json_path)).is_ok_and(|built_with| built_with == flags)
+}
+
+/// Rustdoc flags for JSON output, including `#[doc(hidden)]` items
+pub(super) const RUSTDOCFLAGS: &str =
+    "-Z unstable-options --output-format=json --document-hidden-items";
+
+impl DocSource for LocalSource {
+    fn lookup<'a>(&'a self, name: &str, version: &VersionReq) -> Option<Cow<'a, CrateInfo>> {
         // Handle "crate" alias for single-package workspaces
         let search_name = if name == "crate" {
             self.root_crate()?
@@ -291,7 +961,17 @@ impl Source for LocalSource {
             &CrateName::from(name.to_owned())
         };
 
-        self.crates.get(search_name).map(Cow::Borrowed)
+        // Versions are newest first, so this is the newest match. Bare names accept any version,
+        // including prereleases, which `VersionReq::STAR` itself would not match.
+        self.crates
+            .get(search_name)
+            .or_else(|| self.crates.get(self.aliases.get(search_name)?))?
+            .iter()
+            .find(|info| {
+                *version == VersionReq::STAR
+                    || info.version.as_ref().is_none_or(|v| version.matches(v))
+            })
+            .map(Cow::Borrowed)
     }
 
     fn load(&self, crate_name: &str, version: Option<&Version>) -> Option<RustdocData> {
@@ -304,8 +984,26 @@ impl Source for LocalSource {
         }
     }
 
+    fn package_metadata(
+        &self,
+        crate_name: &str,
+        version: Option<&Version>,
+    ) -> Option<PackageMetadata> {
+        let crate_info = match version {
+            Some(version) => {
+                self.lookup(crate_name, &VersionReq::parse(&format!("={version}")).ok()?)
+            }
+            None => self.lookup(crate_name, &VersionReq::STAR),
+        }?;
+        let key = (
+            CrateName::from(crate_info.name.clone()),
+            crate_info.version.clone()?,
+        );
+        self.packages.get(&key).cloned()
+    }
+
     fn list_available<'a>(&'a self) -> Box<dyn Iterator<Item = &'a CrateInfo> + '_> {
-        Box::new(self.crates.values().filter(|crate_info| {
+        Box::new(self.crates.values().flatten().filter(|crate_info| {
             crate_info.provenance.is_workspace()
                 || match self.root_crate.as_ref() {
                     Some(rc) => crate_info
@@ -321,6 +1019,7 @@ impl Source for LocalSource {
         self.crates
             .get_key_value(input_name)
             .map(|(k, _)| k.clone())
+            .or_else(|| self.aliases.get(input_name).cloned())
     }
 }
 
@@ -0,0 +1,43 @@
+use fieldwork::Fieldwork;
+use semver::Version;
+use std::collections::BTreeMap;
+
+/// What a package says about itself in its manifest: license, links, minimum Rust version,
+/// edition, authors and features
+///
+/// Local crates have this from cargo metadata, and published crates from crates.io, which doesn't
+/// record authors.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Fieldwork)]
+#[fieldwork(get, with(option_set_some, into))]
+pub struct PackageMetadata {
+    /// The package name
+    #[field(with = false)]
+    pub(crate) name: String,
+    /// The version described
+    #[field(with = false)]
+    pub(crate) version: Option<Version>,
+    pub(crate) description: Option<String>,
+    /// An SPDX license expression, such as `MIT OR Apache-2.0`
+    pub(crate) license: Option<String>,
+    pub(crate) repository: Option<String>,
+    pub(crate) homepage: Option<String>,
+    /// Where the documentation is hosted, if not docs.rs
+    pub(crate) documentation: Option<String>,
+    /// The minimum supported Rust version (`rust-version`)
+    pub(crate) rust_version: Option<String>,
+    pub(crate) edition: Option<String>,
+    pub(crate) authors: Vec<String>,
+    /// Each feature, with the features and optional dependencies it enables
+    pub(crate) features: BTreeMap<String, Vec<String>>,
+}
+
+impl PackageMetadata {
+    /// Metadata for a package, to be filled in with the `with_*` methods
+    pub fn new(name: impl Into<String>, version: Option<Version>) -> Self {
+        Self {
+            name: name.into(),
+            version,
+            ..Self::default()
+        }
+    }
+}
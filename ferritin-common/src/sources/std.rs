@@ -1,17 +1,23 @@
 use crate::CrateName;
 use crate::RustdocData;
+use crate::item_index::MappedJson;
 use crate::navigator::CrateInfo;
 use crate::sources::CrateProvenance;
-use crate::sources::Source;
+use crate::sources::DocSource;
+use crate::sources::local::RUSTDOCFLAGS;
+use crate::toolchain::DEFAULT_TOOLCHAIN;
+use anyhow::{Result, anyhow};
 use fieldwork::Fieldwork;
 use rustc_hash::FxHashMap;
-use rustdoc_types::{Crate, FORMAT_VERSION};
 use semver::Version;
 use semver::VersionReq;
 use std::borrow::Cow;
 use std::path::PathBuf;
 use std::process::Command;
 
+mod prebuilt;
+use prebuilt::DefaultToolchain;
+
 /// Descriptions for standard library crates
 const STD_DESCRIPTIONS: [(&str, &str); 6] = [
     ("std", "The Rust Standard Library"),
@@ -39,39 +45,144 @@ pub struct StdSource {
 
 impl StdSource {
     /// Try to create a StdSource from the current rustup installation
+    ///
+    /// This uses the `rust-docs-json` component if it is installed, and otherwise docs previously
+    /// built by [`StdSource::build_from_source`] for the same toolchain. Without a nightly
+    /// toolchain, docs previously fetched by [`StdSource::download_prebuilt`] are used instead.
     pub fn from_rustup() -> Option<Self> {
-        let sysroot = Command::new("rustup")
-            .args(["run", "nightly", "rustc", "--print", "sysroot"])
-            .output()
-            .ok()?;
+        Self::from_toolchain(DEFAULT_TOOLCHAIN)
+    }
 
-        if !sysroot.status.success() {
-            return None;
+    /// Like [`StdSource::from_rustup`], for a toolchain other than `nightly`, such as the one a
+    /// workspace is documented with
+    pub fn from_toolchain(toolchain: &str) -> Option<Self> {
+        let Some(toolchain) = NightlyToolchain::detect(toolchain) else {
+            return Self::from_prebuilt();
+        };
+        let component_path = toolchain.sysroot.join("share/doc/rust/json/");
+        let docs_path = if component_path.exists() {
+            component_path
+        } else {
+            let built_path = built_docs_dir(&toolchain.rustc_version)?.join("doc");
+            if !built_path.join("std.json").exists() {
+                return None;
+            }
+            built_path
+        };
+
+        Some(Self::new(docs_path, toolchain.rustc_version))
+    }
+
+    /// Docs for the default (usually stable) toolchain, if [`StdSource::download_prebuilt`]
+    /// has fetched them
+    pub fn from_prebuilt() -> Option<Self> {
+        let toolchain = DefaultToolchain::detect()?;
+        let docs_path = toolchain.docs_dir()?;
+        docs_path
+            .join("std.json")
+            .exists()
+            .then(|| Self::new(docs_path, toolchain.rustc_version))
+    }
+
+    /// Download the standard library docs for the default (usually stable) toolchain from the
+    /// Rust distribution server, for users without nightly
+    #[cfg(feature = "network")]
+    pub fn download_prebuilt() -> Result<Self> {
+        let toolchain = DefaultToolchain::detect()
+            .ok_or_else(|| anyhow!("could not determine the rustc version"))?;
+        let docs_path = toolchain.download()?;
+        Ok(Self::new(docs_path, toolchain.rustc_version))
+    }
+
+    /// Whether rustup has the given nightly toolchain, which [`StdSource::install_component`] and
+    /// [`StdSource::build_from_source`] need
+    pub fn has_toolchain(toolchain: &str) -> bool {
+        NightlyToolchain::detect(toolchain).is_some()
+    }
+
+    /// Install rustup's `rust-docs-json` component for a nightly toolchain
+    pub fn install_component(toolchain: &str) -> Result<Self> {
+        let output = Command::new("rustup")
+            .args([
+                "component",
+                "add",
+                "--toolchain",
+                toolchain,
+                "rust-docs-json",
+            ])
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("rustup component add failed: {}", stderr));
         }
 
-        let s = std::str::from_utf8(&sysroot.stdout).ok()?;
-        let docs_path = PathBuf::from(s.trim()).join("share/doc/rust/json/");
-        if !docs_path.exists() {
-            return None;
+        Self::from_toolchain(toolchain)
+            .ok_or_else(|| anyhow!("rust-docs-json was installed but not found"))
+    }
+
+    /// Build JSON docs for the standard library from a nightly toolchain's `rust-src` component,
+    /// for when `rust-docs-json` is unavailable
+    ///
+    /// The docs are written to `$CARGO_HOME/rustdoc-json/std/{rustc_version}`, and found there by
+    /// [`StdSource::from_toolchain`] afterwards. This compiles the standard library, so it takes
+    /// a few minutes.
+    pub fn build_from_source(toolchain_name: &str) -> Result<Self> {
+        let toolchain = NightlyToolchain::detect(toolchain_name).ok_or_else(|| {
+            anyhow!("no {toolchain_name} toolchain found; install one with rustup")
+        })?;
+
+        let manifest_path = toolchain
+            .sysroot
+            .join("lib/rustlib/src/rust/library/Cargo.toml");
+        if !manifest_path.exists() {
+            return Err(anyhow!(
+                "the standard library's sources are missing; \
+                 install them with `rustup component add --toolchain {toolchain_name} rust-src`"
+            ));
         }
 
-        let version = Command::new("rustup")
-            .args(["run", "nightly", "rustc", "--version", "--verbose"])
-            .output()
-            .ok()?;
+        let target_dir = built_docs_dir(&toolchain.rustc_version)
+            .ok_or_else(|| anyhow!("could not find the cargo home directory"))?;
+        log::info!(
+            "Documenting the standard library into {}",
+            target_dir.display()
+        );
 
-        if !version.status.success() {
-            return None;
+        let mut command = Command::new("rustup");
+        command.args(["run", toolchain_name, "cargo", "doc", "--no-deps"]);
+        for (name, _) in STD_DESCRIPTIONS {
+            if name != "std_detect" {
+                command.args(["--package", name]);
+            }
         }
+        let output = command
+            .arg("--manifest-path")
+            .arg(&manifest_path)
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .env("RUSTDOCFLAGS", RUSTDOCFLAGS)
+            // The standard library opts into its own unstable features, as `-Zbuild-std` allows
+            .env("RUSTC_BOOTSTRAP", "1")
+            .output()?;
 
-        let rustc_version = std::str::from_utf8(&version.stdout)
-            .ok()?
-            .lines()
-            .find_map(|line| line.strip_prefix("release: "))?
-            .trim();
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("cargo doc failed: {}", stderr));
+        }
 
-        let rustc_version = Version::parse(rustc_version).ok()?;
+        Ok(Self::new(target_dir.join("doc"), toolchain.rustc_version))
+    }
+
+    /// Whether a crate name refers to one of the standard library crates
+    pub fn is_std_crate(name: &str) -> bool {
+        let name = name.strip_suffix("_crate").unwrap_or(name);
+        STD_DESCRIPTIONS
+            .iter()
+            .any(|(std_name, _)| *std_name == name)
+    }
 
+    fn new(docs_path: PathBuf, rustc_version: Version) -> Self {
         let crates = STD_DESCRIPTIONS
             .into_iter()
             .map(|(name, description)| {
@@ -84,22 +195,79 @@ impl StdSource {
                         name: name.to_string(),
                         default_crate: false,
                         used_by: vec![],
+                        aliases: vec![],
                         json_path: (name != "std_detect")
                             .then(|| docs_path.join(format!("{name}.json"))),
+                        readme: None,
                     },
                 )
             })
             .collect();
 
-        Some(Self {
+        Self {
             docs_path,
             rustc_version,
             crates,
+        }
+    }
+}
+
+/// The nightly toolchain that rustdoc JSON is read and built with
+struct NightlyToolchain {
+    sysroot: PathBuf,
+    rustc_version: Version,
+}
+
+impl NightlyToolchain {
+    fn detect(toolchain: &str) -> Option<Self> {
+        let sysroot = Command::new("rustup")
+            .args(["run", toolchain, "rustc", "--print", "sysroot"])
+            .output()
+            .ok()?;
+
+        if !sysroot.status.success() {
+            return None;
+        }
+
+        let s = std::str::from_utf8(&sysroot.stdout).ok()?;
+        let sysroot = PathBuf::from(s.trim());
+
+        let version = Command::new("rustup")
+            .args(["run", toolchain, "rustc", "--version", "--verbose"])
+            .output()
+            .ok()?;
+
+        if !version.status.success() {
+            return None;
+        }
+
+        let rustc_version = std::str::from_utf8(&version.stdout)
+            .ok()?
+            .lines()
+            .find_map(|line| line.strip_prefix("release: "))?
+            .trim();
+
+        let rustc_version = Version::parse(rustc_version).ok()?;
+
+        Some(Self {
+            sysroot,
+            rustc_version,
         })
     }
 }
 
-impl Source for StdSource {
+/// Where [`StdSource::build_from_source`] builds docs for a toolchain
+fn built_docs_dir(rustc_version: &Version) -> Option<PathBuf> {
+    Some(
+        home::cargo_home()
+            .ok()?
+            .join("rustdoc-json")
+            .join("std")
+            .join(rustc_version.to_string()),
+    )
+}
+
+impl DocSource for StdSource {
     fn lookup<'a>(&'a self, name: &str, _version_req: &VersionReq) -> Option<Cow<'a, CrateInfo>> {
         let canonical = self.canonicalize(name)?;
         self.crates.get(&*canonical).map(Cow::Borrowed)
@@ -108,19 +276,12 @@ impl Source for StdSource {
     fn load(&self, crate_name: &str, _version: Option<&Version>) -> Option<RustdocData> {
         let crate_info = self.lookup(crate_name, &VersionReq::STAR)?;
         let json_path = crate_info.json_path.as_ref()?.to_owned();
-        let content = std::fs::read(&json_path).ok()?;
-
-        let Ok(FORMAT_VERSION) = sonic_rs::get_from_slice(&content, &["format_version"])
-            .ok()?
-            .as_raw_str()
-            .parse()
-        else {
-            return None;
-        };
 
-        let crate_data: Crate = sonic_rs::serde::from_slice(&content).ok()?;
+        // Prebuilt docs can predate the current format, so older supported formats are converted
+        let (crate_data, items) = MappedJson::open(&json_path).ok()?.parse().ok()?;
         Some(RustdocData {
             crate_data,
+            items,
             name: crate_name.to_string(),
             provenance: CrateProvenance::Std,
             fs_path: json_path,
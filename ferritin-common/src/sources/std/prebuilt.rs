@@ -0,0 +1,322 @@
+//! Prebuilt standard library docs
+//!
+//! The Rust release infrastructure publishes rustdoc JSON for the standard library as the
+//! `rust-docs-json` component. For users without a nightly toolchain, the component's archive
+//! is downloaded from static.rust-lang.org (or the mirror named by `RUSTUP_DIST_SERVER`, as
+//! rustup itself does), checked against the SHA-256 its manifest lists, and unpacked into
+//! `$CARGO_HOME/rustdoc-json/std/prebuilt/{rustc_version}`.
+
+use semver::Version;
+use std::path::PathBuf;
+use std::process::Command;
+
+// Downloading needs the `network` feature; finding docs downloaded before doesn't
+#[cfg(feature = "network")]
+use {
+    anyhow::{Context, Result, anyhow},
+    sha2::{Digest, Sha256},
+    std::path::Path,
+    tar::Archive,
+    trillium_client::Client,
+    trillium_rustls::RustlsConfig,
+    trillium_smol::ClientConfig,
+    trillium_smol::async_io::block_on,
+    walkdir::WalkDir,
+    xz2::read::XzDecoder,
+};
+
+#[cfg(feature = "network")]
+const DEFAULT_DIST_SERVER: &str = "https://static.rust-lang.org";
+
+/// The toolchain rustc runs as by default, usually stable
+pub(super) struct DefaultToolchain {
+    pub(super) rustc_version: Version,
+    #[cfg_attr(not(feature = "network"), allow(dead_code))]
+    host: String,
+    /// The date of the commit the toolchain was built from, as `YYYY-MM-DD`
+    #[cfg_attr(not(feature = "network"), allow(dead_code))]
+    commit_date: Option<String>,
+}
+
+impl DefaultToolchain {
+    pub(super) fn detect() -> Option<Self> {
+        let output = Command::new("rustc")
+            .args(["--version", "--verbose"])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let verbose_version = std::str::from_utf8(&output.stdout).ok()?;
+        let field = |name: &str| {
+            verbose_version
+                .lines()
+                .find_map(|line| line.strip_prefix(name))
+                .map(|value| value.trim().to_string())
+        };
+
+        Some(Self {
+            rustc_version: Version::parse(&field("release: ")?).ok()?,
+            host: field("host: ")?,
+            commit_date: field("commit-date: "),
+        })
+    }
+
+    /// Where this toolchain's docs are unpacked, whether or not they have been downloaded yet
+    pub(super) fn docs_dir(&self) -> Option<PathBuf> {
+        Some(
+            home::cargo_home()
+                .ok()?
+                .join("rustdoc-json")
+                .join("std")
+                .join("prebuilt")
+                .join(self.rustc_version.to_string())
+                .join("json"),
+        )
+    }
+
+    /// Download and unpack the `rust-docs-json` component, returning the directory holding
+    /// `std.json` and its siblings
+    ///
+    /// The component is looked for in this release's channel manifest first. Stable releases
+    /// don't always ship it, so the nightly from the release's commit date is tried next, which
+    /// documents nearly the same API.
+    #[cfg(feature = "network")]
+    pub(super) fn download(&self) -> Result<PathBuf> {
+        let docs_dir = self
+            .docs_dir()
+            .ok_or_else(|| anyhow!("could not find the cargo home directory"))?;
+        let dist_server =
+            std::env::var("RUSTUP_DIST_SERVER").unwrap_or_else(|_| DEFAULT_DIST_SERVER.to_string());
+        let dist_server = dist_server.trim_end_matches('/');
+
+        let mut manifest_urls = vec![format!(
+            "{dist_server}/dist/channel-rust-{}.toml",
+            self.rustc_version
+        )];
+        if let Some(commit_date) = &self.commit_date {
+            manifest_urls.push(format!(
+                "{dist_server}/dist/{commit_date}/channel-rust-nightly.toml"
+            ));
+        }
+
+        let http_client = Client::new(RustlsConfig::<ClientConfig>::default());
+        let (archive_url, archive_hash) = manifest_urls
+            .iter()
+            .find_map(|manifest_url| {
+                log::info!("Looking for rust-docs-json in {manifest_url}");
+                let manifest = block_on(fetch(&http_client, manifest_url)).ok()?;
+                let manifest = String::from_utf8_lossy(&manifest);
+                let component = component(&manifest, &self.host)?;
+                Some((component.url.to_string(), component.hash.to_string()))
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "no rust-docs-json component for {} on {}",
+                    self.rustc_version,
+                    self.host
+                )
+            })?;
+
+        log::info!("Downloading {archive_url}");
+        let archive = block_on(fetch(&http_client, &archive_url))?;
+        let actual_hash = format!("{:x}", Sha256::digest(&archive));
+        if !actual_hash.eq_ignore_ascii_case(&archive_hash) {
+            return Err(anyhow!(
+                "{archive_url} has SHA-256 {actual_hash}, but the manifest lists {archive_hash}"
+            ));
+        }
+
+        unpack(&archive, &archive_url, &docs_dir)?;
+        Ok(docs_dir)
+    }
+}
+
+#[cfg(feature = "network")]
+async fn fetch(http_client: &Client, url: &str) -> Result<Vec<u8>> {
+    let mut conn = http_client
+        .get(url)
+        .await?
+        .success()
+        .map_err(|e| anyhow!("Failed to fetch {url}: {e}"))?;
+
+    conn.response_body()
+        .read_bytes()
+        .await
+        .with_context(|| format!("Failed to read {url}"))
+}
+
+#[cfg(feature = "network")]
+/// Unpack the JSON files of an xz-compressed component archive into `docs_dir`
+fn unpack(archive: &[u8], archive_url: &str, docs_dir: &Path) -> Result<()> {
+    let parent = docs_dir
+        .parent()
+        .ok_or_else(|| anyhow!("{} has no parent", docs_dir.display()))?;
+    let extract_dir = parent.join("extract");
+    if extract_dir.exists() {
+        std::fs::remove_dir_all(&extract_dir)?;
+    }
+    std::fs::create_dir_all(&extract_dir)?;
+
+    Archive::new(XzDecoder::new(archive))
+        .unpack(&extract_dir)
+        .with_context(|| format!("Failed to unpack {archive_url}"))?;
+
+    let json_dir = WalkDir::new(&extract_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .find(|entry| entry.file_name() == "std.json")
+        .and_then(|entry| entry.path().parent().map(Path::to_path_buf))
+        .ok_or_else(|| anyhow!("{archive_url} does not contain std.json"))?;
+
+    if docs_dir.exists() {
+        std::fs::remove_dir_all(docs_dir)?;
+    }
+    std::fs::rename(json_dir, docs_dir)?;
+    std::fs::remove_dir_all(&extract_dir)?;
+    Ok(())
+}
+
+#[cfg(feature = "network")]
+/// A component archive listed in a channel manifest
+#[derive(Debug, PartialEq, Eq)]
+struct Component<'a> {
+    url: &'a str,
+    /// The archive's SHA-256, as lowercase hex
+    hash: &'a str,
+}
+
+#[cfg(feature = "network")]
+/// The xz archive of the `rust-docs-json` component for `host` in a channel manifest, if the
+/// manifest lists it as available
+///
+/// Channel manifests are TOML with one `[pkg.{component}.target.{host}]` table per component
+/// and host; only the few keys needed here are read.
+fn component<'a>(manifest: &'a str, host: &str) -> Option<Component<'a>> {
+    let sections = [
+        format!("[pkg.rust-docs-json-preview.target.{host}]"),
+        format!("[pkg.rust-docs-json.target.{host}]"),
+    ];
+
+    let mut in_section = false;
+    let mut available = false;
+    let (mut url, mut hash) = (None, None);
+
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            if in_section && available {
+                break;
+            }
+            in_section = sections.iter().any(|section| section == line);
+            available = false;
+            (url, hash) = (None, None);
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"');
+        match key.trim() {
+            "available" => available = value == "true",
+            "xz_url" => url = Some(value),
+            "xz_hash" => hash = Some(value),
+            _ => {}
+        }
+    }
+
+    if !(in_section && available) {
+        return None;
+    }
+    Some(Component {
+        url: url?,
+        hash: hash?,
+    })
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_component_archive_for_host() {
+        let manifest = r#"
+[pkg.rust-docs.target.x86_64-unknown-linux-gnu]
+available = true
+xz_url = "https://static.rust-lang.org/dist/rust-docs.tar.xz"
+xz_hash = "0000"
+
+[pkg.rust-docs-json-preview.target.aarch64-apple-darwin]
+available = true
+xz_url = "https://static.rust-lang.org/dist/rust-docs-json-aarch64.tar.xz"
+xz_hash = "aaaa"
+
+[pkg.rust-docs-json-preview.target.x86_64-unknown-linux-gnu]
+available = true
+hash = "bbbb"
+url = "https://static.rust-lang.org/dist/rust-docs-json-x86_64.tar.gz"
+xz_hash = "cccc"
+xz_url = "https://static.rust-lang.org/dist/rust-docs-json-x86_64.tar.xz"
+
+[pkg.rust-docs-json-preview.target.x86_64-pc-windows-msvc]
+available = false
+
+[pkg.rust-docs-json-preview.target.i686-unknown-linux-gnu]
+available = true
+hash = "dddd"
+url = "https://static.rust-lang.org/dist/rust-docs-json-i686.tar.gz"
+"#;
+
+        assert_eq!(
+            component(manifest, "x86_64-unknown-linux-gnu"),
+            Some(Component {
+                url: "https://static.rust-lang.org/dist/rust-docs-json-x86_64.tar.xz",
+                hash: "cccc",
+            })
+        );
+        assert_eq!(
+            component(manifest, "aarch64-apple-darwin"),
+            Some(Component {
+                url: "https://static.rust-lang.org/dist/rust-docs-json-aarch64.tar.xz",
+                hash: "aaaa",
+            })
+        );
+        assert_eq!(component(manifest, "x86_64-pc-windows-msvc"), None);
+        assert_eq!(component(manifest, "i686-unknown-linux-gnu"), None);
+        assert_eq!(component(manifest, "riscv64gc-unknown-linux-gnu"), None);
+    }
+
+    #[test]
+    fn unpacks_json_from_xz_archive() {
+        let mut tar = tar::Builder::new(vec![]);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(2);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(
+            &mut header,
+            "rust-docs-json-nightly/rust-docs-json/share/doc/rust/json/std.json",
+            &b"{}"[..],
+        )
+        .unwrap();
+        let mut archive = vec![];
+        std::io::copy(
+            &mut xz2::read::XzEncoder::new(&tar.into_inner().unwrap()[..], 6),
+            &mut archive,
+        )
+        .unwrap();
+
+        let temp_dir = std::env::temp_dir().join(format!("ferritin-unpack-{}", std::process::id()));
+        let docs_dir = temp_dir.join("json");
+        unpack(&archive, "rust-docs-json.tar.xz", &docs_dir).unwrap();
+
+        assert_eq!(std::fs::read(docs_dir.join("std.json")).unwrap(), b"{}");
+        assert!(!temp_dir.join("extract").exists());
+        std::fs::remove_dir_all(temp_dir).unwrap();
+    }
+}
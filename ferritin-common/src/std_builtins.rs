@@ -0,0 +1,94 @@
+//! The primitive types and keywords that std documents, which are named without a crate: `u32`,
+//! `str::len` and `match` refer to std's pages for them.
+
+/// Primitive types, which links and paths can name without a crate
+pub const PRIMITIVES: &[&str] = &[
+    "array",
+    "bool",
+    "char",
+    "f16",
+    "f32",
+    "f64",
+    "f128",
+    "fn",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "never",
+    "pointer",
+    "reference",
+    "slice",
+    "str",
+    "tuple",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "unit",
+    "usize",
+];
+
+/// Keywords documented in std, which links and paths can name bare
+///
+/// `crate`, `self`, `super` and `Self` are left out since bare links to them mean the items
+/// they refer to, not the keyword; `keyword@self` still works.
+pub const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "union", "unsafe", "use", "where",
+    "while",
+];
+
+/// The path of std's page for a primitive type or keyword, e.g. `std::prim@str::len` for
+/// `str::len` and `std::keyword@match` for `match`
+///
+/// A `prim@` or `keyword@` disambiguator picks between the two for names that are both, like
+/// `fn`, which is otherwise taken to be the primitive. Returns `None` for paths that don't start
+/// with a primitive or keyword.
+pub fn std_builtin_path(path: &str) -> Option<String> {
+    let (first, rest) = path.split_once("::").unwrap_or((path, ""));
+    let (disambiguator, name) = first.split_once('@').unwrap_or(("", first));
+    let kind = match disambiguator {
+        "prim" | "primitive" => "prim",
+        "keyword" => "keyword",
+        "" if PRIMITIVES.contains(&name) => "prim",
+        // Bare keywords only make sense as a whole path; `Self::new` or `crate::Foo` are paths
+        "" if rest.is_empty() && KEYWORDS.contains(&name) => "keyword",
+        _ => return None,
+    };
+
+    if rest.is_empty() {
+        Some(format!("std::{kind}@{name}"))
+    } else {
+        Some(format!("std::{kind}@{name}::{rest}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_builtins_to_std() {
+        assert_eq!(std_builtin_path("u32").as_deref(), Some("std::prim@u32"));
+        assert_eq!(
+            std_builtin_path("str::len").as_deref(),
+            Some("std::prim@str::len")
+        );
+        assert_eq!(
+            std_builtin_path("match").as_deref(),
+            Some("std::keyword@match")
+        );
+        assert_eq!(std_builtin_path("fn").as_deref(), Some("std::prim@fn"));
+        assert_eq!(
+            std_builtin_path("keyword@fn").as_deref(),
+            Some("std::keyword@fn")
+        );
+        assert_eq!(std_builtin_path("Self::new"), None);
+        assert_eq!(std_builtin_path("serde::Serialize"), None);
+    }
+}
@@ -11,6 +11,86 @@ pub(crate) fn case_aware_jaro_winkler(a: &str, b: &str) -> f64 {
     (1.0 - CASE_WEIGHT) * case_insensitive + CASE_WEIGHT * case_sensitive
 }
 
+/// The names given by `alias = "…"` and `alias("…", …)` in a `#[doc(…)]` attribute
+pub(crate) fn doc_aliases_in(attribute: &str) -> Vec<&str> {
+    let mut aliases = vec![];
+    let Some(start) = attribute.find("doc(") else {
+        return aliases;
+    };
+
+    let mut rest = &attribute[start..];
+    while let Some(pos) = rest.find("alias") {
+        rest = rest[pos + "alias".len()..].trim_start();
+        if let Some(value) = rest.strip_prefix('=') {
+            if let Some((alias, after)) = quoted(value) {
+                aliases.push(alias);
+                rest = after;
+            }
+        } else if let Some(list) = rest.strip_prefix('(') {
+            let end = list.find(')').unwrap_or(list.len());
+            let mut items = &list[..end];
+            while let Some((alias, after)) = quoted(items) {
+                aliases.push(alias);
+                items = after;
+            }
+            rest = &list[end..];
+        }
+    }
+    aliases
+}
+
+/// The contents of the first double-quoted string in `text`, and the text after it
+fn quoted(text: &str) -> Option<(&str, &str)> {
+    let start = text.find('"')? + 1;
+    let len = text[start..].find('"')?;
+    Some((&text[start..start + len], &text[start + len + 1..]))
+}
+
+/// Whether an attribute is exactly `#[doc(hidden)]`, however it's spaced
+///
+/// Attributes that only hide an item conditionally, like `#[cfg_attr(test, doc(hidden))]`,
+/// don't count.
+pub(crate) fn is_doc_hidden(attribute: &str) -> bool {
+    attribute
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .eq("#[doc(hidden)]".chars())
+}
+
+#[cfg(test)]
+mod is_doc_hidden_tests {
+    use crate::string_utils::is_doc_hidden;
+
+    #[test]
+    fn matches_only_doc_hidden() {
+        assert!(is_doc_hidden("#[doc(hidden)]"));
+        assert!(is_doc_hidden("#[doc( hidden )]"));
+        assert!(!is_doc_hidden(r#"#[cfg_attr(feature = "x", doc(hidden))]"#));
+        assert!(!is_doc_hidden(r#"#[doc(alias = "hidden")]"#));
+        assert!(!is_doc_hidden("#[doc(inline)]"));
+    }
+}
+
+#[cfg(test)]
+mod doc_aliases_tests {
+    use crate::string_utils::doc_aliases_in;
+
+    #[test]
+    fn finds_single_and_listed_aliases() {
+        assert_eq!(doc_aliases_in(r#"#[doc(alias = "mkdir")]"#), ["mkdir"]);
+        assert_eq!(
+            doc_aliases_in(r#"#[doc(alias("length", "size"))]"#),
+            ["length", "size"]
+        );
+        assert_eq!(
+            doc_aliases_in(r#"#[doc(alias = "a", alias = "b")]"#),
+            ["a", "b"]
+        );
+        assert!(doc_aliases_in("#[doc(hidden)]").is_empty());
+        assert!(doc_aliases_in(r#"#[serde(alias = "x")]"#).is_empty());
+    }
+}
+
 #[cfg(test)]
 mod case_aware_jaro_winkler_tests {
     use crate::string_utils::case_aware_jaro_winkler;
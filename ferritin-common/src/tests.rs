@@ -198,3 +198,306 @@ fn private_module_path_resolves_via_index() {
         "re-export and private-module path should resolve to the same item"
     );
 }
+
+/// Items reached through a `pub use` report the path they were re-exported at.
+#[test]
+fn reexport_path_for_reexported_items() {
+    let nav = test_navigator();
+
+    let renamed = resolve(&nav, "crate::link_resolution_tests::RenamedTestStruct");
+    assert_eq!(
+        renamed.reexport_path().as_deref(),
+        Some("fixture-crate::link_resolution_tests::RenamedTestStruct")
+    );
+
+    let via_glob = resolve(&nav, "crate::reexport_mod::SubStruct");
+    assert_eq!(
+        via_glob.reexport_path().as_deref(),
+        Some("fixture-crate::reexport_mod::SubStruct")
+    );
+
+    // Items at their canonical location are not re-exports.
+    let canonical = resolve(&nav, "crate::submodule::SubStruct");
+    assert_eq!(canonical.reexport_path(), None);
+}
+
+/// Supertraits resolve through bounds, and subtraits are found in already-loaded crates.
+#[test]
+fn bare_primitives_and_keywords_resolve_to_std() {
+    let nav = test_navigator();
+
+    let u32_item = resolve(&nav, "u32");
+    assert_eq!(u32_item.kind(), ItemKind::Primitive);
+    assert!(
+        u32_item
+            .methods()
+            .any(|method| method.name() == Some("pow"))
+    );
+
+    let match_item = resolve(&nav, "match");
+    assert_eq!(match_item.kind(), ItemKind::Keyword);
+    assert_eq!(match_item.name(), Some("match"));
+
+    assert_eq!(resolve(&nav, "async").kind(), ItemKind::Keyword);
+    assert_eq!(resolve(&nav, "str::len").name(), Some("len"));
+}
+
+#[test]
+fn search_finds_doc_aliases() {
+    let nav = test_navigator();
+
+    let test_function = resolve(&nav, "crate::test_function");
+    assert_eq!(test_function.doc_aliases(), ["transmogrify"]);
+    assert_eq!(
+        test_function.alias_matching("Transmogrify strings"),
+        Some("transmogrify")
+    );
+    assert_eq!(test_function.alias_matching("test function"), None);
+
+    let results = nav.search("transmogrify", &["fixture-crate"]).unwrap();
+    let top = results.first().expect("the alias should match");
+    let (item, _) = nav
+        .get_item_from_id_path(top.crate_name, &top.id_path)
+        .unwrap();
+    assert_eq!(item.name(), Some("test_function"));
+}
+
+#[test]
+fn search_operators_filter_results() {
+    let nav = test_navigator();
+    let names = |query: &str| -> Vec<&str> {
+        nav.search(query, &[])
+            .unwrap()
+            .iter()
+            .filter_map(|result| nav.get_item_from_id_path(result.crate_name, &result.id_path))
+            .filter_map(|(item, _)| item.name())
+            .collect()
+    };
+
+    let all = names("function crate:fixture-crate");
+    assert!(all.contains(&"test_function"), "{all:?}");
+    assert!(all.contains(&"sub_function"), "{all:?}");
+
+    let within = names("function crate:fixture-crate kind:fn in:submodule");
+    assert_eq!(within, ["sub_function"]);
+
+    let excluded = names("function crate:fixture-crate -sub");
+    assert!(excluded.contains(&"test_function"), "{excluded:?}");
+    assert!(!excluded.contains(&"sub_function"), "{excluded:?}");
+}
+
+#[test]
+fn search_matches_abbreviated_names() {
+    let nav = test_navigator();
+    for (query, name) in [("GeSt", "GenericStruct"), ("cotrans", "copy_transformed")] {
+        let results = nav.search(query, &["fixture-crate"]).unwrap();
+        let top = results.first().expect("the abbreviation should match");
+        let (item, _) = nav
+            .get_item_from_id_path(top.crate_name, &top.id_path)
+            .unwrap();
+        assert_eq!(item.name(), Some(name), "{query}");
+    }
+}
+
+#[test]
+fn search_ranks_named_items_first() {
+    let nav = test_navigator();
+    for (query, crate_name, names) in [
+        ("SubStruct::new", "fixture-crate", &["new"][..]),
+        ("submodule", "fixture-crate", &["submodule"]),
+        // The `std::vec` module, `Vec` and `vec!` all match, ahead of everything mentioning vecs
+        ("vec", "std", &["vec", "Vec"]),
+    ] {
+        let crate_names = [crate_name];
+        let results = nav.search(query, &crate_names).unwrap();
+        let top = results.first().expect("the name should match");
+        assert!(top.exact, "{query}");
+        let (item, _) = nav
+            .get_item_from_id_path(top.crate_name, &top.id_path)
+            .unwrap();
+        assert!(names.contains(&item.name().unwrap()), "{query}");
+    }
+}
+
+#[test]
+fn search_finds_signature_types() {
+    let nav = test_navigator();
+    // `copy_transformed` takes an `impl std::io::Write`, which its docs don't mention
+    let results = nav.search("Write", &["fixture-crate"]).unwrap();
+    assert!(results.iter().any(|result| {
+        nav.get_item_from_id_path(result.crate_name, &result.id_path)
+            .is_some_and(|(item, _)| item.name() == Some("copy_transformed"))
+    }));
+}
+
+#[test]
+fn search_filters_unsafe_items() {
+    let nav = test_navigator();
+    let results = nav.search("from_raw_parts unsafe:", &["std"]).unwrap();
+    assert!(!results.is_empty());
+    for result in &results {
+        assert!(result.is_unsafe);
+    }
+    let (item, _) = nav
+        .get_item_from_id_path(results[0].crate_name, &results[0].id_path)
+        .unwrap();
+    assert_eq!(item.name(), Some("from_raw_parts"));
+}
+
+#[test]
+fn search_filters_deprecated_items() {
+    let nav = test_navigator();
+    // `Error::description` has been deprecated in favor of `Display`
+    let results = nav
+        .search("error description deprecated:only", &["std"])
+        .unwrap();
+    assert!(!results.is_empty());
+    assert!(results.iter().all(|result| result.is_deprecated));
+
+    // Two words, so that items named `description` aren't ranked first as exact matches
+    let results = nav.search("error description", &["std"]).unwrap();
+    let first_deprecated = results.iter().position(|result| result.is_deprecated);
+    let last_current = results.iter().rposition(|result| !result.is_deprecated);
+    assert!(first_deprecated > last_current);
+}
+
+#[test]
+fn supertraits_and_subtraits() {
+    let nav = test_navigator();
+
+    let ord = resolve(&nav, "std::cmp::Ord");
+    let supertraits = ord
+        .supertraits()
+        .into_iter()
+        .filter_map(|supertrait| supertrait.name())
+        .collect::<Vec<_>>();
+    assert!(supertraits.contains(&"Eq"), "{supertraits:?}");
+    assert!(supertraits.contains(&"PartialOrd"), "{supertraits:?}");
+
+    let partial_eq = resolve(&nav, "std::cmp::PartialEq");
+    let subtraits = partial_eq
+        .subtraits()
+        .into_iter()
+        .filter_map(|subtrait| subtrait.name())
+        .collect::<Vec<_>>();
+    assert!(subtraits.contains(&"Eq"), "{subtraits:?}");
+
+    // Non-trait items have neither
+    let test_struct = resolve(&nav, "crate::TestStruct");
+    assert!(test_struct.supertraits().is_empty());
+    assert!(test_struct.subtraits().is_empty());
+}
+
+#[test]
+fn version_specifiers() {
+    use crate::parse_version_req;
+    use semver::Version;
+
+    let exact = parse_version_req("1.0.190");
+    assert!(exact.matches(&Version::new(1, 0, 190)));
+    assert!(!exact.matches(&Version::new(1, 0, 200)));
+
+    let caret = parse_version_req("^1.2");
+    assert!(caret.matches(&Version::new(1, 9, 0)));
+    assert!(!caret.matches(&Version::new(1, 1, 0)));
+
+    assert!(parse_version_req("1").matches(&Version::new(1, 4, 2)));
+    assert!(parse_version_req("not a version").matches(&Version::new(0, 1, 0)));
+
+    let (crate_name, version_req) = crate::navigator::parse_crate_specifier("syn@1");
+    assert_eq!(crate_name, "syn");
+    assert!(version_req.matches(&Version::new(1, 0, 109)));
+    assert!(!version_req.matches(&Version::new(2, 0, 0)));
+    assert_eq!(
+        crate::navigator::parse_crate_specifier("syn"),
+        ("syn", semver::VersionReq::STAR)
+    );
+}
+
+/// Loading asynchronously fills the same working set as loading synchronously.
+#[cfg(feature = "network")]
+#[test]
+fn load_crate_async_shares_the_working_set() {
+    use semver::VersionReq;
+    use trillium_smol::async_io::block_on;
+
+    let nav = test_navigator();
+    let loaded = block_on(nav.load_crate_async("fixture-crate", &VersionReq::STAR))
+        .expect("failed to load fixture-crate asynchronously");
+    let reloaded = nav
+        .load_crate("fixture-crate", &VersionReq::STAR)
+        .expect("failed to load fixture-crate");
+    assert!(std::ptr::eq(loaded, reloaded));
+}
+
+/// Sources added with `with_source` are looked up and loaded like the built-in ones.
+#[test]
+fn custom_sources() {
+    use crate::sources::DocSource;
+    use crate::{CrateInfo, RustdocData};
+    use semver::{Version, VersionReq};
+    use std::borrow::Cow;
+
+    /// Stands in for an artifact store, serving one prebuilt JSON file
+    struct ArtifactStore {
+        json_path: PathBuf,
+    }
+
+    impl DocSource for ArtifactStore {
+        fn lookup<'a>(&'a self, crate_name: &str, _: &VersionReq) -> Option<Cow<'a, CrateInfo>> {
+            (crate_name.replace('-', "_") == "fixture_crate").then(|| {
+                Cow::Owned(CrateInfo::new("fixture-crate", None).with_description("from the store"))
+            })
+        }
+
+        fn load(&self, crate_name: &str, _: Option<&Version>) -> Option<RustdocData> {
+            RustdocData::from_json_file(crate_name, self.json_path.clone()).ok()
+        }
+    }
+
+    let json_path = test_navigator()
+        .load_crate("fixture-crate", &VersionReq::STAR)
+        .expect("failed to build fixture-crate")
+        .fs_path()
+        .to_path_buf();
+
+    let from_bytes = RustdocData::from_json_bytes(
+        "fixture-crate",
+        std::fs::read(&json_path).expect("failed to read fixture-crate's JSON"),
+    )
+    .expect("failed to parse fixture-crate's JSON");
+    assert!(from_bytes.items().is_lazy());
+    assert_eq!(from_bytes.name(), "fixture-crate");
+
+    let nav = Navigator::default().with_source(Box::new(ArtifactStore { json_path }));
+    let crate_info = nav
+        .lookup_crate("fixture_crate", &VersionReq::STAR)
+        .expect("the store has fixture-crate");
+    assert!(crate_info.provenance().is_custom());
+    assert_eq!(crate_info.description(), Some("from the store"));
+
+    let data = nav
+        .load_crate("fixture-crate", &VersionReq::STAR)
+        .expect("failed to load fixture-crate from the store");
+    assert!(data.provenance().is_custom());
+    resolve(&nav, "fixture_crate::TestStruct");
+}
+
+#[test]
+fn package_metadata_from_cargo() {
+    let nav = test_navigator();
+    let metadata = nav
+        .package_metadata("fixture-crate", &semver::VersionReq::STAR)
+        .expect("fixture-crate has package metadata");
+    assert_eq!(metadata.license(), Some("MIT OR Apache-2.0"));
+    assert_eq!(
+        metadata.repository(),
+        Some("https://github.com/jbr/ferritin")
+    );
+    assert_eq!(metadata.rust_version(), Some("1.70.0"));
+    assert_eq!(metadata.edition(), Some("2021"));
+    assert_eq!(
+        metadata.features().get("default"),
+        Some(&vec!["extras".to_string()])
+    );
+}
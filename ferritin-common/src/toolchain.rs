@@ -0,0 +1,205 @@
+//! Which nightly toolchains write which rustdoc JSON format
+//!
+//! Rustdoc JSON doesn't record the toolchain that wrote it, but each format version first
+//! appeared in nightlies from a known date. That's enough to explain a format ferritin can't read
+//! ("written by a nightly from after 2025-11-22") and to pick an installed dated nightly that
+//! writes a format it can.
+//!
+//! Projects that pin a nightly in `rust-toolchain.toml` are documented with that nightly, as
+//! their own builds are.
+
+use crate::conversions::{MAX_FORMAT_VERSION, MIN_FORMAT_VERSION, is_supported};
+use rustdoc_types::FORMAT_VERSION;
+#[cfg(not(target_family = "wasm"))]
+use std::{fs, path::Path, process::Command};
+
+/// The toolchain documentation is built with when none is chosen or pinned
+pub const DEFAULT_TOOLCHAIN: &str = "nightly";
+
+/// The date of the rustdoc-types release for each format version, from which nightlies write it
+///
+/// This needs a new entry whenever the rustdoc-types dependency is updated.
+const FORMAT_DATES: &[(u32, &str)] = &[
+    (45, "2025-04-20"),
+    (46, "2025-06-03"),
+    // Format 47 was never released
+    (48, "2025-06-19"),
+    (49, "2025-06-23"),
+    (50, "2025-06-23"),
+    (51, "2025-06-23"),
+    (52, "2025-06-23"),
+    (53, "2025-06-23"),
+    (54, "2025-07-17"),
+    (55, "2025-08-02"),
+    (56, "2025-09-05"),
+    (57, "2025-11-22"),
+];
+
+/// A nightly known to write [`FORMAT_VERSION`], suggested when no installed toolchain does
+pub const SUGGESTED_NIGHTLY: &str = "nightly-2025-11-23";
+
+/// The format a nightly from `date` (`YYYY-MM-DD`) writes, if known
+///
+/// Nightlies newer than the last known format may write a format ferritin doesn't know of, so
+/// they're `None` too.
+pub fn format_version_on(date: &str) -> Option<u32> {
+    let latest_known = FORMAT_DATES.last()?;
+    if date > latest_known.1 {
+        return None;
+    }
+    FORMAT_DATES
+        .iter()
+        .rev()
+        .find(|(_, since)| *since <= date)
+        .map(|(format_version, _)| *format_version)
+}
+
+/// Explain a format version that can't be read, including roughly when its toolchain was built
+pub fn describe_format_mismatch(format_version: u32) -> String {
+    if format_version > MAX_FORMAT_VERSION {
+        let since = FORMAT_DATES.last().map_or("", |(_, date)| date);
+        format!(
+            "rustdoc JSON format {format_version} was written by a nightly from after {since}, \
+             newer than ferritin can read (up to format {MAX_FORMAT_VERSION}). Update ferritin, \
+             or document with an older nightly such as {SUGGESTED_NIGHTLY}"
+        )
+    } else if format_version < MIN_FORMAT_VERSION {
+        let until = FORMAT_DATES.first().map_or("", |(_, date)| date);
+        format!(
+            "rustdoc JSON format {format_version} was written by a nightly from before {until}, \
+             older than ferritin can read (from format {MIN_FORMAT_VERSION}). Run \
+             `rustup update nightly` and rebuild the documentation"
+        )
+    } else {
+        format!(
+            "rustdoc JSON format {format_version} can't be read; ferritin reads formats \
+             {MIN_FORMAT_VERSION} through {MAX_FORMAT_VERSION}, and is built for {FORMAT_VERSION}"
+        )
+    }
+}
+
+/// The build date of a toolchain, from `rustc --version`
+#[cfg(not(target_family = "wasm"))]
+pub fn toolchain_date(toolchain: &str) -> Option<String> {
+    let output = Command::new("rustup")
+        .args(["run", toolchain, "rustc", "--version"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // rustc 1.93.0-nightly (1be6b13be 2025-11-21)
+    let version = String::from_utf8(output.stdout).ok()?;
+    let (_, date) = version.trim().strip_suffix(')')?.rsplit_once(' ')?;
+    is_date(date).then(|| date.to_string())
+}
+
+/// The newest installed dated nightly (like `nightly-2025-11-23`) that writes a format ferritin
+/// can read
+#[cfg(not(target_family = "wasm"))]
+pub fn compatible_nightly() -> Option<String> {
+    let output = Command::new("rustup")
+        .args(["toolchain", "list"])
+        .output()
+        .ok()?;
+    let toolchains = String::from_utf8(output.stdout).ok()?;
+
+    toolchains
+        .lines()
+        .filter_map(|line| {
+            let date = line.strip_prefix("nightly-")?.get(..10)?;
+            is_date(date).then_some(date)
+        })
+        .filter(|date| format_version_on(date).is_some_and(is_supported))
+        .max()
+        .map(|date| format!("nightly-{date}"))
+}
+
+/// The toolchain pinned by the `rust-toolchain.toml` (or legacy `rust-toolchain`) file in `dir`
+/// or the nearest of its ancestors, as rustup finds it, if that toolchain can write rustdoc JSON
+///
+/// Stable and beta releases can't, so projects pinning one are still documented with a nightly.
+#[cfg(not(target_family = "wasm"))]
+pub fn pinned_toolchain(dir: &Path) -> Option<String> {
+    let contents = dir.ancestors().find_map(|dir| {
+        ["rust-toolchain", "rust-toolchain.toml"]
+            .into_iter()
+            .find_map(|file_name| fs::read_to_string(dir.join(file_name)).ok())
+    })?;
+    let channel = toolchain_file_channel(&contents)?;
+    if writes_rustdoc_json(channel) {
+        Some(channel.to_string())
+    } else {
+        log::debug!("Not documenting with the pinned {channel} toolchain, which can't write JSON");
+        None
+    }
+}
+
+/// The channel named by a toolchain file: `channel` in its `[toolchain]` table, or the whole of a
+/// legacy one-line file
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
+fn toolchain_file_channel(contents: &str) -> Option<&str> {
+    if !contents.contains('[') {
+        return Some(contents.trim()).filter(|channel| !channel.is_empty());
+    }
+    contents.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "channel").then(|| value.trim().trim_matches(['"', '\'']))
+    })
+}
+
+/// Whether a toolchain can write rustdoc JSON, which takes `-Z unstable-options`: nightlies and
+/// custom toolchains, but not stable or beta releases
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
+fn writes_rustdoc_json(channel: &str) -> bool {
+    let release = channel.split('-').next().unwrap_or(channel);
+    !matches!(release, "stable" | "beta") && !release.starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
+fn is_date(date: &str) -> bool {
+    date.len() == 10
+        && date.char_indices().all(|(i, c)| {
+            if i == 4 || i == 7 {
+                c == '-'
+            } else {
+                c.is_ascii_digit()
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_format_versions_from_dates() {
+        assert_eq!(format_version_on("2025-04-01"), None);
+        assert_eq!(format_version_on("2025-06-20"), Some(48));
+        assert_eq!(format_version_on("2025-06-23"), Some(53));
+        assert_eq!(format_version_on("2025-11-22"), Some(57));
+        assert_eq!(format_version_on("2030-01-01"), None);
+        assert!(is_date("2025-11-23"));
+        assert!(!is_date("x86_64-unk"));
+    }
+
+    #[test]
+    fn reads_pinned_channels() {
+        let toml = "[toolchain]\nchannel = \"nightly-2025-11-23\"\ncomponents = [\"rustfmt\"]\n";
+        assert_eq!(toolchain_file_channel(toml), Some("nightly-2025-11-23"));
+        assert_eq!(
+            toolchain_file_channel("my-toolchain\n"),
+            Some("my-toolchain")
+        );
+        assert_eq!(
+            toolchain_file_channel("[toolchain]\nprofile = \"minimal\"\n"),
+            None
+        );
+
+        assert!(writes_rustdoc_json("nightly-2025-11-23"));
+        assert!(writes_rustdoc_json("my-toolchain"));
+        assert!(!writes_rustdoc_json("stable"));
+        assert!(!writes_rustdoc_json("beta-2025-11-20"));
+        assert!(!writes_rustdoc_json("1.91.0"));
+    }
+}
@@ -0,0 +1,564 @@
+//! The `ferritin` command line
+
+use clap::Parser;
+
+// Include the generated themes module
+mod themes {
+    include!(concat!(env!("OUT_DIR"), "/themes.rs"));
+}
+use ferritin_common::{
+    CrateName, Navigator,
+    search::Ranking,
+    sources::{BuildOptions, DocsRsSource, JsonFileSource, LocalSource, StdSource},
+};
+use std::{path::PathBuf, process::ExitCode};
+use terminal_size::{Width, terminal_size};
+
+use crate::{
+    color_scheme::ColorOverrides,
+    commands::{self, Commands},
+    config::{Config, RustdocFlags},
+    format_context::FormatContext,
+    logging,
+    render_context::RenderContext,
+    renderer::{self, OutputMode, inline_image},
+    request::{Request, std_toolchain},
+    styled_string::TruncationLevel,
+    terminal_background::Background,
+    verbosity::Verbosity,
+    watch::SourceWatcher,
+};
+
+/// A friendly CLI for browsing Rust documentation
+#[derive(Parser, Debug)]
+#[command(name = "ferritin")]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Path to Cargo.toml (defaults to current directory)
+    #[arg(short, long, global = true)]
+    manifest_path: Option<PathBuf>,
+
+    /// Syntax highlighting theme (theme name or path to .tmTheme file)
+    #[arg(
+        long,
+        short,
+        global = true,
+        env = "FERRITIN_THEME",
+        long_help = build_theme_help()
+    )]
+    theme: Option<String>,
+
+    /// Theme used when no --theme is given and the terminal background is light
+    #[arg(
+        long,
+        global = true,
+        default_value = "Catppuccin Latte",
+        env = "FERRITIN_LIGHT_THEME"
+    )]
+    light_theme: String,
+
+    /// Theme used when no --theme is given and the terminal background is dark or unknown
+    #[arg(
+        long,
+        global = true,
+        default_value = "Catppuccin Frappe",
+        env = "FERRITIN_DARK_THEME"
+    )]
+    dark_theme: String,
+
+    /// Enable interactive mode with scrolling and navigation
+    #[arg(short, long, global = true)]
+    interactive: bool,
+
+    /// Screen-reader friendly output: no decoration, structure announced in words
+    #[arg(long, global = true, env = "FERRITIN_SCREEN_READER")]
+    screen_reader: bool,
+
+    /// Include `#[doc(hidden)]` items in module listings and search results
+    #[arg(long, global = true)]
+    show_hidden: bool,
+
+    /// Leave items that don't exist on this platform (or the --target platform) out of search
+    /// results, going by their `#[doc(cfg(…))]` attributes
+    #[arg(long, global = true)]
+    hide_unavailable: bool,
+
+    /// How much of each signature to show: `short` leaves out where clauses and lifetime
+    /// parameters, `full` adds the auto trait and blanket impls in full
+    #[arg(long, global = true, value_enum, default_value_t)]
+    verbosity: Verbosity,
+
+    /// Rebuild documentation and refresh the output whenever workspace sources change
+    #[arg(long, global = true)]
+    watch: bool,
+
+    /// Directory for building workspace documentation, overriding CARGO_TARGET_DIR and
+    /// build.target-dir
+    #[arg(long, global = true, value_name = "DIR")]
+    target_dir: Option<PathBuf>,
+
+    /// Target triple to build workspace documentation for (e.g. "wasm32-unknown-unknown"),
+    /// showing the items that exist on that platform
+    #[arg(long, global = true, value_name = "TRIPLE")]
+    target: Option<String>,
+
+    /// Rustup toolchain to build documentation with (e.g. "nightly-2025-11-23"), instead of the
+    /// nightly pinned by the project's rust-toolchain.toml or "nightly"
+    #[arg(long, global = true, value_name = "NAME")]
+    toolchain: Option<String>,
+
+    /// Read a rustdoc JSON file, or a directory of them, instead of a cargo project, for docs
+    /// built by other build systems such as Bazel or Buck (repeatable)
+    #[arg(long, global = true, value_name = "PATH", conflicts_with = "watch")]
+    json_file: Vec<PathBuf>,
+
+    /// In interactive mode, the memory (in megabytes) loaded documentation may take before the
+    /// least recently used crates are dropped
+    #[arg(long, global = true, value_name = "MB", env = "FERRITIN_MEMORY_BUDGET")]
+    memory_budget: Option<usize>,
+
+    /// Show all documentation as one-line summaries, for scripts that want an overview
+    #[arg(long, global = true, conflicts_with_all = ["full", "interactive"])]
+    brief: bool,
+
+    /// Show all documentation in full, including what is otherwise shown truncated and
+    /// expanded interactively
+    #[arg(long, global = true, conflicts_with = "interactive")]
+    full: bool,
+
+    /// Print search results and item metadata as stable tab-separated lines, for editor plugins
+    #[arg(long, global = true)]
+    porcelain: bool,
+
+    /// Show how each search result's score was reached: its BM25 relevance, its authority
+    /// (incoming links) and the boost authority gave it
+    #[arg(long, global = true)]
+    rank_debug: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+impl Cli {
+    /// The truncation that `--brief` or `--full` applies to all documentation
+    fn truncation(&self) -> Option<TruncationLevel> {
+        if self.brief {
+            Some(TruncationLevel::SingleLine)
+        } else if self.full {
+            Some(TruncationLevel::Full)
+        } else {
+            None
+        }
+    }
+}
+
+fn build_theme_help() -> &'static str {
+    use std::sync::OnceLock;
+    static HELP: OnceLock<String> = OnceLock::new();
+
+    HELP.get_or_init(|| {
+        let mut help = String::from("Syntax highlighting theme\n\n");
+        help.push_str("Can be either:\n");
+        help.push_str("  - A theme name from the list below\n");
+        help.push_str(
+            "  - A path to a .tmTheme file (reloaded when edited, in interactive mode)\n\n",
+        );
+        help.push_str("Without this, --light-theme or --dark-theme is chosen to suit the\n");
+        help.push_str("terminal's background color.\n\n");
+        help.push_str("Available themes:\n");
+
+        for name in themes::THEME_NAMES {
+            help.push_str(&format!("  - {}\n", name));
+        }
+
+        help
+    })
+}
+
+struct IoFmtWriter<T>(T);
+impl<T> std::fmt::Write for IoFmtWriter<T>
+where
+    T: std::io::Write,
+{
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.write_all(s.as_bytes()).map_err(|_| std::fmt::Error)
+    }
+}
+
+/// Parse the command line arguments and run the command they describe
+pub fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let truncation = cli.truncation();
+    let explicit_manifest_path = cli.manifest_path.is_some();
+    let path = cli
+        .manifest_path
+        .unwrap_or_else(|| std::env::current_dir().unwrap());
+    // Running outside of a cargo project falls back to std and docs.rs documentation, unless a
+    // project was asked for or is evidently there but broken
+    let requires_project = explicit_manifest_path
+        || cli.watch
+        || matches!(cli.command, Some(Commands::Build { .. }))
+        || path.join("Cargo.toml").exists();
+
+    let mut build_options = BuildOptions::default();
+    if let Some(target_dir) = cli.target_dir {
+        build_options = build_options.with_target_dir(target_dir);
+    }
+    if let Some(target) = cli.target {
+        build_options = build_options.with_target(target);
+    }
+    if let Some(toolchain) = cli.toolchain {
+        build_options = build_options.with_toolchain(toolchain);
+    }
+    // Features are chosen for `ferritin build`, and used for whatever it builds
+    if let Some(Commands::Build {
+        features,
+        all_features,
+        no_default_features,
+        ..
+    }) = &cli.command
+    {
+        build_options = build_options
+            .with_features(features.clone())
+            .with_all_features(*all_features)
+            .with_no_default_features(*no_default_features);
+    }
+
+    let output_mode = if cli.porcelain {
+        OutputMode::Plain
+    } else if cli.screen_reader {
+        OutputMode::ScreenReader
+    } else {
+        OutputMode::detect()
+    };
+    let config = Config::load().and_then(|config| {
+        Ok((
+            ColorOverrides::from_config(&config)?,
+            config.images.inline,
+            ranking_from_config(&config),
+            build_options_from_config(&config, build_options),
+        ))
+    });
+    let (color_overrides, images_config, ranking, build_options) = match config {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // The interactive ui draws with ratatui, which has no way to place images
+    let inline_images = images_config
+        && output_mode == OutputMode::Tty
+        && !cli.interactive
+        && inline_image::kitty_graphics_supported();
+
+    let mut render_context = RenderContext::new()
+        .with_output_mode(output_mode)
+        .with_terminal_width(
+            terminal_size()
+                .map(|(Width(w), _)| w as usize)
+                .unwrap_or(80),
+        )
+        .with_interactive(cli.interactive)
+        .with_inline_images(inline_images)
+        .with_color_overrides(color_overrides);
+
+    let theme = cli.theme.unwrap_or_else(|| match Background::detect() {
+        Some(Background::Light) => cli.light_theme,
+        Some(Background::Dark) | None => cli.dark_theme,
+    });
+    if let Err(e) = render_context.set_theme_name(&theme) {
+        eprintln!("{e}");
+        return ExitCode::FAILURE;
+    };
+
+    if cli.interactive {
+        // Interactive mode with scrolling and navigation
+        // Install custom log backend that captures logs for status bar
+        let (log_backend, log_reader) = logging::StatusLogBackend::new(10_000);
+        if let Err(e) = log_backend.install() {
+            eprintln!("Failed to install log backend: {}", e);
+            return ExitCode::FAILURE;
+        }
+
+        let format_context = FormatContext::new()
+            .with_show_hidden(cli.show_hidden)
+            .with_hide_unavailable(cli.hide_unavailable)
+            .with_rank_debug(cli.rank_debug)
+            .with_verbosity(cli.verbosity);
+        // Lazy, so that the UI is up while sources load
+        let request = Request::lazy(path, format_context)
+            // Out-of-date workspace docs are shown while they're rebuilt in the background
+            .with_build_options(build_options.with_defer_rebuilds(true))
+            .with_ranking(ranking)
+            .with_json_files(cli.json_file)
+            .with_memory_budget(cli.memory_budget.map(|megabytes| megabytes * 1024 * 1024));
+        if let Err(e) = renderer::render_interactive(
+            request,
+            render_context,
+            cli.command,
+            log_reader,
+            cli.watch,
+        ) {
+            eprintln!("Interactive mode error: {}", e);
+            return ExitCode::FAILURE;
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    // One-shot mode: execute command and render to stdout
+    // Use env_logger for CLI mode
+    env_logger::init();
+    let command = cli.command.unwrap_or_else(Commands::list);
+    let mut watcher = None;
+    let mut rebuild_workspace_docs = false;
+
+    loop {
+        // Non-interactive mode: build sources eagerly and handle errors upfront
+        let (local_source, json_file_source) = if cli.json_file.is_empty() {
+            match LocalSource::load_with_options(&path, build_options.clone()) {
+                Ok(local_source) => (Some(local_source), None),
+                Err(error) if !requires_project => {
+                    log::info!("No cargo project at {}: {error}", path.display());
+                    (None, None)
+                }
+                Err(error) => {
+                    eprintln!("could not load rust project at {}", path.display());
+                    log::error!("{error:?}");
+                    return failure_exit_code(&command);
+                }
+            }
+        } else {
+            match JsonFileSource::load(&cli.json_file) {
+                Ok(json_file_source) => (None, Some(json_file_source)),
+                Err(error) => {
+                    eprintln!("could not read rustdoc JSON: {error}");
+                    return failure_exit_code(&command);
+                }
+            }
+        };
+
+        if let Some(local_source) = &local_source {
+            if rebuild_workspace_docs && let Err(e) = local_source.rebuild_workspace_docs() {
+                eprintln!("Failed to rebuild documentation: {e}");
+            }
+
+            if cli.watch && watcher.is_none() {
+                match SourceWatcher::new(local_source.project_root(), local_source.target_dir()) {
+                    Ok(source_watcher) => watcher = Some(source_watcher),
+                    Err(e) => {
+                        eprintln!(
+                            "Failed to watch {}: {e}",
+                            local_source.project_root().display()
+                        );
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+        }
+
+        let std_source =
+            StdSource::from_toolchain(std_toolchain(&build_options, local_source.as_ref()));
+        let docsrs_source = DocsRsSource::from_default_cache();
+
+        let navigator = Navigator::default()
+            .with_std_source(std_source)
+            .with_local_source(local_source)
+            .with_json_file_source(json_file_source)
+            .with_docsrs_source(docsrs_source)
+            .with_ranking(ranking);
+
+        let format_context = FormatContext::new()
+            .with_show_hidden(cli.show_hidden)
+            .with_hide_unavailable(cli.hide_unavailable)
+            .with_rank_debug(cli.rank_debug)
+            .with_verbosity(cli.verbosity);
+        let request =
+            Request::new(navigator, format_context).with_build_options(build_options.clone());
+
+        let exit_code = run(
+            &request,
+            command.clone(),
+            &mut render_context,
+            cli.porcelain,
+            truncation,
+        );
+
+        let Some(watcher) = &watcher else {
+            return exit_code;
+        };
+        if !watcher.wait() {
+            return exit_code;
+        }
+        rebuild_workspace_docs = true;
+
+        // Replace the previous output rather than scrolling it away
+        if matches!(render_context.output_mode(), OutputMode::Tty) {
+            print!("\x1b[2J\x1b[H");
+        } else {
+            println!();
+        }
+    }
+}
+
+/// Search ranking parameters from the `[search]` section of the config, defaulting the rest
+fn ranking_from_config(config: &Config) -> Ranking {
+    let default = Ranking::default();
+    let search = &config.search;
+    Ranking {
+        k1: search.k1.unwrap_or(default.k1),
+        b: search.b.unwrap_or(default.b),
+        name_weight: search.name_weight.unwrap_or(default.name_weight),
+        authority_boost: search.authority_boost.unwrap_or(default.authority_boost),
+    }
+}
+
+/// Extra rustdoc flags from the `[build]` section of the config, and the command each package in
+/// `[build.packages]` is documented with
+fn build_options_from_config(config: &Config, build_options: BuildOptions) -> BuildOptions {
+    let rustdoc_flags = config
+        .build
+        .rustdoc_flags
+        .as_ref()
+        .map(RustdocFlags::to_vec)
+        .unwrap_or_default();
+
+    let doc_commands = config
+        .build
+        .packages
+        .iter()
+        .map(|(package, command)| (CrateName::from(package.clone()), *command))
+        .collect();
+
+    build_options
+        .with_rustdoc_flags(rustdoc_flags)
+        .with_doc_commands(doc_commands)
+}
+
+/// The exit code for failing to load documentation sources
+fn failure_exit_code(command: &Commands) -> ExitCode {
+    // `exists` reserves 1 for a missing item
+    if matches!(command, Commands::Exists { .. }) {
+        ExitCode::from(2)
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Execute a one-shot command, printing its output to stdout
+fn run(
+    request: &Request,
+    command: Commands,
+    render_context: &mut RenderContext,
+    porcelain: bool,
+    truncation: Option<TruncationLevel>,
+) -> ExitCode {
+    if porcelain {
+        let output = match &command {
+            Commands::Search {
+                query,
+                limit,
+                crate_,
+                flags,
+            } => Some(commands::porcelain::search(
+                request,
+                &flags.apply(query),
+                *limit,
+                crate_.as_deref(),
+            )),
+            Commands::Get { path, .. } | Commands::Url { path } => {
+                Some(commands::porcelain::item(request, path))
+            }
+            _ => None,
+        };
+        if let Some((output, is_error)) = output {
+            print!("{output}");
+            return if is_error {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            };
+        }
+    }
+    if let Commands::Daemon { socket } = &command {
+        let Some(socket) = socket.clone().or_else(|| {
+            request
+                .local_source()
+                .map(|local| commands::daemon::socket_in(local.target_dir()))
+        }) else {
+            eprintln!("No target directory for the daemon socket; pass --socket");
+            return ExitCode::FAILURE;
+        };
+        return match commands::daemon::serve(request, &socket) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("Daemon error: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    if let Commands::Serve { address } = &command {
+        return match commands::serve::serve(request, address) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("Server error: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    if matches!(command, Commands::Lsp) {
+        return match commands::lsp::serve(request, std::io::stdin().lock(), std::io::stdout()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("Language server error: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    if let Commands::Batch { format } = command {
+        let stdin = std::io::stdin().lock();
+        return match commands::batch::run(request, stdin, std::io::stdout(), format, render_context)
+        {
+            Ok(false) => ExitCode::SUCCESS,
+            Ok(true) => ExitCode::FAILURE,
+            Err(e) => {
+                eprintln!("Batch error: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    if let Commands::Exists { path, quiet } = &command {
+        let existence = commands::exists::check(request, path);
+        if !quiet {
+            match existence {
+                commands::exists::Existence::Found => println!("{}", existence.message(path)),
+                _ => eprintln!("{}", existence.message(path)),
+            }
+        }
+        return ExitCode::from(existence.exit_code());
+    }
+    let preview_themes = matches!(command, Commands::Themes { .. });
+    let (mut document, is_error, _initial_entry) = command.execute(request);
+    if let Some(truncation) = truncation {
+        document.set_truncation(truncation);
+    }
+
+    // Render to stdout
+    let mut output = IoFmtWriter(std::io::stdout());
+    let rendered = if preview_themes {
+        commands::themes::render_each(&document, render_context, &mut output)
+    } else {
+        renderer::render(&document, render_context, &mut output)
+    };
+    if rendered.is_err() {
+        return ExitCode::FAILURE;
+    }
+
+    if is_error {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -1,4 +1,7 @@
+use crate::config::{ColorValue, Config, ConfigError};
 use crate::styled_string::SpanStyle;
+use ratatui::style::Color as TerminalColor;
+use std::collections::HashMap;
 use syntect::highlighting::{Color, Highlighter, Theme};
 use syntect::parsing::{Scope, ScopeStack};
 
@@ -11,6 +14,94 @@ pub struct ColorScheme {
     default_foreground: Color,
     /// Default background color
     default_background: Color,
+    /// Configured colors that take precedence over the theme's
+    overrides: ColorOverrides,
+}
+
+/// Terminal colors configured for span styles in the `[colors]` section of the config file,
+/// independent of the syntax highlighting theme
+///
+/// Values are `#rrggbb`, an ANSI palette index (`0`-`255`), or one of the sixteen named ANSI
+/// colors (`red`, `bright_blue`, ...), which follow the terminal's own palette.
+#[derive(Debug, Clone, Default)]
+pub struct ColorOverrides(HashMap<SpanStyle, TerminalColor>);
+
+impl ColorOverrides {
+    pub(crate) fn from_config(config: &Config) -> Result<Self, ConfigError> {
+        let mut overrides = HashMap::new();
+        for (key, value) in &config.colors {
+            let invalid = |message: String| ConfigError::Invalid {
+                section: "colors".into(),
+                key: key.clone(),
+                message,
+            };
+            let style = parse_span_style(key)
+                .ok_or_else(|| invalid("not a known style (e.g. keyword, type_name)".into()))?;
+            let color = match value {
+                ColorValue::Index(index) => TerminalColor::Indexed(*index),
+                ColorValue::Text(text) => parse_terminal_color(text)
+                    .ok_or_else(|| invalid(format!("`{text}` is not a color")))?,
+            };
+            overrides.insert(style, color);
+        }
+        Ok(Self(overrides))
+    }
+}
+
+fn parse_span_style(name: &str) -> Option<SpanStyle> {
+    Some(match &*name.to_ascii_lowercase().replace('-', "_") {
+        "keyword" => SpanStyle::Keyword,
+        "type" | "type_name" => SpanStyle::TypeName,
+        "function" | "function_name" => SpanStyle::FunctionName,
+        "field" | "field_name" => SpanStyle::FieldName,
+        "lifetime" => SpanStyle::Lifetime,
+        "generic" => SpanStyle::Generic,
+        "plain" => SpanStyle::Plain,
+        "punctuation" => SpanStyle::Punctuation,
+        "operator" => SpanStyle::Operator,
+        "comment" => SpanStyle::Comment,
+        "inline_rust_code" => SpanStyle::InlineRustCode,
+        "inline_code" => SpanStyle::InlineCode,
+        "strong" => SpanStyle::Strong,
+        "emphasis" => SpanStyle::Emphasis,
+        "strikethrough" => SpanStyle::Strikethrough,
+        "footnote_reference" => SpanStyle::FootnoteReference,
+        "image" => SpanStyle::Image,
+        _ => return None,
+    })
+}
+
+fn parse_terminal_color(value: &str) -> Option<TerminalColor> {
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return Some(TerminalColor::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+    if let Ok(index) = value.parse() {
+        return Some(TerminalColor::Indexed(index));
+    }
+    Some(match &*value.to_ascii_lowercase().replace('-', "_") {
+        "default" => TerminalColor::Reset,
+        "black" => TerminalColor::Black,
+        "red" => TerminalColor::Red,
+        "green" => TerminalColor::Green,
+        "yellow" => TerminalColor::Yellow,
+        "blue" => TerminalColor::Blue,
+        "magenta" => TerminalColor::Magenta,
+        "cyan" => TerminalColor::Cyan,
+        "white" => TerminalColor::Gray,
+        "bright_black" => TerminalColor::DarkGray,
+        "bright_red" => TerminalColor::LightRed,
+        "bright_green" => TerminalColor::LightGreen,
+        "bright_yellow" => TerminalColor::LightYellow,
+        "bright_blue" => TerminalColor::LightBlue,
+        "bright_magenta" => TerminalColor::LightMagenta,
+        "bright_cyan" => TerminalColor::LightCyan,
+        "bright_white" => TerminalColor::White,
+        _ => return None,
+    })
 }
 
 impl ColorScheme {
@@ -142,9 +233,33 @@ impl ColorScheme {
             colors,
             default_foreground: default_style.foreground,
             default_background: default_style.background,
+            overrides: ColorOverrides::default(),
         }
     }
 
+    /// Apply configured colors on top of the theme's
+    pub fn with_overrides(mut self, overrides: ColorOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Get the configured color for a span style, if there is one
+    pub fn configured_color_for(&self, style: SpanStyle) -> Option<TerminalColor> {
+        self.overrides.0.get(&style).copied()
+    }
+
+    /// Get the terminal color for a span style: the configured override if there is one,
+    /// otherwise the theme's color
+    pub fn terminal_color_for(&self, style: SpanStyle) -> TerminalColor {
+        self.configured_color_for(style).unwrap_or_else(|| {
+            let color = match style {
+                SpanStyle::Plain => self.default_foreground,
+                _ => self.color_for(style),
+            };
+            TerminalColor::Rgb(color.r, color.g, color.b)
+        })
+    }
+
     /// Get the color for a specific span style
     pub fn color_for(&self, style: SpanStyle) -> Color {
         self.colors
@@ -204,6 +319,7 @@ impl Default for ColorScheme {
                 b: 0,
                 a: 255,
             },
+            overrides: ColorOverrides::default(),
         }
     }
 }
@@ -225,6 +341,36 @@ mod tests {
         assert!(type_color.r != 0 || type_color.g != 0 || type_color.b != 0);
     }
 
+    #[test]
+    fn test_configured_overrides() {
+        let config = Config::parse(
+            "[colors]\nkeyword = \"#ff8800\"\ntype-name = \"bright_blue\"\nlifetime = 13\n",
+        )
+        .unwrap();
+        let scheme =
+            ColorScheme::default().with_overrides(ColorOverrides::from_config(&config).unwrap());
+
+        assert_eq!(
+            scheme.terminal_color_for(SpanStyle::Keyword),
+            TerminalColor::Rgb(0xff, 0x88, 0x00)
+        );
+        assert_eq!(
+            scheme.terminal_color_for(SpanStyle::TypeName),
+            TerminalColor::LightBlue
+        );
+        assert_eq!(
+            scheme.terminal_color_for(SpanStyle::Lifetime),
+            TerminalColor::Indexed(13)
+        );
+        assert_eq!(
+            scheme.terminal_color_for(SpanStyle::Plain),
+            TerminalColor::Rgb(200, 200, 200)
+        );
+
+        let invalid = Config::parse("[colors]\nkeyword = \"mauve\"\n").unwrap();
+        assert!(ColorOverrides::from_config(&invalid).is_err());
+    }
+
     #[test]
     fn test_default_colors() {
         let scheme = ColorScheme::default();
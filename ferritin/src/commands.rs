@@ -1,13 +1,32 @@
 use crate::renderer::HistoryEntry;
 use crate::request::Request;
-use crate::styled_string::Document;
+use crate::styled_string::{Document, DocumentNode, Span};
+use search::SearchFlags;
 use std::fmt::Display;
+use std::path::PathBuf;
 
+pub(crate) mod batch;
+mod build;
+pub(crate) mod build_failure;
+pub(crate) mod daemon;
+mod diff;
+pub(crate) mod exists;
+mod export_site;
 mod get;
+mod grep;
+mod info;
 pub(crate) mod list;
+pub(crate) mod lsp;
+pub(crate) mod porcelain;
 pub(crate) mod search;
+pub(crate) mod serve;
+mod stats;
+mod std_docs;
+pub(crate) mod themes;
+mod url;
+mod versions;
 
-#[derive(clap::Subcommand, Debug)]
+#[derive(clap::Subcommand, Debug, Clone)]
 pub(crate) enum Commands {
     /// Show documentation for an item
     Get {
@@ -21,6 +40,24 @@ pub(crate) enum Commands {
         /// Recursively show nested items
         #[arg(short, long)]
         recursive: bool,
+
+        /// Print the item's documentation URL instead of its documentation
+        #[arg(long)]
+        url: bool,
+    },
+
+    /// Print the documentation URL for an item
+    ///
+    /// Points at docs.rs, doc.rust-lang.org for std, or target/doc for workspace crates
+    Url {
+        /// Path to the item (e.g., "std::vec::Vec::push")
+        path: String,
+    },
+
+    /// Open an item's documentation page in the browser
+    Open {
+        /// Path to the item (e.g., "std::vec::Vec")
+        path: String,
     },
 
     /// Search for items by name or documentation
@@ -35,10 +72,176 @@ pub(crate) enum Commands {
         /// Maximum number of results
         #[arg(short, long, default_value = "10")]
         limit: usize,
+
+        #[command(flatten)]
+        flags: SearchFlags,
+    },
+
+    /// Show every line of documentation matching a pattern
+    ///
+    /// Unlike search, this is unranked: every item whose docs contain the pattern is listed, with
+    /// the matching lines
+    Grep {
+        /// Text to look for, or a regular expression with --regex
+        pattern: String,
+
+        /// Crate to scan (repeatable; defaults to every available crate)
+        #[arg(short, long = "crate")]
+        crates: Vec<String>,
+
+        /// Treat the pattern as a regular expression
+        #[arg(short = 'e', long)]
+        regex: bool,
+
+        /// Match regardless of case (`-i` is taken by --interactive)
+        #[arg(long)]
+        ignore_case: bool,
+
+        /// Maximum number of matching lines to show
+        #[arg(short, long, default_value = "100")]
+        limit: usize,
     },
 
     /// List available crates
     List,
+
+    /// List the versions of a crate published on crates.io
+    ///
+    /// Any of them can be viewed with `crate@version`, or `crate@^1.2` for the newest match
+    Versions {
+        /// Crate name (e.g., "serde")
+        #[arg(value_name = "CRATE")]
+        crate_name: String,
+    },
+
+    /// Compare the public API of two versions of a crate
+    ///
+    /// With one argument, compares a workspace crate against its latest published version
+    Diff {
+        /// Older crate version (e.g., "serde@1.0.190"), or a workspace crate name
+        old: String,
+
+        /// Newer crate version (e.g., "serde@1.0.200")
+        new: Option<String>,
+    },
+
+    /// Summarize a crate's public API: items by kind, documentation coverage, impls and unsafe
+    /// functions
+    Stats {
+        /// Crate name, optionally with a version (e.g., "serde" or "serde@1.0.200")
+        #[arg(value_name = "CRATE")]
+        crate_name: String,
+    },
+
+    /// Show a crate's package metadata: version, license, repository, homepage, minimum Rust
+    /// version, edition, authors and features
+    Info {
+        /// Crate name, optionally with a version (e.g., "serde" or "serde@1.0.200")
+        #[arg(value_name = "CRATE")]
+        crate_name: String,
+    },
+
+    /// Check whether an item exists
+    ///
+    /// Exits with 0 if the item is found, 1 if it is not, and 2 if that couldn't be determined
+    Exists {
+        /// Path to the item (e.g., "std::vec::Vec")
+        path: String,
+
+        /// Print nothing; only set the exit code
+        #[arg(short, long)]
+        quiet: bool,
+    },
+
+    /// Generate documentation for workspace packages now, reporting how long each took
+    ///
+    /// Rebuilds even documentation that's up to date, so that later lookups don't wait on cargo:
+    /// in CI, or before going offline. The global --target builds for another platform
+    Build {
+        /// Package to document (repeatable; defaults to every workspace package)
+        #[arg(short, long = "package")]
+        packages: Vec<String>,
+
+        /// Also document the workspace's dependencies
+        #[arg(long)]
+        deps: bool,
+
+        /// Features to enable for workspace packages (comma-separated or repeated)
+        #[arg(short = 'F', long, value_delimiter = ',')]
+        features: Vec<String>,
+
+        /// Enable every feature of workspace packages
+        #[arg(long)]
+        all_features: bool,
+
+        /// Leave out the default features of workspace packages
+        #[arg(long)]
+        no_default_features: bool,
+    },
+
+    /// Make the standard library's documentation available
+    ///
+    /// Installs rustup's rust-docs-json component for the nightly toolchain. Without nightly, or
+    /// with --download, prebuilt docs for the default toolchain are downloaded instead
+    StdDocs {
+        /// Build the docs from source rather than installing the component
+        #[arg(long, conflicts_with = "download")]
+        build: bool,
+
+        /// Download prebuilt docs for the default toolchain from the Rust distribution server
+        /// (or the mirror in RUSTUP_DIST_SERVER)
+        #[arg(long)]
+        download: bool,
+    },
+
+    /// Preview every syntax highlighting theme
+    ///
+    /// Renders a short sample document, or the given item, once per available theme
+    Themes {
+        /// Path to an item to preview instead of the sample (e.g., "std::vec::Vec")
+        #[arg(long)]
+        preview: Option<String>,
+    },
+
+    /// Run a language server on stdin and stdout
+    ///
+    /// Provides hover documentation and workspace symbol search to editors
+    Lsp,
+
+    /// Keep documentation loaded and answer JSON-RPC requests over a unix socket
+    ///
+    /// Avoids reloading crates for every query; see the daemon module docs for the protocol
+    Daemon {
+        /// Socket path (defaults to target/ferritin/daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+
+    /// Serve rendered documentation over HTTP, with a JSON search API
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        address: String,
+    },
+
+    /// Answer one item path or search query per line of stdin
+    ///
+    /// Lines naming an item are answered like `get`, and other lines like `search`
+    Batch {
+        /// Output format for each answer
+        #[arg(long, value_enum, default_value_t)]
+        format: batch::BatchFormat,
+    },
+
+    /// Export crate documentation as a static HTML site with client-side search
+    ExportSite {
+        /// Directory to write the site to
+        dir: PathBuf,
+
+        /// Crate to export (repeatable; defaults to the workspace crates)
+        #[arg(short, long = "crate")]
+        crates: Vec<String>,
+    },
 }
 
 impl Commands {
@@ -47,6 +250,13 @@ impl Commands {
             path: path.to_string(),
             source: false,
             recursive: false,
+            url: false,
+        }
+    }
+
+    pub fn url(path: impl Display) -> Self {
+        Self::Url {
+            path: path.to_string(),
         }
     }
 
@@ -55,6 +265,7 @@ impl Commands {
             query: query.to_string(),
             limit: 10,
             crate_: None,
+            flags: SearchFlags::default(),
         }
     }
 
@@ -62,14 +273,22 @@ impl Commands {
         Self::List
     }
 
+    pub fn themes() -> Self {
+        Self::Themes { preview: None }
+    }
+
     pub fn with_source(self) -> Self {
         match self {
             Self::Get {
-                path, recursive, ..
+                path,
+                recursive,
+                url,
+                ..
             } => Self::Get {
                 path,
                 source: true,
                 recursive,
+                url,
             },
             other => other,
         }
@@ -77,10 +296,16 @@ impl Commands {
 
     pub fn in_crate(self, crate_: impl Display) -> Self {
         match self {
-            Self::Search { query, limit, .. } => Self::Search {
+            Self::Search {
+                query,
+                limit,
+                flags,
+                ..
+            } => Self::Search {
                 query,
                 limit,
                 crate_: Some(crate_.to_string()),
+                flags,
             },
             other => other,
         }
@@ -88,10 +313,13 @@ impl Commands {
 
     pub fn recursive(self) -> Self {
         match self {
-            Self::Get { path, source, .. } => Self::Get {
+            Self::Get {
+                path, source, url, ..
+            } => Self::Get {
                 path,
                 source,
                 recursive: true,
+                url,
             },
             other => other,
         }
@@ -99,10 +327,16 @@ impl Commands {
 
     pub fn with_limit(self, limit: usize) -> Self {
         match self {
-            Self::Search { query, crate_, .. } => Self::Search {
+            Self::Search {
+                query,
+                crate_,
+                flags,
+                ..
+            } => Self::Search {
                 query,
                 limit,
                 crate_,
+                flags,
             },
             other => other,
         }
@@ -113,10 +347,22 @@ impl Commands {
         request: &'a Request,
     ) -> (Document<'a>, bool, Option<HistoryEntry<'a>>) {
         match self {
+            Commands::Get {
+                path, url: true, ..
+            }
+            | Commands::Url { path } => {
+                let (doc, is_error) = url::execute(request, &path);
+                (doc, is_error, None)
+            }
+            Commands::Open { path } => {
+                let (doc, is_error) = url::open(request, &path);
+                (doc, is_error, None)
+            }
             Commands::Get {
                 path,
                 source,
                 recursive,
+                url: false,
             } => {
                 let (doc, is_error, item_ref) = get::execute(request, &path, source, recursive);
                 let history_entry = item_ref.map(HistoryEntry::Item);
@@ -126,19 +372,88 @@ impl Commands {
                 query,
                 limit,
                 crate_,
+                flags,
             } => {
+                // History keeps the flags as query operators, so that going back repeats them
+                let query = flags.apply(&query);
                 let (doc, is_error) = search::execute(request, &query, limit, crate_.as_deref());
                 let history_entry = Some(HistoryEntry::Search {
                     query,
                     crate_name: crate_,
+                    limit,
                 });
                 (doc, is_error, history_entry)
             }
+            Commands::Grep {
+                pattern,
+                crates,
+                regex,
+                ignore_case,
+                limit,
+            } => {
+                let options = grep::GrepOptions {
+                    regex,
+                    ignore_case,
+                    limit,
+                };
+                let (doc, is_error) = grep::execute(request, &pattern, &crates, options);
+                (doc, is_error, None)
+            }
             Commands::List => {
                 let (doc, is_error, default_crate) = list::execute(request);
                 let history_entry = Some(HistoryEntry::List { default_crate });
                 (doc, is_error, history_entry)
             }
+            Commands::Diff { old, new } => {
+                let (doc, is_error) = diff::execute(request, &old, new.as_deref());
+                (doc, is_error, None)
+            }
+            Commands::Versions { crate_name } => {
+                let (doc, is_error) = versions::execute(request, &crate_name);
+                (doc, is_error, None)
+            }
+            Commands::Stats { crate_name } => {
+                let (doc, is_error) = stats::execute(request, &crate_name);
+                (doc, is_error, None)
+            }
+            Commands::Info { crate_name } => {
+                let (doc, is_error) = info::execute(request, &crate_name);
+                (doc, is_error, None)
+            }
+            Commands::Exists { path, .. } => {
+                let (doc, is_error) = exists::execute(request, &path);
+                (doc, is_error, None)
+            }
+            Commands::Build { packages, deps, .. } => {
+                let (doc, is_error) = build::execute(request, &packages, deps);
+                (doc, is_error, None)
+            }
+            Commands::StdDocs { build, download } => {
+                let (doc, is_error) = std_docs::execute(request, build, download);
+                (doc, is_error, None)
+            }
+            Commands::Themes { preview } => {
+                let (doc, is_error) = themes::execute(request, preview.as_deref());
+                (doc, is_error, None)
+            }
+            Commands::ExportSite { dir, crates } => {
+                let (doc, is_error) = export_site::execute(request, &dir, &crates);
+                (doc, is_error, None)
+            }
+            Commands::Batch { .. } => (
+                Document::from(vec![DocumentNode::paragraph(vec![Span::plain(
+                    "Batch mode reads queries from stdin; run it outside interactive mode",
+                )])]),
+                true,
+                None,
+            ),
+            Commands::Lsp | Commands::Daemon { .. } | Commands::Serve { .. } => (
+                Document::from(vec![DocumentNode::paragraph(vec![Span::plain(
+                    "This command runs a server; start it outside interactive mode",
+                )])]),
+                true,
+                None,
+            ),
         }
     }
 }
@@ -0,0 +1,85 @@
+//! Batch mode: answer many queries with one process
+//!
+//! Reads one item path or search query per line from stdin. Lines that resolve to an item are
+//! answered like `get`, and anything else like `search`, reusing the crates loaded for earlier
+//! lines. In text format each answer is preceded by a `=== <query>` line; in JSON format each
+//! answer is a single line `{ "query", "command", "output", "is_error" }`.
+
+use std::io::{self, BufRead, Write};
+
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::commands::Commands;
+use crate::render_context::RenderContext;
+use crate::renderer;
+use crate::request::Request;
+
+/// How each answer is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub(crate) enum BatchFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Answer every line of `input`, returning whether any query failed
+pub(crate) fn run(
+    request: &Request,
+    input: impl BufRead,
+    mut output: impl Write,
+    format: BatchFormat,
+    render_context: &RenderContext,
+) -> io::Result<bool> {
+    let mut any_failed = false;
+
+    for line in input.lines() {
+        let line = line?;
+        let query = line.trim();
+        if query.is_empty() {
+            continue;
+        }
+
+        let (command_name, command) = command_for(request, query);
+        let (document, mut is_error, _) = command.execute(request);
+        let mut rendered = String::new();
+        if renderer::render(&document, render_context, &mut rendered).is_err() {
+            rendered = "Failed to render documentation".into();
+            is_error = true;
+        }
+        any_failed |= is_error;
+
+        match format {
+            BatchFormat::Text => {
+                writeln!(output, "=== {query}")?;
+                write!(output, "{rendered}")?;
+                if !rendered.ends_with('\n') {
+                    writeln!(output)?;
+                }
+            }
+            BatchFormat::Json => writeln!(
+                output,
+                "{}",
+                json!({
+                    "query": query,
+                    "command": command_name,
+                    "output": rendered,
+                    "is_error": is_error,
+                })
+            )?,
+        }
+        output.flush()?;
+    }
+
+    Ok(any_failed)
+}
+
+/// Look up `query` as a path if it names an item, and search for it otherwise
+fn command_for(request: &Request, query: &str) -> (&'static str, Commands) {
+    let mut suggestions = vec![];
+    if request.resolve_path(query, &mut suggestions).is_some() {
+        ("get", Commands::get(query))
+    } else {
+        ("search", Commands::search(query))
+    }
+}
@@ -0,0 +1,183 @@
+//! Generating documentation ahead of time, so that later lookups never wait on cargo: in CI, or
+//! before going offline
+
+use std::borrow::Cow;
+use std::time::{Duration, Instant};
+
+use ferritin_common::sources::{DocSource, LocalSource};
+use ferritin_common::{CrateInfo, CrateName};
+
+use super::build_failure;
+use super::diff::parse_spec;
+use crate::request::Request;
+use crate::styled_string::{Document, DocumentNode, HeadingLevel, Span, TableCell};
+
+/// The outcome of building one crate's documentation
+struct Built<'a> {
+    crate_info: Cow<'a, CrateInfo>,
+    elapsed: Duration,
+    succeeded: bool,
+}
+
+/// Regenerate rustdoc JSON for `packages`, or every workspace package, along with the
+/// workspace's dependencies if `deps` is set, reporting how long each took
+pub(crate) fn execute<'a>(
+    request: &'a Request,
+    packages: &[String],
+    deps: bool,
+) -> (Document<'a>, bool) {
+    let Some(local_source) = request.local_source() else {
+        return error("Building documentation needs a cargo workspace".to_string());
+    };
+
+    let crates = match selected_crates(local_source, packages, deps) {
+        Ok(crates) => crates,
+        Err(unknown) => return error(format!("'{unknown}' isn't in this workspace")),
+    };
+
+    let start = Instant::now();
+    let built: Vec<Built<'_>> = crates
+        .into_iter()
+        .map(|crate_info| {
+            let crate_name = CrateName::from(crate_info.name());
+            // Only dependencies need their version, since several may be resolved
+            let version = crate_info
+                .provenance()
+                .is_local_dependency()
+                .then(|| crate_info.version())
+                .flatten();
+            log::info!("Building documentation for {crate_name}");
+            let crate_start = Instant::now();
+            let result = local_source.rebuild(&crate_name, version);
+            if let Err(e) = &result {
+                log::error!("Failed to build documentation for {crate_name}: {e}");
+            }
+            Built {
+                crate_info,
+                elapsed: crate_start.elapsed(),
+                succeeded: result.is_ok(),
+            }
+        })
+        .collect();
+    let elapsed = start.elapsed();
+
+    let failed = built.iter().filter(|built| !built.succeeded).count();
+    let mut title = format!(
+        "Built documentation for {} crate{} in {}",
+        built.len() - failed,
+        if built.len() - failed == 1 { "" } else { "s" },
+        seconds(elapsed)
+    );
+    if failed > 0 {
+        title.push_str(&format!("; {failed} failed"));
+    }
+
+    let mut nodes = vec![
+        DocumentNode::heading(HeadingLevel::Title, vec![Span::plain(title)]),
+        timing_table(&built),
+    ];
+    for built in built.iter().filter(|built| !built.succeeded) {
+        let name = built.crate_info.name();
+        if let Some(failure) = local_source.build_failure(name) {
+            nodes.extend(build_failure::nodes(name, &failure));
+        }
+    }
+
+    (Document::from(nodes), failed > 0)
+}
+
+/// The crates to build, workspace packages before dependencies, each sorted by name, or the
+/// first package that isn't known
+///
+/// Packages are looked up like paths are, so `crate` and renamed dependencies work, and
+/// `name@version` picks one of several resolved versions of a dependency.
+fn selected_crates<'a>(
+    local_source: &'a LocalSource,
+    packages: &[String],
+    deps: bool,
+) -> Result<Vec<Cow<'a, CrateInfo>>, String> {
+    let mut crates: Vec<Cow<'a, CrateInfo>> = if packages.is_empty() {
+        local_source
+            .list_available()
+            .filter(|crate_info| deps || crate_info.provenance().is_workspace())
+            .map(Cow::Borrowed)
+            .collect()
+    } else {
+        packages
+            .iter()
+            .map(|package| {
+                let (name, version_req) = parse_spec(package);
+                local_source
+                    .lookup(name, &version_req)
+                    .ok_or_else(|| package.clone())
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    crates.sort_by(|a, b| {
+        (!a.provenance().is_workspace(), a.name(), a.version()).cmp(&(
+            !b.provenance().is_workspace(),
+            b.name(),
+            b.version(),
+        ))
+    });
+    crates.dedup_by(|a, b| a.name() == b.name() && a.version() == b.version());
+    Ok(crates)
+}
+
+/// Each crate with its version and how long its build took
+fn timing_table<'a>(built: &[Built<'_>]) -> DocumentNode<'a> {
+    let rows = built
+        .iter()
+        .map(|built| {
+            let version = built
+                .crate_info
+                .version()
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            let outcome = if built.succeeded {
+                Span::plain(seconds(built.elapsed))
+            } else {
+                Span::strong(format!("failed after {}", seconds(built.elapsed)))
+            };
+            vec![
+                TableCell::from_span(Span::plain(built.crate_info.name().to_string())),
+                TableCell::from_span(Span::comment(version)),
+                TableCell::from_span(outcome),
+            ]
+        })
+        .collect();
+
+    DocumentNode::table(
+        Some(
+            ["Crate", "Version", "Time"]
+                .into_iter()
+                .map(|heading| TableCell::from_span(Span::strong(heading)))
+                .collect(),
+        ),
+        rows,
+    )
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.1}s", duration.as_secs_f64())
+}
+
+fn error<'a>(message: String) -> (Document<'a>, bool) {
+    (
+        Document::from(vec![DocumentNode::paragraph(vec![Span::plain(message)])]),
+        true,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seconds_have_one_decimal() {
+        assert_eq!(seconds(Duration::from_millis(1234)), "1.2s");
+        assert_eq!(seconds(Duration::ZERO), "0.0s");
+    }
+}
@@ -0,0 +1,115 @@
+//! Why a workspace crate has no documentation: the compiler errors from its failed build
+
+use ferritin_common::sources::BuildFailure;
+
+use crate::request::Request;
+use crate::styled_string::{DocumentNode, Span};
+
+/// Cargo's progress lines, which are context rather than diagnostics
+const PROGRESS_VERBS: &[&str] = &[
+    "Compiling",
+    "Checking",
+    "Documenting",
+    "Finished",
+    "Running",
+];
+
+/// The failed build behind a path that couldn't be found, if the crate it names failed to build
+pub(crate) fn for_path<'a>(request: &Request, path: &str) -> Option<Vec<DocumentNode<'a>>> {
+    let crate_name = path.trim_start_matches("::").split("::").next()?;
+    let failure = request.local_source()?.build_failure(crate_name)?;
+    Some(nodes(crate_name, &failure))
+}
+
+/// A paragraph naming the failed command, then its output with diagnostics and their locations
+/// highlighted
+pub(crate) fn nodes<'a>(crate_name: &str, failure: &BuildFailure) -> Vec<DocumentNode<'a>> {
+    vec![
+        DocumentNode::paragraph(vec![
+            Span::plain("Documentation for "),
+            Span::inline_code(crate_name.to_string()),
+            Span::plain(" couldn't be built, because "),
+            Span::inline_code(failure.command().to_string()),
+            Span::plain(" failed:"),
+        ]),
+        DocumentNode::generated_code(stderr_spans(failure.stderr())),
+    ]
+}
+
+/// The lines of cargo's standard error, with each diagnostic's level (`error[E0308]:`) in bold,
+/// the `file:line:column` it points at highlighted, and cargo's progress lines dimmed
+fn stderr_spans<'a>(stderr: &str) -> Vec<Span<'a>> {
+    let mut spans = vec![];
+    for (index, line) in stderr.trim_end().lines().enumerate() {
+        if index > 0 {
+            spans.push(Span::plain("\n"));
+        }
+
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+        if let Some(location) = trimmed.strip_prefix("--> ") {
+            spans.push(Span::plain(indent.to_string()));
+            spans.push(Span::punctuation("--> "));
+            spans.push(Span::type_name(location.to_string()));
+        } else if let Some(level_end) = diagnostic_level_end(line) {
+            spans.push(Span::strong(line[..level_end].to_string()));
+            spans.push(Span::plain(line[level_end..].to_string()));
+        } else if PROGRESS_VERBS
+            .iter()
+            .any(|verb| trimmed.starts_with(&format!("{verb} ")))
+        {
+            spans.push(Span::comment(line.to_string()));
+        } else {
+            spans.push(Span::plain(line.to_string()));
+        }
+    }
+    spans
+}
+
+/// Where the level of a diagnostic's first line ends, after the colon of `error:`,
+/// `error[E0308]:` or `warning:`
+fn diagnostic_level_end(line: &str) -> Option<usize> {
+    let rest = line
+        .strip_prefix("error")
+        .or_else(|| line.strip_prefix("warning"))?;
+    if !rest.starts_with([':', '[']) {
+        return None;
+    }
+    Some(line.find(':')? + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::styled_string::SpanStyle;
+
+    #[test]
+    fn highlights_levels_and_locations() {
+        let stderr = "   Compiling demo v0.1.0\n\
+                      error[E0308]: mismatched types\n  \
+                      --> src/lib.rs:3:5\n";
+        let spans = stderr_spans(stderr);
+        let styled: Vec<_> = spans.iter().map(|span| (&*span.text, span.style)).collect();
+        assert_eq!(
+            styled,
+            [
+                ("   Compiling demo v0.1.0", SpanStyle::Comment),
+                ("\n", SpanStyle::Plain),
+                ("error[E0308]:", SpanStyle::Strong),
+                (" mismatched types", SpanStyle::Plain),
+                ("\n", SpanStyle::Plain),
+                ("  ", SpanStyle::Plain),
+                ("--> ", SpanStyle::Punctuation),
+                ("src/lib.rs:3:5", SpanStyle::TypeName),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_diagnostic_levels_are_bold() {
+        assert_eq!(diagnostic_level_end("warning: unused import"), Some(8));
+        assert_eq!(diagnostic_level_end("error: could not compile"), Some(6));
+        assert_eq!(diagnostic_level_end("errors were found: 2"), None);
+        assert_eq!(diagnostic_level_end("  = note: expected"), None);
+    }
+}
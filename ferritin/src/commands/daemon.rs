@@ -0,0 +1,276 @@
+//! Long-running daemon mode
+//!
+//! Keeps crates, search indexes and the docs.rs cache loaded and answers JSON-RPC 2.0 requests
+//! over a unix socket, one JSON object per line, so that repeated queries skip the startup cost of
+//! `cargo metadata` and parsing rustdoc JSON. Supported methods:
+//!
+//! - `get`: `{ "path": "std::vec::Vec", "source": false, "recursive": false }`
+//! - `search`: `{ "query": "hash map", "crate": "std", "limit": 10 }`
+//! - `list`: `{}`
+//! - `url`: `{ "path": "std::vec::Vec" }`
+//! - `shutdown`: stops the daemon
+//!
+//! Every method also accepts `"output": "plain" | "tty" | "test"` and a `"width"` for wrapping,
+//! and replies with `{ "output": "<rendered text>", "is_error": false }`. With
+//! `"output": "document"`, the reply holds the unrendered document instead, as
+//! `{ "document": { "nodes": [...] }, "is_error": false }`, for clients that draw it themselves.
+//! For example:
+//!
+//! ```text
+//! echo '{"jsonrpc":"2.0","id":1,"method":"get","params":{"path":"Vec"}}' \
+//!     | socat - UNIX-CONNECT:target/ferritin/daemon.sock
+//! ```
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use crate::commands::Commands;
+use crate::render_context::RenderContext;
+use crate::renderer::{self, OutputMode};
+use crate::request::Request;
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Where the daemon listens by default, for the project whose target directory is `target_dir`
+pub(crate) fn socket_in(target_dir: &Path) -> std::path::PathBuf {
+    target_dir.join("ferritin").join("daemon.sock")
+}
+
+/// Listen on `socket_path` and answer requests until a client sends `shutdown`
+///
+/// Each connection is served on a thread of its own until the client disconnects, so an idle
+/// client doesn't hold up the others. Shutting down closes every open connection.
+#[cfg(unix)]
+pub(crate) fn serve(request: &Request, socket_path: &Path) -> io::Result<()> {
+    use std::collections::HashMap;
+    use std::net::Shutdown;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    if let Some(parent) = socket_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // A socket left behind by a daemon that didn't shut down cleanly
+    if socket_path.exists() {
+        std::fs::remove_file(socket_path)?;
+    }
+
+    let listener = UnixListener::bind(socket_path)?;
+    log::info!("Listening on {}", socket_path.display());
+    eprintln!("ferritin daemon listening on {}", socket_path.display());
+
+    let shutting_down = AtomicBool::new(false);
+    // The open connections, so that shutting down can end their reads
+    let connections = Mutex::new(HashMap::<usize, UnixStream>::new());
+
+    std::thread::scope(|scope| {
+        for (index, stream) in listener.incoming().enumerate() {
+            if shutting_down.load(Ordering::SeqCst) {
+                break;
+            }
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::warn!("Failed to accept connection: {e}");
+                    continue;
+                }
+            };
+            if let Ok(clone) = stream.try_clone()
+                && let Ok(mut connections) = connections.lock()
+            {
+                connections.insert(index, clone);
+            }
+
+            let (shutting_down, connections) = (&shutting_down, &connections);
+            scope.spawn(move || {
+                match serve_connection(request, BufReader::new(&stream), &stream) {
+                    Ok(Continue::Listening) => {}
+                    Ok(Continue::Shutdown) => {
+                        shutting_down.store(true, Ordering::SeqCst);
+                        // Wake the accept loop, and end the other connections
+                        let _ = UnixStream::connect(socket_path);
+                        if let Ok(connections) = connections.lock() {
+                            for connection in connections.values() {
+                                let _ = connection.shutdown(Shutdown::Both);
+                            }
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+                    Err(e) => log::warn!("Connection failed: {e}"),
+                }
+                if let Ok(mut connections) = connections.lock() {
+                    connections.remove(&index);
+                }
+            });
+        }
+    });
+
+    let _ = std::fs::remove_file(socket_path);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn serve(_request: &Request, _socket_path: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "daemon mode requires unix sockets",
+    ))
+}
+
+enum Continue {
+    Listening,
+    Shutdown,
+}
+
+/// Answer each line of `input` as a JSON-RPC request
+fn serve_connection(
+    request: &Request,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<Continue> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(e) => {
+                let reply = error_reply(&Value::Null, PARSE_ERROR, e.to_string());
+                writeln!(output, "{reply}")?;
+                continue;
+            }
+        };
+        let id = message.get("id").cloned().unwrap_or(Value::Null);
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        log::info!("daemon: {method}");
+
+        if method == "shutdown" {
+            writeln!(
+                output,
+                "{}",
+                json!({ "jsonrpc": "2.0", "id": id, "result": null })
+            )?;
+            return Ok(Continue::Shutdown);
+        }
+
+        let reply = match command_for(method, params) {
+            Ok(command) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "result": render(request, command, params),
+            }),
+            Err((code, message)) => error_reply(&id, code, message),
+        };
+        writeln!(output, "{reply}")?;
+        output.flush()?;
+    }
+
+    Ok(Continue::Listening)
+}
+
+/// The command a JSON-RPC method and its params ask for
+fn command_for(method: &str, params: &Value) -> Result<Commands, (i64, String)> {
+    let string = |key: &str| {
+        params[key]
+            .as_str()
+            .map(str::to_string)
+            .ok_or((INVALID_PARAMS, format!("expected a `{key}` string")))
+    };
+    let flag = |key: &str| params[key].as_bool().unwrap_or(false);
+
+    Ok(match method {
+        "get" => Commands::Get {
+            path: string("path")?,
+            source: flag("source"),
+            recursive: flag("recursive"),
+            url: false,
+        },
+        "search" => Commands::Search {
+            query: string("query")?,
+            crate_: params["crate"].as_str().map(str::to_string),
+            limit: params["limit"].as_u64().map_or(10, |limit| limit as usize),
+            // Operators such as `unsafe:` go in the query
+            flags: Default::default(),
+        },
+        "list" => Commands::list(),
+        "url" => Commands::url(string("path")?),
+        _ => return Err((METHOD_NOT_FOUND, format!("Unsupported method {method}"))),
+    })
+}
+
+/// Execute `command` and render it in the output mode named in `params`, or serialize the
+/// document for `"output": "document"`
+fn render(request: &Request, command: Commands, params: &Value) -> Value {
+    let output_mode = match params["output"].as_str() {
+        Some("document") => None,
+        Some("tty") => Some(OutputMode::Tty),
+        Some("test") => Some(OutputMode::TestMode),
+        _ => Some(OutputMode::Plain),
+    };
+
+    let (document, is_error, _) = command.execute(request);
+    let Some(output_mode) = output_mode else {
+        return match serde_json::to_value(&document) {
+            Ok(document) => json!({ "document": document, "is_error": is_error }),
+            Err(e) => {
+                json!({ "output": format!("Failed to serialize documentation: {e}"), "is_error": true })
+            }
+        };
+    };
+
+    let render_context = RenderContext::new()
+        .with_output_mode(output_mode)
+        .with_terminal_width(params["width"].as_u64().map_or(80, |width| width as usize));
+    let mut output = String::new();
+    if renderer::render(&document, &render_context, &mut output).is_err() {
+        return json!({ "output": "Failed to render documentation", "is_error": true });
+    }
+    json!({ "output": output, "is_error": is_error })
+}
+
+fn error_reply(id: &Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_methods_to_commands() {
+        let params = json!({ "query": "hash map", "crate": "std", "limit": 3 });
+        assert!(matches!(
+            command_for("search", &params),
+            Ok(Commands::Search { ref query, crate_: Some(ref crate_), limit: 3, .. })
+                if query == "hash map" && crate_ == "std"
+        ));
+        assert!(matches!(
+            command_for("get", &json!({ "path": "Vec", "recursive": true })),
+            Ok(Commands::Get {
+                recursive: true,
+                source: false,
+                ..
+            })
+        ));
+        assert_eq!(
+            command_for("get", &json!({})).err().unwrap().0,
+            INVALID_PARAMS
+        );
+        assert_eq!(
+            command_for("frobnicate", &json!({})).err().unwrap().0,
+            METHOD_NOT_FOUND
+        );
+    }
+}
@@ -0,0 +1,318 @@
+//! Public API comparison between two versions of a crate
+
+use std::collections::{BTreeMap, HashSet};
+
+use ferritin_common::{DocRef, RustdocData, parse_version_req};
+use rustdoc_types::{Id, Item, ItemEnum, ItemKind, Visibility};
+use semver::VersionReq;
+
+use crate::request::Request;
+use crate::styled_string::{Document, DocumentNode, HeadingLevel, ListItem, Span};
+
+/// A public item as seen from one version
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ApiItem {
+    kind: ItemKind,
+    /// The item's declaration as plain text, where it has one
+    signature: Option<String>,
+}
+
+/// Public items present in only one version, or present in both but declared differently
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ApiChanges<'a> {
+    added: Vec<(&'a str, &'a ApiItem)>,
+    removed: Vec<(&'a str, &'a ApiItem)>,
+    changed: Vec<(&'a str, &'a ApiItem, &'a ApiItem)>,
+}
+
+/// Compare `old` with `new`, or with the workspace crate of the same name when `new` is absent
+///
+/// Each argument is a crate name with an optional `@version`; a complete version such as
+/// `serde@1.0.200` means exactly that version. Without `new`, `old` names a workspace crate
+/// and is compared against its latest (or given) published version.
+pub(crate) fn execute<'a>(
+    request: &'a Request,
+    old: &str,
+    new: Option<&str>,
+) -> (Document<'a>, bool) {
+    let (old_name, old_version_req) = parse_spec(old);
+    let (old_data, new_data) = match new {
+        Some(new) => {
+            let (new_name, new_version_req) = parse_spec(new);
+            (
+                request.load_detached(old_name, &old_version_req),
+                request.load_detached(new_name, &new_version_req),
+            )
+        }
+        None => (
+            request.load_published(old_name, &old_version_req),
+            request.load_detached(old_name, &VersionReq::STAR),
+        ),
+    };
+
+    let (old_data, new_data) = match (old_data, new_data) {
+        (Some(old_data), Some(new_data)) => (old_data, new_data),
+        (None, _) => return error(format!("Could not load '{old}'")),
+        (_, None) => return error(format!("Could not load '{}'", new.unwrap_or(old))),
+    };
+
+    let old_api = public_api(request, &old_data);
+    let new_api = public_api(request, &new_data);
+    let changes = compare(&old_api, &new_api);
+
+    let mut nodes = vec![DocumentNode::heading(
+        HeadingLevel::Title,
+        vec![
+            Span::plain("API changes from "),
+            Span::strong(label(&old_data)),
+            Span::plain(" to "),
+            Span::strong(label(&new_data)),
+        ],
+    )];
+
+    if changes == ApiChanges::default() {
+        nodes.push(DocumentNode::paragraph(vec![Span::plain(
+            "The public API is unchanged.",
+        )]));
+        return (Document::from(nodes), false);
+    }
+
+    if !changes.removed.is_empty() {
+        let items = changes
+            .removed
+            .iter()
+            .map(|(path, item)| {
+                ListItem::new(vec![DocumentNode::paragraph(vec![
+                    Span::strikethrough(path.to_string()),
+                    Span::plain(format!(" ({:?})", item.kind)),
+                ])])
+            })
+            .collect();
+        nodes.push(section("Removed", changes.removed.len(), items));
+    }
+
+    if !changes.added.is_empty() {
+        let items = changes
+            .added
+            .iter()
+            .map(|(path, item)| {
+                ListItem::new(vec![DocumentNode::paragraph(vec![
+                    Span::plain(path.to_string()).with_path(path.to_string()),
+                    Span::plain(format!(" ({:?})", item.kind)),
+                ])])
+            })
+            .collect();
+        nodes.push(section("Added", changes.added.len(), items));
+    }
+
+    if !changes.changed.is_empty() {
+        let items = changes
+            .changed
+            .iter()
+            .map(|(path, old_item, new_item)| {
+                let mut spans = vec![Span::plain(path.to_string()).with_path(path.to_string())];
+                if old_item.kind != new_item.kind {
+                    spans.push(Span::plain(format!(
+                        " ({:?} → {:?})",
+                        old_item.kind, new_item.kind
+                    )));
+                }
+                let mut content = vec![DocumentNode::paragraph(spans)];
+                if old_item.signature != new_item.signature {
+                    content.push(DocumentNode::code_block(
+                        Some("diff"),
+                        signature_diff(old_item, new_item),
+                    ));
+                }
+                ListItem::new(content)
+            })
+            .collect();
+        nodes.push(section("Changed", changes.changed.len(), items));
+    }
+
+    (Document::from(nodes), false)
+}
+
+/// Split `name@version` into the crate name and a version requirement
+pub(super) fn parse_spec(spec: &str) -> (&str, VersionReq) {
+    match spec.split_once('@') {
+        Some((name, version)) => (name, parse_version_req(version)),
+        None => (spec, VersionReq::STAR),
+    }
+}
+
+fn label(data: &RustdocData) -> String {
+    match data.version() {
+        Some(version) => format!("{} {version}", data.name()),
+        None => data.name().to_string(),
+    }
+}
+
+fn error<'a>(message: String) -> (Document<'a>, bool) {
+    (
+        Document::from(vec![DocumentNode::paragraph(vec![Span::plain(message)])]),
+        true,
+    )
+}
+
+fn section<'a>(title: &str, count: usize, items: Vec<ListItem<'a>>) -> DocumentNode<'a> {
+    DocumentNode::section(
+        vec![Span::strong(format!("{title} ({count})"))],
+        vec![DocumentNode::list(items)],
+    )
+}
+
+/// Every public, non-hidden item of the crate, keyed by the path it is reachable at
+fn public_api(request: &Request, data: &RustdocData) -> BTreeMap<String, ApiItem> {
+    let root = data.root_item(request);
+    let mut api = BTreeMap::new();
+    collect(
+        request,
+        root,
+        root.name().unwrap_or_default().to_string(),
+        &mut api,
+        &mut HashSet::new(),
+    );
+    api
+}
+
+fn collect<'a>(
+    request: &'a Request,
+    item: DocRef<'a, Item>,
+    path: String,
+    api: &mut BTreeMap<String, ApiItem>,
+    visited: &mut HashSet<Id>,
+) {
+    if !visited.insert(item.id) {
+        return;
+    }
+
+    for child in item.child_items() {
+        if !is_public_api(item, child) {
+            continue;
+        }
+        if let Some(name) = child.name() {
+            collect(request, child, format!("{path}::{name}"), api, visited);
+        }
+    }
+
+    api.insert(
+        path,
+        ApiItem {
+            kind: item.kind(),
+            signature: signature(request, item),
+        },
+    );
+}
+
+/// Whether `child`, reached from `parent`, is part of the public API of `parent`'s crate
+pub(super) fn is_public_api(parent: DocRef<'_, Item>, child: DocRef<'_, Item>) -> bool {
+    // Variants and trait items have no visibility of their own
+    let inherits_visibility =
+        child.kind() == ItemKind::Variant || matches!(parent.inner(), ItemEnum::Trait(_));
+    let exported = (inherits_visibility || matches!(child.visibility, Visibility::Public))
+        && !child.is_hidden();
+    // Items re-exported from other crates belong to those crates' APIs
+    exported && child.crate_docs().name() == parent.crate_docs().name()
+}
+
+/// The declaration ferritin shows for an item, as plain text
+fn signature<'a>(request: &'a Request, item: DocRef<'a, Item>) -> Option<String> {
+    if item.kind() == ItemKind::Module {
+        return None;
+    }
+    request
+        .format_item(item)
+        .into_iter()
+        .find_map(|node| match node {
+            DocumentNode::GeneratedCode { spans } => {
+                Some(spans.iter().map(|span| &*span.text).collect())
+            }
+            _ => None,
+        })
+}
+
+fn compare<'a>(
+    old: &'a BTreeMap<String, ApiItem>,
+    new: &'a BTreeMap<String, ApiItem>,
+) -> ApiChanges<'a> {
+    let mut changes = ApiChanges::default();
+    for (path, old_item) in old {
+        match new.get(path) {
+            None => changes.removed.push((path.as_str(), old_item)),
+            Some(new_item) if new_item != old_item => {
+                changes.changed.push((path.as_str(), old_item, new_item));
+            }
+            Some(_) => {}
+        }
+    }
+    for (path, new_item) in new {
+        if !old.contains_key(path) {
+            changes.added.push((path.as_str(), new_item));
+        }
+    }
+    changes
+}
+
+fn signature_diff(old: &ApiItem, new: &ApiItem) -> String {
+    let mut diff = String::new();
+    for (prefix, signature) in [("-", &old.signature), ("+", &new.signature)] {
+        for line in signature.as_deref().unwrap_or_default().lines() {
+            diff.push_str(prefix);
+            diff.push(' ');
+            diff.push_str(line);
+            diff.push('\n');
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_added_removed_and_changed_items() {
+        let function = |signature: &str| ApiItem {
+            kind: ItemKind::Function,
+            signature: Some(signature.to_string()),
+        };
+        let old = BTreeMap::from([
+            ("demo::kept".to_string(), function("pub fn kept()")),
+            ("demo::gone".to_string(), function("pub fn gone()")),
+            ("demo::widen".to_string(), function("pub fn widen(x: u8)")),
+        ]);
+        let new = BTreeMap::from([
+            ("demo::kept".to_string(), function("pub fn kept()")),
+            ("demo::widen".to_string(), function("pub fn widen(x: u16)")),
+            ("demo::fresh".to_string(), function("pub fn fresh()")),
+        ]);
+
+        let changes = compare(&old, &new);
+        assert_eq!(
+            changes
+                .removed
+                .iter()
+                .map(|(path, _)| *path)
+                .collect::<Vec<_>>(),
+            ["demo::gone"]
+        );
+        assert_eq!(
+            changes
+                .added
+                .iter()
+                .map(|(path, _)| *path)
+                .collect::<Vec<_>>(),
+            ["demo::fresh"]
+        );
+        assert_eq!(changes.changed.len(), 1);
+        assert_eq!(
+            signature_diff(changes.changed[0].1, changes.changed[0].2),
+            "- pub fn widen(x: u8)\n+ pub fn widen(x: u16)\n"
+        );
+
+        assert_eq!(parse_spec("serde@1.0.200").1.to_string(), "=1.0.200");
+        assert_eq!(parse_spec("serde@1.2").1.to_string(), "^1.2");
+        assert_eq!(parse_spec("serde").0, "serde");
+    }
+}
@@ -0,0 +1,68 @@
+use semver::VersionReq;
+
+use crate::request::Request;
+use crate::styled_string::{Document, DocumentNode, Span};
+
+/// Whether a path names a documented item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Existence {
+    Found,
+    Missing,
+    /// The path's crate is known but its documentation could not be loaded
+    Failed(String),
+}
+
+impl Existence {
+    /// 0 when found, 1 when missing and 2 when the answer couldn't be determined
+    pub(crate) fn exit_code(&self) -> u8 {
+        match self {
+            Self::Found => 0,
+            Self::Missing => 1,
+            Self::Failed(_) => 2,
+        }
+    }
+
+    pub(crate) fn message(&self, path: &str) -> String {
+        match self {
+            Self::Found => format!("Found '{path}'"),
+            Self::Missing => format!("Could not find '{path}'"),
+            Self::Failed(reason) => reason.clone(),
+        }
+    }
+}
+
+/// Check whether `path` resolves to an item
+pub(crate) fn check(request: &Request, path: &str) -> Existence {
+    let mut suggestions = vec![];
+    if request.resolve_path(path, &mut suggestions).is_some() {
+        return Existence::Found;
+    }
+
+    let crate_specifier = path
+        .trim_start_matches("::")
+        .split("::")
+        .next()
+        .unwrap_or_default();
+    let (crate_name, version_req) = match crate_specifier.split_once('@') {
+        Some((name, version)) => (name, VersionReq::parse(version).unwrap_or(VersionReq::STAR)),
+        None => (crate_specifier, VersionReq::STAR),
+    };
+
+    if request.lookup_crate(crate_name, &version_req).is_some()
+        && request.load_crate(crate_name, &version_req).is_none()
+    {
+        Existence::Failed(format!("Could not load documentation for '{crate_name}'"))
+    } else {
+        Existence::Missing
+    }
+}
+
+pub(crate) fn execute<'a>(request: &'a Request, path: &str) -> (Document<'a>, bool) {
+    let existence = check(request, path);
+    (
+        Document::from(vec![DocumentNode::paragraph(vec![Span::plain(
+            existence.message(path),
+        )])]),
+        existence != Existence::Found,
+    )
+}
@@ -0,0 +1,294 @@
+//! Static site export
+//!
+//! Renders every public item of the selected crates to an HTML page, laid out like rustdoc's
+//! output (`serde/de/trait.Deserialize.html`, `serde/de/index.html`), with links between exported
+//! items kept relative so the tree can be opened from disk or served by any static file server.
+//! The top-level `index.html` searches `search-index.js` in the browser.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use ferritin_common::DocRef;
+use rustdoc_types::{Id, Item, ItemKind, Visibility};
+use semver::VersionReq;
+use serde_json::json;
+
+use crate::renderer::html;
+use crate::request::Request;
+use crate::styled_string::{Document, DocumentNode, Span, TuiAction};
+
+/// An item that gets its own page
+struct Page<'a> {
+    item: DocRef<'a, Item>,
+    path: String,
+    /// Location relative to the export directory, with `/` separators
+    file: String,
+}
+
+pub(crate) fn execute<'a>(
+    request: &'a Request,
+    dir: &Path,
+    crates: &[String],
+) -> (Document<'a>, bool) {
+    let crate_names: Vec<String> = if crates.is_empty() {
+        request
+            .list_available_crates()
+            .filter(|ci| ci.provenance().is_workspace())
+            .map(|ci| ci.name().to_string())
+            .collect()
+    } else {
+        crates.to_vec()
+    };
+
+    let mut pages = vec![];
+    let mut exported_crates = vec![];
+    for crate_name in &crate_names {
+        match request.load_crate(crate_name, &VersionReq::STAR) {
+            Some(crate_docs) => {
+                let root = crate_docs.root_item(request);
+                exported_crates.push(root.crate_docs().name());
+                collect_pages(root, &mut pages, &mut HashSet::new());
+            }
+            None => {
+                let message = format!("Could not load crate '{crate_name}'");
+                return (
+                    Document::from(vec![DocumentNode::paragraph(vec![Span::plain(message)])]),
+                    true,
+                );
+            }
+        }
+    }
+
+    match write_site(request, dir, &exported_crates, &pages) {
+        Ok(()) => {
+            let message = format!("Exported {} pages to {}", pages.len(), dir.display());
+            let index = dir.join("index.html").display().to_string();
+            (
+                Document::from(vec![
+                    DocumentNode::paragraph(vec![Span::plain(message)]),
+                    DocumentNode::paragraph(vec![
+                        Span::plain("Open "),
+                        Span::plain(index.clone())
+                            .with_action(TuiAction::OpenUrl(format!("file://{index}").into())),
+                    ]),
+                ]),
+                false,
+            )
+        }
+        Err(e) => {
+            let message = format!("Failed to export to {}: {e}", dir.display());
+            (
+                Document::from(vec![DocumentNode::paragraph(vec![Span::plain(message)])]),
+                true,
+            )
+        }
+    }
+}
+
+/// Collect `module` and every public, non-hidden item beneath it that has its own path
+fn collect_pages<'a>(
+    module: DocRef<'a, Item>,
+    pages: &mut Vec<Page<'a>>,
+    visited: &mut HashSet<Id>,
+) {
+    if !visited.insert(module.id) {
+        return;
+    }
+    if let Some(page) = page_for(module) {
+        pages.push(page);
+    }
+
+    for child in module.child_items() {
+        let exported = matches!(child.visibility, Visibility::Public) && !child.is_hidden();
+        // Items reached through a re-export from another crate belong to that crate's docs
+        if !exported || child.crate_docs().name() != module.crate_docs().name() {
+            continue;
+        }
+        if child.kind() == ItemKind::Module {
+            collect_pages(child, pages, visited);
+        } else if visited.insert(child.id)
+            && let Some(page) = page_for(child)
+        {
+            pages.push(page);
+        }
+    }
+}
+
+fn page_for(item: DocRef<'_, Item>) -> Option<Page<'_>> {
+    let path = item.path()?.to_string();
+    let segments: Vec<&str> = path.split("::").collect();
+    let (name, parents) = segments.split_last()?;
+
+    let file = match item.kind() {
+        ItemKind::Module => format!("{}/index.html", segments.join("/")),
+        kind => {
+            let prefix = match kind {
+                ItemKind::Struct => "struct",
+                ItemKind::Enum => "enum",
+                ItemKind::Union => "union",
+                ItemKind::Trait => "trait",
+                ItemKind::TraitAlias => "traitalias",
+                ItemKind::Function => "fn",
+                ItemKind::TypeAlias => "type",
+                ItemKind::Constant => "constant",
+                ItemKind::Static => "static",
+                ItemKind::Macro => "macro",
+                ItemKind::ProcAttribute => "attr",
+                ItemKind::ProcDerive => "derive",
+                ItemKind::Primitive => "primitive",
+                ItemKind::Keyword => "keyword",
+                _ => return None,
+            };
+            format!("{}/{prefix}.{name}.html", parents.join("/"))
+        }
+    };
+
+    Some(Page { item, path, file })
+}
+
+fn write_site(request: &Request, dir: &Path, crates: &[&str], pages: &[Page]) -> io::Result<()> {
+    let files: HashMap<&str, &str> = pages
+        .iter()
+        .map(|page| (page.path.as_str(), page.file.as_str()))
+        .collect();
+
+    for page in pages {
+        let depth = page.file.matches('/').count();
+        let root = "../".repeat(depth);
+        let link = |action: &TuiAction| relative_link(action, &files, &root);
+
+        let document = Document::from(request.format_item(page.item));
+        let mut body = format!("<nav><a href=\"{root}index.html\">Crates and search</a></nav>\n");
+        html::render_with_links(&document, &mut body, &link).map_err(io::Error::other)?;
+
+        write_file(&dir.join(&page.file), &html::page(&page.path, "", &body))?;
+    }
+
+    let index: Vec<_> = pages
+        .iter()
+        .map(|page| {
+            let summary = page
+                .item
+                .docs
+                .as_deref()
+                .and_then(|docs| docs.lines().next())
+                .unwrap_or_default();
+            json!({
+                "path": page.path,
+                "kind": format!("{:?}", page.item.kind()),
+                "href": page.file,
+                "summary": summary,
+            })
+        })
+        .collect();
+    write_file(
+        &dir.join("search-index.js"),
+        &format!(
+            "window.SEARCH_INDEX = {};\n",
+            serde_json::Value::Array(index)
+        ),
+    )?;
+
+    let crate_links: String = crates
+        .iter()
+        .map(|name| {
+            let name = html::escape(name);
+            format!("<li><a href=\"{name}/index.html\">{name}</a></li>\n")
+        })
+        .collect();
+    let body = format!(
+        "<h1>Crates</h1>\n<ul>\n{crate_links}</ul>\n\
+         <input id=\"search\" placeholder=\"Search\" autofocus>\n<ul id=\"results\"></ul>\n\
+         <script>{SEARCH_SCRIPT}</script>\n"
+    );
+    write_file(
+        &dir.join("index.html"),
+        &html::page(
+            "Documentation",
+            "<script src=\"search-index.js\"></script>\n",
+            &body,
+        ),
+    )
+}
+
+/// Link exported items to their pages, relative to a page `root` levels deep, and everything
+/// else to its usual URL
+fn relative_link(action: &TuiAction, files: &HashMap<&str, &str>, root: &str) -> Option<String> {
+    let (path, anchor) = match action {
+        TuiAction::Navigate {
+            doc_ref, anchor, ..
+        } => (doc_ref.path().map(|path| path.to_string()), anchor),
+        TuiAction::NavigateToPath { path, anchor, .. } => (Some(path.to_string()), anchor),
+        _ => (None, &None),
+    };
+
+    match path.as_deref().and_then(|path| files.get(path)) {
+        Some(file) => {
+            let mut href = format!("{root}{file}");
+            if let Some(anchor) = anchor {
+                href.push('#');
+                href.push_str(anchor);
+            }
+            Some(href)
+        }
+        None => action.url().map(|url| url.into_owned()),
+    }
+}
+
+fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Client-side search over `window.SEARCH_INDEX`: every whitespace-separated term must appear in
+/// the item's path, and shorter paths rank first
+const SEARCH_SCRIPT: &str = r#"
+const input = document.getElementById("search");
+const results = document.getElementById("results");
+input.addEventListener("input", () => {
+  const terms = input.value.toLowerCase().split(/\s+/).filter(Boolean);
+  results.replaceChildren();
+  if (terms.length === 0) return;
+  window.SEARCH_INDEX
+    .filter((entry) => terms.every((term) => entry.path.toLowerCase().includes(term)))
+    .sort((a, b) => a.path.length - b.path.length)
+    .slice(0, 50)
+    .forEach((entry) => {
+      const li = document.createElement("li");
+      const a = document.createElement("a");
+      a.href = entry.href;
+      a.textContent = entry.path;
+      li.append(a, ` (${entry.kind}) ${entry.summary}`);
+      results.append(li);
+    });
+});
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_exported_items_relative_to_the_page() {
+        let files = HashMap::from([("demo::Widget", "demo/struct.Widget.html")]);
+        let action = TuiAction::NavigateToPath {
+            path: "demo::Widget".into(),
+            url: None,
+            anchor: Some("examples".into()),
+        };
+        assert_eq!(
+            relative_link(&action, &files, "../../").as_deref(),
+            Some("../../demo/struct.Widget.html#examples")
+        );
+
+        let external = TuiAction::OpenUrl("https://example.com".into());
+        assert_eq!(
+            relative_link(&external, &files, "../").as_deref(),
+            Some("https://example.com")
+        );
+    }
+}
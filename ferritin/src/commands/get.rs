@@ -1,6 +1,8 @@
 use ferritin_common::DocRef;
+use ferritin_common::sources::StdSource;
 use rustdoc_types::Item;
 
+use super::{build_failure, std_docs};
 use crate::request::Request;
 use crate::styled_string::{Document, DocumentNode, ListItem, Span};
 
@@ -36,6 +38,14 @@ pub(crate) fn execute<'a>(
                 "Could not find '{path}'",
             ))])];
 
+            let crate_name = path.trim_start_matches("::").split("::").next();
+            if request.std_source().is_none() && crate_name.is_some_and(StdSource::is_std_crate) {
+                nodes.push(std_docs::missing_docs_hint());
+            }
+            if let Some(failure_nodes) = build_failure::for_path(request, path) {
+                nodes.extend(failure_nodes);
+            }
+
             if !suggestions.is_empty() {
                 nodes.push(DocumentNode::paragraph(vec![Span::plain("Did you mean:")]));
                 let items = suggestions
@@ -0,0 +1,264 @@
+//! Every line of documentation matching a pattern, in the order items appear in each crate
+//!
+//! Unlike `search`, nothing is ranked or tokenized: the raw `docs` of each item are scanned line
+//! by line, so every mention of a word is found, in code examples too.
+
+use std::collections::HashSet;
+
+use ferritin_common::DocRef;
+use regex::{Regex, RegexBuilder};
+use rustdoc_types::{Id, Item, ItemEnum, StructKind};
+
+use super::diff::parse_spec;
+use crate::request::Request;
+use crate::styled_string::{Document, DocumentNode, HeadingLevel, ListItem, Span};
+
+/// How a grep pattern is matched
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct GrepOptions {
+    /// Treat the pattern as a regular expression rather than literal text
+    pub(crate) regex: bool,
+    pub(crate) ignore_case: bool,
+    /// The most matching lines to show
+    pub(crate) limit: usize,
+}
+
+/// An item whose docs match, with the matching lines
+struct GrepMatch<'a> {
+    item: DocRef<'a, Item>,
+    path: String,
+    lines: Vec<&'a str>,
+}
+
+/// Find the lines of documentation matching `pattern` in `crates`, or in every available crate
+pub(crate) fn execute<'a>(
+    request: &'a Request,
+    pattern: &str,
+    crates: &[String],
+    options: GrepOptions,
+) -> (Document<'a>, bool) {
+    let regex = match build_regex(pattern, options) {
+        Ok(regex) => regex,
+        Err(error) => return error_document(format!("Invalid pattern: {error}")),
+    };
+
+    let crate_names: Vec<&str> = if crates.is_empty() {
+        request
+            .list_available_crates()
+            .map(|crate_info| crate_info.name())
+            .collect()
+    } else {
+        crates.iter().map(String::as_str).collect()
+    };
+
+    let show_hidden = request.format_context().show_hidden();
+    let mut matches = vec![];
+    for crate_name in crate_names {
+        let (name, version_req) = parse_spec(crate_name);
+        let Some(data) = request.load_crate(name, &version_req) else {
+            if !crates.is_empty() {
+                return error_document(format!("Could not load crate '{crate_name}'"));
+            }
+            continue;
+        };
+        let root = data.root_item(request);
+        let path = root.name().unwrap_or(name).to_string();
+        collect(
+            root,
+            path,
+            &regex,
+            show_hidden,
+            &mut matches,
+            &mut HashSet::new(),
+        );
+    }
+
+    let total: usize = matches
+        .iter()
+        .map(|grep_match| grep_match.lines.len())
+        .sum();
+    let mut nodes = vec![DocumentNode::heading(
+        HeadingLevel::Title,
+        vec![
+            Span::plain("Documentation matching '"),
+            Span::emphasis(pattern.to_string()),
+            Span::plain(format!(
+                "': {total} line{}",
+                if total == 1 { "" } else { "s" }
+            )),
+        ],
+    )];
+
+    let mut shown = 0;
+    let mut items = vec![];
+    for GrepMatch { item, path, lines } in matches {
+        if shown >= options.limit {
+            break;
+        }
+        let lines = &lines[..lines.len().min(options.limit - shown)];
+        shown += lines.len();
+
+        let mut content = vec![DocumentNode::paragraph(vec![
+            Span::plain(path).with_target(Some(item)),
+            Span::comment(format!(" ({:?})", item.kind())),
+        ])];
+        content.extend(
+            lines
+                .iter()
+                .map(|line| DocumentNode::paragraph(highlight(line.trim(), &regex))),
+        );
+        items.push(ListItem::new(content));
+    }
+
+    if !items.is_empty() {
+        nodes.push(DocumentNode::list(items));
+    }
+    if shown < total {
+        nodes.push(DocumentNode::paragraph(vec![Span::comment(format!(
+            "{} more matching lines not shown; raise --limit to see them",
+            total - shown
+        ))]));
+    }
+
+    (Document::from(nodes), false)
+}
+
+fn build_regex(pattern: &str, options: GrepOptions) -> Result<Regex, regex::Error> {
+    let pattern = if options.regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(options.ignore_case)
+        .build()
+}
+
+/// Scan `item` and everything documented beneath it: module items, methods, variants, fields and
+/// trait items, skipping items re-exported from other crates
+fn collect<'a>(
+    item: DocRef<'a, Item>,
+    path: String,
+    regex: &Regex,
+    show_hidden: bool,
+    matches: &mut Vec<GrepMatch<'a>>,
+    visited: &mut HashSet<Id>,
+) {
+    if !visited.insert(item.id) || (item.is_hidden() && !show_hidden) {
+        return;
+    }
+
+    // Through `item()`, so that the lines borrow from the crate rather than from `item`
+    if let Some(docs) = &item.item().docs {
+        let lines: Vec<&str> = docs.lines().filter(|line| regex.is_match(line)).collect();
+        if !lines.is_empty() {
+            matches.push(GrepMatch {
+                item,
+                path: path.clone(),
+                lines,
+            });
+        }
+    }
+
+    let members: Vec<DocRef<'a, Item>> = match item.inner() {
+        ItemEnum::Trait(trait_item) => item.id_iter(&trait_item.items).collect(),
+        ItemEnum::Struct(struct_item) => match &struct_item.kind {
+            StructKind::Plain { fields, .. } => item.id_iter(fields).collect(),
+            StructKind::Tuple(fields) => fields
+                .iter()
+                .flatten()
+                .filter_map(|id| item.get(id))
+                .collect(),
+            StructKind::Unit => vec![],
+        },
+        _ => vec![],
+    };
+
+    for child in members.into_iter().chain(item.child_items()) {
+        if child.crate_docs().name() != item.crate_docs().name() {
+            continue;
+        }
+        if let Some(name) = child.name() {
+            collect(
+                child,
+                format!("{path}::{name}"),
+                regex,
+                show_hidden,
+                matches,
+                visited,
+            );
+        }
+    }
+}
+
+/// A line with each match in bold
+fn highlight<'a>(line: &str, regex: &Regex) -> Vec<Span<'a>> {
+    let mut spans = vec![];
+    let mut end = 0;
+    for found in regex.find_iter(line) {
+        if found.is_empty() {
+            continue;
+        }
+        if found.start() > end {
+            spans.push(Span::plain(line[end..found.start()].to_string()));
+        }
+        spans.push(Span::strong(found.as_str().to_string()));
+        end = found.end();
+    }
+    if end < line.len() {
+        spans.push(Span::plain(line[end..].to_string()));
+    }
+    spans
+}
+
+fn error_document<'a>(message: String) -> (Document<'a>, bool) {
+    (
+        Document::from(vec![DocumentNode::paragraph(vec![Span::plain(message)])]),
+        true,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::styled_string::SpanStyle;
+
+    #[test]
+    fn literal_patterns_are_escaped() {
+        let regex = build_regex("a.b", GrepOptions::default()).unwrap();
+        assert!(regex.is_match("a.b"));
+        assert!(!regex.is_match("axb"));
+
+        let options = GrepOptions {
+            regex: true,
+            ignore_case: true,
+            ..GrepOptions::default()
+        };
+        assert!(
+            build_regex("dead(lock)?", options)
+                .unwrap()
+                .is_match("DEADLOCK")
+        );
+        assert!(build_regex("(", options).is_err());
+    }
+
+    #[test]
+    fn highlights_each_match() {
+        let regex = build_regex("lock", GrepOptions::default()).unwrap();
+        let spans = highlight("may deadlock if locked twice", &regex);
+        let styled: Vec<_> = spans
+            .iter()
+            .map(|span| (&*span.text, span.style == SpanStyle::Strong))
+            .collect();
+        assert_eq!(
+            styled,
+            [
+                ("may dead", false),
+                ("lock", true),
+                (" if ", false),
+                ("lock", true),
+                ("ed twice", false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! A crate's package metadata: version, license, links, minimum Rust version, edition, authors
+//! and features
+
+use ferritin_common::sources::PackageMetadata;
+
+use super::diff::parse_spec;
+use crate::request::Request;
+use crate::styled_string::{
+    Document, DocumentNode, HeadingLevel, ListItem, Span, TableCell, TuiAction,
+};
+
+/// Show the package metadata of a crate, given by name with an optional `@version`
+pub(crate) fn execute<'a>(request: &'a Request, crate_name: &str) -> (Document<'a>, bool) {
+    let (name, version_req) = parse_spec(crate_name);
+    let Some(metadata) = request.package_metadata(name, &version_req) else {
+        return error(format!(
+            "Could not find package metadata for '{crate_name}'"
+        ));
+    };
+
+    let mut title = vec![Span::type_name(metadata.name().to_string())];
+    if let Some(version) = metadata.version() {
+        title.push(Span::plain(format!(" {version}")));
+    }
+
+    let mut nodes = vec![DocumentNode::heading(HeadingLevel::Title, title)];
+    if let Some(description) = metadata.description() {
+        nodes.push(DocumentNode::paragraph(vec![Span::plain(
+            description.trim().to_string(),
+        )]));
+    }
+    nodes.push(DocumentNode::table(None, fields_table(&metadata)));
+
+    let crate_path = match metadata.version() {
+        Some(version) => format!("{}@{version}", metadata.name()),
+        None => metadata.name().to_string(),
+    };
+    nodes.push(DocumentNode::paragraph(vec![
+        Span::plain("Browse its items with "),
+        Span::inline_code(crate_path.clone()).with_path(crate_path),
+        Span::plain("."),
+    ]));
+
+    if !metadata.features().is_empty() {
+        nodes.push(DocumentNode::section(
+            vec![Span::plain("Features")],
+            vec![features_list(&metadata)],
+        ));
+    }
+
+    (Document::from(nodes), false)
+}
+
+/// One row for each field the package has
+fn fields_table<'a>(metadata: &PackageMetadata) -> Vec<Vec<TableCell<'a>>> {
+    let text = |value: Option<&str>| value.map(|value| Span::plain(value.to_string()));
+    let link = |value: Option<&str>| value.map(url_span);
+    let authors = (!metadata.authors().is_empty()).then(|| metadata.authors().join(", "));
+
+    [
+        ("License", text(metadata.license())),
+        ("Repository", link(metadata.repository())),
+        ("Homepage", link(metadata.homepage())),
+        ("Hosted docs", link(metadata.documentation())),
+        ("Rust version", text(metadata.rust_version())),
+        ("Edition", text(metadata.edition())),
+        ("Authors", text(authors.as_deref())),
+    ]
+    .into_iter()
+    .filter_map(|(label, value)| {
+        Some(vec![
+            TableCell::from_span(Span::strong(label)),
+            TableCell::from_span(value?),
+        ])
+    })
+    .collect()
+}
+
+/// Each feature with what it enables, `default` first
+fn features_list<'a>(metadata: &PackageMetadata) -> DocumentNode<'a> {
+    let mut features: Vec<_> = metadata.features().iter().collect();
+    features.sort_by_key(|(name, _)| name.as_str() != "default");
+
+    DocumentNode::list(
+        features
+            .into_iter()
+            .map(|(name, enables)| {
+                let mut spans = vec![Span::inline_code(name.clone())];
+                if !enables.is_empty() {
+                    spans.push(Span::comment(format!(" enables {}", enables.join(", "))));
+                }
+                ListItem::new(vec![DocumentNode::paragraph(spans)])
+            })
+            .collect(),
+    )
+}
+
+fn url_span<'a>(url: &str) -> Span<'a> {
+    Span::plain(url.to_string()).with_action(TuiAction::OpenUrl(url.to_string().into()))
+}
+
+fn error<'a>(message: String) -> (Document<'a>, bool) {
+    (
+        Document::from(vec![DocumentNode::paragraph(vec![Span::plain(message)])]),
+        true,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn lists_only_known_fields() {
+        let metadata = PackageMetadata::new("demo", None)
+            .with_license("MIT")
+            .with_repository("https://example.com/demo");
+        let rows = fields_table(&metadata);
+        assert_eq!(rows.len(), 2);
+        let link = rows[1][1].spans[0].action.as_ref().and_then(TuiAction::url);
+        assert_eq!(link.as_deref(), Some("https://example.com/demo"));
+    }
+
+    #[test]
+    fn default_feature_comes_first() {
+        let metadata = PackageMetadata::new("demo", None).with_features(BTreeMap::from([
+            ("alloc".to_string(), vec![]),
+            ("default".to_string(), vec!["std".to_string()]),
+            ("std".to_string(), vec!["alloc".to_string()]),
+        ]));
+        let DocumentNode::List { items } = features_list(&metadata) else {
+            panic!("features are a list");
+        };
+        let DocumentNode::Paragraph { spans } = &items[0].content[0] else {
+            panic!("each feature is a paragraph");
+        };
+        assert_eq!(spans[0].text, "default");
+        assert_eq!(spans[1].text, " enables std");
+    }
+}
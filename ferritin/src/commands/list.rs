@@ -1,3 +1,5 @@
+use ferritin_common::CrateInfo;
+
 use crate::request::Request;
 use crate::styled_string::{Document, DocumentNode, HeadingLevel, ListItem, ShowWhen, Span};
 
@@ -18,7 +20,7 @@ pub(crate) fn execute<'a>(request: &'a Request) -> (Document<'a>, bool, Option<&
         available_crates.len()
     );
 
-    available_crates.sort_by(|a, b| a.name().cmp(b.name()));
+    sort_crates(&mut available_crates);
 
     // Find the default crate if any
     let default_crate = available_crates
@@ -33,16 +35,36 @@ pub(crate) fn execute<'a>(request: &'a Request) -> (Document<'a>, bool, Option<&
         )]));
     }
 
+    // Crates resolved at several versions link to each one with a `crate@version` specifier
+    let is_duplicated = |name: &str| {
+        available_crates
+            .iter()
+            .filter(|crate_info| crate_info.name() == name)
+            .count()
+            > 1
+    };
+
     // Format all crates uniformly - extract all needed data to avoid lifetime issues
-    for crate_info in available_crates {
+    for crate_info in &available_crates {
         let crate_name = crate_info.name().to_string();
         let is_default = crate_info.is_default_crate();
         let is_workspace = crate_info.provenance().is_workspace();
         let version = crate_info.version();
         let used_by = crate_info.used_by();
         let description = crate_info.description().as_ref().map(|d| d.to_string());
+        let aliases = crate_info.aliases();
 
         let mut spans = vec![];
+        if !aliases.is_empty() {
+            spans.push(Span::plain(" (imported as "));
+            for (n, alias) in aliases.iter().enumerate() {
+                if n != 0 {
+                    spans.push(Span::plain(", "));
+                }
+                spans.push(Span::strong(alias.to_string()).with_path(alias.to_string()));
+            }
+            spans.push(Span::plain(")"));
+        }
         if is_default {
             spans.push(Span::plain(" (workspace-local, aliased as "));
             spans.push(Span::strong("crate"));
@@ -73,7 +95,11 @@ pub(crate) fn execute<'a>(request: &'a Request) -> (Document<'a>, bool, Option<&
         }
 
         // Prepend crate name label to spans
-        let mut all_spans = vec![Span::strong(crate_name.clone()).with_path(crate_name)];
+        let target = match version {
+            Some(version) if is_duplicated(&crate_name) => format!("{crate_name}@{version}"),
+            _ => crate_name.clone(),
+        };
+        let mut all_spans = vec![Span::strong(crate_name).with_path(target)];
         if !spans.is_empty() {
             all_spans.push(Span::plain(" "));
             all_spans.extend(spans);
@@ -101,3 +127,44 @@ pub(crate) fn execute<'a>(request: &'a Request) -> (Document<'a>, bool, Option<&
 
     (Document::from(nodes), false, default_crate)
 }
+
+/// Sort crates by name, with the newest version of each first
+fn sort_crates(crates: &mut [&CrateInfo]) {
+    crates.sort_by(|a, b| a.name().cmp(b.name()).then(b.version().cmp(&a.version())));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semver::Version;
+
+    #[test]
+    fn newest_version_of_each_crate_comes_first() {
+        let crates = [
+            CrateInfo::new("serde", Some(Version::new(1, 0, 100))),
+            CrateInfo::new("anyhow", None),
+            CrateInfo::new("serde", Some(Version::new(1, 0, 200))),
+            CrateInfo::new("serde", Some(Version::new(0, 9, 0))),
+        ];
+        let mut sorted = crates.iter().collect::<Vec<_>>();
+        sort_crates(&mut sorted);
+        let sorted = sorted
+            .iter()
+            .map(|crate_info| {
+                (
+                    crate_info.name(),
+                    crate_info.version().map(Version::to_string),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sorted,
+            [
+                ("anyhow", None),
+                ("serde", Some("1.0.200".to_string())),
+                ("serde", Some("1.0.100".to_string())),
+                ("serde", Some("0.9.0".to_string())),
+            ]
+        );
+    }
+}
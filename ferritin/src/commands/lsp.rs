@@ -0,0 +1,373 @@
+//! Language server mode
+//!
+//! Speaks the Language Server Protocol over stdin and stdout so that editors can show ferritin's
+//! formatted documentation. Supported requests:
+//!
+//! - `textDocument/hover`: documentation for the path under the cursor, such as `Vec` or
+//!   `std::collections::HashMap::entry`
+//! - `workspace/symbol`: ferritin's search, with each result pointing at its documentation URL
+//! - `ferritin/lookup`: documentation for an item path given as `{ "path": "std::vec::Vec" }`
+//!
+//! Documents are synchronized in full (`TextDocumentSyncKind::Full`) so hover can find the word
+//! at the cursor.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use ferritin_common::DocRef;
+use rustdoc_types::{Item, ItemKind};
+use serde_json::{Value, json};
+
+use crate::generate_docsrs_url::generate_browsable_url;
+use crate::render_context::RenderContext;
+use crate::renderer::{self, OutputMode};
+use crate::request::Request;
+use crate::styled_string::Document;
+
+/// Maximum number of results returned for `workspace/symbol`
+const SYMBOL_LIMIT: usize = 50;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Language server state: the open documents, by URI
+struct Server<'a> {
+    request: &'a Request,
+    render_context: RenderContext,
+    documents: HashMap<String, String>,
+    shutdown_requested: bool,
+}
+
+/// Serve LSP requests from `input` until the client sends `exit` or closes the stream
+pub(crate) fn serve(
+    request: &Request,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let mut server = Server {
+        request,
+        render_context: RenderContext::new().with_output_mode(OutputMode::Plain),
+        documents: HashMap::new(),
+        shutdown_requested: false,
+    };
+
+    while let Some(message) = read_message(&mut input)? {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        log::debug!("lsp: {method}");
+
+        if method == "exit" {
+            break;
+        }
+
+        let result = server.handle(method, params);
+
+        // Notifications have no id and get no reply
+        if let Some(id) = message.get("id") {
+            let reply = match result {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Err((code, message)) => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": code, "message": message },
+                }),
+            };
+            write_message(&mut output, &reply)?;
+        }
+    }
+
+    if !server.shutdown_requested {
+        log::warn!("lsp: exiting without a shutdown request");
+    }
+    Ok(())
+}
+
+impl Server<'_> {
+    fn handle(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "hoverProvider": true,
+                    "workspaceSymbolProvider": true,
+                },
+                "serverInfo": {
+                    "name": "ferritin",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            })),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(Value::Null)
+            }
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                if let (Some(uri), Some(text)) =
+                    (document["uri"].as_str(), document["text"].as_str())
+                {
+                    self.documents.insert(uri.to_string(), text.to_string());
+                }
+                Ok(Value::Null)
+            }
+            "textDocument/didChange" => {
+                let uri = params["textDocument"]["uri"].as_str();
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+                if let (Some(uri), Some(text)) = (uri, text) {
+                    self.documents.insert(uri.to_string(), text.to_string());
+                }
+                Ok(Value::Null)
+            }
+            "textDocument/didClose" => {
+                if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                    self.documents.remove(uri);
+                }
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => Ok(self.hover(params)),
+            "workspace/symbol" => {
+                Ok(self.workspace_symbols(params["query"].as_str().unwrap_or_default()))
+            }
+            "ferritin/lookup" => {
+                let path = params["path"]
+                    .as_str()
+                    .ok_or((INVALID_PARAMS, "expected a `path` string".to_string()))?;
+                Ok(match self.resolve(path) {
+                    Some(item) => json!({
+                        "path": path,
+                        "kind": format!("{:?}", item.kind()),
+                        "url": generate_browsable_url(item),
+                        "documentation": self.render(Document::from(self.request.format_item(item))),
+                    }),
+                    None => Value::Null,
+                })
+            }
+            "initialized" => Ok(Value::Null),
+            _ => Err((METHOD_NOT_FOUND, format!("Unsupported method {method}"))),
+        }
+    }
+
+    /// Documentation for the item path under the cursor
+    fn hover(&self, params: &Value) -> Value {
+        let Some(text) = params["textDocument"]["uri"]
+            .as_str()
+            .and_then(|uri| self.documents.get(uri))
+        else {
+            return Value::Null;
+        };
+        let position = &params["position"];
+        let line_number = position["line"].as_u64().unwrap_or_default() as usize;
+        let character = position["character"].as_u64().unwrap_or_default() as usize;
+
+        let Some(line) = text.lines().nth(line_number) else {
+            return Value::Null;
+        };
+        let Some((start, end)) = path_at(line, character) else {
+            return Value::Null;
+        };
+        let path = &line[start..end];
+        let Some(item) = self.resolve(path) else {
+            return Value::Null;
+        };
+
+        json!({
+            "contents": {
+                "kind": "plaintext",
+                "value": self.render(Document::from(self.request.format_item(item))),
+            },
+            "range": {
+                "start": { "line": line_number, "character": utf16_len(&line[..start]) },
+                "end": { "line": line_number, "character": utf16_len(&line[..end]) },
+            },
+        })
+    }
+
+    /// Search results as `SymbolInformation`, located at each item's documentation URL
+    fn workspace_symbols(&self, query: &str) -> Value {
+        if query.is_empty() {
+            return json!([]);
+        }
+
+        let crate_names: Vec<_> = self
+            .request
+            .list_available_crates()
+            .map(|ci| ci.name())
+            .collect();
+        let Ok(results) = self.request.search(query, &crate_names) else {
+            return json!([]);
+        };
+
+        let show_hidden = self.request.format_context().show_hidden();
+        let symbols: Vec<_> = results
+            .iter()
+            .filter_map(|result| {
+                self.request
+                    .get_item_from_id_path(result.crate_name, &result.id_path)
+            })
+            .filter(|(item, _)| show_hidden || !item.is_hidden())
+            .take(SYMBOL_LIMIT)
+            .map(|(item, path_segments)| {
+                let (name, container) = path_segments
+                    .split_last()
+                    .map_or(("", vec![]), |(name, parents)| (*name, parents.to_vec()));
+                json!({
+                    "name": name,
+                    "kind": symbol_kind(item.kind()),
+                    "containerName": container.join("::"),
+                    "location": {
+                        "uri": generate_browsable_url(item),
+                        "range": {
+                            "start": { "line": 0, "character": 0 },
+                            "end": { "line": 0, "character": 0 },
+                        },
+                    },
+                })
+            })
+            .collect();
+
+        Value::Array(symbols)
+    }
+
+    /// Resolve a path as written in source, falling back to a search for a bare name like `Vec`
+    fn resolve(&self, path: &str) -> Option<DocRef<'_, Item>> {
+        let mut suggestions = vec![];
+        if let Some(item) = self.request.resolve_path(path, &mut suggestions) {
+            return Some(item);
+        }
+
+        let name = path.rsplit("::").next()?;
+        let crate_names: Vec<_> = self
+            .request
+            .list_available_crates()
+            .map(|ci| ci.name())
+            .collect();
+        let results = self.request.search(name, &crate_names).ok()?;
+        results.iter().find_map(|result| {
+            let (item, path_segments) = self
+                .request
+                .get_item_from_id_path(result.crate_name, &result.id_path)?;
+            (path_segments.last() == Some(&name)).then_some(item)
+        })
+    }
+
+    fn render(&self, document: Document) -> String {
+        let mut output = String::new();
+        if renderer::render(&document, &self.render_context, &mut output).is_err() {
+            log::error!("lsp: failed to render documentation");
+        }
+        output
+    }
+}
+
+/// Byte range of the `::`-separated path containing the UTF-16 offset `character` in `line`
+fn path_at(line: &str, character: usize) -> Option<(usize, usize)> {
+    let is_path_char = |c: char| c.is_alphanumeric() || c == '_' || c == ':';
+
+    // LSP positions count UTF-16 code units
+    let mut units = 0;
+    let cursor = line
+        .char_indices()
+        .find(|(_, c)| {
+            units += c.len_utf16();
+            units > character
+        })
+        .map(|(index, _)| index)?;
+    if !line[cursor..].starts_with(is_path_char) {
+        return None;
+    }
+
+    let start = line[..cursor]
+        .rfind(|c: char| !is_path_char(c))
+        .map_or(0, |index| index + 1);
+    let end = line[cursor..]
+        .find(|c: char| !is_path_char(c))
+        .map_or(line.len(), |index| cursor + index);
+
+    // Trim stray colons, as in `x: Vec` or `Vec::`
+    let path = &line[start..end];
+    let trimmed_start = path.len() - path.trim_start_matches(':').len();
+    let path = path.trim_matches(':');
+    (!path.is_empty()).then_some((start + trimmed_start, start + trimmed_start + path.len()))
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+/// The LSP `SymbolKind` closest to a rustdoc item kind
+fn symbol_kind(kind: ItemKind) -> u8 {
+    match kind {
+        ItemKind::Module | ItemKind::ExternCrate => 2,
+        ItemKind::Struct | ItemKind::Union => 23,
+        ItemKind::StructField => 8,
+        ItemKind::Enum => 10,
+        ItemKind::Variant => 22,
+        ItemKind::Function => 12,
+        ItemKind::Trait | ItemKind::TraitAlias => 11,
+        ItemKind::Constant | ItemKind::AssocConst => 14,
+        ItemKind::Static => 13,
+        ItemKind::TypeAlias | ItemKind::AssocType => 26,
+        ItemKind::Macro | ItemKind::ProcAttribute | ItemKind::ProcDerive => 12,
+        _ => 5,
+    }
+}
+
+/// Read one `Content-Length` framed JSON-RPC message, or `None` at end of input
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let content_length = content_length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+    })?;
+    let mut body = vec![0; content_length];
+    input.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_path_under_cursor() {
+        let line = "    let map: std::collections::HashMap<K, V> = Vec::new();";
+        let path = |character| path_at(line, character).map(|(start, end)| &line[start..end]);
+
+        assert_eq!(path(20), Some("std::collections::HashMap"));
+        assert_eq!(path(10), Some("map"));
+        assert_eq!(path(53), Some("Vec::new"));
+        assert_eq!(path(3), None);
+        assert_eq!(path(12), None);
+        assert_eq!(path(200), None);
+
+        let mut input = &b"Content-Length: 17\r\n\r\n{\"method\":\"exit\"}"[..];
+        let message = read_message(&mut input).unwrap().unwrap();
+        assert_eq!(message["method"], "exit");
+        assert!(read_message(&mut input).unwrap().is_none());
+    }
+}
@@ -0,0 +1,225 @@
+//! Machine-readable output for `--porcelain`
+//!
+//! A stable, line-based format for editor plugins that build their own UI on top of ferritin.
+//! Every line is a record of tab-separated fields and is never wrapped. Tabs, newlines and
+//! backslashes inside a field are escaped as `\t`, `\n` and `\\`, and empty fields are empty.
+//! The tabs are shown as spaces below.
+//!
+//! `search` prints one record per result:
+//!
+//! ```text
+//! result <path> <kind> <crate> <score> <first line of docs>
+//! ```
+//!
+//! `get` (and `url`) print one record per piece of item metadata:
+//!
+//! ```text
+//! item <path> <kind> <crate> <crate version>
+//! url <documentation url>
+//! span <file> <begin line> <begin column> <end line> <end column> <begin byte> <end byte>
+//! docs <documentation markdown>
+//! child <path> <kind>
+//! ```
+//!
+//! Lines are one-based and columns are zero-based characters, as rustdoc reports them; the byte
+//! offsets into the file are absent when it can't be read. When an item can't be found, `get`
+//! prints `error` followed by up to five `suggestion <path>` records and exits unsuccessfully.
+
+use std::path::PathBuf;
+
+use rustdoc_types::Span;
+
+use crate::format::read_source_file;
+use crate::generate_docsrs_url::generate_browsable_url;
+use crate::request::Request;
+
+/// Search results as `result` records
+pub(crate) fn search(
+    request: &Request,
+    query: &str,
+    limit: usize,
+    crate_: Option<&str>,
+) -> (String, bool) {
+    let crate_names: Vec<_> = match crate_ {
+        Some(crate_) => vec![crate_],
+        None => request
+            .list_available_crates()
+            .map(|ci| ci.name())
+            .collect(),
+    };
+
+    let Ok(results) = request.search(query, &crate_names) else {
+        return (
+            record(&["error", "No crates could be loaded for search"]),
+            true,
+        );
+    };
+
+    let show_hidden = request.format_context().show_hidden();
+    let hide_unavailable = request.format_context().hide_unavailable();
+    let mut output = String::new();
+    for (item, path_segments, score) in results
+        .iter()
+        .filter_map(|result| {
+            let (item, path_segments) =
+                request.get_item_from_id_path(result.crate_name, &result.id_path)?;
+            let shown = (show_hidden || !item.is_hidden())
+                && (!hide_unavailable || request.is_available(item));
+            shown.then_some((item, path_segments, result.score))
+        })
+        .take(limit)
+    {
+        let summary = item
+            .docs
+            .as_deref()
+            .and_then(|docs| docs.lines().next())
+            .unwrap_or_default();
+        output.push_str(&record(&[
+            "result",
+            &path_segments.join("::"),
+            &format!("{:?}", item.kind()),
+            item.crate_docs().name(),
+            &format!("{score:.3}"),
+            summary,
+        ]));
+    }
+    (output, false)
+}
+
+/// Metadata records for the item at `path`
+pub(crate) fn item(request: &Request, path: &str) -> (String, bool) {
+    let mut suggestions = vec![];
+    let Some(item) = request.resolve_path(path, &mut suggestions) else {
+        let mut output = record(&["error", &format!("Could not find '{path}'")]);
+        for suggestion in suggestions.iter().take(5) {
+            output.push_str(&record(&["suggestion", suggestion.path()]));
+        }
+        return (output, true);
+    };
+
+    let item_path = item.path().map(|path| path.to_string()).unwrap_or_default();
+    let crate_docs = item.crate_docs();
+    let version = crate_docs
+        .version()
+        .map(|version| version.to_string())
+        .unwrap_or_default();
+
+    let mut output = record(&[
+        "item",
+        &item_path,
+        &format!("{:?}", item.kind()),
+        crate_docs.name(),
+        &version,
+    ]);
+    output.push_str(&record(&["url", &generate_browsable_url(item)]));
+
+    if let Some(span) = &item.span {
+        let (begin_byte, end_byte) = read_source_file(request, span)
+            .and_then(|(_, contents)| byte_range(&contents, span))
+            .map(|(begin, end)| (begin.to_string(), end.to_string()))
+            .unwrap_or_default();
+        output.push_str(&record(&[
+            "span",
+            &source_path(request, span).display().to_string(),
+            &span.begin.0.to_string(),
+            &span.begin.1.to_string(),
+            &span.end.0.to_string(),
+            &span.end.1.to_string(),
+            &begin_byte,
+            &end_byte,
+        ]));
+    }
+
+    if let Some(docs) = item.docs.as_deref() {
+        output.push_str(&record(&["docs", docs]));
+    }
+
+    let show_hidden = request.format_context().show_hidden();
+    for child in item.child_items() {
+        if child.is_hidden() && !show_hidden {
+            continue;
+        }
+        let child_path = child
+            .path()
+            .map(|path| path.to_string())
+            .or_else(|| Some(format!("{item_path}::{}", child.name()?)));
+        if let Some(child_path) = child_path {
+            output.push_str(&record(&[
+                "child",
+                &child_path,
+                &format!("{:?}", child.kind()),
+            ]));
+        }
+    }
+
+    (output, false)
+}
+
+fn source_path(request: &Request, span: &Span) -> PathBuf {
+    match request.project_root() {
+        Some(root) if span.filename.is_relative() => root.join(&span.filename),
+        _ => span.filename.clone(),
+    }
+}
+
+/// Byte offsets of a span's start and end within the file contents
+fn byte_range(contents: &str, span: &Span) -> Option<(usize, usize)> {
+    let offset = |(line, column): (usize, usize)| {
+        let line_start = if line <= 1 {
+            0
+        } else {
+            contents
+                .match_indices('\n')
+                .nth(line - 2)
+                .map(|(index, _)| index + 1)?
+        };
+        let line_text = contents[line_start..].split('\n').next()?;
+        let within_line = line_text
+            .char_indices()
+            .nth(column)
+            .map_or(line_text.len(), |(index, _)| index);
+        Some(line_start + within_line)
+    };
+    Some((offset(span.begin)?, offset(span.end)?))
+}
+
+/// One tab-separated line
+fn record(fields: &[&str]) -> String {
+    let mut line = String::new();
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            line.push('\t');
+        }
+        for c in field.chars() {
+            match c {
+                '\\' => line.push_str("\\\\"),
+                '\t' => line.push_str("\\t"),
+                '\n' => line.push_str("\\n"),
+                '\r' => {}
+                c => line.push(c),
+            }
+        }
+    }
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_fields_and_measures_bytes() {
+        assert_eq!(
+            record(&["docs", "Line one\n\tindented \\ done", ""]),
+            "docs\tLine one\\n\\tindented \\\\ done\t\n"
+        );
+
+        let span = Span {
+            filename: PathBuf::from("src/lib.rs"),
+            begin: (2, 4),
+            end: (3, 1),
+        };
+        assert_eq!(byte_range("// é\nfn é() {\n}\n", &span), Some((11, 17)));
+    }
+}
@@ -1,11 +1,60 @@
 use crate::request::Request;
 use crate::styled_string::{Document, DocumentNode, HeadingLevel, ListItem, Span, TruncationLevel};
+use ferritin_common::DocRef;
+use ferritin_common::search::ScoredResult;
+use rustdoc_types::{Item, ItemKind};
 
 pub(crate) fn execute<'a>(
     request: &'a Request,
     query: &str,
     limit: usize,
     crate_: Option<&str>,
+) -> (Document<'a>, bool) {
+    execute_with_progress(request, query, limit, crate_, |_| {})
+}
+
+/// The search command's filtering flags, each standing for a query operator
+#[derive(clap::Args, Debug, Clone, Copy, Default)]
+pub(crate) struct SearchFlags {
+    /// Only show unsafe functions, traits and statics (the `unsafe:` query operator)
+    #[arg(long)]
+    unsafe_only: bool,
+
+    /// Rank deprecated items like any other, rather than below the rest
+    /// (`deprecated:include`)
+    #[arg(long, conflicts_with = "deprecated_only")]
+    include_deprecated: bool,
+
+    /// Only show deprecated items (`deprecated:only`)
+    #[arg(long)]
+    deprecated_only: bool,
+}
+
+impl SearchFlags {
+    /// The query with the operators the flags stand for added
+    pub(crate) fn apply(self, query: &str) -> String {
+        let mut query = query.to_string();
+        if self.unsafe_only {
+            query.push_str(" unsafe:");
+        }
+        if self.include_deprecated {
+            query.push_str(" deprecated:include");
+        }
+        if self.deprecated_only {
+            query.push_str(" deprecated:only");
+        }
+        query
+    }
+}
+
+/// Search, passing `on_partial` a results page for the crates searched so far while others are
+/// still loading
+pub(crate) fn execute_with_progress<'a>(
+    request: &'a Request,
+    query: &str,
+    limit: usize,
+    crate_: Option<&str>,
+    mut on_partial: impl FnMut(Document<'a>),
 ) -> (Document<'a>, bool) {
     log::info!("Searching for {query}");
 
@@ -17,8 +66,13 @@ pub(crate) fn execute<'a>(
             .collect(),
     };
 
-    // Search using Navigator's built-in search
-    let scored_results = match request.search(query, &crate_names) {
+    // Search using Navigator's built-in search, showing early results from a slow search
+    let search = request.search_streaming(query, &crate_names, limit, |partial, remaining| {
+        if !query.is_empty() {
+            on_partial(results_document(request, query, limit, partial, remaining));
+        }
+    });
+    let scored_results = match search {
         Ok(results) => results,
         Err(suggestions) => {
             // No crates could be loaded - show suggestions
@@ -91,6 +145,20 @@ pub(crate) fn execute<'a>(
         }
     }
 
+    (
+        results_document(request, query, limit, &scored_results, 0),
+        false,
+    )
+}
+
+/// A page listing the top `limit` results, noting how many crates are still being searched
+fn results_document<'a>(
+    request: &'a Request,
+    query: &str,
+    limit: usize,
+    scored_results: &[ScoredResult<'_>],
+    remaining: usize,
+) -> Document<'a> {
     // Get top values for normalization (so best result = 100 in each metric)
     let top_score = scored_results
         .first()
@@ -119,43 +187,133 @@ pub(crate) fn execute<'a>(
         ],
     }];
 
-    // Display up to `limit` results
-    let mut list_items = vec![];
+    if remaining > 0 {
+        nodes.push(DocumentNode::paragraph(vec![Span::comment(format!(
+            "Still searching {remaining} more crate{}…",
+            if remaining == 1 { "" } else { "s" }
+        ))]));
+    }
+
+    // Take up to `limit` results, omitting #[doc(hidden)] items unless requested
+    let show_hidden = request.format_context().show_hidden();
+    let hide_unavailable = request.format_context().hide_unavailable();
+    let rank_debug = request.format_context().rank_debug();
+    let authority_boost = request.ranking().authority_boost;
+    let shown: Vec<_> = scored_results
+        .iter()
+        .filter_map(|result| {
+            let (item, path_segments) =
+                request.get_item_from_id_path(result.crate_name, &result.id_path)?;
+            let shown = (show_hidden || !item.is_hidden())
+                && (!hide_unavailable || request.is_available(item));
+            shown.then_some((result, item, path_segments))
+        })
+        .take(limit)
+        .collect();
+
+    let result_item = |result: &ScoredResult<'_>, item: DocRef<'a, Item>, label: String| {
+        let normalized_score = 100.0 * result.score / top_score;
+        let normalized_relevance = 100.0 * result.relevance / top_relevance;
+        let normalized_authority = 100.0 * result.authority / top_authority;
 
-    for (i, result) in scored_results.into_iter().enumerate() {
-        if i >= limit {
-            break;
+        let mut spans = vec![Span::plain(label).with_target(Some(item)), Span::plain(" ")];
+        if item.is_hidden() {
+            spans.push(Span::comment("#[doc(hidden)]"));
+        }
+        if item.deprecation.is_some() {
+            spans.push(Span::comment("#[deprecated]"));
+        }
+        if let Some(alias) = item.alias_matching(query) {
+            spans.push(Span::comment(format!("alias: {alias}")));
+        }
+        if rank_debug {
+            // The raw components, as combined in BM25Scorer
+            spans.push(Span::plain(format!(
+                " ({:?}) - score {:.3} = relevance {:.3} × (1 + boost {} × authority {:.3})",
+                item.kind(),
+                result.score,
+                result.relevance,
+                authority_boost,
+                result.authority
+            )));
+        } else {
+            spans.push(Span::plain(format!(
+                " ({:?}) - score: {:.0} (relevance: {:.0}, authority: {:.0})",
+                item.kind(),
+                normalized_score,
+                normalized_relevance,
+                normalized_authority
+            )));
         }
 
-        if let Some((item, path_segments)) =
-            request.get_item_from_id_path(result.crate_name, &result.id_path)
-        {
-            let path = path_segments.join("::");
-            let normalized_score = 100.0 * result.score / top_score;
-            let normalized_relevance = 100.0 * result.relevance / top_relevance;
-            let normalized_authority = 100.0 * result.authority / top_authority;
-
-            let mut content = vec![DocumentNode::paragraph(vec![
-                Span::plain(path).with_target(Some(item)),
-                Span::plain(" "),
-                Span::plain(format!(
-                    " ({:?}) - score: {:.0} (relevance: {:.0}, authority: {:.0})",
-                    item.kind(),
-                    normalized_score,
-                    normalized_relevance,
-                    normalized_authority
-                )),
-            ])];
-
-            if let Some(docs) = request.docs_to_show(item, TruncationLevel::SingleLine) {
-                content.extend(docs);
-            }
+        let mut content = vec![DocumentNode::paragraph(spans)];
+
+        if let Some(docs) = request.docs_to_show(item, TruncationLevel::SingleLine) {
+            content.extend(docs);
+        }
+
+        ListItem::new(content)
+    };
 
-            list_items.push(ListItem::new(content));
+    // Results that are members of the same type are listed together under it, at the rank of
+    // the best of them
+    let mut groups: Vec<(Option<ParentType<'a>>, Vec<_>)> = vec![];
+    for (result, item, path_segments) in shown {
+        let parent = parent_type(request, result);
+        let group = parent.as_ref().and_then(|(parent, _)| {
+            groups.iter_mut().find(|(group_parent, _)| {
+                group_parent
+                    .as_ref()
+                    .is_some_and(|(group_parent, _)| group_parent == parent)
+            })
+        });
+        match group {
+            Some((_, members)) => members.push((result, item, path_segments)),
+            None => groups.push((parent, vec![(result, item, path_segments)])),
         }
     }
 
+    let list_items = groups
+        .into_iter()
+        .map(|(parent, members)| match (parent, &members[..]) {
+            (Some((parent, parent_path)), [_, _, ..]) => {
+                let children = members
+                    .iter()
+                    .map(|(result, item, path_segments)| {
+                        let name = path_segments.last().copied().unwrap_or_default();
+                        result_item(result, *item, name.to_string())
+                    })
+                    .collect();
+                ListItem::new(vec![
+                    DocumentNode::paragraph(vec![
+                        Span::plain(parent_path).with_target(Some(parent)),
+                        Span::comment(format!(" ({:?}, {} matches)", parent.kind(), members.len())),
+                    ]),
+                    DocumentNode::list(children),
+                ])
+            }
+            _ => {
+                let (result, item, path_segments) = &members[0];
+                result_item(result, *item, path_segments.join("::"))
+            }
+        })
+        .collect();
+
     nodes.push(DocumentNode::List { items: list_items });
 
-    (Document::from(nodes), false)
+    Document::from(nodes)
+}
+
+/// A type that search results are grouped under, with its path
+type ParentType<'a> = (DocRef<'a, Item>, String);
+
+/// The struct, enum, union, trait or primitive a result is a member of, with its path, if any
+fn parent_type<'a>(request: &'a Request, result: &ScoredResult<'_>) -> Option<ParentType<'a>> {
+    let (_, parent_ids) = result.id_path.split_last()?;
+    let (parent, path_segments) = request.get_item_from_id_path(result.crate_name, parent_ids)?;
+    matches!(
+        parent.kind(),
+        ItemKind::Struct | ItemKind::Enum | ItemKind::Union | ItemKind::Trait | ItemKind::Primitive
+    )
+    .then(|| (parent, path_segments.join("::")))
 }
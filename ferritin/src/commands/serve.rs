@@ -0,0 +1,260 @@
+//! Local HTTP documentation server
+//!
+//! Serves rendered documentation as HTML so that workspace crates (including private ones) can be
+//! browsed without publishing them. Routes:
+//!
+//! - `/`: the list of available crates
+//! - `/item/<path>`: documentation for an item, such as `/item/std::vec::Vec`
+//! - `/search?q=<query>&crate=<crate>`: search results
+//! - `/api/search?q=<query>&crate=<crate>&limit=<n>`: search results as JSON
+//!
+//! Connections are answered one at a time.
+
+use std::borrow::Cow;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+
+use percent_encoding::{NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+use serde_json::json;
+
+use crate::commands::{Commands, get, search};
+use crate::renderer::html;
+use crate::request::Request;
+use crate::styled_string::{Document, TuiAction};
+
+const DEFAULT_LIMIT: usize = 20;
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn html(status: u16, title: &str, document: &Document) -> Self {
+        let mut body = String::from(SEARCH_FORM);
+        if html::render_with_links(document, &mut body, &local_link).is_err() {
+            return Self::text(500, "Failed to render documentation");
+        }
+        Self {
+            status,
+            content_type: "text/html; charset=utf-8",
+            body: html::page(title, "", &body),
+        }
+    }
+
+    fn text(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.to_string(),
+        }
+    }
+
+    fn write_to(&self, stream: &mut impl Write) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "Internal Server Error",
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {} {reason}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.content_type,
+            self.body.len(),
+            self.body
+        )?;
+        stream.flush()
+    }
+}
+
+const SEARCH_FORM: &str = "<nav><a href=\"/\">Crates</a> \
+    <form action=\"/search\" style=\"display: inline\">\
+    <input name=\"q\" placeholder=\"Search\" autofocus></form></nav>\n";
+
+/// Serve documentation over HTTP on `address` until the process is stopped
+pub(crate) fn serve(request: &Request, address: &str) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    eprintln!("Serving documentation at http://{}", listener.local_addr()?);
+
+    for stream in listener.incoming() {
+        let result = stream.and_then(|mut stream| handle_connection(request, &mut stream));
+        if let Err(e) = result {
+            log::warn!("HTTP connection failed: {e}");
+        }
+    }
+    Ok(())
+}
+
+fn handle_connection(request: &Request, stream: &mut TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(&*stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Headers don't affect any response
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => {
+            log::info!("GET {target}");
+            route(request, target)
+        }
+        _ => Response::text(405, "Only GET requests are supported"),
+    };
+    response.write_to(stream)
+}
+
+fn route(request: &Request, target: &str) -> Response {
+    let (path, params) = parse_target(target);
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .filter(|value| !value.is_empty())
+    };
+    let limit = param("limit")
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(DEFAULT_LIMIT);
+
+    if path == "/" {
+        let (document, _, _) = Commands::list().execute(request);
+        Response::html(200, "Crates", &document)
+    } else if let Some(item_path) = path.strip_prefix("/item/") {
+        let (document, is_error, _) = get::execute(request, item_path, false, false);
+        Response::html(if is_error { 404 } else { 200 }, item_path, &document)
+    } else if path == "/search" {
+        let query = param("q").unwrap_or_default();
+        let (document, _) = search::execute(request, query, limit, param("crate"));
+        Response::html(200, &format!("Search: {query}"), &document)
+    } else if path == "/api/search" {
+        search_json(
+            request,
+            param("q").unwrap_or_default(),
+            param("crate"),
+            limit,
+        )
+    } else {
+        Response::text(404, "Not found")
+    }
+}
+
+/// Search results as a JSON array of `{ path, kind, score, href }`
+fn search_json(request: &Request, query: &str, crate_: Option<&str>, limit: usize) -> Response {
+    let crate_names: Vec<_> = match crate_ {
+        Some(crate_) => vec![crate_],
+        None => request
+            .list_available_crates()
+            .map(|ci| ci.name())
+            .collect(),
+    };
+    let results = request.search(query, &crate_names).unwrap_or_default();
+
+    let show_hidden = request.format_context().show_hidden();
+    let results: Vec<_> = results
+        .iter()
+        .filter_map(|result| {
+            let (item, path_segments) =
+                request.get_item_from_id_path(result.crate_name, &result.id_path)?;
+            (show_hidden || !item.is_hidden()).then_some((item, path_segments, result.score))
+        })
+        .take(limit)
+        .map(|(item, path_segments, score)| {
+            let path = path_segments.join("::");
+            json!({
+                "href": item_href(&path, None),
+                "path": path,
+                "kind": format!("{:?}", item.kind()),
+                "score": score,
+            })
+        })
+        .collect();
+
+    Response {
+        status: 200,
+        content_type: "application/json",
+        body: serde_json::Value::Array(results).to_string(),
+    }
+}
+
+/// Link items to this server's pages, and everything else to its usual URL
+fn local_link(action: &TuiAction) -> Option<String> {
+    match action {
+        TuiAction::Navigate {
+            doc_ref, anchor, ..
+        } => match doc_ref.path() {
+            Some(path) => Some(item_href(&path.to_string(), anchor.as_deref())),
+            None => action.url().map(Cow::into_owned),
+        },
+        TuiAction::NavigateToPath { path, anchor, .. } => Some(item_href(path, anchor.as_deref())),
+        _ => action.url().map(Cow::into_owned),
+    }
+}
+
+fn item_href(path: &str, anchor: Option<&str>) -> String {
+    let mut href = format!("/item/{}", utf8_percent_encode(path, NON_ALPHANUMERIC));
+    if let Some(anchor) = anchor {
+        href.push('#');
+        href.push_str(anchor);
+    }
+    href
+}
+
+/// Split a request target into its decoded path and query parameters
+fn parse_target(target: &str) -> (String, Vec<(String, String)>) {
+    let decode = |text: &str| {
+        percent_decode_str(&text.replace('+', " "))
+            .decode_utf8_lossy()
+            .into_owned()
+    };
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let params = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), decode(value))
+        })
+        .collect();
+
+    (
+        percent_decode_str(path).decode_utf8_lossy().into_owned(),
+        params,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_request_targets() {
+        let (path, params) = parse_target("/search?q=hash+map%3F&crate=std&flag");
+        assert_eq!(path, "/search");
+        assert_eq!(
+            params,
+            [
+                ("q".to_string(), "hash map?".to_string()),
+                ("crate".to_string(), "std".to_string()),
+                ("flag".to_string(), String::new()),
+            ]
+        );
+
+        assert_eq!(
+            item_href("std::vec::Vec", Some("guarantees")),
+            "/item/std%3A%3Avec%3A%3AVec#guarantees"
+        );
+        assert_eq!(
+            parse_target(&item_href("std::vec::Vec", None)).0,
+            "/item/std::vec::Vec"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Statistics about a crate's public API, for judging a dependency at a glance
+
+use std::collections::{HashMap, HashSet};
+
+use ferritin_common::DocRef;
+use rustdoc_types::{Id, Item, ItemEnum, ItemKind};
+
+use super::diff::is_public_api;
+use crate::request::Request;
+use crate::styled_string::{Document, DocumentNode, HeadingLevel, Span, TableCell};
+
+/// Counts of a crate's public items
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CrateStats {
+    /// Public items of each kind
+    kinds: HashMap<ItemKind, KindStats>,
+    /// Impl blocks written in the crate, leaving out the auto trait and blanket impls rustdoc
+    /// adds to every type
+    impls: usize,
+    /// Of `impls`, those that implement a trait
+    trait_impls: usize,
+    /// Public `unsafe fn`s, including methods
+    unsafe_functions: usize,
+    /// Characters of documentation across the public items
+    doc_length: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct KindStats {
+    count: usize,
+    documented: usize,
+}
+
+impl CrateStats {
+    fn add(&mut self, item: DocRef<'_, Item>) {
+        let docs = item.docs.as_deref().unwrap_or_default().trim();
+        let kind = self.kinds.entry(item.kind()).or_default();
+        kind.count += 1;
+        if !docs.is_empty() {
+            kind.documented += 1;
+        }
+        self.doc_length += docs.chars().count();
+
+        if let ItemEnum::Function(function) = item.inner()
+            && function.header.is_unsafe
+        {
+            self.unsafe_functions += 1;
+        }
+    }
+
+    fn totals(&self) -> KindStats {
+        self.kinds
+            .values()
+            .fold(KindStats::default(), |total, kind| KindStats {
+                count: total.count + kind.count,
+                documented: total.documented + kind.documented,
+            })
+    }
+}
+
+/// Show statistics for a crate, given by name with an optional `@version`
+pub(crate) fn execute<'a>(request: &'a Request, crate_name: &str) -> (Document<'a>, bool) {
+    let mut suggestions = vec![];
+    let Some(item) = request.resolve_path(crate_name, &mut suggestions) else {
+        return error(format!("Could not find crate '{crate_name}'"));
+    };
+    let data = item.crate_docs();
+    let root = data.root_item(request);
+
+    let mut stats = CrateStats::default();
+    collect(root, &mut stats, &mut HashSet::new());
+    for item in data.items().impls() {
+        if let ItemEnum::Impl(impl_block) = &item.inner
+            && !impl_block.is_synthetic
+            && impl_block.blanket_impl.is_none()
+        {
+            stats.impls += 1;
+            if impl_block.trait_.is_some() {
+                stats.trait_impls += 1;
+            }
+        }
+    }
+
+    let mut title = vec![
+        Span::plain("Statistics for "),
+        Span::type_name(data.name().to_string()),
+    ];
+    if let Some(version) = data.version() {
+        title.push(Span::plain(format!(" {version}")));
+    }
+
+    (
+        Document::from(vec![
+            DocumentNode::heading(HeadingLevel::Title, title),
+            items_table(&stats),
+            summary_table(&stats),
+        ]),
+        false,
+    )
+}
+
+/// Count `item` and the public items reachable from it
+fn collect(item: DocRef<'_, Item>, stats: &mut CrateStats, visited: &mut HashSet<Id>) {
+    if !visited.insert(item.id) {
+        return;
+    }
+    stats.add(item);
+    for child in item.child_items() {
+        if is_public_api(item, child) {
+            collect(child, stats, visited);
+        }
+    }
+}
+
+/// Public items by kind, most numerous first, with their documentation coverage
+fn items_table<'a>(stats: &CrateStats) -> DocumentNode<'a> {
+    let mut kinds: Vec<_> = stats.kinds.iter().collect();
+    kinds.sort_by(|(a_kind, a), (b_kind, b)| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| format!("{a_kind:?}").cmp(&format!("{b_kind:?}")))
+    });
+
+    let row = |label: Span<'a>, kind: KindStats| {
+        vec![
+            TableCell::from_span(label),
+            TableCell::from_span(Span::plain(kind.count.to_string())),
+            TableCell::from_span(Span::plain(kind.documented.to_string())),
+            TableCell::from_span(Span::plain(coverage(kind))),
+        ]
+    };
+
+    let mut rows: Vec<_> = kinds
+        .into_iter()
+        .map(|(kind, stats)| row(Span::plain(format!("{kind:?}")), *stats))
+        .collect();
+    rows.push(row(Span::strong("Total"), stats.totals()));
+
+    DocumentNode::table(
+        Some(
+            ["Kind", "Public", "Documented", "Coverage"]
+                .into_iter()
+                .map(|heading| TableCell::from_span(Span::strong(heading)))
+                .collect(),
+        ),
+        rows,
+    )
+}
+
+fn summary_table<'a>(stats: &CrateStats) -> DocumentNode<'a> {
+    let rows = [
+        ("Impl blocks", stats.impls),
+        ("Trait impls", stats.trait_impls),
+        ("Unsafe functions", stats.unsafe_functions),
+        ("Documentation (characters)", stats.doc_length),
+    ]
+    .into_iter()
+    .map(|(label, value)| {
+        vec![
+            TableCell::from_span(Span::plain(label)),
+            TableCell::from_span(Span::plain(value.to_string())),
+        ]
+    })
+    .collect();
+
+    DocumentNode::table(None, rows)
+}
+
+fn coverage(kind: KindStats) -> String {
+    if kind.count == 0 {
+        return "-".to_string();
+    }
+    format!("{:.0}%", 100.0 * kind.documented as f64 / kind.count as f64)
+}
+
+fn error<'a>(message: String) -> (Document<'a>, bool) {
+    (
+        Document::from(vec![DocumentNode::paragraph(vec![Span::plain(message)])]),
+        true,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn totals_and_coverage() {
+        let stats = CrateStats {
+            kinds: HashMap::from([
+                (
+                    ItemKind::Struct,
+                    KindStats {
+                        count: 3,
+                        documented: 2,
+                    },
+                ),
+                (
+                    ItemKind::Function,
+                    KindStats {
+                        count: 1,
+                        documented: 1,
+                    },
+                ),
+            ]),
+            ..CrateStats::default()
+        };
+        let totals = stats.totals();
+        assert_eq!(
+            totals,
+            KindStats {
+                count: 4,
+                documented: 3
+            }
+        );
+        assert_eq!(coverage(totals), "75%");
+        assert_eq!(coverage(KindStats::default()), "-");
+    }
+}
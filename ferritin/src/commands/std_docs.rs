@@ -0,0 +1,74 @@
+use ferritin_common::sources::StdSource;
+
+use crate::request::Request;
+use crate::styled_string::{Document, DocumentNode, Span};
+
+/// Make the standard library's documentation available, by installing rustup's `rust-docs-json`
+/// component, building it from the `rust-src` component, or downloading prebuilt docs for the
+/// default toolchain
+///
+/// Without a nightly toolchain, prebuilt docs are downloaded unless building was asked for.
+pub(crate) fn execute<'a>(
+    request: &'a Request,
+    build: bool,
+    download: bool,
+) -> (Document<'a>, bool) {
+    if !build
+        && !download
+        && let Some(std_source) = request.std_source()
+    {
+        return paragraph(
+            format!(
+                "Standard library docs for {} are already available at {}",
+                std_source.rustc_version(),
+                std_source.docs_path().display()
+            ),
+            false,
+        );
+    }
+
+    let toolchain = request.toolchain();
+    let result = if build {
+        StdSource::build_from_source(toolchain)
+    } else if download || !StdSource::has_toolchain(toolchain) {
+        StdSource::download_prebuilt()
+    } else {
+        StdSource::install_component(toolchain)
+    };
+
+    match result {
+        Ok(std_source) => paragraph(
+            format!(
+                "Standard library docs for {} are now available at {}",
+                std_source.rustc_version(),
+                std_source.docs_path().display()
+            ),
+            false,
+        ),
+        Err(e) => paragraph(
+            format!("Could not provide standard library docs: {e}"),
+            true,
+        ),
+    }
+}
+
+/// Advice for when a path names a std crate but no std docs are installed
+pub(crate) fn missing_docs_hint<'a>() -> DocumentNode<'a> {
+    DocumentNode::paragraph(vec![
+        Span::plain("The standard library's documentation isn't installed. Run "),
+        Span::inline_code("ferritin std-docs"),
+        Span::plain(
+            " to add rustup's rust-docs-json component (or download it, without a nightly \
+             toolchain), or ",
+        ),
+        Span::inline_code("ferritin std-docs --build"),
+        Span::plain(" to build it from the rust-src component."),
+    ])
+}
+
+fn paragraph<'a>(message: String, is_error: bool) -> (Document<'a>, bool) {
+    (
+        Document::from(vec![DocumentNode::paragraph(vec![Span::plain(message)])]),
+        is_error,
+    )
+}
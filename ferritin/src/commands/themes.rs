@@ -0,0 +1,104 @@
+use std::fmt::{self, Write};
+
+use crate::render_context::RenderContext;
+use crate::renderer;
+use crate::request::Request;
+use crate::styled_string::{Document, DocumentNode, HeadingLevel, ListItem, Span};
+
+use super::get;
+
+/// The document to preview in each theme: the documentation for `preview`, or a short sample
+/// that uses every kind of styled text
+pub(crate) fn execute<'a>(request: &'a Request, preview: Option<&str>) -> (Document<'a>, bool) {
+    match preview {
+        Some(path) => {
+            let (document, is_error, _) = get::execute(request, path, false, false);
+            (document, is_error)
+        }
+        None => (sample_document(), false),
+    }
+}
+
+/// Render `document` once per available theme, each under a heading naming the theme
+pub(crate) fn render_each(
+    document: &Document,
+    render_context: &mut RenderContext,
+    output: &mut impl Write,
+) -> fmt::Result {
+    for (index, theme_name) in RenderContext::available_themes().iter().enumerate() {
+        if render_context.set_theme_name(theme_name).is_err() {
+            continue;
+        }
+        if index > 0 {
+            writeln!(output)?;
+        }
+
+        let heading = Document::with_nodes(vec![DocumentNode::heading(
+            HeadingLevel::Title,
+            vec![Span::plain("Theme: "), Span::strong(theme_name.as_str())],
+        )]);
+        renderer::render(&heading, render_context, output)?;
+        writeln!(output)?;
+        renderer::render(document, render_context, output)?;
+    }
+    Ok(())
+}
+
+fn sample_document() -> Document<'static> {
+    Document::with_nodes(vec![
+        DocumentNode::heading(
+            HeadingLevel::Title,
+            vec![Span::plain("Item: "), Span::type_name("Parser")],
+        ),
+        DocumentNode::generated_code(vec![
+            Span::keyword("pub"),
+            Span::plain(" "),
+            Span::keyword("fn"),
+            Span::plain(" "),
+            Span::function_name("parse"),
+            Span::punctuation("<"),
+            Span::lifetime("'a"),
+            Span::punctuation(", "),
+            Span::generic("T"),
+            Span::punctuation(">("),
+            Span::field_name("input"),
+            Span::punctuation(": "),
+            Span::operator("&"),
+            Span::lifetime("'a"),
+            Span::plain(" "),
+            Span::type_name("str"),
+            Span::punctuation(") "),
+            Span::operator("->"),
+            Span::plain(" "),
+            Span::type_name("Result"),
+            Span::punctuation("<"),
+            Span::generic("T"),
+            Span::punctuation(">"),
+        ]),
+        DocumentNode::paragraph(vec![
+            Span::plain("Parses "),
+            Span::inline_code("input"),
+            Span::plain(" as "),
+            Span::inline_rust_code("T"),
+            Span::plain(", which is "),
+            Span::strong("strongly"),
+            Span::plain(" preferred over the "),
+            Span::strikethrough("deprecated"),
+            Span::plain(" "),
+            Span::emphasis("legacy"),
+            Span::plain(" parser."),
+        ]),
+        DocumentNode::list(vec![
+            ListItem::new(vec![DocumentNode::paragraph(vec![Span::plain(
+                "Whitespace is trimmed",
+            )])]),
+            ListItem::new(vec![DocumentNode::paragraph(vec![Span::plain(
+                "Errors carry the byte offset",
+            )])]),
+        ]),
+        DocumentNode::code_block(
+            Some("rust"),
+            "// Parse a number\nlet value: u32 = parse(\"42\")?;\nassert_eq!(value, 42);",
+        ),
+    ])
+}
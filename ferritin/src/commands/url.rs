@@ -0,0 +1,54 @@
+use crate::generate_docsrs_url::generate_browsable_url;
+use crate::request::Request;
+use crate::styled_string::{Document, DocumentNode, Span, TuiAction};
+
+/// Print the documentation URL for an item
+pub(crate) fn execute<'a>(request: &'a Request, path: &str) -> (Document<'a>, bool) {
+    match resolve_url(request, path) {
+        Ok(url) => (url_document(String::new(), url), false),
+        Err(document) => (document, true),
+    }
+}
+
+/// Open an item's documentation page in the default browser
+pub(crate) fn open<'a>(request: &'a Request, path: &str) -> (Document<'a>, bool) {
+    let url = match resolve_url(request, path) {
+        Ok(url) => url,
+        Err(document) => return (document, true),
+    };
+
+    match webbrowser::open(&url) {
+        Ok(()) => (url_document("Opened ".into(), url), false),
+        Err(error) => {
+            log::error!("Failed to open {url}: {error}");
+            (url_document("Could not open ".into(), url), true)
+        }
+    }
+}
+
+fn resolve_url<'a>(request: &'a Request, path: &str) -> Result<String, Document<'a>> {
+    let mut suggestions = vec![];
+    log::info!("Getting URL for {path}...");
+
+    match request.resolve_path(path, &mut suggestions) {
+        Some(item) => Ok(generate_browsable_url(item)),
+        None => {
+            let mut message = format!("Could not find '{path}'");
+            if let Some(suggestion) = suggestions.first() {
+                message.push_str(&format!(" (did you mean '{}'?)", suggestion.path()));
+            }
+            Err(Document::from(vec![DocumentNode::paragraph(vec![
+                Span::plain(message),
+            ])]))
+        }
+    }
+}
+
+fn url_document<'a>(prefix: String, url: String) -> Document<'a> {
+    let mut spans = vec![];
+    if !prefix.is_empty() {
+        spans.push(Span::plain(prefix));
+    }
+    spans.push(Span::plain(url.clone()).with_action(TuiAction::OpenUrl(url.into())));
+    Document::from(vec![DocumentNode::paragraph(spans)])
+}
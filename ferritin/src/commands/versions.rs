@@ -0,0 +1,71 @@
+use crate::request::Request;
+use crate::styled_string::{Document, DocumentNode, HeadingLevel, ListItem, Span};
+
+/// List the versions of a crate published on crates.io, newest first
+///
+/// Each version links to its documentation, and the version the workspace depends on is marked.
+pub(crate) fn execute<'a>(request: &'a Request, crate_name: &str) -> (Document<'a>, bool) {
+    let Some(docsrs_source) = request.docsrs_source() else {
+        return error(format!(
+            "Can't list versions of '{crate_name}' without access to docs.rs"
+        ));
+    };
+    let Some(versions) = docsrs_source.versions(crate_name) else {
+        return error(format!("Could not find '{crate_name}' on crates.io"));
+    };
+
+    let locked_version = request
+        .local_source()
+        .and_then(|local| local.get_dependency_version(crate_name));
+
+    let items = versions
+        .iter()
+        .map(|published| {
+            let version = published.version();
+            let mut spans =
+                vec![Span::plain(version.to_string()).with_path(format!("{crate_name}@{version}"))];
+            if let Some(date) = published
+                .created_at()
+                .and_then(|created_at| created_at.split('T').next())
+            {
+                spans.push(Span::comment(format!(" {date}")));
+            }
+            if published.yanked() {
+                spans.push(Span::plain(" "));
+                spans.push(Span::strikethrough("yanked"));
+            }
+            if locked_version == Some(version) {
+                spans.push(Span::strong(" (used by this workspace)"));
+            }
+            ListItem::new(vec![DocumentNode::paragraph(spans)])
+        })
+        .collect();
+
+    (
+        Document::from(vec![
+            DocumentNode::heading(
+                HeadingLevel::Title,
+                vec![
+                    Span::plain("Versions of "),
+                    Span::type_name(crate_name.to_string()),
+                ],
+            ),
+            DocumentNode::paragraph(vec![
+                Span::plain("Select one with "),
+                Span::inline_code(format!("{crate_name}@<version>")),
+                Span::plain(" or a requirement such as "),
+                Span::inline_code(format!("{crate_name}@^1.2")),
+                Span::plain("."),
+            ]),
+            DocumentNode::list(items),
+        ]),
+        false,
+    )
+}
+
+fn error<'a>(message: String) -> (Document<'a>, bool) {
+    (
+        Document::from(vec![DocumentNode::paragraph(vec![Span::plain(message)])]),
+        true,
+    )
+}
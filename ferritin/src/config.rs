@@ -0,0 +1,235 @@
+//! User configuration file
+//!
+//! Read from `$FERRITIN_CONFIG`, or `ferritin/config.toml` in `$XDG_CONFIG_HOME` (falling back
+//! to `~/.config`). Unknown sections and keys are errors, so that a misspelled setting is
+//! reported rather than silently ignored.
+
+use ferritin_common::sources::DocCommand;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub(crate) enum ConfigError {
+    #[error("Failed to read config file '{0}': {1}")]
+    Read(String, io::Error),
+    #[error("Failed to parse config file '{0}': {1}")]
+    Parse(String, toml::de::Error),
+    #[error("Config [{section}] {key}: {message}")]
+    Invalid {
+        section: String,
+        key: String,
+        message: String,
+    },
+}
+
+/// Parsed configuration, with every section defaulting to empty
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Config {
+    /// `[colors]`: a color for each span style, by style name
+    pub(crate) colors: BTreeMap<String, ColorValue>,
+    pub(crate) images: ImagesConfig,
+    pub(crate) search: SearchConfig,
+    pub(crate) build: BuildConfig,
+}
+
+/// A color in the `[colors]` section: an ANSI palette index, or a name or `#rrggbb` string
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ColorValue {
+    Index(u8),
+    Text(String),
+}
+
+/// The `[images]` section
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ImagesConfig {
+    /// Draw images inline in terminals that support it
+    pub(crate) inline: bool,
+}
+
+/// The `[search]` section, each ranking parameter None to keep its default
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct SearchConfig {
+    pub(crate) k1: Option<f32>,
+    pub(crate) b: Option<f32>,
+    pub(crate) name_weight: Option<usize>,
+    pub(crate) authority_boost: Option<f32>,
+}
+
+/// The `[build]` section
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct BuildConfig {
+    /// Extra flags for rustdoc
+    pub(crate) rustdoc_flags: Option<RustdocFlags>,
+    /// `[build.packages]`: the command each listed package is documented with
+    pub(crate) packages: BTreeMap<String, DocCommand>,
+}
+
+/// `rustdoc_flags`: an array with a flag in each string, or a single string of flags separated
+/// by whitespace
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged, expecting = "a string or an array of strings")]
+pub(crate) enum RustdocFlags {
+    List(Vec<String>),
+    Words(String),
+}
+
+impl RustdocFlags {
+    pub(crate) fn to_vec(&self) -> Vec<String> {
+        match self {
+            Self::List(flags) => flags.clone(),
+            Self::Words(flags) => flags.split_whitespace().map(String::from).collect(),
+        }
+    }
+}
+
+impl Config {
+    /// Load the user's config file, or an empty config if there isn't one
+    pub(crate) fn load() -> Result<Self, ConfigError> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents)
+                .map_err(|e| ConfigError::Parse(path.display().to_string(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ConfigError::Read(path.display().to_string(), e)),
+        }
+    }
+
+    /// Where the config file is read from
+    pub(crate) fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("FERRITIN_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let config_dir = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+        Some(config_dir.join("ferritin").join("config.toml"))
+    }
+
+    /// Parse the contents of a config file, whose errors name the line they're on
+    pub(crate) fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sections_and_values() {
+        let config = Config::parse(
+            r##"
+            # Semantic colors
+            [colors]
+            keyword = "#ff8800" # orange
+            lifetime = 13
+            "type name" = "bright_blue"
+
+            [images]
+            inline = true
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.colors.into_iter().collect::<Vec<_>>(),
+            [
+                (
+                    "keyword".to_string(),
+                    ColorValue::Text("#ff8800".to_string())
+                ),
+                ("lifetime".to_string(), ColorValue::Index(13)),
+                (
+                    "type name".to_string(),
+                    ColorValue::Text("bright_blue".to_string())
+                ),
+            ]
+        );
+        assert!(config.images.inline);
+        assert!(!Config::parse("").unwrap().images.inline);
+    }
+
+    #[test]
+    fn reports_errors_with_their_line() {
+        let error = Config::parse("[colors]\nkeyword = orange\n").unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{error}");
+
+        let error = Config::parse("[images]\n\ninline = yes\n").unwrap_err();
+        assert!(error.to_string().contains("line 3"), "{error}");
+
+        assert!(Config::parse("[colors\n").is_err());
+        assert!(Config::parse("[images]\nshown = true\n").is_err());
+        assert!(Config::parse("[unknown]\n").is_err());
+    }
+
+    #[test]
+    fn parses_numbers() {
+        let config = Config::parse(
+            "[search]
+k1 = 1.5
+name_weight = 10
+",
+        )
+        .unwrap();
+        assert_eq!(config.search.k1, Some(1.5));
+        assert_eq!(config.search.b, None);
+        assert_eq!(config.search.name_weight, Some(10));
+        assert!(Config::parse("[search]\nname_weight = \"ten\"\n").is_err());
+    }
+
+    #[test]
+    fn parses_strings() {
+        let config = Config::parse(
+            r#"
+[build]
+rustdoc_flags = "--cfg \"docsrs\""
+
+[build.packages]
+my-crate = "rustdoc"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.build.rustdoc_flags,
+            Some(RustdocFlags::Words("--cfg \"docsrs\"".to_string()))
+        );
+        assert_eq!(
+            config.build.packages.into_iter().collect::<Vec<_>>(),
+            [("my-crate".to_string(), DocCommand::Rustdoc)]
+        );
+        assert!(Config::parse("[build.packages]\nmy-crate = \"cargo\"\n").is_err());
+    }
+
+    #[test]
+    fn parses_rustdoc_flags() {
+        let flags = |contents: &str| {
+            Config::parse(contents)
+                .map(|config| config.build.rustdoc_flags.map(|flags| flags.to_vec()))
+        };
+        assert_eq!(
+            flags("[build]\nrustdoc_flags = [\"--cfg\", \"docsrs\"]\n").unwrap(),
+            Some(vec!["--cfg".to_string(), "docsrs".to_string()])
+        );
+        assert_eq!(
+            flags("[build]\nrustdoc_flags = \"--cfg  docsrs\"\n").unwrap(),
+            Some(vec!["--cfg".to_string(), "docsrs".to_string()])
+        );
+        assert_eq!(flags("").unwrap(), None);
+
+        let error = flags("[build]\nrustdoc_flags = [\"--cfg\", 1]\n").unwrap_err();
+        assert!(error.to_string().contains("rustdoc_flags"), "{error}");
+        assert!(error.to_string().contains("line 2"), "{error}");
+    }
+}
@@ -0,0 +1,135 @@
+//! Rendering documentation from other programs
+//!
+//! The `ferritin` binary is the usual way in, but tools that show documentation themselves
+//! (cargo subcommands, editor plugins) can render items the same way without running it.
+
+use ferritin_common::{DocRef, Navigator, rustdoc_types::Item};
+use fieldwork::Fieldwork;
+
+use crate::{
+    format_context::FormatContext,
+    render_context::RenderContext,
+    renderer::{self, OutputMode},
+    request::Request,
+    styled_string::Document,
+};
+
+/// The form of rendered output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// ANSI escape codes for terminal colors and styles
+    Tty,
+    /// Plain text, as ferritin prints when piped
+    #[default]
+    Plain,
+    /// CommonMark
+    Markdown,
+}
+
+impl From<OutputFormat> for OutputMode {
+    fn from(format: OutputFormat) -> Self {
+        match format {
+            OutputFormat::Tty => OutputMode::Tty,
+            OutputFormat::Plain => OutputMode::Plain,
+            OutputFormat::Markdown => OutputMode::Markdown,
+        }
+    }
+}
+
+/// What to include in rendered documentation and how to lay it out
+#[derive(Debug, Clone, Fieldwork)]
+#[fieldwork(get, with)]
+pub struct RenderOptions {
+    /// The form of the output
+    format: OutputFormat,
+    /// Width to wrap and lay out to, in columns
+    width: usize,
+    /// Whether to include `#[doc(hidden)]` items in listings
+    show_hidden: bool,
+    /// Whether to include the item's source code
+    include_source: bool,
+    /// Whether to include the full documentation of a module's items, not just a listing
+    recursive: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::default(),
+            width: 80,
+            show_hidden: false,
+            include_source: false,
+            recursive: false,
+        }
+    }
+}
+
+/// Renders items from a [`Navigator`] as ferritin would show them
+///
+/// ```no_run
+/// use ferritin::{OutputFormat, RenderOptions, Renderer};
+/// use ferritin_common::{Navigator, sources::StdSource};
+///
+/// let renderer = Renderer::new(Navigator::default().with_std_source(StdSource::from_rustup()))
+///     .with_options(RenderOptions::default().with_format(OutputFormat::Markdown));
+/// let mut suggestions = vec![];
+/// if let Some(item) = renderer.navigator().resolve_path("std::vec::Vec", &mut suggestions) {
+///     println!("{}", renderer.render_item(item));
+/// }
+/// ```
+pub struct Renderer {
+    request: Request,
+    options: RenderOptions,
+}
+
+impl Renderer {
+    pub fn new(navigator: Navigator) -> Self {
+        Self {
+            request: Request::new(navigator, FormatContext::new()),
+            options: RenderOptions::default(),
+        }
+    }
+
+    /// The navigator to resolve items with
+    pub fn navigator(&self) -> &Navigator {
+        &self.request
+    }
+
+    /// The options items are rendered with
+    pub fn options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    /// Render items with `options` from now on
+    ///
+    /// This takes `&mut self` so that it can't change how an item is formatted while it's being
+    /// rendered, which means no item from [`Renderer::navigator`] can be held across it.
+    pub fn set_options(&mut self, options: RenderOptions) -> &mut Self {
+        self.request
+            .format_context()
+            .set_show_hidden(options.show_hidden)
+            .set_include_source(options.include_source)
+            .set_recursive(options.recursive);
+        self.options = options;
+        self
+    }
+
+    /// Builder method to render items with `options`
+    pub fn with_options(mut self, options: RenderOptions) -> Self {
+        self.set_options(options);
+        self
+    }
+
+    /// Render an item's documentation page
+    pub fn render_item<'a>(&'a self, item: DocRef<'a, Item>) -> String {
+        let document = Document::from(self.request.format_item(item));
+        let render_context = RenderContext::new()
+            .with_output_mode(self.options.format.into())
+            .with_terminal_width(self.options.width);
+
+        let mut output = String::new();
+        // Writing to a String doesn't fail
+        let _ = renderer::render(&document, &render_context, &mut output);
+        output
+    }
+}
@@ -2,8 +2,12 @@ use std::borrow::Cow;
 
 use super::*;
 use crate::markdown::MarkdownRenderer;
-use crate::styled_string::{DocumentNode, LinkTarget, TruncationLevel};
+use crate::styled_string::{
+    DocumentNode, LinkTarget, TruncationLevel, TuiAction, split_identifiers,
+};
+use ferritin_common::std_builtins::std_builtin_path;
 use rustdoc_types::ItemKind;
+use std::collections::HashSet;
 
 /// Information about documentation text with truncation details
 #[derive(Debug, Clone, Default)]
@@ -41,8 +45,107 @@ impl Request {
         item: DocRef<'a, Item>,
         markdown: &str,
     ) -> Vec<DocumentNode<'a>> {
-        MarkdownRenderer::render_with_resolver(markdown, |url| -> Option<LinkTarget<'a>> {
-            self.extract_link_target(item, url)
+        let mut nodes =
+            MarkdownRenderer::render_with_resolver(markdown, |url| -> Option<LinkTarget<'a>> {
+                self.extract_link_target(item, url)
+            });
+        self.link_code_blocks(item, &mut nodes);
+        nodes
+    }
+
+    /// Make identifiers in Rust code examples navigable
+    ///
+    /// Identifiers are resolved against the item's intra-doc links, then against items with a
+    /// unique name in its crate's path map. Like intra-doc links, this never loads other crates.
+    fn link_code_blocks<'a>(&'a self, origin: DocRef<'a, Item>, nodes: &mut [DocumentNode<'a>]) {
+        let mut identifiers = HashSet::new();
+        for_each_rust_code_block(nodes, &mut |code, _| {
+            identifiers.extend(
+                split_identifiers(code)
+                    .into_iter()
+                    .filter(|(run, is_ident)| *is_ident && !run.starts_with(char::is_numeric))
+                    .map(|(run, _)| run.to_string()),
+            );
+        });
+
+        if identifiers.is_empty() {
+            return;
+        }
+
+        let mut targets: HashMap<String, TuiAction<'a>> = HashMap::new();
+        for identifier in &identifiers {
+            let link_id = origin
+                .links
+                .get(identifier)
+                .or_else(|| origin.links.get(&format!("`{identifier}`")));
+            if let Some(target) = link_id.and_then(|id| self.link_id_target(origin, id)) {
+                targets.insert(identifier.clone(), target);
+            }
+        }
+
+        // Fall back to uniquely-named items in the crate's path map, preferring local items
+        let crate_docs = origin.crate_docs();
+        let mut by_name: HashMap<&str, Option<&Id>> = HashMap::new();
+        for (id, summary) in crate_docs.paths() {
+            let Some(name) = summary.path.last() else {
+                continue;
+            };
+            if !identifiers.contains(name)
+                || targets.contains_key(name)
+                || !is_linkable_code_kind(summary.kind)
+            {
+                continue;
+            }
+
+            by_name
+                .entry(name.as_str())
+                .and_modify(|existing| {
+                    let existing_is_local = existing
+                        .is_some_and(|existing_id| crate_docs.paths()[existing_id].crate_id == 0);
+                    match (existing_is_local, summary.crate_id == 0) {
+                        (true, false) => {}
+                        (false, true) => *existing = Some(id),
+                        _ => *existing = None,
+                    }
+                })
+                .or_insert(Some(id));
+        }
+
+        for (name, id) in by_name {
+            if let Some(target) = id.and_then(|id| self.link_id_target(origin, id)) {
+                targets.insert(name.to_string(), target);
+            }
+        }
+
+        if targets.is_empty() {
+            return;
+        }
+
+        for_each_rust_code_block(nodes, &mut |code, links| {
+            for (run, is_ident) in split_identifiers(code) {
+                if is_ident && let Some(target) = targets.get(run) {
+                    links.insert(Cow::Owned(run.to_string()), target.clone());
+                }
+            }
+        });
+    }
+
+    /// Build a navigation action for an item id from the origin's crate, without loading
+    /// external crates
+    fn link_id_target<'a>(&'a self, origin: DocRef<'a, Item>, id: &Id) -> Option<TuiAction<'a>> {
+        if let Some(item) = origin.get(id) {
+            return Some(TuiAction::Navigate {
+                doc_ref: item,
+                url: None,
+                anchor: None,
+            });
+        }
+
+        let summary = origin.crate_docs().paths().get(id)?;
+        Some(TuiAction::NavigateToPath {
+            path: Cow::Owned(summary.path.join("::")),
+            url: None,
+            anchor: None,
         })
     }
 
@@ -56,9 +159,9 @@ impl Request {
         origin: DocRef<'a, Item>,
         url: &str,
     ) -> Option<LinkTarget<'a>> {
-        // Handle fragment-only links
+        // Fragment-only links point at a heading in the origin item's own docs
         if url.starts_with('#') {
-            return None; // Keep as-is
+            return Some(LinkTarget::Resolved(origin));
         }
 
         // Handle external URLs
@@ -93,7 +196,8 @@ impl Request {
         let link_id = origin
             .links
             .get(path)
-            .or_else(|| origin.links.get(&format!("`{}`", path)));
+            .or_else(|| origin.links.get(&format!("`{}`", path)))
+            .or_else(|| origin.links.get(url));
 
         if let Some(link_id) = link_id {
             log::trace!("  ✓ Found in origin.links with ID {:?}", link_id);
@@ -109,34 +213,116 @@ impl Request {
 
             log::trace!("  → Not in same crate index, checking external paths");
             // It's in an external crate - extract path from item_summary without loading
-            if let Some(item_summary) = origin.crate_docs().paths.get(link_id) {
+            if let Some(item_summary) = origin.crate_docs().paths().get(link_id) {
                 log::trace!(
                     "  ✓ Found in paths map: {:?}, kind: {:?}",
                     item_summary.path,
                     item_summary.kind
                 );
-                let full_path = item_summary.path.join("::");
+                // Primitives and keywords share names with modules (`std::str`), so they need a
+                // discriminator to resolve to the right page
+                let full_path = match (item_summary.kind, item_summary.path.as_slice()) {
+                    (ItemKind::Primitive, [krate, .., name]) => format!("{krate}::prim@{name}"),
+                    (ItemKind::Keyword, [krate, .., name]) => format!("{krate}::keyword@{name}"),
+                    _ => item_summary.path.join("::"),
+                };
                 return Some(LinkTarget::Path(Cow::Owned(full_path)));
             }
         }
 
-        // Fallback: try to resolve path relative to current crate
-        // Handle "crate::", "self::", and absolute paths
+        // Primitive types and keywords are documented in std
+        if let Some(builtin_path) =
+            std_builtin_path(path.trim_end_matches("()").trim_end_matches('!'))
+        {
+            log::trace!("  → Primitive or keyword: '{}'", builtin_path);
+            return Some(LinkTarget::Path(Cow::Owned(builtin_path)));
+        }
+
+        // Fallback: qualify the path relative to the origin's scope
         log::trace!("  ✗ Not found in links map, using fallback for '{}'", path);
-        let qualified_path = if let Some(rest) = path.strip_prefix("crate::") {
-            format!("{}::{}", origin.crate_docs().name(), rest)
-        } else if let Some(rest) = path.strip_prefix("self::") {
-            format!("{}::{}", origin.crate_docs().name(), rest)
-        } else if path.contains("::") {
-            path.to_string()
-        } else {
-            format!("{}::{}", origin.crate_docs().name(), path)
-        };
+        let qualified_path = self.qualify_link_path(origin, strip_disambiguator(path));
 
         log::trace!("  → Qualified path: '{}'", qualified_path);
         Some(LinkTarget::Path(Cow::Owned(qualified_path)))
     }
 
+    /// Qualify an intra-doc link path the way rustdoc would resolve it from the origin
+    ///
+    /// `crate::`, `self::` and `super::` are relative to the module containing the origin. A
+    /// leading segment that some link in the crate already resolves, such as the alias of a
+    /// renamed import (`use std::vec::Vec as Vector`), is replaced by its target's path, so
+    /// `Vector::push` becomes `alloc::vec::Vec::push`.
+    fn qualify_link_path(&self, origin: DocRef<'_, Item>, path: &str) -> String {
+        let crate_name = origin.crate_docs().name();
+        let mut module: Vec<&str> = match origin.summary() {
+            Some(summary) if summary.path.len() > 1 => summary.path[..summary.path.len() - 1]
+                .iter()
+                .map(String::as_str)
+                .collect(),
+            _ => vec![crate_name],
+        };
+
+        let mut segments = path.split("::").peekable();
+        let first = segments.peek().copied().unwrap_or_default();
+        if first == "crate" {
+            segments.next();
+            module.truncate(1);
+        } else if first == "self" {
+            segments.next();
+        } else if first == "super" {
+            while segments.next_if_eq(&"super").is_some() {
+                if module.len() > 1 {
+                    module.pop();
+                }
+            }
+        } else if let Some(prefix) = self.resolve_link_prefix(origin, first) {
+            segments.next();
+            return std::iter::once(prefix.as_str())
+                .chain(segments)
+                .collect::<Vec<_>>()
+                .join("::");
+        } else if path.contains("::") {
+            return path.to_string();
+        }
+
+        module
+            .into_iter()
+            .chain(segments)
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    /// Resolve the first segment of a link path through the links rustdoc recorded for the
+    /// origin or, failing that, the shortest path among the crate's own items in its paths table
+    ///
+    /// Other crates' items are only reached through links, since the paths table also names
+    /// the internals that the crate's dependencies re-export (`hashbrown::HashMap` for std's).
+    fn resolve_link_prefix(&self, origin: DocRef<'_, Item>, name: &str) -> Option<String> {
+        let crate_docs = origin.crate_docs();
+        let linked = [name.to_string(), format!("`{name}`")]
+            .iter()
+            .find_map(|key| origin.links.get(key));
+
+        if let Some(id) = linked {
+            if let Some(item) = origin.get(id) {
+                return Some(self.get_item_full_path(item));
+            }
+            return crate_docs
+                .paths()
+                .get(id)
+                .map(|summary| summary.path.join("::"));
+        }
+
+        crate_docs
+            .paths()
+            .values()
+            .filter(|summary| {
+                summary.crate_id == 0 && summary.path.last().is_some_and(|last| last == name)
+            })
+            .min_by_key(|summary| (summary.path.len(), &summary.path))
+            .map(|summary| summary.path.join("::"))
+    }
+
     /// Parse a relative HTML path to an item path for navigation
     ///
     /// Examples:
@@ -228,7 +414,11 @@ impl Request {
     }
 
     /// Generate a docs.rs URL from a path and ItemKind
-    fn generate_url_from_path_and_kind(&self, path: &str, kind: rustdoc_types::ItemKind) -> String {
+    pub(super) fn generate_url_from_path_and_kind(
+        &self,
+        path: &str,
+        kind: rustdoc_types::ItemKind,
+    ) -> String {
         let parts: Vec<&str> = path.split("::").collect();
         if parts.is_empty() {
             return String::new();
@@ -452,3 +642,121 @@ impl Request {
         lines[..cutoff].join("\n")
     }
 }
+
+/// Item kinds that identifiers in code examples may link to. Modules and fields are left
+/// out, since their names commonly collide with local variables.
+fn is_linkable_code_kind(kind: ItemKind) -> bool {
+    matches!(
+        kind,
+        ItemKind::Struct
+            | ItemKind::Enum
+            | ItemKind::Union
+            | ItemKind::Trait
+            | ItemKind::TraitAlias
+            | ItemKind::Function
+            | ItemKind::TypeAlias
+            | ItemKind::Constant
+            | ItemKind::Static
+            | ItemKind::Macro
+    )
+}
+
+/// Visit the code and link map of every Rust code block in `nodes`, recursively
+fn for_each_rust_code_block<'a>(
+    nodes: &mut [DocumentNode<'a>],
+    f: &mut impl FnMut(&str, &mut HashMap<Cow<'a, str>, TuiAction<'a>>),
+) {
+    for node in nodes {
+        match node {
+            DocumentNode::CodeBlock {
+                lang, code, links, ..
+            } if lang.as_deref().is_none_or(|lang| lang == "rust") => f(code, links),
+            DocumentNode::Section { nodes, .. }
+            | DocumentNode::BlockQuote { nodes }
+            | DocumentNode::TruncatedBlock { nodes, .. }
+            | DocumentNode::Conditional { nodes, .. } => for_each_rust_code_block(nodes, f),
+            DocumentNode::List { items } => {
+                for item in items {
+                    for_each_rust_code_block(&mut item.content, f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Strip a rustdoc disambiguator from a link path: a `kind@` prefix, or the `()` and `!`
+/// suffixes marking functions and macros
+fn strip_disambiguator(path: &str) -> &str {
+    let path = path.split_once('@').map_or(path, |(_, rest)| rest);
+    path.trim_end_matches("()").trim_end_matches('!')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_context::FormatContext;
+    use ferritin_common::{Navigator, sources::LocalSource};
+    use std::path::PathBuf;
+
+    fn fixture_request() -> Request {
+        let fixture_crate =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../tests/fixture-crate");
+        Request::new(
+            Navigator::default().with_local_source(LocalSource::load(&fixture_crate).ok()),
+            FormatContext::new(),
+        )
+    }
+
+    fn qualify(request: &Request, origin: &str, link: &str) -> String {
+        let origin = request.resolve_path(origin, &mut vec![]).unwrap();
+        request.qualify_link_path(origin, link)
+    }
+
+    #[test]
+    fn link_prefixes_resolve_through_the_origins_links() {
+        let request = fixture_request();
+        assert_eq!(
+            qualify(&request, "crate::TestStruct", "Vector::push"),
+            "alloc::vec::Vec::push"
+        );
+    }
+
+    #[test]
+    fn link_prefixes_resolve_through_the_crates_paths() {
+        let request = fixture_request();
+        assert_eq!(
+            qualify(&request, "crate::GenericStruct", "TestStruct::new"),
+            "fixture_crate::TestStruct::new"
+        );
+        assert_eq!(
+            qualify(&request, "crate::GenericStruct", "SubStruct::double"),
+            "fixture_crate::submodule::SubStruct::double"
+        );
+        assert_eq!(
+            qualify(&request, "crate::GenericStruct", "HashMap::new"),
+            "HashMap::new"
+        );
+    }
+
+    #[test]
+    fn relative_link_paths_resolve_from_the_origins_module() {
+        let request = fixture_request();
+        assert_eq!(
+            qualify(&request, "crate::submodule::SubStruct", "super::TestStruct"),
+            "fixture_crate::TestStruct"
+        );
+        assert_eq!(
+            qualify(
+                &request,
+                "crate::submodule::SubStruct",
+                "self::sub_function"
+            ),
+            "fixture_crate::submodule::sub_function"
+        );
+        assert_eq!(
+            qualify(&request, "crate::submodule::SubStruct", "crate::TestEnum"),
+            "fixture_crate::TestEnum"
+        );
+    }
+}
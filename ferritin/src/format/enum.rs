@@ -1,5 +1,14 @@
 use super::*;
 use crate::styled_string::{DocumentNode, ListItem, Span};
+use rustdoc_types::{Attribute, ReprKind};
+
+/// The discriminant of an enum variant, as shown next to it in the enum signature
+enum VariantDiscriminant<'a> {
+    /// Written in source as `Variant = expr`, which evaluates to `value`
+    Explicit { expr: &'a str, value: &'a str },
+    /// Assigned by the compiler, one more than the previous variant's value
+    Implicit(i128),
+}
 
 impl Request {
     /// Format an enum
@@ -31,16 +40,21 @@ impl Request {
         code_spans.push(Span::punctuation("{"));
         code_spans.push(Span::plain("\n"));
 
+        let discriminants = self.enum_discriminants(item, enum_data);
+
         // Format variants
         for variant in item.id_iter(&enum_data.item().variants) {
             if let ItemEnum::Variant(variant_enum) = &variant.item().inner {
                 let variant_name = variant.name().unwrap_or("<unnamed>");
+                let discriminant = discriminants.get(&variant.id);
 
                 match &variant_enum.kind {
                     VariantKind::Plain => {
                         code_spans.push(Span::plain("    "));
                         code_spans.push(Span::type_name(variant_name));
+                        code_spans.extend(format_discriminant(discriminant));
                         code_spans.push(Span::punctuation(","));
+                        code_spans.extend(format_discriminant_comment(discriminant));
                         code_spans.push(Span::plain("\n"));
                     }
                     VariantKind::Tuple(fields) => {
@@ -63,7 +77,9 @@ impl Request {
                         }
 
                         code_spans.push(Span::punctuation(")"));
+                        code_spans.extend(format_discriminant(discriminant));
                         code_spans.push(Span::punctuation(","));
+                        code_spans.extend(format_discriminant_comment(discriminant));
                         code_spans.push(Span::plain("\n"));
                     }
                     VariantKind::Struct { fields, .. } => {
@@ -88,7 +104,9 @@ impl Request {
 
                         code_spans.push(Span::plain("    "));
                         code_spans.push(Span::punctuation("}"));
+                        code_spans.extend(format_discriminant(discriminant));
                         code_spans.push(Span::punctuation(","));
+                        code_spans.extend(format_discriminant_comment(discriminant));
                         code_spans.push(Span::plain("\n"));
                     }
                 }
@@ -135,4 +153,102 @@ impl Request {
 
         doc_nodes
     }
+
+    /// Compute the discriminant of each variant, keyed by variant id.
+    ///
+    /// Discriminants are only reported when they are meaningful to the reader: when at least
+    /// one variant has an explicit discriminant, or when the enum has a `C` or primitive
+    /// `#[repr]` and its layout is therefore part of its interface.
+    fn enum_discriminants<'a>(
+        &self,
+        item: DocRef<'a, Item>,
+        enum_data: DocRef<'a, Enum>,
+    ) -> HashMap<Id, VariantDiscriminant<'a>> {
+        let variants = enum_data
+            .variants
+            .iter()
+            .map(|id| match item.get(id).map(|variant| variant.inner()) {
+                Some(ItemEnum::Variant(variant)) => Some((*id, variant)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let has_explicit = variants
+            .iter()
+            .flatten()
+            .any(|(_, variant)| variant.discriminant.is_some());
+        let has_layout_repr = item.attrs.iter().any(|attr| match attr {
+            Attribute::Repr(repr) => repr.kind == ReprKind::C || repr.int.is_some(),
+            _ => false,
+        });
+
+        let mut discriminants = HashMap::new();
+        if !has_explicit && !has_layout_repr {
+            return discriminants;
+        }
+
+        // Implicit discriminants count up from the previous variant. If a variant is missing
+        // from the index or its value doesn't fit in an i128, later values can't be computed.
+        let mut next = Some(0i128);
+        for variant in variants {
+            let Some((id, variant)) = variant else {
+                next = None;
+                continue;
+            };
+
+            match &variant.discriminant {
+                Some(discriminant) => {
+                    next = discriminant
+                        .value
+                        .parse::<i128>()
+                        .ok()
+                        .and_then(|value| value.checked_add(1));
+                    discriminants.insert(
+                        id,
+                        VariantDiscriminant::Explicit {
+                            expr: &discriminant.expr,
+                            value: &discriminant.value,
+                        },
+                    );
+                }
+                None => {
+                    if let Some(value) = next {
+                        discriminants.insert(id, VariantDiscriminant::Implicit(value));
+                    }
+                    next = next.and_then(|value| value.checked_add(1));
+                }
+            }
+        }
+
+        discriminants
+    }
+}
+
+/// Format the ` = expr` suffix for a variant with an explicit discriminant
+fn format_discriminant<'a>(discriminant: Option<&VariantDiscriminant<'a>>) -> Vec<Span<'a>> {
+    match discriminant {
+        Some(VariantDiscriminant::Explicit { expr, .. }) => vec![
+            Span::plain(" "),
+            Span::operator("="),
+            Span::plain(" "),
+            Span::inline_rust_code(*expr),
+        ],
+        _ => vec![],
+    }
+}
+
+/// Format a trailing comment with the numeric discriminant value, when it isn't already
+/// spelled out by the source expression
+fn format_discriminant_comment<'a>(
+    discriminant: Option<&VariantDiscriminant<'a>>,
+) -> Vec<Span<'a>> {
+    let comment = match discriminant {
+        Some(VariantDiscriminant::Explicit { expr, value }) if expr != value => {
+            format!("// = {value}")
+        }
+        Some(VariantDiscriminant::Implicit(value)) => format!("// = {value}"),
+        _ => return vec![],
+    };
+
+    vec![Span::plain(" "), Span::comment(comment)]
 }
@@ -1,7 +1,21 @@
-use rustdoc_types::{AssocItemConstraint, AssocItemConstraintKind, TraitBoundModifier};
+use rustdoc_types::{
+    AssocItemConstraint, AssocItemConstraintKind, PreciseCapturingArg, TraitBoundModifier,
+};
 
 use super::*;
-use crate::styled_string::{DocumentNode, Span as StyledSpan};
+use crate::styled_string::{DocumentNode, Span as StyledSpan, TuiAction};
+
+/// Width signatures are laid out to before parameters and bounds are broken onto their own
+/// lines, matching rustfmt's default `max_width`
+const SIGNATURE_WIDTH: usize = 100;
+
+/// Indentation for parameters and where predicates on their own lines
+const SIGNATURE_INDENT: &str = "    ";
+
+/// The width of spans on a single line
+fn flat_width(spans: &[StyledSpan]) -> usize {
+    spans.iter().map(|span| span.text.chars().count()).sum()
+}
 
 impl Request {
     /// Format a function signature
@@ -128,25 +142,50 @@ impl Request {
         if !func.generics.params.is_empty() {
             spans.extend(self.format_generics(item, &func.generics));
         }
-        spans.push(StyledSpan::punctuation("("));
 
-        // Add parameters
-        for (i, (param_name, param_type)) in func.sig.inputs.iter().enumerate() {
-            if i > 0 {
-                spans.push(StyledSpan::punctuation(","));
-                spans.push(StyledSpan::plain(" "));
-            }
-            spans.extend(self.format_parameter(item, param_name, param_type));
-        }
-        spans.push(StyledSpan::punctuation(")"));
+        let params: Vec<_> = func
+            .sig
+            .inputs
+            .iter()
+            .map(|(param_name, param_type)| self.format_parameter(item, param_name, param_type))
+            .collect();
 
         // Add return type if not unit
+        let mut output_spans = vec![];
         if let Some(output) = &func.sig.output {
-            spans.push(StyledSpan::plain(" "));
-            spans.push(StyledSpan::operator("->"));
-            spans.push(StyledSpan::plain(" "));
-            spans.extend(self.format_type(item, output));
+            output_spans.push(StyledSpan::plain(" "));
+            output_spans.push(StyledSpan::operator("->"));
+            output_spans.push(StyledSpan::plain(" "));
+            output_spans.extend(self.format_type(item, output));
+        }
+
+        // Parameters stay on the signature's line if it fits, and otherwise go one per line
+        // with trailing commas, as rustfmt lays them out
+        let flat_len = flat_width(&spans)
+            + params
+                .iter()
+                .map(|param| flat_width(param) + 2)
+                .sum::<usize>()
+            + flat_width(&output_spans);
+        spans.push(StyledSpan::punctuation("("));
+        if flat_len > SIGNATURE_WIDTH && !params.is_empty() {
+            for param in params {
+                spans.push(StyledSpan::plain(format!("\n{SIGNATURE_INDENT}")));
+                spans.extend(param);
+                spans.push(StyledSpan::punctuation(","));
+            }
+            spans.push(StyledSpan::plain("\n"));
+        } else {
+            for (i, param) in params.into_iter().enumerate() {
+                if i > 0 {
+                    spans.push(StyledSpan::punctuation(","));
+                    spans.push(StyledSpan::plain(" "));
+                }
+                spans.extend(param);
+            }
         }
+        spans.push(StyledSpan::punctuation(")"));
+        spans.extend(output_spans);
 
         // Add where clause if present
         if !func.generics.where_predicates.is_empty() {
@@ -254,9 +293,25 @@ impl Request {
             return vec![];
         }
 
+        // `impl Trait` arguments are shown in the parameter list, not as generics, and short
+        // signatures leave out lifetimes
+        let short = self.format_context().verbosity().is_short();
+        let params: Vec<_> = generics
+            .params
+            .iter()
+            .filter(|param| match param.kind {
+                GenericParamDefKind::Type { is_synthetic, .. } => !is_synthetic,
+                GenericParamDefKind::Lifetime { .. } => !short,
+                GenericParamDefKind::Const { .. } => true,
+            })
+            .collect();
+        if params.is_empty() {
+            return vec![];
+        }
+
         let mut spans = vec![StyledSpan::punctuation("<")];
 
-        for (i, param) in generics.params.iter().enumerate() {
+        for (i, param) in params.into_iter().enumerate() {
             if i > 0 {
                 spans.push(StyledSpan::punctuation(","));
                 spans.push(StyledSpan::plain(" "));
@@ -383,7 +438,21 @@ impl Request {
                 spans
             }
             GenericBound::Outlives(lifetime) => vec![StyledSpan::lifetime(lifetime)],
-            GenericBound::Use(_) => vec![StyledSpan::plain("use<...>")], // Handle new bound type
+            GenericBound::Use(args) => {
+                let mut spans = vec![StyledSpan::keyword("use"), StyledSpan::punctuation("<")];
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        spans.push(StyledSpan::punctuation(","));
+                        spans.push(StyledSpan::plain(" "));
+                    }
+                    spans.push(match arg {
+                        PreciseCapturingArg::Lifetime(lifetime) => StyledSpan::lifetime(lifetime),
+                        PreciseCapturingArg::Param(param) => StyledSpan::generic(param),
+                    });
+                }
+                spans.push(StyledSpan::punctuation(">"));
+                spans
+            }
         }
     }
 
@@ -393,22 +462,41 @@ impl Request {
         item: DocRef<'a, Item>,
         predicates: &'a [WherePredicate],
     ) -> Vec<StyledSpan<'a>> {
-        if predicates.is_empty() {
+        if predicates.is_empty() || self.format_context().verbosity().is_short() {
             return vec![];
         }
 
         let mut spans = vec![
             StyledSpan::plain("\n"),
             StyledSpan::keyword("where"),
-            StyledSpan::plain("\n    "),
+            StyledSpan::plain(format!("\n{SIGNATURE_INDENT}")),
         ];
 
         for (i, pred) in predicates.iter().enumerate() {
             if i > 0 {
                 spans.push(StyledSpan::punctuation(","));
-                spans.push(StyledSpan::plain("\n    "));
+                spans.push(StyledSpan::plain(format!("\n{SIGNATURE_INDENT}")));
             }
-            spans.extend(self.format_where_predicate(item, pred));
+            let mut pred_spans = self.format_where_predicate(item, pred);
+
+            // A predicate too long for its line gets one bound per line, indented under it
+            if let WherePredicate::BoundPredicate {
+                type_,
+                bounds,
+                generic_params,
+            } = pred
+                && bounds.len() > 1
+                && SIGNATURE_INDENT.len() + flat_width(&pred_spans) > SIGNATURE_WIDTH
+            {
+                pred_spans = self.format_bound_predicate(
+                    item,
+                    type_,
+                    bounds,
+                    generic_params,
+                    &format!("\n{SIGNATURE_INDENT}{SIGNATURE_INDENT}+ "),
+                );
+            }
+            spans.extend(pred_spans);
         }
 
         spans
@@ -425,7 +513,7 @@ impl Request {
                 type_,
                 bounds,
                 generic_params,
-            } => self.format_bound_predicate(item, type_, bounds, generic_params),
+            } => self.format_bound_predicate(item, type_, bounds, generic_params, " + "),
             WherePredicate::LifetimePredicate { lifetime, outlives } => {
                 let mut spans = vec![StyledSpan::lifetime(lifetime), StyledSpan::punctuation(":")];
                 if !outlives.is_empty() {
@@ -457,6 +545,7 @@ impl Request {
         type_: &'a Type,
         bounds: &'a [GenericBound],
         generic_params: &'a [GenericParamDef],
+        bound_separator: &str,
     ) -> Vec<StyledSpan<'a>> {
         let mut spans = vec![];
 
@@ -477,7 +566,12 @@ impl Request {
         spans.extend(self.format_type(item, type_));
         spans.push(StyledSpan::punctuation(":"));
         spans.push(StyledSpan::plain(" "));
-        spans.extend(self.format_generic_bounds(item, bounds));
+        for (i, bound) in bounds.iter().enumerate() {
+            if i > 0 {
+                spans.push(StyledSpan::plain(bound_separator.to_string()));
+            }
+            spans.extend(self.format_generic_bound(item, bound));
+        }
         spans
     }
 
@@ -503,7 +597,23 @@ impl Request {
             return vec![];
         }
 
-        let type_span = StyledSpan::type_name(&path.path).with_target(item.get_path(path.id));
+        let type_span = match item.get_path(path.id) {
+            Some(target) => StyledSpan::type_name(&path.path).with_target(Some(target)),
+            // The defining crate isn't available, but rustdoc's path map still knows where the
+            // item lives and what kind it is, which is enough for a link
+            None => match item.crate_docs().paths().get(&path.id) {
+                Some(summary) => {
+                    let full_path = summary.path.join("::");
+                    let url = self.generate_url_from_path_and_kind(&full_path, summary.kind);
+                    StyledSpan::type_name(&path.path).with_action(TuiAction::NavigateToPath {
+                        path: full_path.into(),
+                        url: Some(url.into()),
+                        anchor: None,
+                    })
+                }
+                None => StyledSpan::type_name(&path.path),
+            },
+        };
 
         let mut spans = vec![type_span];
         if let Some(args) = &path.args {
@@ -563,6 +673,11 @@ impl Request {
         args: &'a [GenericArg],
         constraints: &'a [AssocItemConstraint],
     ) -> Vec<StyledSpan<'a>> {
+        let short = self.format_context().verbosity().is_short();
+        let args: Vec<_> = args
+            .iter()
+            .filter(|arg| !(short && matches!(arg, GenericArg::Lifetime(_))))
+            .collect();
         if args.is_empty() && constraints.is_empty() {
             return vec![];
         }
@@ -580,7 +695,7 @@ impl Request {
             match arg {
                 GenericArg::Lifetime(lifetime) => spans.push(StyledSpan::lifetime(lifetime)),
                 GenericArg::Type(type_) => spans.extend(self.format_type(item, type_)),
-                GenericArg::Const(const_) => spans.push(StyledSpan::inline_code(&const_.expr)),
+                GenericArg::Const(const_) => spans.push(StyledSpan::plain(&const_.expr)),
                 GenericArg::Infer => spans.push(StyledSpan::plain("_")),
             }
         }
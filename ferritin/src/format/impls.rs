@@ -2,7 +2,7 @@ use ferritin_common::CrateProvenance;
 use rustdoc_types::ItemKind;
 
 use super::*;
-use crate::styled_string::{DocumentNode, ListItem, Span};
+use crate::styled_string::{DocumentNode, HeadingLevel, ListItem, Span};
 use semver::VersionReq;
 use std::cmp::Ordering;
 
@@ -21,7 +21,7 @@ struct TraitImpl {
 }
 
 impl Request {
-    /// Add associated methods for a struct or enum
+    /// Add associated methods for a struct, enum or primitive type
     pub(super) fn format_associated_methods<'a>(
         &'a self,
         item: DocRef<'a, Item>,
@@ -29,25 +29,115 @@ impl Request {
         let mut doc_nodes = vec![];
 
         let inherent_methods = item.methods().collect::<Vec<_>>();
-        // Show inherent methods first
+        // Show inherent methods first, expanded, since they are the type's own API
         if !inherent_methods.is_empty() {
-            doc_nodes.extend(self.format_item_list(inherent_methods, "Associated Types"));
+            doc_nodes.extend(self.format_item_list(inherent_methods, "Inherent Implementations"));
+        }
+
+        // Separate compiler-implied auto trait impls and blanket impls from the rest, since
+        // they are present on nearly every type and rarely what the reader is looking for
+        let mut trait_impls = vec![];
+        let mut auto_trait_impls = vec![];
+        let mut blanket_impls = vec![];
+        for impl_block in item.traits() {
+            match impl_block.inner() {
+                ItemEnum::Impl(impl_item) if impl_item.is_synthetic => {
+                    auto_trait_impls.push(impl_block)
+                }
+                ItemEnum::Impl(impl_item) if impl_item.blanket_impl.is_some() => {
+                    blanket_impls.push(impl_block)
+                }
+                _ => trait_impls.push(impl_block),
+            }
         }
 
-        let trait_impls = item.traits().collect::<Vec<_>>();
         // Show trait implementations
         if !trait_impls.is_empty() {
             doc_nodes.extend(self.format_trait_implementations(&trait_impls));
         }
 
+        // At full verbosity these get their impl headers too, rather than just trait names
+        let full = self.format_context().verbosity().is_full();
+        for (title, impls) in [
+            ("Auto Trait Implementations", auto_trait_impls),
+            ("Blanket Implementations", blanket_impls),
+        ] {
+            if impls.is_empty() {
+                continue;
+            }
+            if full {
+                let impl_nodes = impls
+                    .into_iter()
+                    .filter_map(|impl_block| self.format_impl_block(impl_block))
+                    .collect();
+                doc_nodes.push(DocumentNode::section(vec![Span::plain(title)], impl_nodes));
+            } else {
+                doc_nodes.push(self.format_collapsed_trait_list(title, &impls));
+            }
+        }
+
         doc_nodes
     }
 
+    /// Format a collapsible list of implemented trait names
+    fn format_collapsed_trait_list<'a>(
+        &self,
+        title: &'static str,
+        impls: &[DocRef<'a, Item>],
+    ) -> DocumentNode<'a> {
+        let mut traits = impls
+            .iter()
+            .filter_map(|impl_block| self.trait_impl(*impl_block))
+            .collect::<Vec<_>>();
+        traits.sort();
+
+        let mut trait_spans = vec![];
+        for t in traits {
+            trait_spans.push(Span::plain(t.name).with_path(t.full_path));
+            trait_spans.push(Span::plain(" "));
+        }
+
+        DocumentNode::truncated_block(
+            vec![
+                DocumentNode::heading(HeadingLevel::Section, vec![Span::plain(title)]),
+                DocumentNode::paragraph(trait_spans),
+            ],
+            TruncationLevel::SingleLine,
+        )
+    }
+
+    /// Describe the trait implemented by an impl block, if it is a trait impl
+    fn trait_impl(&self, impl_block: DocRef<'_, Item>) -> Option<TraitImpl> {
+        let ItemEnum::Impl(impl_item) = &impl_block.inner else {
+            return None;
+        };
+        let trait_path = impl_item.trait_.as_ref()?;
+
+        let full_path = impl_block
+            .crate_docs()
+            .path(&trait_path.id)
+            .map(|path| path.to_string())
+            .unwrap_or(trait_path.path.clone());
+
+        // Use the simple path name for display (generics not needed in trait lists)
+        let display_name = trait_path.path.clone();
+
+        Some(self.categorize_trait(full_path, display_name))
+    }
+
     fn format_item_list<'a>(
         &'a self,
-        mut items: Vec<DocRef<'a, Item>>,
+        items: Vec<DocRef<'a, Item>>,
         title: &'a str,
     ) -> Vec<DocumentNode<'a>> {
+        vec![DocumentNode::section(
+            vec![Span::plain(title)],
+            vec![DocumentNode::list(self.format_list_items(items))],
+        )]
+    }
+
+    /// Build list entries for associated items, in source order
+    fn format_list_items<'a>(&'a self, mut items: Vec<DocRef<'a, Item>>) -> Vec<ListItem<'a>> {
         items.sort_by(|a, b| {
             match (&a.span, &b.span) {
                 (Some(span_a), Some(span_b)) => {
@@ -75,7 +165,7 @@ impl Request {
             }
         });
 
-        let list_items: Vec<ListItem> = items
+        items
             .iter()
             .map(|item| {
                 let mut signature_spans = vec![];
@@ -106,14 +196,26 @@ impl Request {
                 let name = item.name().unwrap_or("<unnamed>");
                 let kind = item.kind();
 
-                // For functions, show the signature inline
+                // For functions and associated types, show the signature inline
                 if let ItemEnum::Function(inner) = &item.item().inner {
                     signature_spans.extend(self.format_function_signature(*item, name, inner));
+                } else if let ItemEnum::AssocType {
+                    generics,
+                    bounds,
+                    type_,
+                } = &item.item().inner
+                {
+                    signature_spans.extend(self.format_assoc_type_signature(
+                        *item,
+                        generics,
+                        bounds,
+                        type_.as_ref(),
+                        name,
+                    ));
                 } else {
                     // For other items, show kind + name
                     let kind_str = match kind {
                         ItemKind::AssocConst => "const",
-                        ItemKind::AssocType => "type",
                         _ => "",
                     };
 
@@ -134,88 +236,78 @@ impl Request {
 
                 ListItem::new(item_nodes)
             })
-            .collect();
-
-        vec![DocumentNode::section(
-            vec![Span::plain(title)],
-            vec![DocumentNode::list(list_items)],
-        )]
+            .collect()
     }
 
-    /// Format trait implementations with explicit category groups
+    /// Format trait implementations as one collapsible block per impl, ordered by relevance
+    /// (crate-local, then external, then std) and alphabetically within each group
     fn format_trait_implementations<'a>(
-        &self,
+        &'a self,
         trait_impls: &[DocRef<'a, Item>],
     ) -> Vec<DocumentNode<'a>> {
-        let mut crate_local = Vec::new();
-        let mut external = Vec::new();
-        let mut std_traits = Vec::new();
-
-        // Extract trait implementations
-        for impl_block in trait_impls {
-            if let ItemEnum::Impl(impl_item) = &impl_block.inner
-                && let Some(trait_path) = &impl_item.trait_
-            {
-                let full_path = impl_block
-                    .crate_docs()
-                    .path(&trait_path.id)
-                    .map(|path| path.to_string())
-                    .unwrap_or(trait_path.path.clone());
-
-                // Use the simple path name for display (generics not needed in trait lists)
-                let display_name = trait_path.path.clone();
-
-                let impl_ = self.categorize_trait(full_path, display_name);
-
-                match impl_.category {
-                    TraitCategory::CrateLocal => crate_local.push(impl_),
-                    TraitCategory::External => external.push(impl_),
-                    TraitCategory::Std => std_traits.push(impl_),
-                }
-            }
+        let mut impls = trait_impls
+            .iter()
+            .filter_map(|impl_block| Some((self.trait_impl(*impl_block)?, *impl_block)))
+            .collect::<Vec<_>>();
+
+        if impls.is_empty() {
+            return vec![];
         }
 
-        // Sort each category alphabetically for stable output
-        crate_local.sort();
-        external.sort();
-        std_traits.sort();
+        impls.sort_by(|(a, _), (b, _)| {
+            a.category
+                .cmp(&b.category)
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.full_path.cmp(&b.full_path))
+        });
 
-        // Build trait implementation content
-        let mut trait_content = vec![];
+        let impl_nodes = impls
+            .into_iter()
+            .filter_map(|(_, impl_block)| self.format_impl_block(impl_block))
+            .collect();
 
-        // Add crate-local and external traits (most relevant)
-        let mut primary_traits = Vec::new();
-        primary_traits.extend(crate_local);
-        primary_traits.extend(external);
+        vec![DocumentNode::section(
+            vec![Span::plain("Trait Implementations")],
+            impl_nodes,
+        )]
+    }
 
-        if !primary_traits.is_empty() {
-            let mut trait_spans = vec![Span::plain("Trait Implementations: ")];
-            for t in primary_traits {
-                trait_spans.push(Span::plain(t.name).with_path(t.full_path));
-                trait_spans.push(Span::plain(" "));
+    /// Format a single impl block as a collapsible block: the `impl` header, followed by the
+    /// items it provides
+    fn format_impl_block<'a>(&'a self, impl_block: DocRef<'a, Item>) -> Option<DocumentNode<'a>> {
+        let ItemEnum::Impl(impl_item) = impl_block.inner() else {
+            return None;
+        };
+
+        let mut header_spans = vec![Span::keyword("impl")];
+        header_spans.extend(self.format_generics(impl_block, &impl_item.generics));
+        header_spans.push(Span::plain(" "));
+        if let Some(trait_path) = &impl_item.trait_ {
+            if impl_item.is_negative {
+                header_spans.push(Span::operator("!"));
             }
-            trait_content.push(DocumentNode::paragraph(trait_spans));
+            header_spans.extend(self.format_path(impl_block, trait_path));
+            header_spans.push(Span::plain(" "));
+            header_spans.push(Span::keyword("for"));
+            header_spans.push(Span::plain(" "));
         }
+        header_spans.extend(self.format_type(impl_block, &impl_item.for_));
+        header_spans
+            .extend(self.format_where_clause(impl_block, &impl_item.generics.where_predicates));
 
-        // Add std traits separately
-        if !std_traits.is_empty() {
-            let mut trait_spans = vec![Span::plain("std traits: ")];
-            for t in std_traits {
-                trait_spans.push(Span::plain(t.name).with_path(t.full_path));
-                trait_spans.push(Span::plain(" "));
-            }
-            trait_content.push(DocumentNode::paragraph(trait_spans));
-        }
+        // The header is a paragraph rather than generated code so that it remains visible
+        // when the block is collapsed
+        let mut nodes = vec![DocumentNode::paragraph(header_spans)];
 
-        // Wrap in a section if we have any trait implementations
-        if !trait_content.is_empty() {
-            vec![DocumentNode::section(
-                vec![Span::plain("Trait Implementations")],
-                trait_content,
-            )]
-        } else {
-            vec![]
+        let items = impl_block.id_iter(&impl_item.items).collect::<Vec<_>>();
+        if !items.is_empty() {
+            nodes.push(DocumentNode::list(self.format_list_items(items)));
         }
+
+        Some(DocumentNode::truncated_block(
+            nodes,
+            TruncationLevel::SingleLine,
+        ))
     }
 
     fn categorize_trait(&self, full_path: String, rendered_path: String) -> TraitImpl {
@@ -228,7 +320,7 @@ impl Request {
         {
             let category = match lookup_result.provenance() {
                 CrateProvenance::Std => TraitCategory::Std,
-                CrateProvenance::Workspace => TraitCategory::CrateLocal,
+                CrateProvenance::Workspace | CrateProvenance::JsonFile => TraitCategory::CrateLocal,
                 _ => TraitCategory::External,
             };
 
@@ -0,0 +1,295 @@
+use super::*;
+use crate::styled_string::{DocumentNode, ListItem, Span};
+use std::{iter::Peekable, str::CharIndices};
+
+/// Keywords highlighted inside macro matchers and transcribers
+const KEYWORDS: &[&str] = &[
+    "as", "async", "const", "crate", "else", "enum", "fn", "for", "if", "impl", "in", "let",
+    "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static",
+    "struct", "super", "trait", "type", "unsafe", "use", "where", "while",
+];
+
+/// A single `matcher => transcriber` arm of a `macro_rules!` definition
+#[derive(Debug, PartialEq, Eq)]
+struct MacroRule<'a> {
+    matcher: &'a str,
+    transcriber: &'a str,
+}
+
+impl Request {
+    /// Format a declarative macro, showing its invocation forms and each rule separately
+    ///
+    /// Falls back to the raw definition for macros that can't be split into rules, such as
+    /// `macro` 2.0 items.
+    pub(super) fn format_macro<'a>(
+        &'a self,
+        item: DocRef<'a, Item>,
+        macro_def: &'a str,
+    ) -> Vec<DocumentNode<'a>> {
+        let Some(rules) = parse_macro_rules(macro_def).filter(|rules| !rules.is_empty()) else {
+            return vec![
+                DocumentNode::paragraph(vec![Span::plain("Macro definition:")]),
+                DocumentNode::code_block(Some("rust"), macro_def),
+            ];
+        };
+
+        let name = item.name().unwrap_or("<unnamed>");
+
+        let invocation_items = rules
+            .iter()
+            .map(|rule| {
+                let mut spans = vec![Span::type_name(name), Span::punctuation("!")];
+                spans.extend(highlight_macro_tokens(&collapse_whitespace(rule.matcher)));
+                ListItem::new(vec![DocumentNode::generated_code(spans)])
+            })
+            .collect();
+
+        let rule_items = rules
+            .iter()
+            .map(|rule| {
+                let mut spans = highlight_macro_tokens(rule.matcher);
+                spans.push(Span::plain(" "));
+                spans.push(Span::operator("=>"));
+                spans.push(Span::plain(" "));
+                spans.extend(highlight_macro_tokens(rule.transcriber));
+                spans.push(Span::punctuation(";"));
+                ListItem::new(vec![DocumentNode::generated_code(spans)])
+            })
+            .collect();
+
+        vec![
+            DocumentNode::section(
+                vec![Span::plain("Invocation forms:")],
+                vec![DocumentNode::list(invocation_items)],
+            ),
+            DocumentNode::section(
+                vec![Span::plain("Rules:")],
+                vec![DocumentNode::list(rule_items)],
+            ),
+        ]
+    }
+}
+
+/// Split a `macro_rules! name { ... }` definition into its rules
+///
+/// Returns `None` if the definition isn't in `macro_rules!` form or its delimiters don't
+/// balance.
+fn parse_macro_rules(macro_def: &str) -> Option<Vec<MacroRule<'_>>> {
+    let definition = macro_def.trim().strip_prefix("macro_rules!")?;
+    let body_start = definition.find(['{', '(', '['])?;
+    let body = definition[body_start..].trim_end().trim_end_matches(';');
+    let body = body.get(1..body.len().checked_sub(1)?)?;
+
+    let mut rules = vec![];
+    for arm in split_top_level(body, ";")? {
+        let arm = arm.trim();
+        if arm.is_empty() {
+            continue;
+        }
+
+        let mut parts = split_top_level(arm, "=>")?.into_iter();
+        let matcher = parts.next()?.trim();
+        let transcriber = parts.next()?.trim();
+        if parts.next().is_some() {
+            return None;
+        }
+
+        rules.push(MacroRule {
+            matcher,
+            transcriber,
+        });
+    }
+
+    Some(rules)
+}
+
+/// Split `text` on every occurrence of `separator` that isn't nested inside delimiters or a
+/// string literal
+fn split_top_level<'a>(text: &'a str, separator: &str) -> Option<Vec<&'a str>> {
+    let mut parts = vec![];
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.checked_sub(1)?,
+            _ if depth == 0 && index >= start && text[index..].starts_with(separator) => {
+                parts.push(&text[start..index]);
+                start = index + separator.len();
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 || in_string {
+        return None;
+    }
+
+    parts.push(&text[start..]);
+    Some(parts)
+}
+
+/// Replace runs of whitespace with a single space, for one-line invocation summaries
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Highlight macro tokens: metavariables and their fragment specifiers, repetition
+/// operators, keywords and punctuation
+fn highlight_macro_tokens<'a>(text: &str) -> Vec<Span<'a>> {
+    let mut spans = vec![];
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '$' => match chars.peek() {
+                Some(&(_, '(')) => {
+                    chars.next();
+                    spans.push(Span::operator("$("));
+                }
+                Some(&(_, next)) if is_ident_char(next) => {
+                    let end = take_while(&mut chars, start + 1, is_ident_char);
+                    let name = &text[start..end];
+                    if name == "$crate" {
+                        spans.push(Span::keyword(name.to_string()));
+                        continue;
+                    }
+                    spans.push(Span::field_name(name.to_string()));
+
+                    // `$name:fragment`
+                    if let Some(&(colon, ':')) = chars.peek() {
+                        chars.next();
+                        let end = take_while(&mut chars, colon + 1, is_ident_char);
+                        spans.push(Span::punctuation(":"));
+                        spans.push(Span::keyword(text[colon + 1..end].to_string()));
+                    }
+                }
+                _ => spans.push(Span::operator("$")),
+            },
+            '*' | '+' | '?' => spans.push(Span::operator(c.to_string())),
+            '"' => {
+                let mut end = text.len();
+                let mut escaped = false;
+                for (index, c) in chars.by_ref() {
+                    match c {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        '"' => {
+                            end = index + 1;
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                spans.push(Span::inline_rust_code(text[start..end].to_string()));
+            }
+            c if c.is_whitespace() => {
+                let end = take_while(&mut chars, start + c.len_utf8(), char::is_whitespace);
+                spans.push(Span::plain(text[start..end].to_string()));
+            }
+            c if is_ident_char(c) => {
+                let end = take_while(&mut chars, start + c.len_utf8(), is_ident_char);
+                let word = &text[start..end];
+                if KEYWORDS.contains(&word) {
+                    spans.push(Span::keyword(word.to_string()));
+                } else {
+                    spans.push(Span::plain(word.to_string()));
+                }
+            }
+            c => spans.push(Span::punctuation(c.to_string())),
+        }
+    }
+
+    spans
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Advance `chars` past every character matching `predicate`, returning the byte offset just
+/// after the last one consumed (or `start` if none were)
+fn take_while(
+    chars: &mut Peekable<CharIndices<'_>>,
+    start: usize,
+    predicate: fn(char) -> bool,
+) -> usize {
+    let mut end = start;
+    while let Some(&(index, c)) = chars.peek() {
+        if !predicate(c) {
+            break;
+        }
+        end = index + c.len_utf8();
+        chars.next();
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_each_rule() {
+        let rules = parse_macro_rules(
+            "macro_rules! vec {\n    () => { ... };\n    ($elem:expr; $n:expr) => { ... };\n    \
+             ($($x:expr),+ $(,)?) => { ... };\n}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rules,
+            vec![
+                MacroRule {
+                    matcher: "()",
+                    transcriber: "{ ... }"
+                },
+                MacroRule {
+                    matcher: "($elem:expr; $n:expr)",
+                    transcriber: "{ ... }"
+                },
+                MacroRule {
+                    matcher: "($($x:expr),+ $(,)?)",
+                    transcriber: "{ ... }"
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_separators_in_strings() {
+        let rules =
+            parse_macro_rules(r#"macro_rules! m { ($x:expr) => { println!("{}; =>", $x) }; }"#)
+                .unwrap();
+
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].transcriber, r#"{ println!("{}; =>", $x) }"#);
+    }
+
+    #[test]
+    fn rejects_non_macro_rules() {
+        assert_eq!(parse_macro_rules("pub macro m($x:expr) { $x }"), None);
+        assert_eq!(parse_macro_rules("macro_rules! m { ($x:expr => {} }"), None);
+    }
+
+    #[test]
+    fn highlights_metavariables() {
+        let spans = highlight_macro_tokens("($x:expr),*");
+        let texts = spans.iter().map(|span| &*span.text).collect::<Vec<_>>();
+        assert_eq!(texts, vec!["(", "$x", ":", "expr", ")", ",", "*"]);
+    }
+}
@@ -3,9 +3,9 @@ use crate::styled_string::{DocumentNode, Span as StyledSpan, TruncationLevel};
 use ferritin_common::doc_ref::DocRef;
 use rustdoc_types::{
     Abi, Constant, Enum, Function, FunctionPointer, GenericArg, GenericArgs, GenericBound,
-    GenericParamDef, GenericParamDefKind, Generics, Id, Item, ItemEnum, ItemSummary, Path, Span,
-    Static, Struct, StructKind, Term, Trait, Type, TypeAlias, Union, VariantKind, Visibility,
-    WherePredicate,
+    GenericParamDef, GenericParamDefKind, Generics, Id, Item, ItemEnum, ItemKind, ItemSummary,
+    Path, Span, Static, Struct, StructKind, Term, Trait, Type, TypeAlias, Union, VariantKind,
+    Visibility, WherePredicate,
 };
 use std::{collections::HashMap, fs};
 
@@ -14,12 +14,16 @@ mod r#enum;
 mod functions;
 mod impls;
 mod items;
+mod macros;
 mod r#module;
+mod proc_macro;
 mod source;
 mod r#struct;
 mod r#trait;
 mod types;
 
+pub(crate) use source::read_source_file;
+
 impl Request {
     /// Format an item with automatic recursion tracking
     pub(crate) fn format_item<'a>(&'a self, item: DocRef<'a, Item>) -> Vec<DocumentNode<'a>> {
@@ -28,6 +32,15 @@ impl Request {
         // Item metadata (name, kind, visibility, location, crate)
         doc_nodes.extend(self.format_item_metadata(item));
 
+        // Platforms or features the item is limited to, from `#[doc(cfg(…))]`
+        if let Some(cfg) = item.cfg() {
+            doc_nodes.push(DocumentNode::paragraph(vec![
+                StyledSpan::plain("Available on "),
+                StyledSpan::strong(cfg.to_string()),
+                StyledSpan::plain(" only."),
+            ]));
+        }
+
         // Add documentation if available
         if let Some(docs) = self.docs_to_show(item, TruncationLevel::Full) {
             doc_nodes.extend(docs);
@@ -35,9 +48,14 @@ impl Request {
 
         // Handle different item types
         match item.inner() {
+            // Keywords are modules with nothing in them
+            ItemEnum::Module(_) if item.kind() == ItemKind::Keyword => {}
             ItemEnum::Module(_) => {
                 doc_nodes.extend(self.format_module(item));
             }
+            ItemEnum::Primitive(_) => {
+                doc_nodes.extend(self.format_associated_methods(item));
+            }
             ItemEnum::Struct(struct_data) => {
                 doc_nodes.extend(self.format_struct(item, item.build_ref(struct_data)));
             }
@@ -63,10 +81,10 @@ impl Request {
                 doc_nodes.extend(self.format_static(item, static_data));
             }
             ItemEnum::Macro(macro_def) => {
-                doc_nodes.push(DocumentNode::paragraph(vec![StyledSpan::plain(
-                    "Macro definition:",
-                )]));
-                doc_nodes.push(DocumentNode::code_block(Some("rust"), macro_def));
+                doc_nodes.extend(self.format_macro(item, macro_def));
+            }
+            ItemEnum::ProcMacro(proc_macro) => {
+                doc_nodes.extend(self.format_proc_macro(item, proc_macro));
             }
             _ => {
                 // For any other item, just print its name and kind
@@ -159,13 +177,21 @@ impl Request {
             }
             spans.push(StyledSpan::plain("\n"));
 
+            // Re-export path, when reached through a `pub use`
+            if let Some(reexport_path) = item.reexport_path() {
+                spans.push(StyledSpan::strong("Re-exported as:"));
+                spans.push(StyledSpan::plain(" "));
+                spans.push(StyledSpan::plain(reexport_path));
+                spans.push(StyledSpan::plain("\n"));
+            }
+
             // In crate
             spans.push(StyledSpan::strong("In crate:"));
             spans.push(StyledSpan::plain(" "));
 
             let item_crate = item.crate_docs();
             spans.push(StyledSpan::plain(item_crate.name()));
-            if let Some(version) = item_crate.crate_version.as_deref() {
+            if let Some(version) = item_crate.crate_version() {
                 spans.push(StyledSpan::plain(" ("));
                 // Replace tabs with spaces for consistent rendering across output modes
                 let version_normalized = version.replace('\t', " ");
@@ -222,7 +248,7 @@ impl Request {
             StyledSpan::plain(" "),
             StyledSpan::plain(item_crate.name()),
         ];
-        if let Some(version) = item_crate.crate_version.as_deref() {
+        if let Some(version) = item_crate.crate_version() {
             crate_info_spans.push(StyledSpan::plain(" ("));
             // Replace tabs with spaces for consistent rendering across output modes
             let version_normalized = version.replace('\t', " ");
@@ -1,8 +1,12 @@
 use rustdoc_types::ItemKind;
 
 use super::*;
+use crate::markdown::MarkdownRenderer;
 use crate::styled_string::{DocumentNode, ListItem, Span};
 
+/// Crate docs shorter than this many bytes are followed by the package README
+const THIN_CRATE_DOCS: usize = 500;
+
 // Define display order for groups
 const GROUP_ORDER: &[(ItemKind, &str)] = &[
     (ItemKind::Module, "Modules"),
@@ -16,6 +20,8 @@ const GROUP_ORDER: &[(ItemKind, &str)] = &[
     (ItemKind::Static, "Statics"),
     (ItemKind::Macro, "Macros"),
     (ItemKind::Variant, "Variants"),
+    (ItemKind::Primitive, "Primitive Types"),
+    (ItemKind::Keyword, "Keywords"),
 ];
 
 #[derive(Debug)]
@@ -32,8 +38,11 @@ impl Request {
         path: Option<String>,
         item: DocRef<'a, Item>,
     ) {
+        let show_hidden = self.format_context().show_hidden();
         for child in item.child_items() {
-            if let Some(item_name) = child.name() {
+            if let Some(item_name) = child.name()
+                && (show_hidden || !child.is_hidden())
+            {
                 let path = path.as_deref().map_or_else(
                     || item_name.to_string(),
                     |path| format!("{path}::{item_name}"),
@@ -108,10 +117,20 @@ impl Request {
     /// Format a single flat item as a ListItem
     fn format_flat_item<'a>(&'a self, flat_item: &FlatItem<'a>) -> ListItem<'a> {
         // Prepend item name as a paragraph
-        let mut content = vec![DocumentNode::paragraph(vec![
+        let mut name_spans = vec![
             Span::type_name(flat_item.path.clone()).with_target(Some(flat_item.item)),
             Span::plain(" "),
-        ])];
+        ];
+        if flat_item.item.reexport_path().is_some()
+            && let Some(original_path) = flat_item.item.path()
+        {
+            name_spans.push(Span::comment(format!("(re-export of {original_path})")));
+            name_spans.push(Span::plain(" "));
+        }
+        if flat_item.item.is_hidden() {
+            name_spans.push(Span::comment("#[doc(hidden)]"));
+        }
+        let mut content = vec![DocumentNode::paragraph(name_spans)];
 
         // Add brief documentation if available
         if let Some(docs) = self.docs_to_show(flat_item.item, TruncationLevel::SingleLine) {
@@ -123,8 +142,36 @@ impl Request {
 
     /// Format a module
     pub(super) fn format_module<'a>(&'a self, item: DocRef<'a, Item>) -> Vec<DocumentNode<'a>> {
+        let mut doc_nodes = vec![];
+        if item.id == item.crate_docs().root() {
+            doc_nodes.extend(self.format_readme(item));
+        }
+
         let mut collected = Vec::new();
         self.collect_flat_items(&mut collected, None, item);
-        self.format_grouped_flat_items(&collected)
+        doc_nodes.extend(self.format_grouped_flat_items(&collected));
+        doc_nodes
+    }
+
+    /// The package README, for crate roots whose own docs say little
+    ///
+    /// Many crates document themselves in their README and leave the crate docs short. The
+    /// README is collapsed to its opening, so the item listing stays close by.
+    fn format_readme<'a>(&'a self, root: DocRef<'a, Item>) -> Option<DocumentNode<'a>> {
+        let crate_docs = root.docs.as_deref().unwrap_or_default();
+        if crate_docs.trim().len() >= THIN_CRATE_DOCS {
+            return None;
+        }
+
+        let readme = self.readme(root.crate_docs())?;
+        let nodes = MarkdownRenderer::render_with_resolver(&readme, |_| None);
+        if nodes.is_empty() {
+            return None;
+        }
+
+        Some(DocumentNode::section(
+            vec![Span::plain("README")],
+            vec![DocumentNode::truncated_block(nodes, TruncationLevel::Brief)],
+        ))
     }
 }
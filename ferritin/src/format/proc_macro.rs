@@ -0,0 +1,106 @@
+use super::*;
+use crate::markdown::MarkdownRenderer;
+use crate::styled_string::{DocumentNode, ListItem, Span};
+use rustdoc_types::{MacroKind, ProcMacro};
+
+/// Maximum number of doc examples shown in the usage section
+const MAX_USAGE_EXAMPLES: usize = 3;
+
+impl Request {
+    /// Format a procedural macro: how it is invoked, any helper attributes it registers, and
+    /// examples from its documentation that invoke it
+    pub(super) fn format_proc_macro<'a>(
+        &'a self,
+        item: DocRef<'a, Item>,
+        proc_macro: &'a ProcMacro,
+    ) -> Vec<DocumentNode<'a>> {
+        let name = item.name().unwrap_or("<unnamed>");
+
+        let invocation = match proc_macro.kind {
+            MacroKind::Derive => vec![
+                Span::punctuation("#["),
+                Span::keyword("derive"),
+                Span::punctuation("("),
+                Span::type_name(name),
+                Span::punctuation(")"),
+                Span::punctuation("]"),
+            ],
+            MacroKind::Attr => vec![
+                Span::punctuation("#["),
+                Span::type_name(name),
+                Span::punctuation("]"),
+            ],
+            MacroKind::Bang => vec![
+                Span::type_name(name),
+                Span::punctuation("!"),
+                Span::punctuation("("),
+                Span::plain("..."),
+                Span::punctuation(")"),
+            ],
+        };
+
+        let mut doc_nodes = vec![DocumentNode::generated_code(invocation)];
+
+        if !proc_macro.helpers.is_empty() {
+            let helper_items = proc_macro
+                .helpers
+                .iter()
+                .map(|helper| {
+                    ListItem::new(vec![DocumentNode::generated_code(vec![
+                        Span::punctuation("#["),
+                        Span::type_name(helper),
+                        Span::punctuation("]"),
+                    ])])
+                })
+                .collect();
+
+            doc_nodes.push(DocumentNode::section(
+                vec![Span::plain("Helper attributes:")],
+                vec![DocumentNode::list(helper_items)],
+            ));
+        }
+
+        let examples = self.proc_macro_usage_examples(item, name, &proc_macro.kind);
+        if !examples.is_empty() {
+            doc_nodes.push(DocumentNode::section(vec![Span::plain("Usage:")], examples));
+        }
+
+        doc_nodes
+    }
+
+    /// Collect Rust code blocks from the item's docs that actually invoke the macro
+    fn proc_macro_usage_examples<'a>(
+        &self,
+        item: DocRef<'a, Item>,
+        name: &str,
+        kind: &MacroKind,
+    ) -> Vec<DocumentNode<'a>> {
+        let Some(docs) = &item.docs else {
+            return vec![];
+        };
+
+        let invocation = match kind {
+            MacroKind::Derive => name.to_string(),
+            MacroKind::Attr => format!("#[{name}"),
+            MacroKind::Bang => format!("{name}!"),
+        };
+
+        MarkdownRenderer::render_with_resolver(docs, |_| None)
+            .into_iter()
+            .filter(|node| match node {
+                DocumentNode::CodeBlock { lang, code, .. } => {
+                    lang.as_deref().is_none_or(|lang| lang == "rust")
+                        && match kind {
+                            // A derive can appear alongside others, as in `#[derive(Debug, Name)]`
+                            MacroKind::Derive => code
+                                .lines()
+                                .any(|line| line.contains("derive(") && line.contains(&invocation)),
+                            _ => code.contains(&invocation),
+                        }
+                }
+                _ => false,
+            })
+            .take(MAX_USAGE_EXAMPLES)
+            .collect()
+    }
+}
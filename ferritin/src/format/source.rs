@@ -1,19 +1,39 @@
 use super::*;
-use crate::styled_string::{DocumentNode, Span as StyledSpan};
+use crate::styled_string::{DocumentNode, ShowWhen, Span as StyledSpan};
+use std::path::{Path as FsPath, PathBuf};
+
+/// Number of lines shown before and after the item's span
+const CONTEXT_LINES: usize = 2;
+
+impl Request {
+    /// Format the whole source file containing an item, for display alongside its docs
+    ///
+    /// Returns the listing along with the 0-indexed line on which the item starts.
+    pub(crate) fn format_source_file<'a>(
+        &'a self,
+        item: DocRef<'a, Item>,
+    ) -> Option<(Vec<DocumentNode<'a>>, usize)> {
+        let span = item.span.as_ref()?;
+        let (file_path, file_content) = read_source_file(self, span)?;
+        let start_line = span.begin.0.saturating_sub(1);
+
+        let nodes = vec![
+            DocumentNode::paragraph(vec![StyledSpan::plain(format!(
+                "Source: {}:{}-{}",
+                file_path.display(),
+                span.begin.0,
+                span.end.0
+            ))]),
+            DocumentNode::source_code(source_language(&file_path), file_content, 1),
+        ];
+
+        Some((nodes, start_line))
+    }
+}
 
 /// Format source code
 pub(crate) fn format_source_code<'a>(request: &'a Request, span: &Span) -> Vec<DocumentNode<'a>> {
-    // Resolve the file path - if it's relative, make it relative to the project root
-    let file_path = if span.filename.is_absolute() {
-        span.filename.clone()
-    } else if let Some(project_root) = request.project_root() {
-        project_root.join(&span.filename)
-    } else {
-        // No project and relative path - can't resolve
-        return vec![];
-    };
-
-    let Ok(file_content) = fs::read_to_string(&file_path) else {
+    let Some((file_path, file_content)) = read_source_file(request, span) else {
         return vec![];
     };
 
@@ -27,22 +47,86 @@ pub(crate) fn format_source_code<'a>(request: &'a Request, span: &Span) -> Vec<D
         return vec![];
     }
 
-    let end_line = end_line.min(lines.len().saturating_sub(1));
+    let last_line = lines.len() - 1;
+    let end_line = end_line.clamp(start_line, last_line);
 
     // Add a few lines of context around the item
-    let context_lines = if end_line - start_line < 10 { 1 } else { 3 };
-    let context_start = start_line.saturating_sub(context_lines);
-    let context_end = (end_line + context_lines).min(lines.len().saturating_sub(1));
+    let context_start = start_line.saturating_sub(CONTEXT_LINES);
+    let context_end = (end_line + CONTEXT_LINES).min(last_line);
 
-    // Collect source lines
     let code = lines[context_start..=context_end].join("\n");
+    let lang = source_language(&file_path);
 
-    // Build document nodes
-    vec![
+    let mut nodes = vec![
         DocumentNode::paragraph(vec![StyledSpan::plain(format!(
-            "Source: {}",
-            file_path.display()
+            "Source: {}:{}-{}",
+            file_path.display(),
+            start_line + 1,
+            end_line + 1
         ))]),
-        DocumentNode::code_block(Some("rust"), code),
-    ]
+        DocumentNode::source_code(lang.clone(), code, context_start + 1),
+    ];
+
+    // The whole file can be expanded in place in interactive mode
+    if context_start > 0 || context_end < last_line {
+        nodes.push(DocumentNode::Conditional {
+            show_when: ShowWhen::Interactive,
+            nodes: vec![DocumentNode::truncated_block(
+                vec![
+                    DocumentNode::paragraph(vec![StyledSpan::plain(format!(
+                        "Full file ({} lines)",
+                        lines.len()
+                    ))]),
+                    DocumentNode::source_code(lang, file_content.clone(), 1),
+                ],
+                TruncationLevel::SingleLine,
+            )],
+        });
+    }
+
+    nodes
+}
+
+/// Format exactly the lines of a span, such as the body of a provided trait method
+pub(crate) fn format_span_source<'a>(
+    request: &'a Request,
+    span: &Span,
+) -> Option<DocumentNode<'a>> {
+    let (file_path, file_content) = read_source_file(request, span)?;
+    let start_line = span.begin.0.saturating_sub(1);
+    let code = file_content
+        .lines()
+        .skip(start_line)
+        .take(span.end.0.saturating_sub(start_line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if code.is_empty() {
+        return None;
+    }
+    Some(DocumentNode::source_code(
+        source_language(&file_path),
+        code,
+        start_line + 1,
+    ))
+}
+
+/// Read the file a span points into, resolving relative paths against the project root
+pub(crate) fn read_source_file(request: &Request, span: &Span) -> Option<(PathBuf, String)> {
+    let file_path = if span.filename.is_absolute() {
+        span.filename.clone()
+    } else {
+        // No project and relative path - can't resolve
+        request.project_root()?.join(&span.filename)
+    };
+
+    let file_content = fs::read_to_string(&file_path).ok()?;
+    Some((file_path, file_content))
+}
+
+/// Determine the highlighting language for a source file from its extension
+fn source_language(file_path: &FsPath) -> Option<String> {
+    match file_path.extension()?.to_str()? {
+        "rs" => Some("rust".to_string()),
+        other => Some(other.to_string()),
+    }
 }
@@ -1,5 +1,6 @@
 use super::*;
 use crate::styled_string::{DocumentNode, ListItem, Span};
+use std::collections::VecDeque;
 
 impl Request {
     /// Format a trait
@@ -21,6 +22,12 @@ impl Request {
             signature_spans.extend(self.format_generics(item, &trait_data.item().generics));
         }
 
+        if !trait_data.bounds.is_empty() {
+            signature_spans.push(Span::punctuation(":"));
+            signature_spans.push(Span::plain(" "));
+            signature_spans.extend(self.format_generic_bounds(item, &trait_data.item().bounds));
+        }
+
         if !trait_data.generics.where_predicates.is_empty() {
             signature_spans.extend(
                 self.format_where_clause(item, &trait_data.item().generics.where_predicates),
@@ -34,36 +41,52 @@ impl Request {
 
         let mut nodes: Vec<DocumentNode> = vec![DocumentNode::generated_code(signature_spans)];
 
-        // Build list of trait members
-        let mut member_items = vec![];
+        // Trait members, grouped into sections as rustdoc does
+        let mut assoc_types = vec![];
+        let mut assoc_consts = vec![];
+        let mut required_methods = vec![];
+        let mut provided_methods = vec![];
+        let mut other_items = vec![];
 
         for trait_item in item.id_iter(&trait_data.item().items) {
             let item_name = trait_item.name().unwrap_or("<unnamed>");
 
-            let signature_spans = match &trait_item.item().inner {
-                ItemEnum::Function(f) => {
-                    self.format_trait_method_signature(trait_item, f, item_name)
-                }
+            let (signature_spans, section) = match &trait_item.item().inner {
+                ItemEnum::Function(f) => (
+                    self.format_trait_method_signature(trait_item, f, item_name),
+                    if f.has_body {
+                        &mut provided_methods
+                    } else {
+                        &mut required_methods
+                    },
+                ),
                 ItemEnum::AssocType {
                     generics,
                     bounds,
                     type_,
-                } => self.format_trait_assoc_type_signature(
-                    item,
-                    generics,
-                    bounds,
-                    type_.as_ref(),
-                    item_name,
+                } => (
+                    self.format_assoc_type_signature(
+                        item,
+                        generics,
+                        bounds,
+                        type_.as_ref(),
+                        item_name,
+                    ),
+                    &mut assoc_types,
+                ),
+                ItemEnum::AssocConst { type_, value } => (
+                    self.format_trait_assoc_const_signature(item, type_, value, item_name),
+                    &mut assoc_consts,
                 ),
-                ItemEnum::AssocConst { type_, value } => {
-                    self.format_trait_assoc_const_signature(item, type_, value, item_name)
-                }
                 _ => {
                     // Fallback for unknown item types
-                    vec![Span::comment(format!(
-                        "// {}: {:?}",
-                        item_name, trait_item.inner
-                    ))]
+                    (
+                        vec![Span::comment(format!(
+                            "// {}: {:?}",
+                            item_name, trait_item.inner
+                        ))],
+                        &mut other_items,
+                    )
                 }
             };
 
@@ -79,11 +102,101 @@ impl Request {
                 item_content.extend(docs);
             }
 
-            member_items.push(ListItem::new(item_content));
+            // Default method bodies, when source is shown
+            if let ItemEnum::Function(f) = &trait_item.item().inner
+                && f.has_body
+                && self.format_context().include_source()
+                && let Some(span) = &trait_item.span
+            {
+                item_content.extend(source::format_span_source(self, span));
+            }
+
+            section.push(ListItem::new(item_content));
+        }
+
+        for (title, members) in [
+            ("Associated Types", assoc_types),
+            ("Associated Constants", assoc_consts),
+            ("Required Methods", required_methods),
+            ("Provided Methods", provided_methods),
+            ("Other Items", other_items),
+        ] {
+            if !members.is_empty() {
+                nodes.push(DocumentNode::section(
+                    vec![Span::plain(title)],
+                    vec![DocumentNode::list(members)],
+                ));
+            }
+        }
+
+        nodes.extend(self.format_trait_hierarchy(item));
+
+        nodes
+    }
+
+    /// Format the transitive supertrait chain and any subtraits found in loaded crates
+    fn format_trait_hierarchy<'a>(&'a self, item: DocRef<'a, Item>) -> Vec<DocumentNode<'a>> {
+        let mut nodes = vec![];
+
+        // Walk the supertrait graph breadth-first, emitting one `Trait: Bounds` line per trait
+        let mut chain_spans = vec![];
+        let mut chain_len = 0;
+        let mut queue = VecDeque::from([item]);
+        let mut visited = vec![item];
+        while let Some(trait_item) = queue.pop_front() {
+            let ItemEnum::Trait(trait_data) = trait_item.inner() else {
+                continue;
+            };
+            if trait_data.bounds.is_empty() {
+                continue;
+            }
+
+            if chain_len > 0 {
+                chain_spans.push(Span::plain("\n"));
+            }
+            chain_len += 1;
+
+            let name = trait_item.name().unwrap_or("<unnamed>");
+            chain_spans.push(Span::type_name(name).with_target(Some(trait_item)));
+            chain_spans.push(Span::punctuation(":"));
+            chain_spans.push(Span::plain(" "));
+            chain_spans.extend(self.format_generic_bounds(trait_item, &trait_data.bounds));
+
+            for supertrait in trait_item.supertraits() {
+                if !visited.contains(&supertrait) {
+                    visited.push(supertrait);
+                    queue.push_back(supertrait);
+                }
+            }
+        }
+
+        // A single line would only repeat the bounds already shown in the signature
+        if chain_len > 1 {
+            nodes.push(DocumentNode::section(
+                vec![Span::plain("Supertraits")],
+                vec![DocumentNode::generated_code(chain_spans)],
+            ));
         }
 
-        if !member_items.is_empty() {
-            nodes.push(DocumentNode::list(member_items));
+        let subtrait_items = item
+            .subtraits()
+            .into_iter()
+            .map(|subtrait| {
+                let path = subtrait.path().map_or_else(
+                    || subtrait.name().unwrap_or("<unnamed>").to_string(),
+                    |path| path.to_string(),
+                );
+                ListItem::new(vec![DocumentNode::paragraph(vec![
+                    Span::type_name(path).with_target(Some(subtrait)),
+                ])])
+            })
+            .collect::<Vec<_>>();
+
+        if !subtrait_items.is_empty() {
+            nodes.push(DocumentNode::section(
+                vec![Span::plain("Subtraits in Loaded Crates")],
+                vec![DocumentNode::list(subtrait_items)],
+            ));
         }
 
         nodes
@@ -117,41 +230,6 @@ impl Request {
         spans
     }
 
-    fn format_trait_assoc_type_signature<'a>(
-        &self,
-        item: DocRef<'a, Item>,
-        generics: &'a Generics,
-        bounds: &'a [GenericBound],
-        type_: Option<&'a Type>,
-        type_name: &'a str,
-    ) -> Vec<Span<'a>> {
-        let mut spans = vec![
-            Span::keyword("type"),
-            Span::plain(" "),
-            Span::type_name(type_name),
-        ];
-
-        if !generics.params.is_empty() {
-            spans.extend(self.format_generics(item, generics));
-        }
-
-        if !bounds.is_empty() {
-            spans.push(Span::punctuation(":"));
-            spans.push(Span::plain(" "));
-            spans.extend(self.format_generic_bounds(item, bounds));
-        }
-
-        if let Some(default_type) = type_ {
-            spans.push(Span::plain(" "));
-            spans.push(Span::operator("="));
-            spans.push(Span::plain(" "));
-            spans.extend(self.format_type(item, default_type));
-        }
-
-        spans.push(Span::punctuation(";"));
-        spans
-    }
-
     fn format_trait_method_signature<'a>(
         &self,
         item: DocRef<'a, Item>,
@@ -17,7 +17,9 @@ impl Request {
                 spans
             }
             Type::Generic(name) => vec![Span::generic(name)],
-            Type::Primitive(prim) => vec![Span::type_name(prim)],
+            Type::Primitive(prim) => {
+                vec![Span::type_name(prim).with_path(format!("std::prim@{prim}"))]
+            }
             Type::Array { type_, len } => {
                 let mut spans = vec![Span::punctuation("[")];
                 spans.extend(self.format_type(item, type_));
@@ -40,7 +42,9 @@ impl Request {
                 ..
             } => {
                 let mut spans = vec![Span::operator("&")];
-                if let Some(lt) = lifetime {
+                if let Some(lt) = lifetime
+                    && !self.format_context().verbosity().is_short()
+                {
                     spans.push(Span::lifetime(lt));
                     spans.push(Span::plain(" "));
                 }
@@ -74,10 +78,61 @@ impl Request {
                 self_type,
                 trait_,
             } => self.format_qualified_path(item, name, args.as_deref(), self_type, trait_),
-            Type::Pat { .. } => vec![Span::plain("pattern")],
+            // The pattern itself isn't part of the stable format, so show the base type
+            Type::Pat { type_, .. } => self.format_type(item, type_),
         }
     }
 
+    /// Format an associated type declaration, as in a trait, or its definition in an impl
+    ///
+    /// Generic associated types keep their parameters and where clause, kept on the same line
+    /// as rustfmt does: `type Item<'a>: Debug where Self: 'a;`
+    pub(super) fn format_assoc_type_signature<'a>(
+        &self,
+        item: DocRef<'a, Item>,
+        generics: &'a Generics,
+        bounds: &'a [GenericBound],
+        type_: Option<&'a Type>,
+        type_name: &'a str,
+    ) -> Vec<Span<'a>> {
+        let mut spans = vec![
+            Span::keyword("type"),
+            Span::plain(" "),
+            Span::type_name(type_name),
+        ];
+
+        spans.extend(self.format_generics(item, generics));
+
+        if !bounds.is_empty() {
+            spans.push(Span::punctuation(":"));
+            spans.push(Span::plain(" "));
+            spans.extend(self.format_generic_bounds(item, bounds));
+        }
+
+        if !generics.where_predicates.is_empty() && !self.format_context().verbosity().is_short() {
+            spans.push(Span::plain(" "));
+            spans.push(Span::keyword("where"));
+            spans.push(Span::plain(" "));
+            for (i, predicate) in generics.where_predicates.iter().enumerate() {
+                if i > 0 {
+                    spans.push(Span::punctuation(","));
+                    spans.push(Span::plain(" "));
+                }
+                spans.extend(self.format_where_predicate(item, predicate));
+            }
+        }
+
+        if let Some(default_type) = type_ {
+            spans.push(Span::plain(" "));
+            spans.push(Span::operator("="));
+            spans.push(Span::plain(" "));
+            spans.extend(self.format_type(item, default_type));
+        }
+
+        spans.push(Span::punctuation(";"));
+        spans
+    }
+
     pub(crate) fn format_tuple<'a>(
         &self,
         item: DocRef<'a, Item>,
@@ -1,4 +1,6 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use crate::verbosity::Verbosity;
 
 /// Context for formatting operations
 ///
@@ -10,6 +12,14 @@ pub(crate) struct FormatContext {
     include_source: AtomicBool,
     /// Whether to show recursive/nested content
     recursive: AtomicBool,
+    /// Whether to include `#[doc(hidden)]` items in listings and search (toggled at runtime)
+    show_hidden: AtomicBool,
+    /// Whether to omit search results that don't exist on the documented platform
+    hide_unavailable: AtomicBool,
+    /// Whether search results show the components of their scores
+    rank_debug: AtomicBool,
+    /// How much of each signature to show, as a [`Verbosity`] (changed at runtime)
+    verbosity: AtomicU8,
 }
 
 impl FormatContext {
@@ -17,6 +27,10 @@ impl FormatContext {
         Self {
             include_source: AtomicBool::new(false),
             recursive: AtomicBool::new(false),
+            show_hidden: AtomicBool::new(false),
+            hide_unavailable: AtomicBool::new(false),
+            rank_debug: AtomicBool::new(false),
+            verbosity: AtomicU8::new(Verbosity::default().as_u8()),
         }
     }
 
@@ -47,4 +61,60 @@ impl FormatContext {
         self.set_recursive(value);
         self
     }
+
+    /// Check if `#[doc(hidden)]` items should be shown
+    pub(crate) fn show_hidden(&self) -> bool {
+        self.show_hidden.load(Ordering::Relaxed)
+    }
+
+    /// Set hidden item display (thread-safe)
+    pub(crate) fn set_show_hidden(&self, value: bool) -> &Self {
+        self.show_hidden.store(value, Ordering::Relaxed);
+        self // For chaining
+    }
+
+    /// Builder method for show_hidden
+    pub(crate) fn with_show_hidden(self, value: bool) -> Self {
+        self.set_show_hidden(value);
+        self
+    }
+
+    /// Check if items unavailable on the documented platform should be left out of search
+    pub(crate) fn hide_unavailable(&self) -> bool {
+        self.hide_unavailable.load(Ordering::Relaxed)
+    }
+
+    /// Builder method for hide_unavailable
+    pub(crate) fn with_hide_unavailable(self, value: bool) -> Self {
+        self.hide_unavailable.store(value, Ordering::Relaxed);
+        self
+    }
+
+    /// Check if search results should show how their scores were reached
+    pub(crate) fn rank_debug(&self) -> bool {
+        self.rank_debug.load(Ordering::Relaxed)
+    }
+
+    /// Builder method for rank_debug
+    pub(crate) fn with_rank_debug(self, value: bool) -> Self {
+        self.rank_debug.store(value, Ordering::Relaxed);
+        self
+    }
+
+    /// How much of each signature to show
+    pub(crate) fn verbosity(&self) -> Verbosity {
+        Verbosity::from_u8(self.verbosity.load(Ordering::Relaxed))
+    }
+
+    /// Set signature verbosity (thread-safe)
+    pub(crate) fn set_verbosity(&self, value: Verbosity) -> &Self {
+        self.verbosity.store(value.as_u8(), Ordering::Relaxed);
+        self // For chaining
+    }
+
+    /// Builder method for verbosity
+    pub(crate) fn with_verbosity(self, value: Verbosity) -> Self {
+        self.set_verbosity(value);
+        self
+    }
 }
@@ -1,25 +1,54 @@
 use ferritin_common::{DocRef, doc_ref::Path};
 use rustdoc_types::{Item, ItemEnum};
+use std::path::Path as FsPath;
 
 pub(crate) fn generate_docsrs_url(item: DocRef<'_, Item>) -> String {
+    generate_item_url(item, None)
+}
+
+/// Generate the URL of the page a user should be sent to for an item: the local
+/// `target/doc` HTML for workspace crates whose docs have been built, docs.rs (or
+/// doc.rust-lang.org) otherwise
+pub(crate) fn generate_browsable_url(item: DocRef<'_, Item>) -> String {
+    let crate_dir = item.crate_docs().name().replace('-', "_");
+    let local_doc_dir = item
+        .navigator()
+        .local_source()
+        .map(|local_source| local_source.target_dir().join("doc"))
+        .filter(|doc_dir| doc_dir.join(&crate_dir).join("index.html").is_file());
+    generate_item_url(item, local_doc_dir.as_deref())
+}
+
+/// Generate the documentation URL for an item
+///
+/// Std items link to doc.rust-lang.org and everything else to docs.rs, except that workspace
+/// crates link into `local_doc_dir` (usually `target/doc`) when one is given, since they
+/// may not be published.
+pub(crate) fn generate_item_url(item: DocRef<'_, Item>, local_doc_dir: Option<&FsPath>) -> String {
     let docs = item.crate_docs();
     let crate_name = docs.name();
-    let version = docs.crate_version.as_deref().unwrap_or("latest");
-    let is_std = docs.provenance().is_std();
+    let version = docs.crate_version().unwrap_or("latest");
+
+    let base = if docs.provenance().is_std() {
+        String::from("https://doc.rust-lang.org/nightly")
+    } else if let Some(local_doc_dir) = local_doc_dir.filter(|_| docs.provenance().is_workspace()) {
+        format!("file://{}", local_doc_dir.display())
+    } else {
+        format!("https://docs.rs/{crate_name}/{version}")
+    };
 
     // Check if this item has its own page (has a path in the paths map)
     if let Some(path) = item.path() {
-        generate_url_for_item_with_path(crate_name, version, is_std, &path, &item)
+        generate_url_for_item_with_path(crate_name, &base, &path, &item)
     } else {
         // This is an associated item or variant - need to find parent and generate fragment URL
-        generate_url_for_associated_item(item, crate_name, version, is_std)
+        generate_url_for_associated_item(item, crate_name, &base, local_doc_dir)
     }
 }
 
 fn generate_url_for_item_with_path(
     crate_name: &str,
-    version: &str,
-    is_std: bool,
+    base: &str,
     path: &Path<'_>,
     item: &DocRef<'_, Item>,
 ) -> String {
@@ -29,11 +58,8 @@ fn generate_url_for_item_with_path(
     let item_name = item.name().unwrap_or("unknown");
     let kind = item.kind();
 
-    let base = if is_std {
-        String::from("http://docs.rust-lang.org/nightly")
-    } else {
-        format!("https://docs.rs/{crate_name}/{version}",)
-    };
+    // rustdoc's output directories use the crate's underscored name
+    let crate_name = crate_name.replace('-', "_");
 
     // For modules, the full path (after crate name) forms the module path
     // For other items, the last part is the item name, everything before is the module path
@@ -90,14 +116,24 @@ fn generate_url_for_item_with_path(
                 rustdoc_types::ItemKind::Union => {
                     format!("{}/{}/union.{}.html", base, path_prefix, item_name)
                 }
-                rustdoc_types::ItemKind::Macro
-                | rustdoc_types::ItemKind::ProcAttribute
-                | rustdoc_types::ItemKind::ProcDerive => {
+                rustdoc_types::ItemKind::TraitAlias => {
+                    format!("{}/{}/traitalias.{}.html", base, path_prefix, item_name)
+                }
+                rustdoc_types::ItemKind::Macro => {
                     format!("{}/{}/macro.{}.html", base, path_prefix, item_name)
                 }
+                rustdoc_types::ItemKind::ProcAttribute => {
+                    format!("{}/{}/attr.{}.html", base, path_prefix, item_name)
+                }
+                rustdoc_types::ItemKind::ProcDerive => {
+                    format!("{}/{}/derive.{}.html", base, path_prefix, item_name)
+                }
                 rustdoc_types::ItemKind::Primitive => {
                     format!("{}/{}/primitive.{}.html", base, crate_name, item_name)
                 }
+                rustdoc_types::ItemKind::Keyword => {
+                    format!("{}/{}/keyword.{}.html", base, crate_name, item_name)
+                }
                 _ => {
                     // Fallback for unknown kinds
                     format!("{}/{}/", base, crate_name)
@@ -110,8 +146,8 @@ fn generate_url_for_item_with_path(
 fn generate_url_for_associated_item(
     item: DocRef<'_, Item>,
     crate_name: &str,
-    version: &str,
-    is_std: bool,
+    base: &str,
+    local_doc_dir: Option<&FsPath>,
 ) -> String {
     let docs = item.crate_docs();
     let item_id = &item.id;
@@ -119,51 +155,66 @@ fn generate_url_for_associated_item(
     let kind = item.kind();
 
     // Search through all impl blocks to find which one contains this item
-    for impl_item in docs.index.values() {
+    for impl_item in docs.items().impls() {
         if let ItemEnum::Impl(impl_block) = &impl_item.inner
             && impl_block.items.contains(item_id)
         {
             // Found the parent impl
-            if let rustdoc_types::Type::ResolvedPath(path) = &impl_block.for_
-                && let Some(parent) = item.get(&path.id)
-            {
-                // Generate parent URL
-                let parent_url = generate_docsrs_url(parent);
-
-                // Generate fragment based on item kind
-                let fragment = match kind {
-                    rustdoc_types::ItemKind::Function => {
-                        if impl_block.trait_.is_some() {
-                            // Trait method
-                            format!("#method.{}", item_name)
-                        } else {
-                            // Inherent method
-                            format!("#method.{}", item_name)
-                        }
-                    }
-                    rustdoc_types::ItemKind::AssocConst => {
-                        format!("#associatedconstant.{}", item_name)
-                    }
-                    rustdoc_types::ItemKind::AssocType => {
-                        format!("#associatedtype.{}", item_name)
-                    }
-                    _ => String::new(),
-                };
-
-                return format!("{}{}", parent_url, fragment);
-            }
+            let parent_url = match &impl_block.for_ {
+                rustdoc_types::Type::ResolvedPath(path) => match item.get(&path.id) {
+                    Some(parent) => generate_item_url(parent, local_doc_dir),
+                    None => continue,
+                },
+                // Inherent impls on primitives only exist in core/alloc/std
+                rustdoc_types::Type::Primitive(primitive) => format!(
+                    "{}/{}/primitive.{}.html",
+                    base,
+                    crate_name.replace('-', "_"),
+                    primitive
+                ),
+                _ => continue,
+            };
+
+            // Generate fragment based on item kind (trait and inherent methods alike)
+            let fragment = match kind {
+                rustdoc_types::ItemKind::Function => format!("#method.{}", item_name),
+                rustdoc_types::ItemKind::AssocConst => {
+                    format!("#associatedconstant.{}", item_name)
+                }
+                rustdoc_types::ItemKind::AssocType => format!("#associatedtype.{}", item_name),
+                _ => String::new(),
+            };
+
+            return format!("{}{}", parent_url, fragment);
+        }
+    }
+
+    // Check if this is declared in a trait; required methods have their own anchor prefix
+    for trait_item in docs.items().values() {
+        if let ItemEnum::Trait(trait_data) = &trait_item.inner
+            && trait_data.items.contains(item_id)
+        {
+            let parent_url = generate_item_url(item.build_ref(trait_item), local_doc_dir);
+            let fragment = match item.inner() {
+                ItemEnum::Function(function) if function.has_body => "method",
+                ItemEnum::Function(_) => "tymethod",
+                ItemEnum::AssocConst { .. } => "associatedconstant",
+                ItemEnum::AssocType { .. } => "associatedtype",
+                _ => return parent_url,
+            };
+            return format!("{}#{}.{}", parent_url, fragment, item_name);
         }
     }
 
     // Check if this is an enum variant
     if matches!(kind, rustdoc_types::ItemKind::Variant) {
         // Find the parent enum
-        for enum_item in docs.index.values() {
+        for enum_item in docs.items().values() {
             if let ItemEnum::Enum(enum_data) = &enum_item.inner
                 && enum_data.variants.contains(item_id)
             {
                 let parent = item.build_ref(enum_item);
-                let parent_url = generate_docsrs_url(parent);
+                let parent_url = generate_item_url(parent, local_doc_dir);
                 return format!("{}#variant.{}", parent_url, item_name);
             }
         }
@@ -172,21 +223,17 @@ fn generate_url_for_associated_item(
     // Check if this is a struct field
     if matches!(kind, rustdoc_types::ItemKind::StructField) {
         // Find the parent struct
-        for struct_item in docs.index.values() {
+        for struct_item in docs.items().values() {
             if let ItemEnum::Struct(struct_data) = &struct_item.inner
                 && matches!(&struct_data.kind, rustdoc_types::StructKind::Plain { fields, .. } if fields.contains(item_id))
             {
                 let parent = item.build_ref(struct_item);
-                let parent_url = generate_docsrs_url(parent);
+                let parent_url = generate_item_url(parent, local_doc_dir);
                 return format!("{}#structfield.{}", parent_url, item_name);
             }
         }
     }
 
     // Fallback - couldn't determine parent
-    if is_std {
-        format!("https://doc.rust-lang.org/nightly/{}/", crate_name)
-    } else {
-        format!("https://docs.rs/{}/{}/{}/", crate_name, version, crate_name)
-    }
+    format!("{}/{}/", base, crate_name.replace('-', "_"))
 }
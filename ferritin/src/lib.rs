@@ -0,0 +1,37 @@
+//! Human-friendly rendering of Rust documentation
+//!
+//! This crate is mostly the `ferritin` binary, but [`Renderer`] renders items the way it shows
+//! them (in tty, plain text or markdown form) for tools that would otherwise run it and capture
+//! its output. Items are found with a [`ferritin_common::Navigator`].
+
+#![allow(dead_code)]
+
+mod cli;
+mod color_scheme;
+mod commands;
+mod config;
+mod embed;
+mod format;
+mod format_context;
+mod generate_docsrs_url;
+mod indent;
+mod logging;
+mod markdown;
+mod render_context;
+mod renderer;
+mod request;
+mod styled_string;
+mod terminal_background;
+#[cfg(test)]
+mod tests;
+mod traits;
+mod verbosity;
+mod watch;
+
+pub use embed::{OutputFormat, RenderOptions, Renderer};
+
+/// The `ferritin` binary's entry point, which isn't part of the library's API
+#[doc(hidden)]
+pub fn main() -> std::process::ExitCode {
+    cli::main()
+}
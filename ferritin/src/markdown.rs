@@ -1,13 +1,22 @@
 use crate::styled_string::{
-    DocumentNode, HeadingLevel, LinkTarget, ListItem, Span, SpanStyle, TuiAction,
+    DocumentNode, Footnote, HeadingLevel, LinkTarget, ListItem, Span, SpanStyle, TruncationLevel,
+    TuiAction,
 };
+use html::HtmlConverter;
 use pulldown_cmark::{BrokenLink, CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+mod html;
 
 /// Stack item for building the document tree
 /// We need this because Lists contain ListItems (not DocumentNodes directly)
 enum StackItem<'a> {
     Node(DocumentNode<'a>),
     Item(ListItem<'a>),
+    /// A footnote definition, by label, which is moved to the end of the document once
+    /// it's complete
+    Footnote(String, Vec<DocumentNode<'a>>),
 }
 
 pub struct MarkdownRenderer;
@@ -31,8 +40,21 @@ impl MarkdownRenderer {
 
         let mut options = Options::empty();
         options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_FOOTNOTES);
+        options.insert(Options::ENABLE_DEFINITION_LIST);
         let parser = Parser::new_with_broken_link_callback(markdown, options, Some(&callback));
 
+        // HTML in the markdown is converted to the events for its markdown equivalent
+        let mut html = HtmlConverter::default();
+        let events = parser.flat_map(|event| match event {
+            Event::Html(chunk) | Event::InlineHtml(chunk) => html.convert(&chunk),
+            Event::End(TagEnd::HtmlBlock) => {
+                html.end_block();
+                vec![]
+            }
+            event => vec![event],
+        });
+
         let mut root: Vec<DocumentNode<'a>> = Vec::new();
         let mut stack: Vec<StackItem<'a>> = Vec::new();
         let mut current_spans: Vec<Span<'a>> = Vec::new();
@@ -44,9 +66,14 @@ impl MarkdownRenderer {
         let mut in_strong = false;
         let mut in_emphasis = false;
         let mut in_strikethrough = false;
+        let mut in_superscript = false;
+        let mut in_subscript = false;
+        let mut in_html_code = false;
         let mut in_heading = false;
         let mut heading_level: Option<HeadingLevel> = None;
         let mut current_link_action: Option<TuiAction<'a>> = None;
+        // The url and alt text collected so far while inside an image
+        let mut current_image: Option<(String, String)> = None;
 
         // Table state
         let mut in_table_head = false;
@@ -54,7 +81,12 @@ impl MarkdownRenderer {
         let mut table_rows: Vec<Vec<crate::styled_string::TableCell<'a>>> = Vec::new();
         let mut current_row: Vec<crate::styled_string::TableCell<'a>> = Vec::new();
 
-        for event in parser {
+        // Footnotes are numbered in the order they're first referenced, and their definitions
+        // are collected for the end of the document
+        let mut footnote_numbers: HashMap<String, usize> = HashMap::new();
+        let mut footnote_definitions: Vec<(String, Vec<DocumentNode<'a>>)> = Vec::new();
+
+        for event in events {
             match event {
                 Event::Start(tag) => match tag {
                     Tag::CodeBlock(kind) => {
@@ -81,17 +113,31 @@ impl MarkdownRenderer {
                     Tag::Strikethrough => {
                         in_strikethrough = true;
                     }
+                    Tag::Superscript => {
+                        in_superscript = true;
+                    }
+                    Tag::Subscript => {
+                        in_subscript = true;
+                    }
                     Tag::Link { dest_url, .. } => {
                         // Resolve the link and determine the action
                         let action = if let Some(target) = link_resolver(dest_url.as_ref()) {
+                            // Keep any `#fragment` so the target heading can be scrolled to
+                            let anchor = dest_url
+                                .split_once('#')
+                                .map(|(_, fragment)| fragment)
+                                .filter(|fragment| !fragment.is_empty())
+                                .map(|fragment| Cow::Owned(fragment.to_string()));
                             match target {
                                 LinkTarget::Resolved(doc_ref) => TuiAction::Navigate {
                                     doc_ref,
                                     url: None, // URL generation deferred to renderer
+                                    anchor,
                                 },
                                 LinkTarget::Path(path) => TuiAction::NavigateToPath {
                                     path,
                                     url: None, // URL generation deferred to renderer
+                                    anchor,
                                 },
                             }
                         } else {
@@ -99,6 +145,9 @@ impl MarkdownRenderer {
                         };
                         current_link_action = Some(action);
                     }
+                    Tag::Image { dest_url, .. } => {
+                        current_image = Some((dest_url.to_string(), String::new()));
+                    }
                     Tag::Heading { level, .. } => {
                         in_heading = true;
                         // Map pulldown_cmark HeadingLevel to our HeadingLevel
@@ -147,6 +196,21 @@ impl MarkdownRenderer {
                     Tag::Paragraph => {
                         // Paragraphs will be created when we hit TagEnd::Paragraph
                     }
+                    Tag::FootnoteDefinition(label) => {
+                        Self::flush_paragraph(&mut stack, &mut root, &mut current_spans);
+                        stack.push(StackItem::Footnote(label.to_string(), vec![]));
+                    }
+                    Tag::DefinitionList => {
+                        // Each term becomes a list item, with its definitions beneath it
+                        Self::flush_paragraph(&mut stack, &mut root, &mut current_spans);
+                        stack.push(StackItem::Node(DocumentNode::List { items: vec![] }));
+                    }
+                    Tag::DefinitionListTitle => {
+                        Self::close_definition_item(&mut stack, &mut root);
+                        stack.push(StackItem::Item(ListItem::new(vec![])));
+                        in_strong = true;
+                    }
+                    Tag::DefinitionListDefinition => {}
                     _ => {}
                 },
                 Event::End(tag_end) => match tag_end {
@@ -205,10 +269,21 @@ impl MarkdownRenderer {
                     TagEnd::Strikethrough => {
                         in_strikethrough = false;
                     }
+                    TagEnd::Superscript => {
+                        in_superscript = false;
+                    }
+                    TagEnd::Subscript => {
+                        in_subscript = false;
+                    }
                     TagEnd::Link => {
                         // Just clear the link action - spans have already been created with it
                         current_link_action = None;
                     }
+                    TagEnd::Image => {
+                        if let Some((url, alt)) = current_image.take() {
+                            current_spans.push(Span::image(alt, url));
+                        }
+                    }
                     TagEnd::BlockQuote(_) => {
                         // Flush any remaining spans as a paragraph before closing the blockquote
                         if !current_spans.is_empty() {
@@ -270,13 +345,43 @@ impl MarkdownRenderer {
                         };
                         Self::push_to_parent(&mut stack, &mut root, StackItem::Node(table));
                     }
+                    TagEnd::FootnoteDefinition => {
+                        Self::flush_paragraph(&mut stack, &mut root, &mut current_spans);
+                        if let Some(StackItem::Footnote(label, content)) = stack.pop() {
+                            footnote_definitions.push((label, content));
+                        }
+                    }
+                    TagEnd::DefinitionListTitle => {
+                        in_strong = false;
+                        Self::flush_paragraph(&mut stack, &mut root, &mut current_spans);
+                    }
+                    TagEnd::DefinitionListDefinition => {
+                        Self::flush_paragraph(&mut stack, &mut root, &mut current_spans);
+                    }
+                    TagEnd::DefinitionList => {
+                        Self::close_definition_item(&mut stack, &mut root);
+                        if let Some(StackItem::Node(list)) = stack.pop() {
+                            Self::push_to_parent(&mut stack, &mut root, StackItem::Node(list));
+                        }
+                    }
                     _ => {}
                 },
                 Event::Text(text) => {
                     if in_code_block {
                         code_block_content.push_str(&text);
+                    } else if let Some((_, alt)) = &mut current_image {
+                        alt.push_str(&text);
                     } else {
-                        let style = if in_strong {
+                        let text = if in_superscript {
+                            html::superscript(&text).into()
+                        } else if in_subscript {
+                            html::subscript(&text).into()
+                        } else {
+                            text
+                        };
+                        let style = if in_html_code {
+                            SpanStyle::InlineCode
+                        } else if in_strong {
                             SpanStyle::Strong
                         } else if in_emphasis {
                             SpanStyle::Emphasis
@@ -316,6 +421,45 @@ impl MarkdownRenderer {
                         StackItem::Node(DocumentNode::HorizontalRule),
                     );
                 }
+                Event::InlineHtml(tag) => match &*tag {
+                    html::DETAILS_START => {
+                        // The summary is the first node, which is all that shows until the
+                        // block is expanded
+                        Self::flush_paragraph(&mut stack, &mut root, &mut current_spans);
+                        stack.push(StackItem::Node(DocumentNode::truncated_block(
+                            vec![],
+                            TruncationLevel::SingleLine,
+                        )));
+                    }
+                    html::DETAILS_END => {
+                        Self::flush_paragraph(&mut stack, &mut root, &mut current_spans);
+                        if matches!(
+                            stack.last(),
+                            Some(StackItem::Node(DocumentNode::TruncatedBlock { .. }))
+                        ) && let Some(details) = stack.pop()
+                        {
+                            Self::push_to_parent(&mut stack, &mut root, details);
+                        }
+                    }
+                    html::SUMMARY_START => {
+                        Self::flush_paragraph(&mut stack, &mut root, &mut current_spans);
+                        in_strong = true;
+                    }
+                    html::SUMMARY_END => {
+                        in_strong = false;
+                        Self::flush_paragraph(&mut stack, &mut root, &mut current_spans);
+                    }
+                    html::CODE_START => in_html_code = true,
+                    html::CODE_END => in_html_code = false,
+                    _ => {}
+                },
+                Event::FootnoteReference(label) => {
+                    let next_number = footnote_numbers.len() + 1;
+                    let number = *footnote_numbers
+                        .entry(label.to_string())
+                        .or_insert(next_number);
+                    current_spans.push(Span::footnote_reference(number));
+                }
                 _ => {}
             }
         }
@@ -325,9 +469,46 @@ impl MarkdownRenderer {
             root.push(DocumentNode::paragraph(std::mem::take(&mut current_spans)));
         }
 
+        if !footnote_definitions.is_empty() {
+            // Definitions that are never referenced are numbered after the ones that are
+            let mut notes: Vec<Footnote<'a>> = footnote_definitions
+                .into_iter()
+                .map(|(label, content)| {
+                    let next_number = footnote_numbers.len() + 1;
+                    let number = *footnote_numbers.entry(label).or_insert(next_number);
+                    Footnote { number, content }
+                })
+                .collect();
+            notes.sort_by_key(|note| note.number);
+            root.push(DocumentNode::footnotes(notes));
+        }
+
         root
     }
 
+    /// Push any accumulated spans to the current parent as a paragraph
+    fn flush_paragraph<'a>(
+        stack: &mut Vec<StackItem<'a>>,
+        root: &mut Vec<DocumentNode<'a>>,
+        current_spans: &mut Vec<Span<'a>>,
+    ) {
+        if !current_spans.is_empty() {
+            let para = DocumentNode::Paragraph {
+                spans: std::mem::take(current_spans),
+            };
+            Self::push_to_parent(stack, root, StackItem::Node(para));
+        }
+    }
+
+    /// Finish the definition list item for the previous term, if there is one
+    fn close_definition_item<'a>(stack: &mut Vec<StackItem<'a>>, root: &mut Vec<DocumentNode<'a>>) {
+        if matches!(stack.last(), Some(StackItem::Item(_)))
+            && let Some(item) = stack.pop()
+        {
+            Self::push_to_parent(stack, root, item);
+        }
+    }
+
     /// Push a completed StackItem to its parent container
     fn push_to_parent<'a>(
         stack: &mut Vec<StackItem<'a>>,
@@ -339,7 +520,7 @@ impl MarkdownRenderer {
                 // Push DocumentNode to ListItem's content
                 match item {
                     StackItem::Node(node) => list_item.content.push(node),
-                    StackItem::Item(_) => {
+                    StackItem::Item(_) | StackItem::Footnote(..) => {
                         panic!(
                             "Cannot nest ListItem directly in ListItem - lists should be nested via DocumentNode::List"
                         )
@@ -350,18 +531,31 @@ impl MarkdownRenderer {
                 // Push ListItem to List's items
                 match item {
                     StackItem::Item(list_item) => items.push(list_item),
-                    StackItem::Node(_) => {
+                    StackItem::Node(_) | StackItem::Footnote(..) => {
                         panic!(
                             "Cannot push DocumentNode directly to List - must be wrapped in ListItem"
                         )
                     }
                 }
             }
-            Some(StackItem::Node(DocumentNode::BlockQuote { nodes })) => {
-                // Push DocumentNode to BlockQuote's nodes
+            Some(StackItem::Footnote(_, content)) => {
+                // Push DocumentNode to the footnote's content
+                match item {
+                    StackItem::Node(node) => content.push(node),
+                    StackItem::Item(_) | StackItem::Footnote(..) => {
+                        panic!(
+                            "Cannot push ListItem directly to a footnote - lists should be nested via DocumentNode::List"
+                        )
+                    }
+                }
+            }
+            Some(StackItem::Node(
+                DocumentNode::BlockQuote { nodes } | DocumentNode::TruncatedBlock { nodes, .. },
+            )) => {
+                // Push DocumentNode to BlockQuote's (or a `<details>` block's) nodes
                 match item {
                     StackItem::Node(node) => nodes.push(node),
-                    StackItem::Item(_) => {
+                    StackItem::Item(_) | StackItem::Footnote(..) => {
                         panic!(
                             "Cannot push ListItem directly to BlockQuote - lists should be nested via DocumentNode::List"
                         )
@@ -372,7 +566,7 @@ impl MarkdownRenderer {
                 // Push to root
                 match item {
                     StackItem::Node(node) => root.push(node),
-                    StackItem::Item(_) => {
+                    StackItem::Item(_) | StackItem::Footnote(..) => {
                         panic!("Cannot push ListItem to root - must be inside a List")
                     }
                 }
@@ -506,4 +700,165 @@ mod tests {
             panic!("Expected a List node");
         }
     }
+
+    #[test]
+    fn test_footnotes() {
+        let input = "Second[^b] and first[^a], then second again[^b].\n\n\
+                     [^a]: The first note.\n\n\
+                     [^b]: The second note.";
+        let nodes = MarkdownRenderer::render_with_resolver(input, |_| None);
+
+        let DocumentNode::Paragraph { spans } = &nodes[0] else {
+            panic!("Expected a paragraph, got {:?}", nodes[0]);
+        };
+        let references: Vec<_> = spans
+            .iter()
+            .filter(|span| span.style == SpanStyle::FootnoteReference)
+            .map(|span| &*span.text)
+            .collect();
+        assert_eq!(references, ["[1]", "[2]", "[1]"]);
+
+        // The definitions move to the end, in the order of their numbers
+        let Some(DocumentNode::Footnotes { notes }) = nodes.last() else {
+            panic!("Expected footnotes at the end, got {nodes:?}");
+        };
+        assert_eq!(nodes.len(), 2);
+        let texts: Vec<_> = notes
+            .iter()
+            .map(|note| match &note.content[..] {
+                [DocumentNode::Paragraph { spans }] => (note.number, &*spans[0].text),
+                other => panic!("Unexpected footnote content {other:?}"),
+            })
+            .collect();
+        assert_eq!(texts, [(1, "The second note."), (2, "The first note.")]);
+    }
+
+    #[test]
+    fn test_definition_list() {
+        let input = "Term\n: Its definition\n\nOther term\n: One meaning\n: Another meaning";
+        let nodes = MarkdownRenderer::render_with_resolver(input, |_| None);
+
+        let [DocumentNode::List { items }] = &nodes[..] else {
+            panic!("Expected a single list, got {nodes:?}");
+        };
+        assert_eq!(items.len(), 2);
+
+        let texts = |item: &ListItem| -> Vec<(String, SpanStyle)> {
+            item.content
+                .iter()
+                .map(|node| match node {
+                    DocumentNode::Paragraph { spans } => (
+                        spans.iter().map(|span| &*span.text).collect(),
+                        spans[0].style,
+                    ),
+                    other => panic!("Unexpected node {other:?}"),
+                })
+                .collect()
+        };
+        assert_eq!(
+            texts(&items[0]),
+            [
+                ("Term".to_string(), SpanStyle::Strong),
+                ("Its definition".to_string(), SpanStyle::Plain)
+            ]
+        );
+        assert_eq!(
+            texts(&items[1]),
+            [
+                ("Other term".to_string(), SpanStyle::Strong),
+                ("One meaning".to_string(), SpanStyle::Plain),
+                ("Another meaning".to_string(), SpanStyle::Plain)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_details() {
+        let input = "<details>\n<summary>More about <code>Foo</code></summary>\n\n\
+                     Hidden **markdown**.\n\n</details>\n\nAfter.";
+        let nodes = MarkdownRenderer::render_with_resolver(input, |_| None);
+
+        let [
+            DocumentNode::TruncatedBlock {
+                nodes: details,
+                level,
+            },
+            after,
+        ] = &nodes[..]
+        else {
+            panic!("Expected a details block and a paragraph, got {nodes:?}");
+        };
+        assert_eq!(*level, TruncationLevel::SingleLine);
+        assert!(matches!(after, DocumentNode::Paragraph { .. }));
+
+        let [
+            DocumentNode::Paragraph { spans: summary },
+            DocumentNode::Paragraph { spans: body },
+        ] = &details[..]
+        else {
+            panic!("Expected the summary and the hidden paragraph, got {details:?}");
+        };
+        let summary: Vec<_> = summary.iter().map(|s| (&*s.text, s.style)).collect();
+        assert_eq!(
+            summary,
+            [
+                ("More about ", SpanStyle::Strong),
+                ("Foo", SpanStyle::InlineCode)
+            ]
+        );
+        assert_eq!(body[1].style, SpanStyle::Strong);
+    }
+
+    #[test]
+    fn test_inline_html() {
+        let input = "x<sup>2</sup> and H<sub>2</sub>O<br>next line";
+        let nodes = MarkdownRenderer::render_with_resolver(input, |_| None);
+
+        let [DocumentNode::Paragraph { spans }] = &nodes[..] else {
+            panic!("Expected a paragraph, got {nodes:?}");
+        };
+        let text: String = spans.iter().map(|span| &*span.text).collect();
+        assert_eq!(text, "x² and H₂O\nnext line");
+    }
+
+    #[test]
+    fn test_image() {
+        let input = "See ![the *logo*](https://example.com/logo.png) and <img src=\"a.png\">";
+        let nodes = MarkdownRenderer::render_with_resolver(input, |_| None);
+
+        let [DocumentNode::Paragraph { spans }] = &nodes[..] else {
+            panic!("Expected a paragraph, got {nodes:?}");
+        };
+        let images: Vec<_> = spans
+            .iter()
+            .filter(|span| span.style == SpanStyle::Image)
+            .map(|span| (&*span.text, span.url().unwrap()))
+            .collect();
+        assert_eq!(
+            images,
+            [
+                ("the logo", "https://example.com/logo.png".into()),
+                ("image", "a.png".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_html_table() {
+        let input = "<table>\n<tr><th>Name</th><th>Value</th></tr>\n\
+                     <tr><td><b>a</b></td><td>1 &amp; 2</td></tr>\n</table>";
+        let nodes = MarkdownRenderer::render_with_resolver(input, |_| None);
+
+        let [DocumentNode::Table { header, rows }] = &nodes[..] else {
+            panic!("Expected a table, got {nodes:?}");
+        };
+        let cell_text = |cell: &crate::styled_string::TableCell| -> String {
+            cell.spans.iter().map(|span| &*span.text).collect()
+        };
+        let header: Vec<_> = header.iter().flatten().map(cell_text).collect();
+        assert_eq!(header, ["Name", "Value"]);
+        let row: Vec<_> = rows[0].iter().map(cell_text).collect();
+        assert_eq!(row, ["a", "1 & 2"]);
+        assert_eq!(rows[0][0].spans[0].style, SpanStyle::Strong);
+    }
 }
@@ -0,0 +1,488 @@
+//! The subset of HTML that doc comments commonly use, converted to markdown events
+//!
+//! Most tags have a markdown equivalent (`<b>` is strong, `<table>` is a table, `<br>` is a
+//! hard break, `<img>` is an image), so they're turned into the events the markdown parser would produce for the
+//! same content. `<details>`, `<summary>` and inline `<code>` have none and are passed on as
+//! normalized [`Event::InlineHtml`] tags for the caller to handle. Other tags are dropped and
+//! their text kept.
+
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Tag, TagEnd};
+
+/// Tags passed through to the caller, which builds them into the document itself
+pub(super) const DETAILS_START: &str = "<details>";
+pub(super) const DETAILS_END: &str = "</details>";
+pub(super) const SUMMARY_START: &str = "<summary>";
+pub(super) const SUMMARY_END: &str = "</summary>";
+pub(super) const CODE_START: &str = "<code>";
+pub(super) const CODE_END: &str = "</code>";
+
+/// Converts the HTML in a document, which arrives a line (for HTML blocks) or a tag (for
+/// inline HTML) at a time
+#[derive(Debug, Default)]
+pub(super) struct HtmlConverter {
+    /// The start of a tag or comment that continues in the next chunk
+    partial: String,
+    in_comment: bool,
+    /// Inside `<pre>`, where whitespace is kept as written
+    in_pre: bool,
+    /// A `<tr>` has started and we're waiting to see whether its cells are headers
+    row_pending: bool,
+    /// The current row is the table's header
+    in_head_row: bool,
+    /// The current table has had a row, so later header cells start ordinary rows
+    table_has_rows: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Open { name: String, attrs: &'a str },
+    Close { name: String },
+    Text(&'a str),
+}
+
+impl HtmlConverter {
+    /// Convert a chunk of HTML from an [`Event::Html`] or [`Event::InlineHtml`]
+    pub(super) fn convert(&mut self, html: &str) -> Vec<Event<'static>> {
+        let input = if self.partial.is_empty() {
+            html.to_string()
+        } else {
+            std::mem::take(&mut self.partial) + html
+        };
+
+        let mut events = vec![];
+        let mut rest = input.as_str();
+        while !rest.is_empty() {
+            if self.in_comment {
+                match rest.find("-->") {
+                    Some(end) => {
+                        self.in_comment = false;
+                        rest = &rest[end + 3..];
+                    }
+                    None => break,
+                }
+                continue;
+            }
+
+            let (token, remaining) = match next_token(rest) {
+                Some(next) => next,
+                None => {
+                    // An unfinished tag, to be completed by the next chunk
+                    self.partial = rest.to_string();
+                    break;
+                }
+            };
+            rest = remaining;
+
+            match token {
+                Some(token) => self.convert_token(token, &mut events),
+                None => self.in_comment = true,
+            }
+        }
+
+        events
+    }
+
+    fn convert_token(&mut self, token: Token<'_>, events: &mut Vec<Event<'static>>) {
+        match token {
+            Token::Text(text) => {
+                let text = decode_entities(text);
+                if self.in_pre {
+                    // Like a fenced block, a newline right after the opening tag isn't code
+                    let text = match events.last() {
+                        Some(Event::Start(Tag::CodeBlock(_))) => {
+                            text.strip_prefix('\n').unwrap_or(&text).to_string()
+                        }
+                        _ => text,
+                    };
+                    events.push(Event::Text(text.into()));
+                } else {
+                    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                    if !collapsed.is_empty() {
+                        // Keep a single space where the text had whitespace at either end, so
+                        // words either side of inline tags stay apart
+                        let leading = if text.starts_with(char::is_whitespace) {
+                            " "
+                        } else {
+                            ""
+                        };
+                        let trailing = if text.ends_with(char::is_whitespace) {
+                            " "
+                        } else {
+                            ""
+                        };
+                        events.push(Event::Text(
+                            format!("{leading}{collapsed}{trailing}").into(),
+                        ));
+                    }
+                }
+            }
+            Token::Open { name, attrs } => self.open_tag(&name, attrs, events),
+            Token::Close { name } => self.close_tag(&name, events),
+        }
+    }
+
+    fn open_tag(&mut self, name: &str, attrs: &str, events: &mut Vec<Event<'static>>) {
+        let tag = match name {
+            "b" | "strong" => Tag::Strong,
+            "i" | "em" => Tag::Emphasis,
+            "s" | "del" | "strike" => Tag::Strikethrough,
+            "sup" => Tag::Superscript,
+            "sub" => Tag::Subscript,
+            "p" | "div" | "center" => Tag::Paragraph,
+            "blockquote" => Tag::BlockQuote(None),
+            "ul" => Tag::List(None),
+            "ol" => Tag::List(Some(1)),
+            "li" => Tag::Item,
+            "a" => match attribute(attrs, "href") {
+                Some(href) => Tag::Link {
+                    link_type: LinkType::Inline,
+                    dest_url: href.into(),
+                    title: CowStr::Borrowed(""),
+                    id: CowStr::Borrowed(""),
+                },
+                // An anchor without a link, like `<a name="...">`, has nothing to show
+                None => return,
+            },
+            "br" => return events.push(Event::HardBreak),
+            "img" => {
+                if let Some(src) = attribute(attrs, "src") {
+                    events.extend([
+                        Event::Start(Tag::Image {
+                            link_type: LinkType::Inline,
+                            dest_url: src.into(),
+                            title: CowStr::Borrowed(""),
+                            id: CowStr::Borrowed(""),
+                        }),
+                        Event::Text(attribute(attrs, "alt").unwrap_or_default().into()),
+                        Event::End(TagEnd::Image),
+                    ]);
+                }
+                return;
+            }
+            "hr" => return events.push(Event::Rule),
+            "pre" => {
+                self.in_pre = true;
+                Tag::CodeBlock(CodeBlockKind::Fenced("text".into()))
+            }
+            // Code inside `<pre>` is already part of the code block
+            "code" | "kbd" | "tt" if !self.in_pre => {
+                return events.push(Event::InlineHtml(CODE_START.into()));
+            }
+            "details" => return events.push(Event::InlineHtml(DETAILS_START.into())),
+            "summary" => return events.push(Event::InlineHtml(SUMMARY_START.into())),
+            "table" => {
+                self.table_has_rows = false;
+                Tag::Table(vec![])
+            }
+            "tr" => {
+                self.row_pending = true;
+                return;
+            }
+            "th" | "td" => {
+                if self.row_pending {
+                    // A first row of header cells is the table's header, as in markdown tables
+                    self.row_pending = false;
+                    self.in_head_row = name == "th" && !self.table_has_rows;
+                    self.table_has_rows = true;
+                    events.push(Event::Start(if self.in_head_row {
+                        Tag::TableHead
+                    } else {
+                        Tag::TableRow
+                    }));
+                }
+                Tag::TableCell
+            }
+            _ => match heading_level(name) {
+                Some(level) => Tag::Heading {
+                    level,
+                    id: None,
+                    classes: vec![],
+                    attrs: vec![],
+                },
+                None => return,
+            },
+        };
+        events.push(Event::Start(tag));
+    }
+
+    fn close_tag(&mut self, name: &str, events: &mut Vec<Event<'static>>) {
+        let tag_end = match name {
+            "b" | "strong" => TagEnd::Strong,
+            "i" | "em" => TagEnd::Emphasis,
+            "s" | "del" | "strike" => TagEnd::Strikethrough,
+            "sup" => TagEnd::Superscript,
+            "sub" => TagEnd::Subscript,
+            "p" | "div" | "center" => TagEnd::Paragraph,
+            "blockquote" => TagEnd::BlockQuote(None),
+            "ul" => TagEnd::List(false),
+            "ol" => TagEnd::List(true),
+            "li" => TagEnd::Item,
+            "a" => TagEnd::Link,
+            "pre" => {
+                self.in_pre = false;
+                TagEnd::CodeBlock
+            }
+            "code" | "kbd" | "tt" if !self.in_pre => {
+                return events.push(Event::InlineHtml(CODE_END.into()));
+            }
+            "details" => return events.push(Event::InlineHtml(DETAILS_END.into())),
+            "summary" => return events.push(Event::InlineHtml(SUMMARY_END.into())),
+            "table" => TagEnd::Table,
+            "tr" => {
+                // A row without cells was never started
+                if std::mem::take(&mut self.row_pending) {
+                    return;
+                }
+                if std::mem::take(&mut self.in_head_row) {
+                    TagEnd::TableHead
+                } else {
+                    TagEnd::TableRow
+                }
+            }
+            "th" | "td" => TagEnd::TableCell,
+            _ => match heading_level(name) {
+                Some(level) => TagEnd::Heading(level),
+                None => return,
+            },
+        };
+        events.push(Event::End(tag_end));
+    }
+
+    /// Forget anything left unfinished at the end of an HTML block
+    pub(super) fn end_block(&mut self) {
+        self.partial.clear();
+        self.in_comment = false;
+    }
+}
+
+/// Split the next token from the front of `html`
+///
+/// Returns `None` for a tag that isn't closed yet, and `Some((None, rest))` for the start of
+/// a comment.
+fn next_token(html: &str) -> Option<(Option<Token<'_>>, &str)> {
+    if let Some(rest) = html.strip_prefix("<!--") {
+        return Some((None, rest));
+    }
+
+    if !html.starts_with('<') {
+        let end = html.find('<').unwrap_or(html.len());
+        return Some((Some(Token::Text(&html[..end])), &html[end..]));
+    }
+
+    let close = html.find('>')?;
+    let inner = &html[1..close];
+    let rest = &html[close + 1..];
+
+    let token = if let Some(name) = inner.strip_prefix('/') {
+        Token::Close {
+            name: name.trim().to_ascii_lowercase(),
+        }
+    } else {
+        let inner = inner.trim_end_matches('/');
+        let name_end = inner
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(inner.len());
+        let name = &inner[..name_end];
+        if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            // Not a tag, like a `<` in text or a `<!DOCTYPE>`
+            return Some((Some(Token::Text("")), rest));
+        }
+        Token::Open {
+            name: name.to_ascii_lowercase(),
+            attrs: &inner[name_end..],
+        }
+    };
+    Some((Some(token), rest))
+}
+
+fn heading_level(name: &str) -> Option<HeadingLevel> {
+    Some(match name {
+        "h1" => HeadingLevel::H1,
+        "h2" => HeadingLevel::H2,
+        "h3" => HeadingLevel::H3,
+        "h4" => HeadingLevel::H4,
+        "h5" => HeadingLevel::H5,
+        "h6" => HeadingLevel::H6,
+        _ => return None,
+    })
+}
+
+/// The value of attribute `name`, quoted or not
+fn attribute(attrs: &str, name: &str) -> Option<String> {
+    let mut rest = attrs;
+    while let Some(pos) = rest.find('=') {
+        let key = rest[..pos].split_whitespace().last().unwrap_or_default();
+        let value_start = rest[pos + 1..].trim_start();
+        let (value, remaining) = match value_start.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = &value_start[1..];
+                let end = value.find(quote).unwrap_or(value.len());
+                (&value[..end], &value[(end + 1).min(value.len())..])
+            }
+            _ => {
+                let end = value_start
+                    .find(char::is_whitespace)
+                    .unwrap_or(value_start.len());
+                (&value_start[..end], &value_start[end..])
+            }
+        };
+        if key.eq_ignore_ascii_case(name) {
+            return Some(decode_entities(value));
+        }
+        rest = remaining;
+    }
+    None
+}
+
+/// Replace the character references that show up in doc comments with the characters they
+/// stand for
+pub(super) fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let reference = rest[1..]
+            .find(';')
+            .map(|end| &rest[1..end + 1])
+            .filter(|reference| reference.len() <= 10);
+        let character = reference.and_then(|reference| match reference {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => {
+                let number = reference.strip_prefix('#')?;
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (reference, character) {
+            (Some(reference), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[reference.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Write `text` in superscript characters, or mark it with `^` when some character has no
+/// superscript form
+pub(super) fn superscript(text: &str) -> String {
+    const FROM: &str = "0123456789+-=()ni";
+    const TO: &str = "⁰¹²³⁴⁵⁶⁷⁸⁹⁺⁻⁼⁽⁾ⁿⁱ";
+    script(text, FROM, TO, '^')
+}
+
+/// Write `text` in subscript characters, or mark it with `_` when some character has no
+/// subscript form
+pub(super) fn subscript(text: &str) -> String {
+    const FROM: &str = "0123456789+-=()";
+    const TO: &str = "₀₁₂₃₄₅₆₇₈₉₊₋₌₍₎";
+    script(text, FROM, TO, '_')
+}
+
+fn script(text: &str, from: &str, to: &str, marker: char) -> String {
+    let mapped: Option<String> = text
+        .chars()
+        .map(|c| {
+            from.chars()
+                .position(|f| f == c)
+                .and_then(|i| to.chars().nth(i))
+        })
+        .collect();
+    match mapped {
+        Some(mapped) => mapped,
+        None if text.contains(char::is_whitespace) => format!("{marker}({text})"),
+        None => format!("{marker}{text}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &amp; b &lt;T&gt;"), "a & b <T>");
+        assert_eq!(decode_entities("&#169; &#x2014;"), "© —");
+        assert_eq!(decode_entities("AT&T &unknown;"), "AT&T &unknown;");
+    }
+
+    #[test]
+    fn test_scripts() {
+        assert_eq!(superscript("2"), "²");
+        assert_eq!(superscript("TM"), "^TM");
+        assert_eq!(subscript("10"), "₁₀");
+        assert_eq!(subscript("a b"), "_(a b)");
+    }
+
+    #[test]
+    fn test_tags_split_across_chunks() {
+        let mut converter = HtmlConverter::default();
+        assert!(converter.convert("<a\n").is_empty());
+        let events = converter.convert("href=\"https://example.com\">");
+        assert!(matches!(
+            &events[..],
+            [Event::Start(Tag::Link { dest_url, .. })] if &**dest_url == "https://example.com"
+        ));
+
+        assert!(converter.convert("<!-- a comment\n").is_empty());
+        assert_eq!(
+            converter.convert("still a comment --> text"),
+            [Event::Text(" text".into())]
+        );
+    }
+
+    #[test]
+    fn test_img() {
+        let mut converter = HtmlConverter::default();
+        assert_eq!(
+            converter.convert("<img src=\"logo.png\" alt=\"The logo\" width=\"100\">"),
+            [
+                Event::Start(Tag::Image {
+                    link_type: LinkType::Inline,
+                    dest_url: "logo.png".into(),
+                    title: "".into(),
+                    id: "".into(),
+                }),
+                Event::Text("The logo".into()),
+                Event::End(TagEnd::Image),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_header_row() {
+        let mut converter = HtmlConverter::default();
+        let events = converter.convert("<table><tr><th>A</th></tr><tr><td>1</td></tr></table>");
+        assert_eq!(
+            events,
+            [
+                Event::Start(Tag::Table(vec![])),
+                Event::Start(Tag::TableHead),
+                Event::Start(Tag::TableCell),
+                Event::Text("A".into()),
+                Event::End(TagEnd::TableCell),
+                Event::End(TagEnd::TableHead),
+                Event::Start(Tag::TableRow),
+                Event::Start(Tag::TableCell),
+                Event::Text("1".into()),
+                Event::End(TagEnd::TableCell),
+                Event::End(TagEnd::TableRow),
+                Event::End(TagEnd::Table),
+            ]
+        );
+    }
+}
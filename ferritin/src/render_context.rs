@@ -1,4 +1,4 @@
-use crate::color_scheme::ColorScheme;
+use crate::color_scheme::{ColorOverrides, ColorScheme};
 use crate::renderer::OutputMode;
 use fieldwork::Fieldwork;
 use std::path::Path;
@@ -41,6 +41,12 @@ pub(crate) struct RenderContext {
     theme: Theme,
     /// The name of the currently loaded theme
     current_theme_name: Option<String>,
+    /// Draw images inline with the kitty graphics protocol, rather than as linked alt text
+    #[field(get = "inline_images")]
+    inline_images: bool,
+    /// Colors from the config file, applied over every theme
+    #[field = false]
+    color_overrides: ColorOverrides,
 }
 
 impl RenderContext {
@@ -49,6 +55,12 @@ impl RenderContext {
         themes::THEME_NAMES.iter().map(|s| s.to_string()).collect()
     }
 
+    pub(crate) fn with_color_overrides(mut self, overrides: ColorOverrides) -> Self {
+        self.color_scheme = self.color_scheme.with_overrides(overrides.clone());
+        self.color_overrides = overrides;
+        self
+    }
+
     pub(crate) fn with_theme_name(mut self, theme_name_or_path: &str) -> Result<Self, ThemeError> {
         self.set_theme_name(theme_name_or_path)?;
         Ok(self)
@@ -66,7 +78,8 @@ impl RenderContext {
                 ThemeError::FileLoadError(theme_name_or_path.to_string(), e.to_string())
             })?;
 
-            self.color_scheme = ColorScheme::from_syntect_theme(&theme);
+            self.color_scheme = ColorScheme::from_syntect_theme(&theme)
+                .with_overrides(self.color_overrides.clone());
             self.theme = theme;
             self.current_theme_name = Some(theme_name_or_path.to_string());
             return Ok(self);
@@ -74,7 +87,8 @@ impl RenderContext {
 
         // Try to load it as a theme name from the embedded set
         if let Some(theme) = themes::load_theme(theme_name_or_path) {
-            self.color_scheme = ColorScheme::from_syntect_theme(&theme);
+            self.color_scheme = ColorScheme::from_syntect_theme(&theme)
+                .with_overrides(self.color_overrides.clone());
             self.theme = theme;
             self.current_theme_name = Some(theme_name_or_path.to_string());
             Ok(self)
@@ -100,6 +114,8 @@ impl RenderContext {
             syntax_set: SyntaxSet::load_defaults_newlines(),
             theme: default_theme,
             current_theme_name: Some(default_theme_name.to_string()),
+            inline_images: false,
+            color_overrides: ColorOverrides::default(),
         }
     }
 }
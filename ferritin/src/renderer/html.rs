@@ -0,0 +1,365 @@
+//! HTML renderer, for serving documentation to a browser.
+//!
+//! Produces a fragment of semantic HTML: headings, paragraphs, lists and tables map to their
+//! HTML elements, and styled spans become `<span>`s with a class per [`SpanStyle`] so that
+//! [`STYLESHEET`] (or any other stylesheet) can color them. Links are resolved by a caller
+//! supplied function, so that a server can point item links at its own pages.
+
+use std::fmt::{Result, Write};
+
+use crate::styled_string::{
+    Document, DocumentNode, HeadingLevel, ListItem, ShowWhen, Span, SpanStyle, TableCell,
+    TruncationLevel, TuiAction, heading_anchor, split_identifiers,
+};
+
+/// Default styles for the classes the renderer emits
+pub(crate) const STYLESHEET: &str = r#"
+body { font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem;
+       line-height: 1.5; color: #303446; background: #fdfdfd; }
+a { color: #1e66f5; text-decoration: none; }
+a:hover { text-decoration: underline; }
+pre { background: #eff1f5; padding: 0.75rem; overflow-x: auto; border-radius: 4px; }
+code { font-family: ui-monospace, monospace; }
+blockquote { border-left: 3px solid #ccd0da; margin-left: 0; padding-left: 1rem; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccd0da; padding: 0.25rem 0.5rem; }
+nav { margin-bottom: 1rem; }
+.keyword { color: #8839ef; }
+.type-name { color: #df8e1d; }
+.function-name { color: #1e66f5; }
+.field-name { color: #179299; }
+.lifetime, .generic { color: #d20f39; }
+.punctuation, .operator { color: #5c5f77; }
+.comment { color: #8c8fa1; font-style: italic; }
+.inline-rust-code, .inline-code { font-family: ui-monospace, monospace; }
+.line-number { color: #8c8fa1; user-select: none; }
+.footnotes { border-top: 1px solid #ccd0da; margin-top: 2rem; font-size: 0.9em; }
+"#;
+
+/// HTML renderer state
+struct HtmlRenderer<'w, W: Write> {
+    output: &'w mut W,
+    link: &'w dyn Fn(&TuiAction) -> Option<String>,
+}
+
+/// Render a document as an HTML fragment, linking items to their documentation URLs
+pub fn render(document: &Document, output: &mut impl Write) -> Result {
+    render_with_links(document, output, &|action| {
+        action.url().map(|url| url.into_owned())
+    })
+}
+
+/// Render a document as an HTML fragment, using `link` to choose each link's `href`
+pub(crate) fn render_with_links(
+    document: &Document,
+    output: &mut impl Write,
+    link: &dyn Fn(&TuiAction) -> Option<String>,
+) -> Result {
+    let mut renderer = HtmlRenderer { output, link };
+    renderer.render_nodes(&document.nodes)
+}
+
+/// Wrap an HTML fragment in a complete page with [`STYLESHEET`]
+pub(crate) fn page(title: &str, head: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{STYLESHEET}</style>\n{head}</head>\n<body>\n{body}</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+/// Escape text for use in HTML content and attribute values
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn class_name(style: SpanStyle) -> Option<&'static str> {
+    Some(match style {
+        SpanStyle::Keyword => "keyword",
+        SpanStyle::TypeName => "type-name",
+        SpanStyle::FunctionName => "function-name",
+        SpanStyle::FieldName => "field-name",
+        SpanStyle::Lifetime => "lifetime",
+        SpanStyle::Generic => "generic",
+        SpanStyle::Punctuation => "punctuation",
+        SpanStyle::Operator => "operator",
+        SpanStyle::Comment => "comment",
+        SpanStyle::InlineRustCode => "inline-rust-code",
+        SpanStyle::InlineCode => "inline-code",
+        SpanStyle::Plain
+        | SpanStyle::Strong
+        | SpanStyle::Emphasis
+        | SpanStyle::Strikethrough
+        | SpanStyle::FootnoteReference
+        | SpanStyle::Image => {
+            return None;
+        }
+    })
+}
+
+impl<'w, W: Write> HtmlRenderer<'w, W> {
+    fn render_nodes(&mut self, nodes: &[DocumentNode]) -> Result {
+        for node in nodes {
+            self.render_node(node)?;
+        }
+        Ok(())
+    }
+
+    fn render_node(&mut self, node: &DocumentNode) -> Result {
+        match node {
+            DocumentNode::Paragraph { spans } => {
+                write!(self.output, "<p>")?;
+                self.render_spans(spans)?;
+                writeln!(self.output, "</p>")
+            }
+            DocumentNode::Heading { level, spans } => {
+                let tag = match level {
+                    HeadingLevel::Title => "h1",
+                    HeadingLevel::Section => "h2",
+                };
+                write!(
+                    self.output,
+                    "<{tag} id=\"{}\">",
+                    escape(&heading_anchor(spans))
+                )?;
+                self.render_spans(spans)?;
+                writeln!(self.output, "</{tag}>")
+            }
+            DocumentNode::Section { title, nodes } => {
+                writeln!(self.output, "<section>")?;
+                if let Some(title_spans) = title {
+                    write!(self.output, "<h3>")?;
+                    self.render_spans(title_spans)?;
+                    writeln!(self.output, "</h3>")?;
+                }
+                self.render_nodes(nodes)?;
+                writeln!(self.output, "</section>")
+            }
+            DocumentNode::List { items } => {
+                writeln!(self.output, "<ul>")?;
+                for item in items {
+                    self.render_list_item(item)?;
+                }
+                writeln!(self.output, "</ul>")
+            }
+            DocumentNode::CodeBlock {
+                lang,
+                code,
+                first_line,
+                links,
+            } => {
+                match lang.as_deref().filter(|lang| !lang.is_empty()) {
+                    Some(lang) => write!(
+                        self.output,
+                        "<pre><code class=\"language-{}\">",
+                        escape(lang)
+                    )?,
+                    None => write!(self.output, "<pre><code>")?,
+                }
+                let gutter_width = first_line
+                    .map(|first_line| (first_line + code.lines().count()).to_string().len());
+                for (idx, line) in code.lines().enumerate() {
+                    if idx > 0 {
+                        writeln!(self.output)?;
+                    }
+                    if let (Some(first_line), Some(width)) = (first_line, gutter_width) {
+                        write!(
+                            self.output,
+                            "<span class=\"line-number\">{:>width$} </span>",
+                            first_line + idx
+                        )?;
+                    }
+                    for (run, is_ident) in split_identifiers(line) {
+                        match links
+                            .get(run)
+                            .filter(|_| is_ident)
+                            .and_then(|action| (self.link)(action))
+                        {
+                            Some(href) => write!(
+                                self.output,
+                                "<a href=\"{}\">{}</a>",
+                                escape(&href),
+                                escape(run)
+                            )?,
+                            None => write!(self.output, "{}", escape(run))?,
+                        }
+                    }
+                }
+                writeln!(self.output, "</code></pre>")
+            }
+            DocumentNode::GeneratedCode { spans } => {
+                write!(self.output, "<pre class=\"signature\"><code>")?;
+                self.render_spans(spans)?;
+                writeln!(self.output, "</code></pre>")
+            }
+            DocumentNode::HorizontalRule => writeln!(self.output, "<hr>"),
+            DocumentNode::BlockQuote { nodes } => {
+                writeln!(self.output, "<blockquote>")?;
+                self.render_nodes(nodes)?;
+                writeln!(self.output, "</blockquote>")
+            }
+            DocumentNode::Table { header, rows } => {
+                writeln!(self.output, "<table>")?;
+                if let Some(header) = header {
+                    self.render_row(header, "th")?;
+                }
+                for row in rows {
+                    self.render_row(row, "td")?;
+                }
+                writeln!(self.output, "</table>")
+            }
+            DocumentNode::TruncatedBlock { nodes, level } => match level {
+                TruncationLevel::SingleLine => match nodes.first() {
+                    Some(
+                        DocumentNode::Paragraph { spans } | DocumentNode::Heading { spans, .. },
+                    ) => {
+                        write!(self.output, "<p>")?;
+                        self.render_spans(spans)?;
+                        writeln!(self.output, "</p>")
+                    }
+                    Some(first_node) => self.render_node(first_node),
+                    None => Ok(()),
+                },
+                TruncationLevel::Brief => {
+                    let Some((first_node, rest)) = nodes.split_first() else {
+                        return Ok(());
+                    };
+                    self.render_node(first_node)?;
+                    if !rest.is_empty() {
+                        writeln!(self.output, "<details><summary>More</summary>")?;
+                        self.render_nodes(rest)?;
+                        writeln!(self.output, "</details>")?;
+                    }
+                    Ok(())
+                }
+                TruncationLevel::Full => self.render_nodes(nodes),
+            },
+            DocumentNode::Conditional { show_when, nodes } => match show_when {
+                ShowWhen::Always | ShowWhen::NonInteractive => self.render_nodes(nodes),
+                ShowWhen::Interactive => Ok(()),
+            },
+            DocumentNode::Footnotes { notes } => {
+                writeln!(self.output, "<section class=\"footnotes\">\n<ol>")?;
+                for note in notes {
+                    let number = note.number;
+                    write!(self.output, "<li id=\"fn-{number}\" value=\"{number}\">")?;
+                    self.render_nodes(&note.content)?;
+                    writeln!(
+                        self.output,
+                        "<a href=\"#fnref-{number}\" class=\"footnote-backref\">↩</a></li>"
+                    )?;
+                }
+                writeln!(self.output, "</ol>\n</section>")
+            }
+        }
+    }
+
+    fn render_row(&mut self, cells: &[TableCell], tag: &str) -> Result {
+        write!(self.output, "<tr>")?;
+        for cell in cells {
+            write!(self.output, "<{tag}>")?;
+            self.render_spans(&cell.spans)?;
+            write!(self.output, "</{tag}>")?;
+        }
+        writeln!(self.output, "</tr>")
+    }
+
+    fn render_list_item(&mut self, item: &ListItem) -> Result {
+        write!(self.output, "<li>")?;
+        self.render_nodes(&item.content)?;
+        writeln!(self.output, "</li>")
+    }
+
+    fn render_spans(&mut self, spans: &[Span]) -> Result {
+        for span in spans {
+            self.render_span(span)?;
+        }
+        Ok(())
+    }
+
+    fn render_span(&mut self, span: &Span) -> Result {
+        if span.style == SpanStyle::Image
+            && let Some(TuiAction::OpenUrl(src)) = &span.action
+        {
+            return write!(
+                self.output,
+                "<img src=\"{}\" alt=\"{}\">",
+                escape(src),
+                escape(&span.text)
+            );
+        }
+        if span.style == SpanStyle::FootnoteReference {
+            let number = escape(span.text.trim_matches(['[', ']']));
+            return write!(
+                self.output,
+                "<sup id=\"fnref-{number}\"><a href=\"#fn-{number}\">{number}</a></sup>"
+            );
+        }
+
+        let href = span.action.as_ref().and_then(|action| (self.link)(action));
+        if let Some(href) = &href {
+            write!(self.output, "<a href=\"{}\">", escape(href))?;
+        }
+
+        let (open, close) = match span.style {
+            SpanStyle::Strong => ("<strong>", "</strong>"),
+            SpanStyle::Emphasis => ("<em>", "</em>"),
+            SpanStyle::Strikethrough => ("<del>", "</del>"),
+            SpanStyle::InlineCode | SpanStyle::InlineRustCode => ("<code>", "</code>"),
+            _ => ("", ""),
+        };
+        write!(self.output, "{open}")?;
+        match class_name(span.style) {
+            Some(class) => write!(
+                self.output,
+                "<span class=\"{class}\">{}</span>",
+                escape(&span.text)
+            )?,
+            None => write!(self.output, "{}", escape(&span.text))?,
+        }
+        write!(self.output, "{close}")?;
+
+        if href.is_some() {
+            write!(self.output, "</a>")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_escaped_html() {
+        let doc = Document::with_nodes(vec![
+            DocumentNode::heading(
+                HeadingLevel::Title,
+                vec![Span::plain("Item: "), Span::type_name("Vec<T>")],
+            ),
+            DocumentNode::paragraph(vec![
+                Span::plain("See "),
+                Span::plain("docs").with_action(TuiAction::OpenUrl("https://a.b/?x=1&y=2".into())),
+            ]),
+        ]);
+
+        let mut output = String::new();
+        render(&doc, &mut output).unwrap();
+
+        assert!(output.contains("<h1 id=\"item-vect\">"));
+        assert!(output.contains("<span class=\"type-name\">Vec&lt;T&gt;</span></h1>"));
+        assert!(output.contains("<p>See <a href=\"https://a.b/?x=1&amp;y=2\">docs</a></p>"));
+    }
+}
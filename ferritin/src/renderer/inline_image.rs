@@ -0,0 +1,132 @@
+//! Images drawn in the terminal with the kitty graphics protocol
+//!
+//! Only PNG images are drawn, since kitty-compatible terminals decode those themselves. Images
+//! are read from local paths or fetched with `curl`; anything that can't be loaded is left as
+//! its alt text.
+
+use std::{env, io::IsTerminal, process::Command};
+
+use super::base64::base64_encode;
+use crate::terminal_background::query_terminal;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Roughly the width of a terminal cell, for estimating how many columns an image takes
+const CELL_WIDTH_PIXELS: u32 = 10;
+
+/// The most base64 data kitty accepts in one escape sequence
+const CHUNK_SIZE: usize = 4096;
+
+/// Whether the terminal on stdout draws images with the kitty graphics protocol
+///
+/// Terminals known to support it are recognized from their environment variables, and others
+/// are asked with a graphics query.
+pub(crate) fn kitty_graphics_supported() -> bool {
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+
+    let known_terminal = env::var("TERM").is_ok_and(|term| term.contains("kitty"))
+        || env::var_os("KITTY_WINDOW_ID").is_some()
+        || env::var("TERM_PROGRAM")
+            .is_ok_and(|program| matches!(program.as_str(), "WezTerm" | "ghostty"));
+
+    // A query for a 1x1 image, which supporting terminals answer with `OK`
+    known_terminal
+        || query_terminal("\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\")
+            .is_some_and(|reply| reply.contains("_Gi=31;OK"))
+}
+
+/// The escape sequences that draw the PNG image at `url`, at most `max_columns` wide
+///
+/// Returns `None` when the image can't be loaded or isn't a PNG.
+pub(super) fn kitty_image(url: &str, max_columns: usize) -> Option<String> {
+    let data = load(url)?;
+    let width = png_width(&data)?;
+    Some(kitty_escape(&data, width, max_columns))
+}
+
+fn load(url: &str) -> Option<Vec<u8>> {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        let output = Command::new("curl")
+            .args(["--fail", "--silent", "--location", "--max-time", "5", url])
+            .output()
+            .ok()?;
+        output.status.success().then_some(output.stdout)
+    } else {
+        std::fs::read(url.strip_prefix("file://").unwrap_or(url)).ok()
+    }
+}
+
+/// The width of a PNG image in pixels, from its header
+fn png_width(data: &[u8]) -> Option<u32> {
+    // The IHDR chunk always comes first, with the width right after its type
+    if !data.starts_with(PNG_SIGNATURE) || data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some(u32::from_be_bytes(data.get(16..20)?.try_into().ok()?))
+}
+
+fn kitty_escape(data: &[u8], width_pixels: u32, max_columns: usize) -> String {
+    // Images wider than the space available are scaled down, keeping their aspect ratio
+    let columns = if width_pixels.div_ceil(CELL_WIDTH_PIXELS) as usize > max_columns {
+        format!(",c={max_columns}")
+    } else {
+        String::new()
+    };
+
+    let encoded = base64_encode(data);
+    let chunks: Vec<&str> = encoded
+        .as_bytes()
+        .chunks(CHUNK_SIZE)
+        .map(|chunk| std::str::from_utf8(chunk).expect("base64 is ascii"))
+        .collect();
+
+    let mut escape = String::new();
+    for (idx, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(idx + 1 < chunks.len());
+        // `q=2` keeps the terminal from replying, which would show up as typed input
+        if idx == 0 {
+            escape.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2{columns},m={more};{chunk}\x1b\\"
+            ));
+        } else {
+            escape.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    escape
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32) -> Vec<u8> {
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend_from_slice(&13u32.to_be_bytes());
+        data.extend_from_slice(b"IHDR");
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&[0; 9]);
+        data
+    }
+
+    #[test]
+    fn reads_png_width() {
+        assert_eq!(png_width(&png(640)), Some(640));
+        assert_eq!(png_width(b"<svg></svg>"), None);
+    }
+
+    #[test]
+    fn scales_down_wide_images() {
+        assert!(kitty_escape(&png(2000), 2000, 80).starts_with("\x1b_Ga=T,f=100,q=2,c=80,m=0;"));
+        assert!(kitty_escape(&png(100), 100, 80).starts_with("\x1b_Ga=T,f=100,q=2,m=0;"));
+    }
+
+    #[test]
+    fn splits_data_into_chunks() {
+        let escape = kitty_escape(&vec![0; 4000], 10, 80);
+        assert_eq!(escape.matches("\x1b_G").count(), 2);
+        assert!(escape.contains(",m=1;"));
+        assert!(escape.contains("\x1b_Gm=0;"));
+    }
+}
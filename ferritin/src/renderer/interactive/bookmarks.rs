@@ -0,0 +1,213 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Widget},
+};
+use std::{borrow::Cow, fs, io, path::PathBuf};
+
+use super::{render_theme_picker::centered_rect, state::InteractiveState};
+use crate::styled_string::TuiAction;
+
+/// Item paths the user has bookmarked, persisted one per line
+///
+/// Without a file (outside a cargo project, or with no home directory) bookmarks only last for
+/// the session.
+#[derive(Debug, Default)]
+pub(super) struct Bookmarks {
+    paths: Vec<String>,
+    file: Option<PathBuf>,
+}
+
+impl Bookmarks {
+    /// Load bookmarks from `file`, starting empty if it doesn't exist yet
+    pub(super) fn load(file: Option<PathBuf>) -> Self {
+        let paths = file
+            .as_deref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .map(|contents| {
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { paths, file }
+    }
+
+    pub(super) fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    pub(super) fn is_persistent(&self) -> bool {
+        self.file.is_some()
+    }
+
+    /// Add `path` if it isn't bookmarked, otherwise remove it
+    ///
+    /// Returns whether the path is now bookmarked.
+    pub(super) fn toggle(&mut self, path: &str) -> io::Result<bool> {
+        let added = match self.paths.iter().position(|p| p == path) {
+            Some(index) => {
+                self.paths.remove(index);
+                false
+            }
+            None => {
+                self.paths.push(path.to_string());
+                true
+            }
+        };
+        self.save()?;
+        Ok(added)
+    }
+
+    pub(super) fn remove(&mut self, index: usize) -> io::Result<()> {
+        if index < self.paths.len() {
+            self.paths.remove(index);
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut contents = self.paths.join("\n");
+        contents.push('\n');
+        fs::write(file, contents)
+    }
+}
+
+impl<'a> InteractiveState<'a> {
+    /// Bookmark the current item, or remove its bookmark if it already has one
+    pub(super) fn toggle_bookmark(&mut self) {
+        let Some(path) = self.current_item_path() else {
+            self.ui.debug_message = "No item to bookmark".into();
+            return;
+        };
+
+        self.ui.debug_message = match self.bookmarks.toggle(&path) {
+            Ok(true) if self.bookmarks.is_persistent() => format!("Bookmarked {path}").into(),
+            Ok(true) => format!("Bookmarked {path} (this session only)").into(),
+            Ok(false) => format!("Removed bookmark {path}").into(),
+            Err(e) => format!("Could not save bookmarks: {e}").into(),
+        };
+    }
+
+    /// Render bookmarks modal overlay
+    pub(super) fn render_bookmarks(&mut self, buf: &mut Buffer, area: Rect, selected_index: usize) {
+        // Clear document actions - modal should block all background interactions
+        self.render_cache.actions.clear();
+
+        let modal_area = centered_rect(60, 70, area);
+        Clear.render(modal_area, buf);
+
+        // Register clickable actions for each bookmark, inside the block's borders
+        let list_inner_y = modal_area.y + 1;
+        for (i, path) in self.bookmarks.paths().iter().enumerate() {
+            let item_y = list_inner_y + i as u16;
+            if item_y < modal_area.y + modal_area.height.saturating_sub(1) {
+                let item_rect = Rect {
+                    x: modal_area.x + 1,
+                    y: item_y,
+                    width: modal_area.width.saturating_sub(2),
+                    height: 1,
+                };
+                self.render_cache.actions.push((
+                    item_rect,
+                    TuiAction::NavigateToPath {
+                        path: Cow::Owned(path.clone()),
+                        url: None,
+                        anchor: None,
+                    },
+                ));
+            }
+        }
+
+        let items: Vec<ListItem> = if self.bookmarks.paths().is_empty() {
+            vec![ListItem::new(Line::from(
+                "  No bookmarks yet - press b on an item",
+            ))]
+        } else {
+            self.bookmarks
+                .paths()
+                .iter()
+                .map(|path| ListItem::new(Line::from(format!("  {path}"))))
+                .collect()
+        };
+
+        let mut list_state = ListState::default();
+        if !self.bookmarks.paths().is_empty() {
+            list_state.select(Some(selected_index));
+        }
+
+        let block = Block::default()
+            .title(" Bookmarks ")
+            .borders(Borders::ALL)
+            .style(self.theme.help_bg_style);
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(
+                Style::default()
+                    .bg(self
+                        .theme
+                        .breadcrumb_style
+                        .bg
+                        .unwrap_or(ratatui::style::Color::Blue))
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ");
+
+        ratatui::widgets::StatefulWidget::render(list, modal_area, buf, &mut list_state);
+
+        // Render instructions at the bottom of the modal
+        let instruction_y = modal_area.y + modal_area.height.saturating_sub(2);
+        if instruction_y < area.height {
+            let instructions = " ↑/↓:Navigate  Enter:Go  d:Delete  Esc:Close ";
+            let instructions_width = instructions.chars().count() as u16;
+            let instruction_x =
+                modal_area.x + (modal_area.width.saturating_sub(instructions_width)) / 2;
+
+            for (i, ch) in instructions.chars().enumerate() {
+                let x = instruction_x + i as u16;
+                if x < modal_area.x + modal_area.width
+                    && let Some(cell) = buf.cell_mut((x, instruction_y))
+                {
+                    cell.set_char(ch);
+                    cell.set_style(self.theme.status_hint_style);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persists_toggled_bookmarks() {
+        let dir = std::env::temp_dir().join(format!("ferritin-bookmarks-{}", std::process::id()));
+        let file = dir.join("bookmarks");
+
+        let mut bookmarks = Bookmarks::load(Some(file.clone()));
+        assert!(bookmarks.paths().is_empty());
+        assert!(bookmarks.toggle("std::vec::Vec").unwrap());
+        assert!(bookmarks.toggle("std::string::String").unwrap());
+        assert!(!bookmarks.toggle("std::vec::Vec").unwrap());
+
+        let reloaded = Bookmarks::load(Some(file));
+        assert_eq!(reloaded.paths(), ["std::string::String"]);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
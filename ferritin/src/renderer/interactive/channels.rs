@@ -4,7 +4,10 @@ use ferritin_common::DocRef;
 use rustdoc_types::Item;
 
 use super::history::HistoryEntry;
+use super::{bookmarks::Bookmarks, input_history::InputHistory};
 use crate::styled_string::Document;
+use crate::verbosity::Verbosity;
+use crate::watch::SourceWatcher;
 use std::borrow::Cow;
 
 /// Commands sent from UI thread to Request thread
@@ -16,6 +19,13 @@ pub enum UiCommand<'a> {
     /// Navigate to a path by string (e.g., "std::vec::Vec" from GoTo mode)
     NavigateToPath(Cow<'a, str>),
 
+    /// Look up a double-clicked word or path as it would resolve in the source of `scope`, the
+    /// item being viewed
+    LookUp {
+        text: Cow<'a, str>,
+        scope: Option<DocRef<'a, Item>>,
+    },
+
     /// Search for items
     Search {
         query: Cow<'a, str>,
@@ -32,6 +42,21 @@ pub enum UiCommand<'a> {
         current_item: Option<DocRef<'a, Item>>,
     },
 
+    /// Load the source file of an item for the split-pane source view
+    LoadSource(DocRef<'a, Item>),
+
+    /// Toggle display of `#[doc(hidden)]` items
+    ToggleHidden {
+        show_hidden: bool,
+        current_entry: Option<HistoryEntry<'a>>,
+    },
+
+    /// Change how much of each signature is shown
+    SetVerbosity {
+        verbosity: Verbosity,
+        current_item: Option<DocRef<'a, Item>>,
+    },
+
     /// Shutdown the request thread
     Shutdown,
 }
@@ -44,9 +69,39 @@ pub enum RequestResponse<'a> {
         entry: Option<HistoryEntry<'a>>,
     },
 
+    /// Source listing for the split-pane view, `None` if the item's source can't be read
+    Source {
+        doc: Option<Document<'a>>,
+        /// 0-indexed line on which the item starts
+        start_line: usize,
+    },
+
+    /// Bookmarks for the project, loaded once sources are available
+    Bookmarks(Bookmarks),
+
+    /// Go-to and search history for the project, loaded alongside bookmarks
+    InputHistory(InputHistory),
+
+    /// Watcher for the workspace's sources, with `--watch`, sent once sources are loaded
+    Watching(SourceWatcher),
+
+    /// Workspace crates shown from out-of-date documentation, which is being rebuilt in the
+    /// background
+    Rebuilding(Vec<String>),
+
+    /// Background rebuilds finished, so the session should restart on the fresh documentation
+    Rebuilt,
+
+    /// Background rebuilds failed, leaving the out-of-date documentation in place
+    RebuildFailed,
+
     /// An error occurred (path not found, etc.)
     Error(String),
 
+    /// Loaded documentation outgrew the memory budget, so the session should restart with only
+    /// the most recently used crates
+    OverMemoryBudget,
+
     /// Acknowledgment that shutdown is complete
     ShuttingDown,
 }
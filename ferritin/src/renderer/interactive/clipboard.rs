@@ -0,0 +1,142 @@
+use std::{
+    env,
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, prelude::Backend};
+
+use super::{InteractiveState, utils::find_node_at_path_mut};
+use crate::renderer::base64::base64_encode;
+use crate::styled_string::DocumentNode;
+
+/// Clipboard tools tried in order when running locally
+const CLIPBOARD_COMMANDS: &[&[&str]] = &[
+    &["pbcopy"],
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+    &["clip.exe"],
+];
+
+impl<'a> InteractiveState<'a> {
+    /// Handle the key following `y`: copy the current item's path (`p`), its signature (`s`),
+    /// or the code block under the mouse cursor or at the top of the viewport (`c`)
+    pub(super) fn handle_yank(
+        &mut self,
+        key: KeyCode,
+        terminal: &mut Terminal<impl Backend + Write>,
+    ) {
+        let (what, text) = match key {
+            KeyCode::Char('p') => ("path", self.current_item_path()),
+            KeyCode::Char('s') => ("signature", self.current_signature()),
+            KeyCode::Char('c') => ("code block", self.code_block_at_cursor()),
+            _ => {
+                self.ui.debug_message = "Yank cancelled".into();
+                return;
+            }
+        };
+
+        let Some(text) = text else {
+            self.ui.debug_message = format!("No {what} to copy").into();
+            return;
+        };
+
+        self.ui.debug_message = match copy_to_clipboard(&text, terminal.backend_mut()) {
+            Ok(method) => format!("Copied {what} to clipboard ({method})").into(),
+            Err(e) => format!("Could not copy {what}: {e}").into(),
+        };
+    }
+
+    pub(super) fn current_item_path(&self) -> Option<String> {
+        let item = self.document.history.current()?.item()?;
+        Some(match item.path() {
+            Some(path) => path.to_string(),
+            None => format!("{}::{}", item.crate_docs().name(), item.name()?),
+        })
+    }
+
+    /// The signature is the first generated code block on an item's page
+    fn current_signature(&self) -> Option<String> {
+        self.document.history.current()?.item()?;
+        self.document
+            .document
+            .nodes
+            .iter()
+            .find_map(|node| match node {
+                DocumentNode::GeneratedCode { spans } => {
+                    Some(spans.iter().map(|span| &*span.text).collect())
+                }
+                _ => None,
+            })
+    }
+
+    fn code_block_at_cursor(&mut self) -> Option<String> {
+        let scroll_offset = self.viewport.scroll_offset;
+        let viewport_bottom = scroll_offset + self.viewport.last_viewport_height;
+        let blocks = &self.render_cache.code_blocks;
+
+        // The mouse position is already in document coordinates
+        let under_cursor = self
+            .viewport
+            .cursor_pos
+            .and_then(|pos| blocks.iter().find(|(rows, _)| rows.contains(&pos.y)));
+        let first_visible = || {
+            blocks
+                .iter()
+                .find(|(rows, _)| rows.end > scroll_offset && rows.start < viewport_bottom)
+        };
+        let (_, node_path) = under_cursor.or_else(first_visible)?;
+        let node_path = *node_path;
+
+        match find_node_at_path_mut(&mut self.document.document.nodes, node_path.indices())? {
+            DocumentNode::CodeBlock { code, .. } => Some(code.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Copy text to the system clipboard, returning the name of the mechanism used
+///
+/// Over SSH the clipboard tools would copy on the remote host, so this goes straight to OSC 52,
+/// which asks the terminal emulator to set its clipboard. Locally the first clipboard tool
+/// that works is used, with OSC 52 as the fallback.
+fn copy_to_clipboard(text: &str, terminal: &mut impl Write) -> io::Result<&'static str> {
+    let over_ssh = env::var_os("SSH_CONNECTION").is_some() || env::var_os("SSH_TTY").is_some();
+    if !over_ssh {
+        for command in CLIPBOARD_COMMANDS {
+            if pipe_to_command(command, text).is_ok() {
+                return Ok(command[0]);
+            }
+        }
+    }
+
+    write!(terminal, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))?;
+    terminal.flush()?;
+    Ok("OSC 52")
+}
+
+fn pipe_to_command(command: &[&str], text: &str) -> io::Result<()> {
+    let mut child = Command::new(command[0])
+        .args(&command[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Dropping stdin closes it, letting the tool see the end of the input
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "{} exited with {status}",
+            command[0]
+        )))
+    }
+}
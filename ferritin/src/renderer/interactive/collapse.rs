@@ -0,0 +1,76 @@
+use super::state::{InteractiveState, KeyboardCursor};
+
+impl<'a> InteractiveState<'a> {
+    /// Collapse or expand the innermost titled section under the mouse cursor, the focused link,
+    /// or the top of the viewport, in that order of preference
+    pub(super) fn toggle_section_at_cursor(&mut self) {
+        let focused_row = match self.viewport.keyboard_cursor {
+            KeyboardCursor::Focused { action_index } => self
+                .render_cache
+                .actions
+                .get(action_index)
+                .map(|(rect, _)| rect.y),
+            KeyboardCursor::VirtualTop | KeyboardCursor::VirtualBottom => None,
+        };
+        let row = self
+            .viewport
+            .cursor_pos
+            .map(|pos| pos.y)
+            .or(focused_row)
+            .unwrap_or(self.viewport.scroll_offset);
+
+        let Some((rows, path)) = self
+            .render_cache
+            .sections
+            .iter()
+            .find(|(rows, _)| rows.contains(&row))
+            .cloned()
+        else {
+            self.ui.debug_message = "No section to collapse here".into();
+            return;
+        };
+
+        if self.document.collapsed.remove(&path) {
+            self.ui.debug_message = "Section expanded".into();
+        } else {
+            self.document.collapsed.insert(path);
+            self.ui.debug_message = "Section collapsed".into();
+            // Keep the collapsed section's title in view
+            if rows.start < self.viewport.scroll_offset {
+                self.set_scroll_offset(rows.start);
+            }
+        }
+
+        self.remember_collapsed_sections();
+        self.viewport.cached_layout = None;
+    }
+
+    /// Expand every collapsed section in the current document
+    pub(super) fn expand_all_sections(&mut self) {
+        self.document.collapsed.clear();
+        self.remember_collapsed_sections();
+        self.viewport.cached_layout = None;
+        self.ui.debug_message = "All sections expanded".into();
+    }
+
+    /// Restore the sections collapsed when the current item was last shown this session
+    pub(super) fn restore_collapsed_sections(&mut self) {
+        self.document.collapsed = self
+            .current_item_path()
+            .and_then(|path| self.document.collapsed_by_item.get(&path))
+            .cloned()
+            .unwrap_or_default();
+    }
+
+    fn remember_collapsed_sections(&mut self) {
+        let Some(path) = self.current_item_path() else {
+            return;
+        };
+        if self.document.collapsed.is_empty() {
+            self.document.collapsed_by_item.remove(&path);
+        } else {
+            let collapsed = self.document.collapsed.clone();
+            self.document.collapsed_by_item.insert(path, collapsed);
+        }
+    }
+}
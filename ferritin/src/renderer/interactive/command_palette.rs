@@ -0,0 +1,330 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Terminal,
+    buffer::Buffer,
+    layout::Rect,
+    prelude::Backend,
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Widget},
+};
+use std::{borrow::Cow, io::Write};
+
+use super::{
+    channels::UiCommand,
+    render_theme_picker::centered_rect,
+    state::{InteractiveState, UiMode},
+};
+use crate::render_context::RenderContext;
+
+/// Commands offered by the palette: label, shortcut shown beside it, and the keys it replays
+const COMMANDS: &[(&str, &str, &[KeyCode])] = &[
+    ("Go to item by path", "g", &[KeyCode::Char('g')]),
+    ("Search", "s", &[KeyCode::Char('s')]),
+    ("List crates", "l", &[KeyCode::Char('l')]),
+    ("Navigate back", "←", &[KeyCode::Left]),
+    ("Navigate forward", "→", &[KeyCode::Right]),
+    ("Jump to top", "Home", &[KeyCode::Home]),
+    ("Jump to bottom", "End", &[KeyCode::End]),
+    ("Show link hints", "f", &[KeyCode::Char('f')]),
+    ("Toggle source code display", "c", &[KeyCode::Char('c')]),
+    ("Toggle source view beside docs", "v", &[KeyCode::Char('v')]),
+    ("Toggle outline sidebar", "O", &[KeyCode::Char('O')]),
+    ("Switch pane focus", "Tab", &[KeyCode::Tab]),
+    ("Collapse/expand section", "z", &[KeyCode::Char('z')]),
+    ("Expand all sections", "Z", &[KeyCode::Char('Z')]),
+    ("Toggle #[doc(hidden)] items", "H", &[KeyCode::Char('H')]),
+    ("Cycle signature verbosity", "V", &[KeyCode::Char('V')]),
+    ("Open current item in browser", "o", &[KeyCode::Char('o')]),
+    ("Bookmark current item", "b", &[KeyCode::Char('b')]),
+    ("Show bookmarks", "B", &[KeyCode::Char('B')]),
+    (
+        "Copy item path",
+        "yp",
+        &[KeyCode::Char('y'), KeyCode::Char('p')],
+    ),
+    (
+        "Copy signature",
+        "ys",
+        &[KeyCode::Char('y'), KeyCode::Char('s')],
+    ),
+    (
+        "Copy code block",
+        "yc",
+        &[KeyCode::Char('y'), KeyCode::Char('c')],
+    ),
+    ("Select theme", "t", &[KeyCode::Char('t')]),
+    ("Toggle mouse mode", "m", &[KeyCode::Char('m')]),
+    ("Show help", "?", &[KeyCode::Char('?')]),
+    ("Quit", "q", &[KeyCode::Char('q')]),
+];
+
+/// What choosing a palette entry does
+#[derive(Debug, Clone)]
+enum PaletteAction {
+    /// Replay normal-mode keys, so the palette stays in step with the keymap
+    Keys(&'static [KeyCode]),
+    OpenBookmark(String),
+    ApplyTheme(String),
+}
+
+#[derive(Debug, Clone)]
+struct PaletteEntry {
+    label: Cow<'static, str>,
+    shortcut: &'static str,
+    action: PaletteAction,
+}
+
+impl<'a> InteractiveState<'a> {
+    /// Open the command palette with an empty query
+    pub(super) fn open_command_palette(&mut self) {
+        self.ui_mode = UiMode::CommandPalette {
+            query: String::new(),
+            selected_index: 0,
+        };
+    }
+
+    /// Every palette entry: commands, then bookmarks, then themes
+    fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let commands = COMMANDS
+            .iter()
+            .map(|&(label, shortcut, keys)| PaletteEntry {
+                label: Cow::Borrowed(label),
+                shortcut,
+                action: PaletteAction::Keys(keys),
+            });
+        let bookmarks = self.bookmarks.paths().iter().map(|path| PaletteEntry {
+            label: Cow::Owned(format!("Open bookmark: {path}")),
+            shortcut: "",
+            action: PaletteAction::OpenBookmark(path.clone()),
+        });
+        let themes = RenderContext::available_themes()
+            .into_iter()
+            .map(|theme| PaletteEntry {
+                label: Cow::Owned(format!("Theme: {theme}")),
+                shortcut: "",
+                action: PaletteAction::ApplyTheme(theme),
+            });
+
+        commands.chain(bookmarks).chain(themes).collect()
+    }
+
+    /// Entries matching `query`, best match first
+    fn palette_matches(&self, query: &str) -> Vec<PaletteEntry> {
+        let mut scored: Vec<_> = self
+            .palette_entries()
+            .into_iter()
+            .filter_map(|entry| Some((fuzzy_score(query, &entry.label)?, entry)))
+            .collect();
+        // Stable, so ties keep the palette's own order
+        scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Handle a key while the command palette is open
+    ///
+    /// Returns true if the chosen command quits.
+    pub(super) fn handle_command_palette_key(
+        &mut self,
+        key: KeyEvent,
+        terminal: &mut Terminal<impl Backend + Write>,
+    ) -> bool {
+        let UiMode::CommandPalette {
+            query,
+            selected_index,
+        } = &self.ui_mode
+        else {
+            return false;
+        };
+        let (mut query, mut selected_index) = (query.clone(), *selected_index);
+        let matches = self.palette_matches(&query);
+
+        match (key.code, key.modifiers) {
+            (KeyCode::Enter, _) => {
+                self.ui_mode = UiMode::Normal;
+                return match matches.into_iter().nth(selected_index) {
+                    Some(entry) => self.run_palette_action(entry.action, terminal),
+                    None => false,
+                };
+            }
+            (KeyCode::Up, _) | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                selected_index = selected_index.saturating_sub(1);
+            }
+            (KeyCode::Down, _) | (KeyCode::Char('n'), KeyModifiers::CONTROL)
+                if selected_index + 1 < matches.len() =>
+            {
+                selected_index += 1;
+            }
+            (KeyCode::Backspace, _) => {
+                query.pop();
+                selected_index = 0;
+            }
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                query.push(c);
+                selected_index = 0;
+            }
+            _ => {}
+        }
+
+        self.ui_mode = UiMode::CommandPalette {
+            query,
+            selected_index,
+        };
+        false
+    }
+
+    fn run_palette_action(
+        &mut self,
+        action: PaletteAction,
+        terminal: &mut Terminal<impl Backend + Write>,
+    ) -> bool {
+        match action {
+            PaletteAction::Keys(keys) => {
+                for &code in keys {
+                    if self.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE), terminal) {
+                        return true;
+                    }
+                }
+            }
+            PaletteAction::OpenBookmark(path) => {
+                self.ui.debug_message = format!("Loading: {path}...").into();
+                self.send_request(UiCommand::NavigateToPath(Cow::Owned(path)));
+            }
+            PaletteAction::ApplyTheme(theme) => {
+                self.ui.debug_message = match self.apply_theme(&theme) {
+                    Ok(()) => format!("Theme: {theme}").into(),
+                    Err(e) => format!("Could not apply theme {theme}: {e}").into(),
+                };
+            }
+        }
+        false
+    }
+
+    /// Render command palette modal overlay
+    pub(super) fn render_command_palette(
+        &mut self,
+        buf: &mut Buffer,
+        area: Rect,
+        query: &str,
+        selected_index: usize,
+    ) {
+        // Clear document actions - modal should block all background interactions
+        self.render_cache.actions.clear();
+
+        let modal_area = centered_rect(60, 70, area);
+        Clear.render(modal_area, buf);
+
+        let matches = self.palette_matches(query);
+        let inner_width = modal_area.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = if matches.is_empty() {
+            vec![ListItem::new(Line::from("  No matching commands"))]
+        } else {
+            matches
+                .iter()
+                .map(|entry| {
+                    // Leave room for the highlight symbol and a space before the shortcut
+                    let label_width =
+                        inner_width.saturating_sub(entry.shortcut.chars().count() + 4);
+                    ListItem::new(Line::from(format!(
+                        "{:<label_width$} {}",
+                        entry.label, entry.shortcut
+                    )))
+                })
+                .collect()
+        };
+
+        let mut list_state = ListState::default();
+        if !matches.is_empty() {
+            list_state.select(Some(selected_index));
+        }
+
+        let block = Block::default()
+            .title(format!(" : {query} "))
+            .borders(Borders::ALL)
+            .style(self.theme.help_bg_style);
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(
+                Style::default()
+                    .bg(self
+                        .theme
+                        .breadcrumb_style
+                        .bg
+                        .unwrap_or(ratatui::style::Color::Blue))
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ");
+
+        ratatui::widgets::StatefulWidget::render(list, modal_area, buf, &mut list_state);
+
+        // Render instructions at the bottom of the modal
+        let instruction_y = modal_area.y + modal_area.height.saturating_sub(2);
+        if instruction_y < area.height {
+            let instructions = " Type to filter  ↑/↓:Navigate  Enter:Run  Esc:Close ";
+            let instructions_width = instructions.chars().count() as u16;
+            let instruction_x =
+                modal_area.x + (modal_area.width.saturating_sub(instructions_width)) / 2;
+
+            for (i, ch) in instructions.chars().enumerate() {
+                let x = instruction_x + i as u16;
+                if x < modal_area.x + modal_area.width
+                    && let Some(cell) = buf.cell_mut((x, instruction_y))
+                {
+                    cell.set_char(ch);
+                    cell.set_style(self.theme.status_hint_style);
+                }
+            }
+        }
+    }
+}
+
+/// Score how well `query` fuzzy-matches `candidate`, or `None` if it doesn't match at all
+///
+/// Every query character must appear in order, ignoring case and whitespace in the query.
+/// Consecutive characters and characters at the start of a word score higher.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let mut position = 0;
+    let mut previous = None;
+    let mut score = 0;
+
+    for c in query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+    {
+        let index = position + candidate[position..].iter().position(|&x| x == c)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == index) {
+            score += 4;
+        }
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 2;
+        }
+        previous = Some(index);
+        position = index + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matches_in_order_preferring_word_starts() {
+        assert_eq!(fuzzy_score("", "Quit"), Some(0));
+        assert!(fuzzy_score("tso", "Toggle source code display").is_some());
+        assert!(fuzzy_score("ost", "Toggle source code display").is_none());
+
+        let outline = fuzzy_score("outl", "Toggle outline sidebar").unwrap();
+        let scattered = fuzzy_score("outl", "Open current item in browser").unwrap_or(0);
+        assert!(outline > scattered);
+
+        let word_start = fuzzy_score("th", "Theme: default").unwrap();
+        let mid_word = fuzzy_score("th", "Go to item by path").unwrap();
+        assert!(word_start > mid_word);
+    }
+}
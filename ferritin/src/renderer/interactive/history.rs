@@ -5,7 +5,9 @@ use rustdoc_types::Item;
 
 use super::channels::UiCommand;
 use super::render_document::BASELINE_LEFT_MARGIN;
+use super::state::KeyboardCursor;
 use super::theme::InteractiveTheme;
+use crate::commands::Commands;
 use std::borrow::Cow;
 use std::fmt::{self, Display, Formatter};
 use std::ops::Range;
@@ -19,19 +21,29 @@ pub enum HistoryEntry<'a> {
     Search {
         query: String,
         crate_name: Option<String>,
+        /// How many results were shown, so that showing the page again shows as many
+        limit: usize,
     },
     /// List crates page
     List {
         /// The default crate (if any) - used for scoped search
         default_crate: Option<&'a str>,
     },
+    /// An item from before the session restarted, looked up again when it's shown
+    Unresolved {
+        /// The item's [`DocRef::discriminated_path`], or its plain path
+        path: String,
+        name: String,
+    },
 }
 
 impl Display for HistoryEntry<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             HistoryEntry::Item(item) => f.write_str(item.name().unwrap_or("<unnamed>")),
-            HistoryEntry::Search { query, crate_name } => {
+            HistoryEntry::Search {
+                query, crate_name, ..
+            } => {
                 if query.is_empty() {
                     // Empty query - show "Search in crate_name" or just "Search"
                     if let Some(crate_name) = crate_name {
@@ -49,6 +61,7 @@ impl Display for HistoryEntry<'_> {
                 }
             }
             HistoryEntry::List { .. } => f.write_str("List"),
+            HistoryEntry::Unresolved { name, .. } => f.write_str(name),
         }
     }
 }
@@ -73,6 +86,7 @@ impl<'a> HistoryEntry<'a> {
             HistoryEntry::Item(item) => Some(item.crate_docs().name()),
             HistoryEntry::Search { crate_name, .. } => crate_name.as_deref(),
             HistoryEntry::List { default_crate } => default_crate.as_deref(),
+            HistoryEntry::Unresolved { path, .. } => path.split("::").next(),
         }
     }
 
@@ -80,12 +94,110 @@ impl<'a> HistoryEntry<'a> {
     pub(super) fn to_command(&self) -> UiCommand<'a> {
         match self {
             HistoryEntry::Item(item) => UiCommand::Navigate(*item),
-            HistoryEntry::Search { query, crate_name } => UiCommand::Search {
+            HistoryEntry::Search {
+                query,
+                crate_name,
+                limit,
+            } => UiCommand::Search {
                 query: Cow::Owned(query.clone()),
                 crate_name: crate_name.as_ref().map(|c| Cow::Owned(c.clone())),
-                limit: 20,
+                limit: *limit,
             },
             HistoryEntry::List { .. } => UiCommand::List,
+            HistoryEntry::Unresolved { path, .. } => {
+                UiCommand::NavigateToPath(Cow::Owned(path.clone()))
+            }
+        }
+    }
+
+    /// The command that shows this entry again, for starting a fresh session on it
+    pub(super) fn to_cli_command(&self) -> Commands {
+        match self {
+            HistoryEntry::Item(item) => item.path().map_or_else(Commands::list, Commands::get),
+            HistoryEntry::Search {
+                query,
+                crate_name: Some(crate_name),
+                limit,
+            } => Commands::search(query)
+                .in_crate(crate_name)
+                .with_limit(*limit),
+            HistoryEntry::Search {
+                query,
+                crate_name: None,
+                limit,
+            } => Commands::search(query).with_limit(*limit),
+            HistoryEntry::List { .. } => Commands::list(),
+            HistoryEntry::Unresolved { path, .. } => Commands::get(path),
+        }
+    }
+
+    /// This entry without borrowing from the session's documentation, to carry it over to the
+    /// next session
+    ///
+    /// Items become [`HistoryEntry::Unresolved`], and the list page forgets its default crate
+    /// until it's shown again.
+    fn detached(&self) -> HistoryEntry<'static> {
+        match self {
+            HistoryEntry::Item(item) => match item
+                .discriminated_path()
+                .or_else(|| item.path().map(|path| path.to_string()))
+            {
+                Some(path) => HistoryEntry::Unresolved {
+                    path,
+                    name: self.to_string(),
+                },
+                None => HistoryEntry::List {
+                    default_crate: None,
+                },
+            },
+            HistoryEntry::Search {
+                query,
+                crate_name,
+                limit,
+            } => HistoryEntry::Search {
+                query: query.clone(),
+                crate_name: crate_name.clone(),
+                limit: *limit,
+            },
+            HistoryEntry::List { .. } => HistoryEntry::List {
+                default_crate: None,
+            },
+            HistoryEntry::Unresolved { path, name } => HistoryEntry::Unresolved {
+                path: path.clone(),
+                name: name.clone(),
+            },
+        }
+    }
+
+    /// Whether showing `other` shows this entry's page, though one of them may have been
+    /// carried over from before a restart
+    fn is_same_page(&self, other: &HistoryEntry<'a>) -> bool {
+        match (self, other) {
+            (HistoryEntry::Unresolved { path, .. }, HistoryEntry::Item(item))
+            | (HistoryEntry::Item(item), HistoryEntry::Unresolved { path, .. }) => {
+                item.discriminated_path().as_ref() == Some(path)
+                    || item
+                        .path()
+                        .is_some_and(|item_path| item_path.to_string() == *path)
+            }
+            (HistoryEntry::List { .. }, HistoryEntry::List { .. }) => true,
+            _ => self == other,
+        }
+    }
+}
+
+/// Where the reader was on a history entry's page, restored when navigating back to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SavedPosition {
+    pub scroll_offset: u16,
+    pub keyboard_cursor: KeyboardCursor,
+}
+
+impl Default for SavedPosition {
+    fn default() -> Self {
+        Self {
+            scroll_offset: 0,
+            keyboard_cursor: KeyboardCursor::VirtualTop,
         }
     }
 }
@@ -94,6 +206,8 @@ impl<'a> HistoryEntry<'a> {
 #[derive(Debug)]
 pub(super) struct History<'a> {
     entries: Vec<HistoryEntry<'a>>,
+    /// Saved position for each entry, parallel to `entries`
+    positions: Vec<SavedPosition>,
     current_index: usize,
     // Breadcrumb rendering state (owned by history since it's breadcrumb-specific)
     clickable_areas: Vec<(usize, Range<u16>)>,
@@ -102,12 +216,11 @@ pub(super) struct History<'a> {
 
 impl<'a> History<'a> {
     pub(super) fn new(initial_entry: Option<HistoryEntry<'a>>) -> Self {
-        let mut entries = Vec::new();
-        if let Some(entry) = initial_entry {
-            entries.push(entry);
-        }
+        let entries: Vec<_> = initial_entry.into_iter().collect();
+        let positions = vec![SavedPosition::default(); entries.len()];
         Self {
             entries,
+            positions,
             current_index: 0,
             clickable_areas: Vec::new(),
             hover_pos: None,
@@ -115,14 +228,54 @@ impl<'a> History<'a> {
     }
 
     /// Push a new entry to history, truncating forward history
-    pub(super) fn push(&mut self, entry: HistoryEntry<'a>) {
-        if self.entries.is_empty() || self.current() != Some(&entry) {
-            self.entries.truncate(self.current_index + 1);
-            self.entries.push(entry);
-            self.current_index = self.entries.len() - 1;
+    ///
+    /// Returns false if the entry's page is already current, as when navigating back or forward,
+    /// in which case the entry replaces the current one, resolving it if it was carried over
+    /// from before a restart.
+    pub(super) fn push(&mut self, entry: HistoryEntry<'a>) -> bool {
+        match self.entries.get_mut(self.current_index) {
+            Some(current) if current.is_same_page(&entry) => {
+                *current = entry;
+                false
+            }
+            _ => {
+                self.entries.truncate(self.current_index + 1);
+                self.positions.truncate(self.current_index + 1);
+                self.entries.push(entry);
+                self.positions.push(SavedPosition::default());
+                self.current_index = self.entries.len() - 1;
+                true
+            }
         }
     }
 
+    /// This history without borrowing from the session's documentation, to carry back and
+    /// forward navigation and saved positions over to the next session
+    pub(super) fn detached(&self) -> History<'static> {
+        History {
+            entries: self.entries.iter().map(HistoryEntry::detached).collect(),
+            positions: self.positions.clone(),
+            current_index: self.current_index,
+            clickable_areas: Vec::new(),
+            hover_pos: None,
+        }
+    }
+
+    /// Remember the reader's position on the current entry's page
+    pub(super) fn record_position(&mut self, position: SavedPosition) {
+        if let Some(saved) = self.positions.get_mut(self.current_index) {
+            *saved = position;
+        }
+    }
+
+    /// The last recorded position on the current entry's page
+    pub(super) fn current_position(&self) -> SavedPosition {
+        self.positions
+            .get(self.current_index)
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Navigate backward in history
     pub(super) fn go_back(&mut self) -> Option<&HistoryEntry<'a>> {
         if self.current_index > 0 {
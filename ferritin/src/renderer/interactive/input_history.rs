@@ -0,0 +1,190 @@
+use std::{fs, io, path::PathBuf};
+
+/// Most queries remembered for each prompt
+const MAX_ENTRIES: usize = 100;
+
+/// Which prompt a query was entered at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Prompt {
+    GoTo,
+    Search,
+}
+
+impl Prompt {
+    fn prefix(self) -> &'static str {
+        match self {
+            Prompt::GoTo => "goto\t",
+            Prompt::Search => "search\t",
+        }
+    }
+}
+
+/// Previous go-to and search queries, recalled with ↑/↓ like shell history
+///
+/// Persisted as one `prompt<TAB>query` line per query, oldest first. Without a file (outside a
+/// cargo project, or with no home directory) history only lasts for the session.
+#[derive(Debug, Default)]
+pub(super) struct InputHistory {
+    goto: Vec<String>,
+    search: Vec<String>,
+    file: Option<PathBuf>,
+    /// Index of the recalled query, counting back from the most recent
+    recalled: Option<usize>,
+    /// What was typed before recalling began, restored by moving past the most recent query
+    draft: String,
+}
+
+impl InputHistory {
+    /// Load history from `file`, starting empty if it doesn't exist yet
+    pub(super) fn load(file: Option<PathBuf>) -> Self {
+        let mut history = Self {
+            file,
+            ..Self::default()
+        };
+
+        let contents = history
+            .file
+            .as_deref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .unwrap_or_default();
+        for line in contents.lines() {
+            for prompt in [Prompt::GoTo, Prompt::Search] {
+                if let Some(query) = line.strip_prefix(prompt.prefix()) {
+                    history.entries_mut(prompt).push(query.to_string());
+                }
+            }
+        }
+
+        history
+    }
+
+    fn entries(&self, prompt: Prompt) -> &[String] {
+        match prompt {
+            Prompt::GoTo => &self.goto,
+            Prompt::Search => &self.search,
+        }
+    }
+
+    fn entries_mut(&mut self, prompt: Prompt) -> &mut Vec<String> {
+        match prompt {
+            Prompt::GoTo => &mut self.goto,
+            Prompt::Search => &mut self.search,
+        }
+    }
+
+    /// Forget any query being recalled, for a fresh prompt
+    pub(super) fn reset_recall(&mut self) {
+        self.recalled = None;
+        self.draft.clear();
+    }
+
+    /// Step back to an older query, returning it if there is one
+    ///
+    /// `current` is what's typed at the prompt, kept as the draft when recall begins.
+    pub(super) fn previous(&mut self, prompt: Prompt, current: &str) -> Option<&str> {
+        let count = self.entries(prompt).len();
+        let index = match self.recalled {
+            None => 0,
+            Some(index) if index + 1 < count => index + 1,
+            Some(_) => return None,
+        };
+        if index >= count {
+            return None;
+        }
+
+        if self.recalled.is_none() {
+            self.draft = current.to_string();
+        }
+        self.recalled = Some(index);
+        Some(&self.entries(prompt)[count - 1 - index])
+    }
+
+    /// Step forward to a newer query, or back to the draft after the most recent one
+    pub(super) fn next(&mut self, prompt: Prompt) -> Option<&str> {
+        match self.recalled? {
+            0 => {
+                self.recalled = None;
+                Some(&self.draft)
+            }
+            index => {
+                self.recalled = Some(index - 1);
+                let entries = self.entries(prompt);
+                Some(&entries[entries.len() - index])
+            }
+        }
+    }
+
+    /// Remember a submitted query, moving it to the most recent position if already present
+    pub(super) fn record(&mut self, prompt: Prompt, query: &str) -> io::Result<()> {
+        self.reset_recall();
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(());
+        }
+
+        let entries = self.entries_mut(prompt);
+        entries.retain(|entry| entry != query);
+        entries.push(query.to_string());
+        if entries.len() > MAX_ENTRIES {
+            entries.drain(..entries.len() - MAX_ENTRIES);
+        }
+
+        self.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut contents = String::new();
+        for prompt in [Prompt::GoTo, Prompt::Search] {
+            for query in self.entries(prompt) {
+                contents.push_str(prompt.prefix());
+                contents.push_str(query);
+                contents.push('\n');
+            }
+        }
+        fs::write(file, contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recalls_queries_like_shell_history() {
+        let mut history = InputHistory::default();
+        history.record(Prompt::Search, "vec").unwrap();
+        history.record(Prompt::Search, "hashmap").unwrap();
+        history.record(Prompt::GoTo, "std::vec::Vec").unwrap();
+
+        assert_eq!(history.previous(Prompt::Search, "draft"), Some("hashmap"));
+        assert_eq!(history.previous(Prompt::Search, "hashmap"), Some("vec"));
+        assert_eq!(history.previous(Prompt::Search, "vec"), None);
+        assert_eq!(history.next(Prompt::Search), Some("hashmap"));
+        assert_eq!(history.next(Prompt::Search), Some("draft"));
+        assert_eq!(history.next(Prompt::Search), None);
+    }
+
+    #[test]
+    fn persists_both_prompts() {
+        let dir = std::env::temp_dir().join(format!("ferritin-input-{}", std::process::id()));
+        let file = dir.join("input-history");
+
+        let mut history = InputHistory::load(Some(file.clone()));
+        history.record(Prompt::GoTo, "std::vec::Vec").unwrap();
+        history.record(Prompt::Search, "parse int").unwrap();
+        history.record(Prompt::GoTo, "std::vec::Vec").unwrap();
+
+        let reloaded = InputHistory::load(Some(file));
+        assert_eq!(reloaded.entries(Prompt::GoTo), ["std::vec::Vec"]);
+        assert_eq!(reloaded.entries(Prompt::Search), ["parse int"]);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
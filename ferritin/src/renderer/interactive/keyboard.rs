@@ -1,12 +1,18 @@
 use std::{borrow::Cow, io::Write};
 
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent, KeyModifiers},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+    },
     execute,
 };
 use ratatui::{Terminal, prelude::Backend};
 
-use super::{InputMode, InteractiveState, UiMode, channels::UiCommand};
+use super::{
+    InputMode, InteractiveState, UiMode, channels::UiCommand, input_history::Prompt,
+    line_editor::LineEditor, state::PaneFocus,
+};
+use crate::generate_docsrs_url::generate_browsable_url;
 use crate::render_context::RenderContext;
 
 impl<'a> InteractiveState<'a> {
@@ -44,27 +50,50 @@ impl<'a> InteractiveState<'a> {
                     self.ui.debug_message =
                         "ferritin - q:quit ?:help ←/→:history g:go s:search l:list c:code".into();
                 }
+                UiMode::Bookmarks { .. } | UiMode::CommandPalette { .. } => {
+                    // Already set to Normal by replace
+                }
                 UiMode::Normal => {
-                    return true;
+                    if self.loading.cancel() {
+                        self.ui.debug_message = "Cancelled".into();
+                    } else if std::mem::take(&mut self.ui.yank_pending) {
+                        self.ui.debug_message = "Yank cancelled".into();
+                    } else if self.ui.link_hints.take().is_some() {
+                        self.ui.debug_message = "Link hints cancelled".into();
+                    } else if self.ui.focus == PaneFocus::Outline {
+                        self.toggle_outline();
+                    } else {
+                        return true;
+                    }
                 }
             }
         } else if matches!(self.ui_mode, UiMode::Help) {
             // Any key (except Escape, handled above) exits help
             self.ui_mode = UiMode::Normal;
         } else if let UiMode::Input(ref mut input_mode) = self.ui_mode {
+            let buffer = match input_mode {
+                InputMode::GoTo { buffer } | InputMode::Search { buffer, .. } => buffer,
+            };
+            if buffer.handle_key(key) {
+                return false;
+            }
+
             match key.code {
-                KeyCode::Char(c) => match input_mode {
-                    InputMode::GoTo { buffer } => buffer.push(c),
-                    InputMode::Search { buffer, .. } => buffer.push(c),
-                },
-                KeyCode::Backspace => match input_mode {
-                    InputMode::GoTo { buffer } => {
-                        buffer.pop();
-                    }
-                    InputMode::Search { buffer, .. } => {
-                        buffer.pop();
+                KeyCode::Up | KeyCode::Down => {
+                    // Recall earlier queries from this prompt, like shell history
+                    let (prompt, buffer) = match input_mode {
+                        InputMode::GoTo { buffer } => (Prompt::GoTo, buffer),
+                        InputMode::Search { buffer, .. } => (Prompt::Search, buffer),
+                    };
+                    let recalled = if key.code == KeyCode::Up {
+                        self.input_history.previous(prompt, buffer.text())
+                    } else {
+                        self.input_history.next(prompt)
+                    };
+                    if let Some(query) = recalled {
+                        buffer.set_text(query);
                     }
-                },
+                }
                 KeyCode::Tab => {
                     // Toggle search scope (only in Search mode and only if there's a crate to scope to)
                     if let InputMode::Search { all_crates, .. } = input_mode {
@@ -84,8 +113,9 @@ impl<'a> InteractiveState<'a> {
                     // Execute the command based on current input mode
                     let command = match input_mode {
                         InputMode::GoTo { buffer } => {
-                            self.ui.debug_message = format!("Loading: {buffer}...").into();
-                            Some(UiCommand::NavigateToPath(Cow::Owned(buffer.clone())))
+                            let path = buffer.text();
+                            self.ui.debug_message = format!("Loading: {path}...").into();
+                            Some(UiCommand::NavigateToPath(Cow::Owned(path.to_string())))
                         }
                         InputMode::Search { buffer, all_crates } => {
                             // Determine search scope
@@ -99,18 +129,26 @@ impl<'a> InteractiveState<'a> {
                                     .map(|s| Cow::Owned(s.into()))
                             };
 
-                            self.ui.debug_message = format!("Searching: {buffer}...").into();
+                            let query = buffer.text();
+                            self.ui.debug_message = format!("Searching: {query}...").into();
                             Some(UiCommand::Search {
-                                query: Cow::Owned(buffer.clone()),
+                                query: Cow::Owned(query.to_string()),
                                 crate_name: search_crate,
                                 limit: 20,
                             })
                         }
                     };
 
+                    let (prompt, buffer) = match input_mode {
+                        InputMode::GoTo { buffer } => (Prompt::GoTo, buffer),
+                        InputMode::Search { buffer, .. } => (Prompt::Search, buffer),
+                    };
+                    if let Err(e) = self.input_history.record(prompt, buffer.text()) {
+                        log::warn!("Could not save input history: {e}");
+                    }
+
                     if let Some(cmd) = command {
-                        let _ = self.cmd_tx.send(cmd);
-                        self.loading.start();
+                        self.send_request(cmd);
                     }
                     self.ui_mode = UiMode::Normal;
                 }
@@ -157,6 +195,50 @@ impl<'a> InteractiveState<'a> {
                 }
                 _ => {}
             }
+        } else if let UiMode::Bookmarks {
+            ref mut selected_index,
+        } = self.ui_mode
+        {
+            let bookmark_count = self.bookmarks.paths().len();
+
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    *selected_index = selected_index.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') if *selected_index + 1 < bookmark_count => {
+                    *selected_index += 1;
+                }
+                KeyCode::Enter => {
+                    if let Some(path) = self.bookmarks.paths().get(*selected_index) {
+                        self.ui.debug_message = format!("Loading: {path}...").into();
+                        self.send_request(UiCommand::NavigateToPath(Cow::Owned(path.clone())));
+                    }
+                    self.ui_mode = UiMode::Normal;
+                }
+                KeyCode::Char('d') | KeyCode::Delete => {
+                    let index = *selected_index;
+                    if index + 1 >= bookmark_count {
+                        *selected_index = index.saturating_sub(1);
+                    }
+                    if let Err(e) = self.bookmarks.remove(index) {
+                        self.ui.debug_message = format!("Could not save bookmarks: {e}").into();
+                    }
+                }
+                KeyCode::Char('B') | KeyCode::Char('q') => {
+                    self.ui_mode = UiMode::Normal;
+                }
+                _ => {}
+            }
+        } else if matches!(self.ui_mode, UiMode::CommandPalette { .. }) {
+            return self.handle_command_palette_key(key, terminal);
+        } else if std::mem::take(&mut self.ui.yank_pending) {
+            self.handle_yank(key.code, terminal);
+        } else if self.ui.link_hints.is_some() {
+            self.handle_link_hint_key(key.code);
+        } else if self.ui.focus == PaneFocus::Source && self.handle_source_pane_key(key) {
+            // Scrolling keys move the source pane while it has focus
+        } else if self.ui.focus == PaneFocus::Outline && self.handle_outline_key(key) {
+            // Selection keys move through the outline while it has focus
         } else {
             // Normal mode keybindings
             match (key.code, key.modifiers) {
@@ -165,18 +247,35 @@ impl<'a> InteractiveState<'a> {
                     return true;
                 }
 
-                // Navigate down / scroll down
+                // Navigate back and forward, as in vim's jump list. Without the kitty keyboard
+                // protocol, Ctrl+i arrives as Tab
+                (KeyCode::Char('o'), KeyModifiers::CONTROL) => {
+                    self.navigate_back();
+                }
+                (KeyCode::Char('i'), KeyModifiers::CONTROL) => {
+                    self.navigate_forward();
+                }
+
+                // Navigate down / scroll down, scrolling faster while held
                 (KeyCode::Char('j'), _)
                 | (KeyCode::Down, _)
                 | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
-                    self.handle_navigate_down();
+                    if key.kind == KeyEventKind::Repeat {
+                        self.scroll_held(true);
+                    } else {
+                        self.handle_navigate_down();
+                    }
                 }
 
-                // Navigate up / scroll up
+                // Navigate up / scroll up, scrolling faster while held
                 (KeyCode::Char('k'), _)
                 | (KeyCode::Up, _)
                 | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
-                    self.handle_navigate_up();
+                    if key.kind == KeyEventKind::Repeat {
+                        self.scroll_held(false);
+                    } else {
+                        self.handle_navigate_up();
+                    }
                 }
 
                 // Activate focused link
@@ -184,6 +283,11 @@ impl<'a> InteractiveState<'a> {
                     self.handle_activate_focused_link();
                 }
 
+                // Label the links in view, to follow one by typing its label
+                (KeyCode::Char('f'), _) => {
+                    self.show_link_hints();
+                }
+
                 // Page down
                 (KeyCode::Char('d'), KeyModifiers::CONTROL)
                 | (KeyCode::Char('v'), KeyModifiers::CONTROL)
@@ -260,8 +364,9 @@ impl<'a> InteractiveState<'a> {
 
                 // Enter GoTo mode
                 (KeyCode::Char('g'), _) => {
+                    self.input_history.reset_recall();
                     self.ui_mode = UiMode::Input(InputMode::GoTo {
-                        buffer: String::new(),
+                        buffer: LineEditor::default(),
                     });
                 }
 
@@ -275,8 +380,9 @@ impl<'a> InteractiveState<'a> {
                         .and_then(|entry| entry.crate_name())
                         .is_some();
 
+                    self.input_history.reset_recall();
                     self.ui_mode = UiMode::Input(InputMode::Search {
-                        buffer: String::new(),
+                        buffer: LineEditor::default(),
                         all_crates: !has_crate, // Search all crates if no current crate
                     });
                 }
@@ -284,8 +390,7 @@ impl<'a> InteractiveState<'a> {
                 // Show list of crates
                 (KeyCode::Char('l'), _) => {
                     // Send List command to request thread (non-blocking)
-                    let _ = self.cmd_tx.send(UiCommand::List);
-                    self.loading.start();
+                    self.send_request(UiCommand::List);
                     self.ui.debug_message = "Loading crate list...".into();
                 }
 
@@ -317,6 +422,91 @@ impl<'a> InteractiveState<'a> {
                     };
                 }
 
+                // Collapse or expand the section under the cursor
+                (KeyCode::Char('z'), _) => {
+                    self.toggle_section_at_cursor();
+                }
+
+                // Expand all collapsed sections
+                (KeyCode::Char('Z'), _) => {
+                    self.expand_all_sections();
+                }
+
+                // Toggle the split-pane source view
+                (KeyCode::Char('v'), _) => {
+                    self.toggle_split_source();
+                }
+
+                // Switch focus between the documentation, source and outline panes
+                (KeyCode::Tab, _) => {
+                    self.cycle_focus();
+                }
+
+                // Toggle the outline sidebar
+                (KeyCode::Char('O'), _) => {
+                    self.toggle_outline();
+                }
+
+                // Toggle display of #[doc(hidden)] items
+                (KeyCode::Char('H'), _) => {
+                    self.ui.show_hidden = !self.ui.show_hidden;
+                    let _ = self.cmd_tx.send(UiCommand::ToggleHidden {
+                        show_hidden: self.ui.show_hidden,
+                        current_entry: self.document.history.current().cloned(),
+                    });
+                    self.ui.debug_message = if self.ui.show_hidden {
+                        "Hidden items shown".into()
+                    } else {
+                        "Hidden items omitted".into()
+                    };
+                }
+
+                // Cycle signature verbosity
+                (KeyCode::Char('V'), _) => {
+                    self.ui.verbosity = self.ui.verbosity.next();
+                    let _ = self.cmd_tx.send(UiCommand::SetVerbosity {
+                        verbosity: self.ui.verbosity,
+                        current_item: self.document.history.current().and_then(|e| e.item()),
+                    });
+                    self.ui.debug_message =
+                        format!("Signature verbosity: {}", self.ui.verbosity.name()).into();
+                }
+
+                // Copy to clipboard: the next key chooses what
+                (KeyCode::Char('y'), _) => {
+                    self.ui.yank_pending = true;
+                    self.ui.debug_message =
+                        "Copy: p=item path, s=signature, c=code block (Esc to cancel)".into();
+                }
+
+                // Bookmark the current item
+                (KeyCode::Char('b'), _) => {
+                    self.toggle_bookmark();
+                }
+
+                // Show bookmarks
+                (KeyCode::Char('B'), _) => {
+                    self.ui_mode = UiMode::Bookmarks { selected_index: 0 };
+                    self.ui.debug_message =
+                        "Bookmarks (↑/↓ to navigate, Enter to go, d to delete, Esc to close)"
+                            .into();
+                }
+
+                // Open the current item's documentation in the browser
+                (KeyCode::Char('o'), _) => {
+                    let current_item = self.document.history.current().and_then(|e| e.item());
+                    self.ui.debug_message = match current_item {
+                        Some(item) => {
+                            let url = generate_browsable_url(item);
+                            match webbrowser::open(&url) {
+                                Ok(()) => format!("Opened {url}").into(),
+                                Err(e) => format!("Could not open {url}: {e}").into(),
+                            }
+                        }
+                        None => "No item to open".into(),
+                    };
+                }
+
                 // Enter theme picker mode
                 (KeyCode::Char('t'), _) => {
                     let themes = RenderContext::available_themes();
@@ -337,6 +527,11 @@ impl<'a> InteractiveState<'a> {
                         "Select theme (↑/↓ to navigate, Enter to save, Esc to cancel)".into();
                 }
 
+                // Open the command palette
+                (KeyCode::Char(':'), _) => {
+                    self.open_command_palette();
+                }
+
                 // Show help
                 (KeyCode::Char('?'), _) | (KeyCode::Char('h'), _) => {
                     self.ui_mode = UiMode::Help;
@@ -344,28 +539,12 @@ impl<'a> InteractiveState<'a> {
 
                 // Navigate back
                 (KeyCode::Left, _) | (KeyCode::Backspace, _) => {
-                    if let Some(entry) = self.document.history.go_back() {
-                        // Send command from history entry (non-blocking)
-                        let _ = self.cmd_tx.send(entry.to_command());
-                        self.loading.start();
-                        self.ui.debug_message =
-                            format!("Loading: {}...", entry.display_name()).into();
-                    } else {
-                        self.ui.debug_message = "Already at beginning of history".into();
-                    }
+                    self.navigate_back();
                 }
 
                 // Navigate forward
                 (KeyCode::Right, _) => {
-                    if let Some(entry) = self.document.history.go_forward() {
-                        // Send command from history entry (non-blocking)
-                        let _ = self.cmd_tx.send(entry.to_command());
-                        self.loading.start();
-                        self.ui.debug_message =
-                            format!("Loading: {}...", entry.display_name()).into();
-                    } else {
-                        self.ui.debug_message = "Already at end of history".into();
-                    }
+                    self.navigate_forward();
                 }
 
                 _ => { /*unhandled key event*/ }
@@ -374,6 +553,66 @@ impl<'a> InteractiveState<'a> {
         false
     }
 
+    /// Handle text pasted with bracketed paste: into the prompt being typed at, or from normal
+    /// mode into a new go-to prompt, so a copied path can be pasted straight in
+    pub(crate) fn handle_paste(&mut self, text: &str) {
+        match &mut self.ui_mode {
+            UiMode::Input(InputMode::GoTo { buffer } | InputMode::Search { buffer, .. }) => {
+                buffer.paste(text);
+            }
+            UiMode::Normal => {
+                let mut buffer = LineEditor::default();
+                buffer.paste(text);
+                self.input_history.reset_recall();
+                self.ui_mode = UiMode::Input(InputMode::GoTo { buffer });
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle a key release, which only the kitty keyboard protocol reports
+    pub(crate) fn handle_key_release(&mut self) {
+        self.ui.held_key_repeats = 0;
+    }
+
+    fn navigate_back(&mut self) {
+        if let Some(entry) = self.document.history.go_back() {
+            // Send command from history entry (non-blocking)
+            let command = entry.to_command();
+            self.ui.debug_message = format!("Loading: {}...", entry.display_name()).into();
+            self.send_request(command);
+        } else {
+            self.ui.debug_message = "Already at beginning of history".into();
+        }
+    }
+
+    fn navigate_forward(&mut self) {
+        if let Some(entry) = self.document.history.go_forward() {
+            // Send command from history entry (non-blocking)
+            let command = entry.to_command();
+            self.ui.debug_message = format!("Loading: {}...", entry.display_name()).into();
+            self.send_request(command);
+        } else {
+            self.ui.debug_message = "Already at end of history".into();
+        }
+    }
+
+    /// Scroll for a repeat of a held scrolling key, past links rather than stopping at each, and
+    /// further with each repeat until the key is released
+    ///
+    /// Repeats are only told apart from presses with the kitty keyboard protocol, which reports
+    /// them for keys like ↓ and Ctrl+n, though not for keys that type text, like j.
+    fn scroll_held(&mut self, down: bool) {
+        self.ui.held_key_repeats = self.ui.held_key_repeats.saturating_add(1);
+        let step = held_scroll_step(self.ui.held_key_repeats);
+        let offset = if down {
+            self.viewport.scroll_offset.saturating_add(step)
+        } else {
+            self.viewport.scroll_offset.saturating_sub(step)
+        };
+        self.set_scroll_offset(offset);
+    }
+
     /// Handle j/↓ key: navigate to next link or scroll down
     ///
     /// Implements seamless transition between link navigation and scrolling:
@@ -565,7 +804,7 @@ impl<'a> InteractiveState<'a> {
     /// request thread and reset keyboard focus to VirtualTop for the new document.
     /// ExpandBlock actions mutate the document in place and preserve focus.
     /// Does nothing when in VirtualTop or VirtualBottom states.
-    fn handle_activate_focused_link(&mut self) {
+    pub(super) fn handle_activate_focused_link(&mut self) {
         use super::state::KeyboardCursor;
 
         if let KeyboardCursor::Focused { action_index } = self.viewport.keyboard_cursor {
@@ -587,10 +826,11 @@ impl<'a> InteractiveState<'a> {
                     }
                     self.ui.debug_message = format!("Selected theme: {theme_name}").into();
                 } else {
+                    let anchor = action.anchor().map(str::to_string);
                     match super::events::handle_action(&mut self.document.document, action) {
                         Some(command) => {
-                            let _ = self.cmd_tx.send(command);
-                            self.loading.start();
+                            self.send_request(command);
+                            self.viewport.pending_anchor = anchor;
                             // Reset keyboard cursor on navigation
                             self.viewport.keyboard_cursor = KeyboardCursor::VirtualTop;
                         }
@@ -604,3 +844,22 @@ impl<'a> InteractiveState<'a> {
         }
     }
 }
+
+/// Lines to scroll on the given repeat of a held key: one at first, then a line more for every
+/// ten repeats, up to five
+fn held_scroll_step(repeats: u16) -> u16 {
+    (1 + repeats / 10).min(5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn held_scrolling_accelerates() {
+        assert_eq!(held_scroll_step(1), 1);
+        assert_eq!(held_scroll_step(10), 2);
+        assert_eq!(held_scroll_step(35), 4);
+        assert_eq!(held_scroll_step(u16::MAX), 5);
+    }
+}
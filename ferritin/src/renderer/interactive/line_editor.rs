@@ -0,0 +1,227 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// The text of a go-to or search prompt and the cursor within it, edited with readline's keys
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct LineEditor {
+    text: String,
+    /// Byte offset of the cursor, always on a char boundary
+    cursor: usize,
+}
+
+impl From<&str> for LineEditor {
+    fn from(text: &str) -> Self {
+        Self::from(text.to_string())
+    }
+}
+
+impl From<String> for LineEditor {
+    /// Text with the cursor at its end
+    fn from(text: String) -> Self {
+        Self {
+            cursor: text.len(),
+            text,
+        }
+    }
+}
+
+impl LineEditor {
+    pub(super) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Byte offset of the cursor in [`LineEditor::text`]
+    pub(super) fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replace the text, as when recalling history, with the cursor at its end
+    pub(super) fn set_text(&mut self, text: impl Into<String>) {
+        *self = Self::from(text.into());
+    }
+
+    /// Insert pasted text at the cursor, as a single line with its whitespace runs collapsed,
+    /// so that a path copied with its line ending still goes to the right place
+    pub(super) fn paste(&mut self, text: &str) {
+        let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.text.insert_str(self.cursor, &line);
+        self.cursor += line.len();
+    }
+
+    /// Apply an editing key, returning whether it was one
+    ///
+    /// - ←/→ and Ctrl+b/Ctrl+f move a character, Alt+b/Alt+f and Ctrl+←/Ctrl+→ a word, and
+    ///   Home/End and Ctrl+a/Ctrl+e to the start or end
+    /// - Backspace deletes the character before the cursor, Delete and Ctrl+d the one under it
+    /// - Ctrl+w deletes back to the previous space, and Alt+Backspace and Alt+d the word before
+    ///   or after the cursor
+    /// - Ctrl+u and Ctrl+k delete to the start or end
+    ///
+    /// Words are runs of letters, digits and underscores, so they stop at each `::` of a path.
+    pub(super) fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Char('b') if control => self.cursor = self.previous_char(),
+            KeyCode::Char('f') if control => self.cursor = self.next_char(),
+            KeyCode::Char('a') if control => self.cursor = 0,
+            KeyCode::Char('e') if control => self.cursor = self.text.len(),
+            KeyCode::Char('d') if control => self.delete_to(self.next_char()),
+            KeyCode::Char('h') if control => self.delete_to(self.previous_char()),
+            KeyCode::Char('w') if control => self.delete_to(self.previous_space()),
+            KeyCode::Char('u') if control => self.delete_to(0),
+            KeyCode::Char('k') if control => self.delete_to(self.text.len()),
+            KeyCode::Char('b') if alt => self.cursor = self.previous_word(),
+            KeyCode::Char('f') if alt => self.cursor = self.next_word(),
+            KeyCode::Char('d') if alt => self.delete_to(self.next_word()),
+            KeyCode::Char(_) if control || alt => return false,
+            KeyCode::Char(c) => {
+                self.text.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+            }
+            KeyCode::Left if control => self.cursor = self.previous_word(),
+            KeyCode::Right if control => self.cursor = self.next_word(),
+            KeyCode::Left => self.cursor = self.previous_char(),
+            KeyCode::Right => self.cursor = self.next_char(),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.text.len(),
+            KeyCode::Backspace if alt || control => self.delete_to(self.previous_word()),
+            KeyCode::Backspace => self.delete_to(self.previous_char()),
+            KeyCode::Delete => self.delete_to(self.next_char()),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Delete the text between the cursor and `position`, leaving the cursor where the deleted
+    /// text began
+    fn delete_to(&mut self, position: usize) {
+        let range = self.cursor.min(position)..self.cursor.max(position);
+        self.cursor = range.start;
+        self.text.replace_range(range, "");
+    }
+
+    fn previous_char(&self) -> usize {
+        self.text[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_char(&self) -> usize {
+        self.text[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    /// The start of the word before the cursor, skipping anything between them
+    fn previous_word(&self) -> usize {
+        let before = &self.text[..self.cursor];
+        let word_end = before.trim_end_matches(|c| !is_word_char(c)).len();
+        before[..word_end].trim_end_matches(is_word_char).len()
+    }
+
+    /// The end of the word after the cursor, skipping anything between them
+    fn next_word(&self) -> usize {
+        let after = &self.text[self.cursor..];
+        let word_start = after.len() - after.trim_start_matches(|c| !is_word_char(c)).len();
+        let word = after[word_start..].trim_start_matches(is_word_char);
+        self.text.len() - word.len()
+    }
+
+    /// The start of the whitespace-delimited word before the cursor, as readline's Ctrl+w deletes
+    fn previous_space(&self) -> usize {
+        let before = self.text[..self.cursor].trim_end();
+        before
+            .rfind(char::is_whitespace)
+            .map_or(0, |index| index + 1)
+    }
+}
+
+/// Whether `c` can be part of a word: a letter, digit or underscore, as in identifiers
+pub(super) fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(editor: &mut LineEditor, code: KeyCode, modifiers: KeyModifiers) {
+        assert!(editor.handle_key(KeyEvent::new(code, modifiers)));
+    }
+
+    fn with_cursor(editor: &LineEditor) -> String {
+        let (before, after) = editor.text().split_at(editor.cursor());
+        format!("{before}|{after}")
+    }
+
+    #[test]
+    fn moves_by_characters_and_words() {
+        let mut editor = LineEditor::from("tokio::sync::mpsc");
+        press(&mut editor, KeyCode::Left, KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&editor), "tokio::sync::|mpsc");
+        press(&mut editor, KeyCode::Char('b'), KeyModifiers::ALT);
+        assert_eq!(with_cursor(&editor), "tokio::|sync::mpsc");
+        press(&mut editor, KeyCode::Char('f'), KeyModifiers::ALT);
+        assert_eq!(with_cursor(&editor), "tokio::sync|::mpsc");
+        press(&mut editor, KeyCode::Char('b'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&editor), "tokio::syn|c::mpsc");
+        press(&mut editor, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut editor, KeyCode::Right, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&editor), "t|okio::sync::mpsc");
+        press(&mut editor, KeyCode::Char('e'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&editor), "tokio::sync::mpsc|");
+    }
+
+    #[test]
+    fn inserts_and_deletes_at_the_cursor() {
+        let mut editor = LineEditor::from("Vec");
+        press(&mut editor, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        for c in "std::".chars() {
+            press(&mut editor, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert_eq!(with_cursor(&editor), "std::|Vec");
+        press(&mut editor, KeyCode::Backspace, KeyModifiers::NONE);
+        press(&mut editor, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&editor), "std:|ec");
+        press(&mut editor, KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&editor), "std:|");
+        press(&mut editor, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&editor), "|");
+        assert!(!editor.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn deletes_words() {
+        let mut editor = LineEditor::from("hash map::entry");
+        press(&mut editor, KeyCode::Backspace, KeyModifiers::ALT);
+        assert_eq!(with_cursor(&editor), "hash map::|");
+        press(&mut editor, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(with_cursor(&editor), "hash |");
+        press(&mut editor, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        press(&mut editor, KeyCode::Char('d'), KeyModifiers::ALT);
+        assert_eq!(with_cursor(&editor), "| ");
+    }
+
+    #[test]
+    fn pastes_one_line() {
+        let mut editor = LineEditor::from("::Sender");
+        press(&mut editor, KeyCode::Home, KeyModifiers::NONE);
+        editor.paste("tokio::sync::mpsc\n");
+        assert_eq!(with_cursor(&editor), "tokio::sync::mpsc|::Sender");
+
+        let mut editor = LineEditor::default();
+        editor.paste("  async\r\n  channel ");
+        assert_eq!(editor.text(), "async channel");
+    }
+
+    #[test]
+    fn handles_multibyte_characters() {
+        let mut editor = LineEditor::from("→é");
+        press(&mut editor, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&editor), "→|é");
+        press(&mut editor, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(with_cursor(&editor), "|é");
+    }
+}
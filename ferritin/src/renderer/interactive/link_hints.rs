@@ -0,0 +1,142 @@
+//! Link hints - labelling the links in view so one can be followed by typing its label, as
+//! vimium does in the browser
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+};
+
+use super::{InteractiveState, state::KeyboardCursor};
+
+/// Letters hint labels are made of, home row first
+const HINT_LETTERS: &str = "asdfghjklqwertyuiopzxcvbnm";
+
+impl<'a> InteractiveState<'a> {
+    /// Label each link in view, for `f`
+    pub(super) fn show_link_hints(&mut self) {
+        if self.visible_links().next().is_none() {
+            self.ui.debug_message = "No links in view".into();
+            return;
+        }
+        self.ui.link_hints = Some(String::new());
+        self.ui.debug_message = "Type a link's label to follow it (Esc to cancel)".into();
+    }
+
+    /// Handle a key while link hints are shown: letters narrow the hints down until one is
+    /// followed, Backspace takes a letter back, and any other key hides them
+    pub(super) fn handle_link_hint_key(&mut self, key: KeyCode) {
+        let Some(typed) = self.ui.link_hints.as_mut() else {
+            return;
+        };
+        match key {
+            KeyCode::Char(c) if HINT_LETTERS.contains(c) => typed.push(c),
+            KeyCode::Backspace => {
+                typed.pop();
+                return;
+            }
+            _ => {
+                self.ui.link_hints = None;
+                self.ui.debug_message = "Link hints cancelled".into();
+                return;
+            }
+        }
+        if typed.len() < 2 {
+            return;
+        }
+
+        let typed = self.ui.link_hints.take().unwrap_or_default();
+        let action_index = label_index(&typed).and_then(|index| self.visible_links().nth(index));
+        match action_index {
+            Some(action_index) => {
+                self.viewport.keyboard_cursor = KeyboardCursor::Focused { action_index };
+                self.handle_activate_focused_link();
+            }
+            None => self.ui.debug_message = format!("No link labelled {typed}").into(),
+        }
+    }
+
+    /// Draw each link's label over its start, without the letters already typed, leaving out
+    /// the links whose labels don't start with them
+    pub(super) fn render_link_hints(&self, buf: &mut Buffer, area: Rect) {
+        let Some(typed) = &self.ui.link_hints else {
+            return;
+        };
+        let style = Style::default()
+            .fg(self.theme.status_loading_fg)
+            .bg(self.theme.status_loading_bg)
+            .add_modifier(Modifier::BOLD);
+
+        for (index, action_index) in self.visible_links().enumerate() {
+            let label = hint_label(index);
+            let Some(remaining) = label.strip_prefix(typed.as_str()) else {
+                continue;
+            };
+            let (rect, _) = &self.render_cache.actions[action_index];
+            let y = area.y + rect.y - self.viewport.scroll_offset;
+            for (x, c) in (rect.x..area.right()).zip(remaining.chars()) {
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    cell.set_char(c).set_style(style);
+                }
+            }
+        }
+    }
+
+    /// Indices of the links in view in document order, which is the order labels are handed
+    /// out in
+    fn visible_links(&self) -> impl Iterator<Item = usize> + '_ {
+        let viewport_top = self.viewport.scroll_offset;
+        let viewport_bottom = viewport_top + self.viewport.last_viewport_height;
+
+        self.render_cache
+            .actions
+            .iter()
+            .enumerate()
+            .filter(move |(_, (rect, _))| rect.y >= viewport_top && rect.y < viewport_bottom)
+            .map(|(index, _)| index)
+            .take(HINT_LETTERS.len() * HINT_LETTERS.len())
+    }
+}
+
+/// The two-letter label of the `index`th link in view
+fn hint_label(index: usize) -> String {
+    let letters = HINT_LETTERS.as_bytes();
+    let first = letters[index / letters.len() % letters.len()];
+    let second = letters[index % letters.len()];
+    [first as char, second as char].into_iter().collect()
+}
+
+/// Which link in view a two-letter label belongs to
+fn label_index(label: &str) -> Option<usize> {
+    let mut letters = label.chars();
+    let first = HINT_LETTERS.find(letters.next()?)?;
+    let second = HINT_LETTERS.find(letters.next()?)?;
+    letters
+        .next()
+        .is_none()
+        .then_some(first * HINT_LETTERS.len() + second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_are_two_home_row_letters_first() {
+        assert_eq!(hint_label(0), "aa");
+        assert_eq!(hint_label(1), "as");
+        assert_eq!(hint_label(26), "sa");
+        assert_eq!(hint_label(26 * 26 - 1), "mm");
+    }
+
+    #[test]
+    fn labels_map_back_to_their_link() {
+        for index in [0, 1, 25, 26, 300, 26 * 26 - 1] {
+            assert_eq!(label_index(&hint_label(index)), Some(index));
+        }
+        assert_eq!(label_index("a"), None);
+        assert_eq!(label_index("a1"), None);
+        assert_eq!(label_index("asd"), None);
+    }
+}
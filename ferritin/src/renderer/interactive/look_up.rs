@@ -0,0 +1,229 @@
+//! Double-click lookup - resolving the identifier under the mouse, like an IDE's ctrl-click
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use ferritin_common::{DocRef, RustdocData, std_builtins::std_builtin_path};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+};
+use rustdoc_types::Item;
+
+use super::channels::UiCommand;
+use super::history::HistoryEntry;
+use super::line_editor::is_word_char;
+use crate::request::Request;
+
+/// Two clicks on the same cell within this long make a double-click
+pub(super) const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+impl<'a> super::InteractiveState<'a> {
+    /// Look up the word double-clicked at `position`, now that the frame under it is in `buffer`
+    pub(super) fn look_up_word_at(
+        &mut self,
+        buffer: &Buffer,
+        mut areas: impl Iterator<Item = Rect>,
+        position: Position,
+    ) {
+        let Some(text) = areas.find_map(|area| word_at(buffer, area, position)) else {
+            return;
+        };
+
+        let scope = match self.document.history.current() {
+            Some(HistoryEntry::Item(item)) => Some(*item),
+            _ => None,
+        };
+        self.send_request(UiCommand::LookUp {
+            text: Cow::Owned(text),
+            scope,
+        });
+    }
+}
+
+/// The identifier rendered at `position` in `area`, along with the path segments qualifying it
+pub(super) fn word_at(buffer: &Buffer, area: Rect, position: Position) -> Option<String> {
+    if !area.contains(position) {
+        return None;
+    }
+
+    let mut line = String::new();
+    let mut clicked = 0;
+    for x in area.left()..area.right() {
+        if x == position.x {
+            clicked = line.len();
+        }
+        line.push_str(buffer.cell((x, position.y))?.symbol());
+    }
+    qualified_word_at(&line, clicked).map(str::to_string)
+}
+
+/// The word of `line` that `index` falls in, extended back over the `::`-separated segments
+/// before it, so that `mpsc` in `tokio::sync::mpsc::channel` is `tokio::sync::mpsc`
+///
+/// Numbers aren't identifiers, so they aren't words here.
+fn qualified_word_at(line: &str, index: usize) -> Option<&str> {
+    if !line[index..].starts_with(is_word_char) {
+        return None;
+    }
+
+    let end = line[index..]
+        .find(|c| !is_word_char(c))
+        .map_or(line.len(), |len| index + len);
+    let mut start = line[..index].trim_end_matches(is_word_char).len();
+    if line[start..].starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    while let Some(before) = line[..start].strip_suffix("::") {
+        let segment_start = before.trim_end_matches(is_word_char).len();
+        if segment_start == before.len() {
+            break;
+        }
+        start = segment_start;
+    }
+    Some(&line[start..end])
+}
+
+/// Resolve a double-clicked word or path as it would be in the source of `scope`: among the
+/// item's own members, then in each enclosing module out to the crate root, then as a path from
+/// a crate root or a primitive type or keyword, and finally as anything else of that name the
+/// crate defines or refers to
+pub(super) fn resolve<'a>(
+    request: &'a Request,
+    text: &str,
+    scope: Option<DocRef<'a, Item>>,
+) -> Option<DocRef<'a, Item>> {
+    let text = text
+        .strip_prefix("Self::")
+        .or_else(|| text.strip_prefix("self::"))
+        .unwrap_or(text);
+
+    if let Some(scope) = scope {
+        let specifier = crate_specifier(scope.crate_docs());
+        if let Some(path) = text.strip_prefix("crate::") {
+            return request.resolve_path(&format!("{specifier}::{path}"), &mut vec![]);
+        }
+
+        let scope_path = scope_path(scope);
+        let segments = scope_path
+            .split("::")
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+        for depth in (0..=segments.len()).rev() {
+            let path = std::iter::once(specifier.as_str())
+                .chain(segments[..depth].iter().copied())
+                .chain(std::iter::once(text))
+                .collect::<Vec<_>>()
+                .join("::");
+            if let Some(item) = request.resolve_path(&path, &mut vec![]) {
+                return Some(item);
+            }
+        }
+    }
+
+    // Other crates are only tried by name when they're known, since a word anywhere in the
+    // documentation could otherwise send off for a crate of that name
+    let first = text.split("::").next().unwrap_or(text);
+    let names_crate = request.list_available_crates().any(|crate_info| {
+        crate_info.name().replace('-', "_") == first
+            || crate_info.aliases().iter().any(|alias| alias == first)
+    });
+    if (names_crate || std_builtin_path(text).is_some())
+        && let Some(item) = request.resolve_path(text, &mut vec![])
+    {
+        return Some(item);
+    }
+
+    if text.contains("::") {
+        return None;
+    }
+    named_in_crate(request, scope?, text)
+}
+
+/// The item named `name` that `scope`'s crate defines or refers to, preferring its own items,
+/// then those with the shortest paths
+fn named_in_crate<'a>(
+    request: &'a Request,
+    scope: DocRef<'a, Item>,
+    name: &str,
+) -> Option<DocRef<'a, Item>> {
+    let mut candidates = scope
+        .crate_docs()
+        .paths()
+        .iter()
+        .filter(|(_, summary)| summary.path.last().is_some_and(|last| last == name))
+        .collect::<Vec<_>>();
+    candidates
+        .sort_by_key(|(_, summary)| (summary.crate_id != 0, summary.path.len(), &summary.path));
+
+    candidates.into_iter().find_map(|(id, summary)| {
+        if summary.crate_id == 0 {
+            return scope.get(id);
+        }
+        let external_docs = scope.build_ref(summary).external_crate()?.load()?;
+        let path = summary.path.get(1..)?.join("::");
+        request.resolve_path(
+            &format!("{}::{path}", crate_specifier(external_docs)),
+            &mut vec![],
+        )
+    })
+}
+
+/// `scope`'s path after its crate, with the kind discriminator on its last segment
+/// (`vec::struct@Vec`) so that a module and a type of the same name aren't confused
+///
+/// Empty for the crate root, and for items whose path isn't known.
+fn scope_path(scope: DocRef<'_, Item>) -> String {
+    if scope.id == scope.crate_docs().root() {
+        return String::new();
+    }
+    scope
+        .discriminated_path()
+        .and_then(|path| Some(path.split_once("::")?.1.to_string()))
+        .unwrap_or_default()
+}
+
+/// `name@version` for a crate's documentation, so that paths resolve within that same version
+fn crate_specifier(crate_docs: &RustdocData) -> String {
+    match crate_docs.version() {
+        Some(version) => format!("{}@{version}", crate_docs.name()),
+        None => crate_docs.name().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_at_char(line: &str, clicked: char) -> Option<&str> {
+        qualified_word_at(line, line.find(clicked).unwrap())
+    }
+
+    #[test]
+    fn words_take_their_path_qualifiers() {
+        let line = "let (tx, rx) = tokio::sync::mpsc::channel(8);";
+        assert_eq!(word_at_char(line, 'm'), Some("tokio::sync::mpsc"));
+        assert_eq!(word_at_char(line, 'h'), Some("tokio::sync::mpsc::channel"));
+        assert_eq!(word_at_char(line, 'x'), Some("tx"));
+        assert_eq!(word_at_char(line, '8'), None);
+        assert_eq!(word_at_char(line, '='), None);
+        assert_eq!(word_at_char("<T as Iterator>::Item", 'I'), Some("Iterator"));
+        assert_eq!(word_at_char("<T as Iterator>::Item", 'm'), Some("Item"));
+    }
+
+    #[test]
+    fn reads_words_from_the_rendered_buffer() {
+        let buffer = Buffer::with_lines(["  Returns a Vec<String>"]);
+        let area = Rect::new(2, 0, 21, 1);
+        assert_eq!(
+            word_at(&buffer, area, Position::new(13, 0)).as_deref(),
+            Some("Vec")
+        );
+        assert_eq!(
+            word_at(&buffer, area, Position::new(17, 0)).as_deref(),
+            Some("String")
+        );
+        assert_eq!(word_at(&buffer, area, Position::new(0, 0)), None);
+    }
+}
@@ -69,12 +69,22 @@
 //!
 //! The layout state is saved and restored when rendering children at different indentation levels.
 
+mod bookmarks;
 mod channels;
+mod clipboard;
+mod collapse;
+mod command_palette;
 mod dev_log;
 mod events;
 mod history;
+mod input_history;
 mod keyboard;
+mod line_editor;
+mod link_hints;
+mod look_up;
 mod mouse;
+mod outline;
+mod rebuild_thread;
 mod render_code_block;
 mod render_document;
 mod render_frame;
@@ -88,6 +98,7 @@ mod render_table;
 mod render_theme_picker;
 mod request_thread;
 mod response;
+mod source_pane;
 mod span_style;
 mod state;
 mod theme;
@@ -102,7 +113,7 @@ use theme::InteractiveTheme;
 
 pub use history::HistoryEntry;
 
-use utils::set_cursor_shape;
+use utils::{project_data_file, set_cursor_shape, supports_keyboard_protocol};
 
 use crate::{
     commands::Commands,
@@ -111,20 +122,31 @@ use crate::{
     renderer::interactive::state::{InputMode, InteractiveState, UiMode},
     request::Request,
     styled_string::{Document, DocumentNode, HeadingLevel, Span},
+    verbosity::Verbosity,
+    watch::SourceWatcher,
 };
 use crossbeam_channel::select;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyEventKind, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use ferritin_common::CancelToken;
 use ratatui::{Terminal, backend::CrosstermBackend};
 use std::{
     io::{self, stdout},
     thread,
 };
 
+use bookmarks::Bookmarks;
 use channels::{RequestResponse, UiCommand};
+use history::History;
+use input_history::InputHistory;
+use rebuild_thread::{queue_stale_crates, rebuild_thread_loop};
 use request_thread::request_thread_loop;
 
 /// Create a static loading document to show while sources are being loaded
@@ -140,34 +162,97 @@ fn initial_document() -> Document<'static> {
     ])
 }
 
+/// How an interactive session ended
+enum SessionEnd {
+    /// The user quit
+    Quit,
+    /// Workspace sources changed, out-of-date documentation finished rebuilding, or loaded
+    /// documentation outgrew the memory budget, so the session should restart on the page it
+    /// was showing
+    Reload {
+        command: Commands,
+        carryover: Box<Carryover>,
+        /// Whether the workspace's sources changed, rather than documentation being rebuilt or
+        /// memory running short
+        sources_changed: bool,
+    },
+}
+
+/// What the UI keeps from one session to the next
+struct Carryover {
+    /// Back and forward navigation, so that it survives restarts
+    history: History<'static>,
+    render_context: RenderContext,
+    log_reader: LogReader,
+}
+
 /// Render a document in interactive mode with scrolling and hover tracking
+///
+/// `request` should be lazy, so that the UI is up while sources load.
 pub fn render_interactive(
-    manifest_path: std::path::PathBuf,
+    mut request: Request,
     render_context: RenderContext,
     initial_command: Option<Commands>,
     log_reader: LogReader,
+    watch: bool,
 ) -> io::Result<()> {
-    use crate::format_context::FormatContext;
+    // The terminal answers this on its input, so it's asked before the event reader takes over
+    supports_keyboard_protocol();
+
+    // Spawn event reader thread that blocks on crossterm events. It outlives each session, since
+    // a blocked read can't be cancelled when a session is reloaded
+    let (event_tx, event_rx) = crossbeam_channel::unbounded();
+    let _event_reader = thread::spawn(move || {
+        while let Ok(evt) = event::read() {
+            if event_tx.send(evt).is_err() {
+                // UI thread dropped receiver, exit
+                break;
+            }
+        }
+    });
 
-    // Create lazy Request - exists immediately but Navigator not built yet
-    let format_context = FormatContext::new();
-    let request = Request::lazy(manifest_path, format_context);
+    let mut command = initial_command;
+    let mut carryover = Carryover {
+        history: History::new(None),
+        render_context,
+        log_reader,
+    };
 
-    // Use scoped threads so request can be borrowed by both threads
-    thread::scope(|scope| {
-        render_interactive_impl(scope, &request, render_context, initial_command, log_reader)
-    })
+    loop {
+        // Use scoped threads so request can be borrowed by both threads
+        let session_end = thread::scope(|scope| {
+            render_interactive_impl(scope, &request, command, carryover, event_rx.clone(), watch)
+        })?;
+
+        match session_end {
+            SessionEnd::Quit => return Ok(()),
+            SessionEnd::Reload {
+                command: current_command,
+                carryover: session_carryover,
+                sources_changed,
+            } => {
+                // Documents borrow from the Navigator, so fresh documentation (or dropping
+                // documentation to free memory) needs a fresh request and session rather than
+                // an update in place
+                request = request.restarted(sources_changed);
+                command = Some(current_command);
+                carryover = *session_carryover;
+            }
+        }
+    }
 }
 
 fn render_interactive_impl<'scope, 'env: 'scope>(
     scope: &'scope thread::Scope<'scope, 'env>,
     request: &'env Request,
-    render_context: RenderContext,
     initial_command: Option<Commands>,
-    log_reader: LogReader,
-) -> io::Result<()> {
-    // Build interactive theme from render context
-    let interactive_theme = InteractiveTheme::from_render_context(&render_context);
+    carryover: Carryover,
+    event_rx: crossbeam_channel::Receiver<Event>,
+    watch: bool,
+) -> io::Result<SessionEnd> {
+    let show_hidden = request.format_context().show_hidden();
+    let verbosity = request.format_context().verbosity();
+    let cancel_token = request.cancel_token().clone();
 
     // Create channels for communication between UI and request threads
     let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded::<UiCommand<'env>>();
@@ -175,13 +260,15 @@ fn render_interactive_impl<'scope, 'env: 'scope>(
 
     // Spawn UI thread - it only renders and handles input
     // UI thread starts without a document - will receive initial document via channel
-    let ui_handle = scope.spawn(|| -> io::Result<()> {
+    let ui_handle = scope.spawn(move || -> io::Result<SessionEnd> {
         ui_thread_loop(
-            render_context,
-            interactive_theme,
+            carryover,
+            show_hidden,
+            verbosity,
+            cancel_token,
             cmd_tx,
             resp_rx,
-            log_reader,
+            event_rx,
         )
     });
 
@@ -199,31 +286,88 @@ fn render_interactive_impl<'scope, 'env: 'scope>(
         entry: initial_entry,
     });
 
+    // Out-of-date workspace documentation is shown as it is while it's rebuilt on a thread of
+    // its own, so navigation never waits on cargo
+    let (stale_tx, stale_rx) = crossbeam_channel::unbounded();
+    let rebuild_cancel_token = CancelToken::default();
+    {
+        let resp_tx = resp_tx.clone();
+        let cancel_token = rebuild_cancel_token.clone();
+        scope.spawn(move || rebuild_thread_loop(request, stale_rx, resp_tx, cancel_token));
+    }
+    queue_stale_crates(request, &stale_tx, &resp_tx);
+
+    // Bookmarks and input history are kept per project, so they can only be loaded once the
+    // workspace is known
+    let project_root = request.local_source().map(|local| local.project_root());
+    let bookmarks_file = project_root.and_then(|root| project_data_file("bookmarks", root));
+    let _ = resp_tx.send(RequestResponse::Bookmarks(Bookmarks::load(bookmarks_file)));
+    let input_history_file = project_root.and_then(|root| project_data_file("input-history", root));
+    let _ = resp_tx.send(RequestResponse::InputHistory(InputHistory::load(
+        input_history_file,
+    )));
+
+    if watch && let Some(local_source) = request.local_source() {
+        match SourceWatcher::new(local_source.project_root(), local_source.target_dir()) {
+            Ok(source_watcher) => {
+                let _ = resp_tx.send(RequestResponse::Watching(source_watcher));
+            }
+            Err(e) => log::error!("Failed to watch workspace sources: {e}"),
+        }
+    }
+
     // Run request thread loop
-    request_thread_loop(request, cmd_rx, resp_tx);
+    request_thread_loop(request, cmd_rx, resp_tx, stale_tx);
 
-    // Wait for UI thread to complete and return its result
-    ui_handle.join().unwrap()?;
+    // A rebuild still running is for a session that's over
+    rebuild_cancel_token.cancel();
 
-    Ok(())
+    // Wait for UI thread to complete and return its result
+    ui_handle.join().unwrap()
 }
 
 /// UI thread loop - handles terminal rendering and input events only
 fn ui_thread_loop<'a>(
-    render_context: RenderContext,
-    interactive_theme: InteractiveTheme,
+    carryover: Carryover,
+    show_hidden: bool,
+    verbosity: Verbosity,
+    cancel_token: CancelToken,
     cmd_tx: crossbeam_channel::Sender<UiCommand<'a>>,
     resp_rx: crossbeam_channel::Receiver<RequestResponse<'a>>,
-    log_reader: LogReader,
-) -> io::Result<()> {
+    event_rx: crossbeam_channel::Receiver<Event>,
+) -> io::Result<SessionEnd> {
     // Set up terminal
     enable_raw_mode()?;
     let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    // Each screen keeps its own keyboard protocol flags, so they're pushed once on the alternate
+    // screen, and popped as each session ends
+    if supports_keyboard_protocol() {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+            )
+        )?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
+    // Build interactive theme from render context
+    let Carryover {
+        history,
+        render_context,
+        log_reader,
+    } = carryover;
+    let interactive_theme = InteractiveTheme::from_render_context(&render_context);
+
     // Create interactive state with static loading document - don't wait for sources
     let mut state = InteractiveState::new(
         initial_document(),
@@ -234,21 +378,20 @@ fn ui_thread_loop<'a>(
         interactive_theme,
         log_reader,
     );
-
-    // Spawn event reader thread that blocks on crossterm events
-    let (event_tx, event_rx) = crossbeam_channel::unbounded();
-    let _event_reader = thread::spawn(move || {
-        while let Ok(evt) = event::read() {
-            if event_tx.send(evt).is_err() {
-                // UI thread dropped receiver, exit
-                break;
-            }
-        }
-    });
+    state.document.history = history;
+    state.ui.show_hidden = show_hidden;
+    state.ui.verbosity = verbosity;
+    state.loading.cancel_token = cancel_token;
 
     // Timer for spinner animation during loading - fires every 30ms
     let timer_tick = crossbeam_channel::tick(std::time::Duration::from_millis(30));
 
+    // Poll a custom .tmTheme file for edits, so theme authors see changes without restarting
+    let theme_check = crossbeam_channel::tick(std::time::Duration::from_millis(500));
+
+    // Stands in for the source watcher until one arrives (or forever, without --watch)
+    let no_source_changes = crossbeam_channel::never();
+
     // Initial render before entering event loop
     terminal.draw(|frame| state.render_frame(frame))?;
     state.update_cursor(&mut terminal);
@@ -275,17 +418,50 @@ fn ui_thread_loop<'a>(
                 // Fall through to render below
             }
 
+            // Reload an edited .tmTheme file
+            recv(theme_check) -> _ => {
+                if !state.reload_theme_if_changed() {
+                    continue; // Skip render if the theme is unchanged
+                }
+            }
+
+            // Restart on fresh documentation once workspace sources change, with --watch
+            recv(state
+                .source_watcher
+                .as_ref()
+                .map_or(&no_source_changes, |watcher| watcher.changes())) -> _ => {
+                state.set_debug_message("Sources changed, rebuilding documentation...");
+                terminal.draw(|frame| state.render_frame(frame))?;
+                if let Some(source_watcher) = &state.source_watcher {
+                    source_watcher.settle();
+                }
+                let _ = state.cmd_tx.send(UiCommand::Shutdown);
+                break Ok(Some(true));
+            }
+
             // Request responses (documents, errors, shutdown)
             recv(state.resp_rx) -> response => {
                 match response {
+                    Ok(RequestResponse::OverMemoryBudget) => {
+                        state.set_debug_message("Freeing memory, reloading documentation...");
+                        terminal.draw(|frame| state.render_frame(frame))?;
+                        let _ = state.cmd_tx.send(UiCommand::Shutdown);
+                        break Ok(Some(false));
+                    }
+                    Ok(RequestResponse::Rebuilt) => {
+                        state.set_debug_message("Documentation rebuilt, reloading...");
+                        terminal.draw(|frame| state.render_frame(frame))?;
+                        let _ = state.cmd_tx.send(UiCommand::Shutdown);
+                        break Ok(Some(false));
+                    }
                     Ok(response) => {
                         if state.handle_response(response) {
-                            break Ok(());
+                            break Ok(None);
                         }
                     }
                     Err(_) => {
                         // Request thread dropped sender, exit
-                        break Ok(());
+                        break Ok(None);
                     }
                 }
             }
@@ -293,18 +469,26 @@ fn ui_thread_loop<'a>(
             // Keyboard and mouse events
             recv(event_rx) -> event => {
                 match event {
+                    // Only reported with the kitty keyboard protocol
+                    Ok(Event::Key(key)) if key.kind == KeyEventKind::Release => {
+                        state.handle_key_release();
+                        continue;
+                    }
                     Ok(Event::Key(key)) => {
                         if state.handle_key_event(key, &mut terminal) {
-                            break Ok(());
+                            break Ok(None);
                         }
                     }
                     Ok(Event::Mouse(mouse_event)) => {
                         state.handle_mouse_event(mouse_event, &terminal);
                     }
+                    Ok(Event::Paste(text)) => {
+                        state.handle_paste(&text);
+                    }
                     Ok(_) => {}
                     Err(_) => {
                         // Event reader thread exited
-                        break Ok(());
+                        break Ok(None);
                     }
                 }
             }
@@ -319,6 +503,27 @@ fn ui_thread_loop<'a>(
         state.update_cursor(&mut terminal);
     };
 
+    if supports_keyboard_protocol() {
+        let _ = execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags);
+    }
+
+    if let Ok(Some(sources_changed)) = result {
+        // Keep the rest of the terminal set up for the next session
+        return Ok(SessionEnd::Reload {
+            command: state
+                .document
+                .history
+                .current()
+                .map_or_else(Commands::list, HistoryEntry::to_cli_command),
+            carryover: Box::new(Carryover {
+                history: state.document.history.detached(),
+                render_context: state.render_context,
+                log_reader: state.log_reader,
+            }),
+            sources_changed,
+        });
+    }
+
     // Clean up terminal
     disable_raw_mode()?;
 
@@ -330,11 +535,12 @@ fn ui_thread_loop<'a>(
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )?;
     terminal.show_cursor()?;
 
-    result
+    result.map(|_| SessionEnd::Quit)
 }
 
 #[cfg(test)]
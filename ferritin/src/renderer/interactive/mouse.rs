@@ -1,6 +1,7 @@
 use std::io::Write;
+use std::time::Instant;
 
-use crossterm::event::{MouseEvent, MouseEventKind};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{Terminal, layout::Position, prelude::Backend};
 
 use crate::{
@@ -9,7 +10,7 @@ use crate::{
     styled_string::TuiAction,
 };
 
-use super::UiMode;
+use super::{UiMode, look_up::DOUBLE_CLICK_INTERVAL};
 
 impl<'a> super::InteractiveState<'a> {
     pub(super) fn handle_mouse_event(
@@ -32,8 +33,13 @@ impl<'a> super::InteractiveState<'a> {
                     return;
                 };
 
-                // In ThemePicker mode, use absolute screen coordinates (no scroll offset)
-                if matches!(self.ui_mode, super::UiMode::ThemePicker { .. }) {
+                // In modal overlays, use absolute screen coordinates (no scroll offset)
+                if matches!(
+                    self.ui_mode,
+                    UiMode::ThemePicker { .. }
+                        | UiMode::Bookmarks { .. }
+                        | UiMode::CommandPalette { .. }
+                ) {
                     self.viewport.cursor_pos = Some(Position::new(column, row));
                     return;
                 }
@@ -41,7 +47,8 @@ impl<'a> super::InteractiveState<'a> {
                 let terminal_height = size.height;
                 let terminal_width = size.width;
                 let content_height = terminal_height.saturating_sub(2); // Exclude 2 status lines
-                let content_width = terminal_width.saturating_sub(1); // Exclude scrollbar column
+                // Exclude scrollbar column, and the source pane in split-pane mode
+                let content_width = self.docs_pane_width(terminal_width.saturating_sub(1));
                 let breadcrumb_row = terminal_height.saturating_sub(2);
 
                 // Check if hovering over scrollbar
@@ -68,20 +75,30 @@ impl<'a> super::InteractiveState<'a> {
 
             MouseEvent {
                 kind: MouseEventKind::ScrollDown,
+                column,
                 ..
             } => {
-                self.set_scroll_offset(self.viewport.scroll_offset.saturating_add(1));
+                if let Some(pane) = self.source_pane_at(column, terminal) {
+                    self.set_source_scroll_offset(pane.saturating_add(1));
+                } else {
+                    self.set_scroll_offset(self.viewport.scroll_offset.saturating_add(1));
+                }
             }
 
             MouseEvent {
                 kind: MouseEventKind::ScrollUp,
+                column,
                 ..
             } => {
-                self.set_scroll_offset(self.viewport.scroll_offset.saturating_sub(1));
+                if let Some(pane) = self.source_pane_at(column, terminal) {
+                    self.set_source_scroll_offset(pane.saturating_sub(1));
+                } else {
+                    self.set_scroll_offset(self.viewport.scroll_offset.saturating_sub(1));
+                }
             }
 
             MouseEvent {
-                kind: MouseEventKind::Down(_),
+                kind: MouseEventKind::Down(button),
                 column,
                 row,
                 ..
@@ -92,7 +109,8 @@ impl<'a> super::InteractiveState<'a> {
 
                 let terminal_height = size.height;
                 let content_height = terminal_height.saturating_sub(2); // Exclude 2 status lines
-                let content_width = size.width.saturating_sub(1); // Exclude scrollbar column
+                // Exclude scrollbar column, and the source pane in split-pane mode
+                let content_width = self.docs_pane_width(size.width.saturating_sub(1));
                 let breadcrumb_row = terminal_height.saturating_sub(2);
 
                 // Check if click is in scrollbar column
@@ -102,9 +120,25 @@ impl<'a> super::InteractiveState<'a> {
                     // Calculate scroll position from click Y
                     self.handle_scrollbar_drag(row, content_height);
                 } else if row < content_height {
-                    // Click in main content area
-                    self.viewport.clicked_position =
-                        Some(Position::new(column, row + self.viewport.scroll_offset));
+                    // Click in main content area. A second click on the same cell looks up the
+                    // word there instead, once the frame under it has been rendered
+                    let position = Position::new(column, row);
+                    let now = Instant::now();
+                    match self.viewport.last_click.take() {
+                        Some((at, last_position))
+                            if button == MouseButton::Left
+                                && last_position == position
+                                && now.duration_since(at) < DOUBLE_CLICK_INTERVAL
+                                && matches!(self.ui_mode, UiMode::Normal) =>
+                        {
+                            self.viewport.double_click = Some(position);
+                        }
+                        _ => {
+                            self.viewport.last_click = Some((now, position));
+                            self.viewport.clicked_position =
+                                Some(Position::new(column, row + self.viewport.scroll_offset));
+                        }
+                    }
                 } else if row == breadcrumb_row {
                     // Click on breadcrumb bar
                     if let Some(entry) = self
@@ -113,8 +147,8 @@ impl<'a> super::InteractiveState<'a> {
                         .handle_click(Position::new(column, row))
                     {
                         // Send command from history entry (non-blocking)
-                        let _ = self.cmd_tx.send(entry.to_command());
-                        self.loading.start();
+                        let command = entry.to_command();
+                        self.send_request(command);
                     }
                 }
             }
@@ -145,6 +179,15 @@ impl<'a> super::InteractiveState<'a> {
         }
     }
 
+    /// The source pane's scroll offset, if `column` is over the source pane
+    fn source_pane_at(&self, column: u16, terminal: &Terminal<impl Backend>) -> Option<u16> {
+        let pane = self.source_pane.as_ref()?;
+        let content_width = terminal.size().ok()?.width.saturating_sub(1);
+        let source_end = content_width + 1 - self.outline_width(content_width);
+        (column > self.docs_pane_width(content_width) && column < source_end)
+            .then_some(pane.scroll_offset)
+    }
+
     pub(super) fn handle_click(&mut self) {
         // Handle any clicked action from previous iteration
         if let Some(click_pos) = self.viewport.clicked_position.take() {
@@ -156,6 +199,9 @@ impl<'a> super::InteractiveState<'a> {
                 .map(|(_, action)| action.clone());
 
             if let Some(action) = action_opt {
+                // Following a link isn't the first half of a double-click
+                self.viewport.last_click = None;
+
                 // Handle SelectTheme specially (doesn't go through request thread)
                 if let TuiAction::SelectTheme(theme_name) = &action {
                     // Apply theme immediately
@@ -175,11 +221,16 @@ impl<'a> super::InteractiveState<'a> {
 
                     self.ui.debug_message = format!("Selected theme: {theme_name}").into();
                 } else {
+                    let anchor = action.anchor().map(str::to_string);
                     match handle_action(&mut self.document.document, action) {
                         Some(command) => {
                             // Send command to request thread (non-blocking)
-                            let _ = self.cmd_tx.send(command);
-                            self.loading.start();
+                            self.send_request(command);
+                            self.viewport.pending_anchor = anchor;
+                            // Choosing a bookmark closes the overlay
+                            if matches!(self.ui_mode, UiMode::Bookmarks { .. }) {
+                                self.ui_mode = UiMode::Normal;
+                            }
                         }
                         None => {
                             // Action mutated document in place (e.g., ExpandBlock)
@@ -204,7 +255,7 @@ impl<'a> super::InteractiveState<'a> {
             KeyboardCursor::Focused { action_index } => {
                 if let Some((_, action)) = self.render_cache.actions.get(action_index) {
                     self.ui.debug_message = match action {
-                        TuiAction::Navigate { doc_ref, url: _ } => {
+                        TuiAction::Navigate { doc_ref, .. } => {
                             if let Some(path) = doc_ref.path() {
                                 format!("Navigate: {path} (⏎ to activate)").into()
                             } else if let Some(name) = doc_ref.name() {
@@ -213,7 +264,7 @@ impl<'a> super::InteractiveState<'a> {
                                 "Navigate: <unknown> (⏎ to activate)".into()
                             }
                         }
-                        TuiAction::NavigateToPath { path, url: _ } => {
+                        TuiAction::NavigateToPath { path, .. } => {
                             format!("Go to: {} (⏎ to activate)", path).into()
                         }
                         TuiAction::ExpandBlock(path) => {
@@ -243,7 +294,7 @@ impl<'a> super::InteractiveState<'a> {
                     .find(|(rect, _)| rect.contains(pos))
                 {
                     self.ui.debug_message = match action {
-                        TuiAction::Navigate { doc_ref, url: _ } => {
+                        TuiAction::Navigate { doc_ref, .. } => {
                             if let Some(path) = doc_ref.path() {
                                 format!("Navigate: {path}").into()
                             } else if let Some(name) = doc_ref.name() {
@@ -252,9 +303,7 @@ impl<'a> super::InteractiveState<'a> {
                                 "Navigate: <unknown>".into()
                             }
                         }
-                        TuiAction::NavigateToPath { path, url: _ } => {
-                            format!("Go to: {}", path).into()
-                        }
+                        TuiAction::NavigateToPath { path, .. } => format!("Go to: {}", path).into(),
                         TuiAction::ExpandBlock(path) => {
                             format!("Expand: {:?}", path.indices()).into()
                         }
@@ -0,0 +1,155 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget, Widget},
+};
+
+use super::state::{InteractiveState, KeyboardCursor, OutlineEntry, PaneFocus};
+use crate::styled_string::Span;
+
+/// Widest the outline sidebar gets, including its border
+const MAX_OUTLINE_WIDTH: u16 = 36;
+
+impl<'a> InteractiveState<'a> {
+    /// Show or hide the outline sidebar, focusing it when shown
+    pub(super) fn toggle_outline(&mut self) {
+        self.ui.show_outline = !self.ui.show_outline;
+        if self.ui.show_outline {
+            self.ui.focus = PaneFocus::Outline;
+            self.ui.outline_selected = self.current_outline_entry().unwrap_or(0);
+            self.ui.debug_message =
+                "Outline (↑/↓ to select, Enter to jump, Tab to switch focus, Esc to close)".into();
+        } else {
+            self.unfocus(PaneFocus::Outline);
+            self.ui.debug_message = "Outline hidden".into();
+        }
+    }
+
+    /// Width of the outline sidebar, given the width available for content
+    pub(super) fn outline_width(&self, content_width: u16) -> u16 {
+        if self.ui.show_outline {
+            (content_width / 3).min(MAX_OUTLINE_WIDTH)
+        } else {
+            0
+        }
+    }
+
+    /// Record a heading or section title while laying out the whole document
+    pub(super) fn record_outline_entry(&mut self, spans: &[Span<'a>], depth: u16) {
+        if !self.layout.recording_outline {
+            return;
+        }
+
+        let title: String = spans.iter().map(|span| &*span.text).collect();
+        let title = title.trim().trim_end_matches(':');
+        if title.is_empty() {
+            return;
+        }
+
+        self.render_cache.outline.push(OutlineEntry {
+            row: self.layout.pos.y,
+            title: title.to_string(),
+            depth,
+        });
+    }
+
+    /// Index of the last outline entry at or above the top of the viewport
+    fn current_outline_entry(&self) -> Option<usize> {
+        self.render_cache
+            .outline
+            .iter()
+            .rposition(|entry| entry.row <= self.viewport.scroll_offset)
+    }
+
+    /// Handle a selection key while the outline has focus
+    ///
+    /// Returns false for other keys, which fall through to normal mode.
+    pub(super) fn handle_outline_key(&mut self, key: KeyEvent) -> bool {
+        let entry_count = self.render_cache.outline.len();
+        let selected = &mut self.ui.outline_selected;
+
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('j'), _)
+            | (KeyCode::Down, _)
+            | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+                if *selected + 1 < entry_count {
+                    *selected += 1;
+                }
+            }
+            (KeyCode::Char('k'), _)
+            | (KeyCode::Up, _)
+            | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                *selected = selected.saturating_sub(1);
+            }
+            (KeyCode::Home, _) => *selected = 0,
+            (KeyCode::End, _) => *selected = entry_count.saturating_sub(1),
+            (KeyCode::Enter, _) | (KeyCode::Char(' '), _) => {
+                if let Some(entry) = self.render_cache.outline.get(*selected) {
+                    let (row, title) = (entry.row, entry.title.clone());
+                    self.set_scroll_offset(row);
+                    self.viewport.keyboard_cursor = KeyboardCursor::VirtualTop;
+                    self.ui.debug_message = format!("Jumped to {title}").into();
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Render the outline sidebar into `area`
+    pub(super) fn render_outline(&mut self, buf: &mut Buffer, area: Rect) {
+        let current = self.current_outline_entry();
+        let focused = self.ui.focus == PaneFocus::Outline;
+        self.ui.outline_selected = self
+            .ui
+            .outline_selected
+            .min(self.render_cache.outline.len().saturating_sub(1));
+
+        let items: Vec<ListItem> = if self.render_cache.outline.is_empty() {
+            vec![ListItem::new(Line::from(" No headings"))]
+        } else {
+            self.render_cache
+                .outline
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| {
+                    let marker = if Some(index) == current { '›' } else { ' ' };
+                    let indent = " ".repeat(entry.depth as usize * 2);
+                    ListItem::new(Line::from(format!("{marker}{indent}{}", entry.title)))
+                })
+                .collect()
+        };
+
+        let mut list_state = ListState::default();
+        if focused && !self.render_cache.outline.is_empty() {
+            list_state.select(Some(self.ui.outline_selected));
+        }
+
+        let border_style = if focused {
+            self.theme.breadcrumb_style
+        } else {
+            self.theme.muted_style
+        };
+        let block = Block::default()
+            .title(" Outline ")
+            .borders(Borders::LEFT)
+            .border_style(border_style)
+            .style(self.theme.document_bg_style);
+
+        let list = List::new(items).block(block).highlight_style(
+            Style::default()
+                .bg(self
+                    .theme
+                    .breadcrumb_style
+                    .bg
+                    .unwrap_or(ratatui::style::Color::Blue))
+                .add_modifier(Modifier::BOLD),
+        );
+
+        ratatui::widgets::Clear.render(area, buf);
+        StatefulWidget::render(list, area, buf, &mut list_state);
+    }
+}
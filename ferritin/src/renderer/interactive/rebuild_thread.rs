@@ -0,0 +1,65 @@
+//! Rebuild thread - regenerates out-of-date workspace documentation while it's being browsed
+
+use super::channels::RequestResponse;
+use crate::request::Request;
+use crossbeam_channel::{Receiver, Sender};
+use ferritin_common::{CancelToken, CrateName};
+
+/// Hand the workspace crates just served from out-of-date documentation to the rebuild thread,
+/// telling the UI which they are
+pub(super) fn queue_stale_crates<'a>(
+    request: &'a Request,
+    stale_tx: &Sender<CrateName<'static>>,
+    resp_tx: &Sender<RequestResponse<'a>>,
+) {
+    let Some(local_source) = request.local_source() else {
+        return;
+    };
+    let stale_crates = local_source.take_stale_crates();
+    if stale_crates.is_empty() {
+        return;
+    }
+
+    let names = stale_crates.iter().map(ToString::to_string).collect();
+    let _ = resp_tx.send(RequestResponse::Rebuilding(names));
+    for crate_name in stale_crates {
+        let _ = stale_tx.send(crate_name);
+    }
+}
+
+/// Rebuild thread loop - rebuilds each crate queued by the request thread, and once the queue
+/// is empty tells the UI to restart on the fresh documentation
+///
+/// Runs until the request thread drops its sender. Builds are killed when `cancel_token` is
+/// cancelled, as the session ends.
+pub(super) fn rebuild_thread_loop<'a>(
+    request: &'a Request,
+    stale_rx: Receiver<CrateName<'static>>,
+    resp_tx: Sender<RequestResponse<'a>>,
+    cancel_token: CancelToken,
+) {
+    let Some(local_source) = request.local_source() else {
+        return;
+    };
+
+    let mut rebuilt_any = false;
+    for crate_name in &stale_rx {
+        log::info!("Rebuilding documentation for {crate_name} in the background");
+        match local_source.rebuild_stale_docs(&crate_name, &cancel_token) {
+            Ok(()) => rebuilt_any = true,
+            Err(_) if cancel_token.is_cancelled() => return,
+            Err(e) => log::error!("Failed to rebuild documentation for {crate_name}: {e}"),
+        }
+
+        // Crates queued while this one built are rebuilt before restarting, so the session
+        // restarts once rather than once per crate
+        if stale_rx.is_empty() {
+            let response = if std::mem::take(&mut rebuilt_any) {
+                RequestResponse::Rebuilt
+            } else {
+                RequestResponse::RebuildFailed
+            };
+            let _ = resp_tx.send(response);
+        }
+    }
+}
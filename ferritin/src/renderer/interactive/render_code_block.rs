@@ -1,11 +1,15 @@
 use ratatui::{
     buffer::Buffer,
-    style::{Color, Style},
+    layout::Rect,
+    style::{Color, Modifier, Style},
 };
+use std::{borrow::Cow, collections::HashMap};
 use syntect::easy::HighlightLines;
 use syntect::util::LinesWithEndings;
 
-use super::state::InteractiveState;
+use super::state::{InteractiveState, KeyboardCursor};
+use crate::renderer::text_width::display_width;
+use crate::styled_string::{TuiAction, split_identifiers};
 
 // Code block borders are outdented to the left of content so that the code text
 // aligns with surrounding prose, and the border is purely decorative.
@@ -14,7 +18,14 @@ const CODE_BLOCK_BORDER_OUTDENT: i16 = -2; // Draw border 2 columns left of cont
 
 impl<'a> InteractiveState<'a> {
     /// Render code block with syntax highlighting
-    pub(super) fn render_code_block(&mut self, lang: Option<&str>, code: &str, buf: &mut Buffer) {
+    pub(super) fn render_code_block(
+        &mut self,
+        lang: Option<&str>,
+        code: &str,
+        first_line: Option<usize>,
+        links: &HashMap<Cow<'a, str>, TuiAction<'a>>,
+        buf: &mut Buffer,
+    ) {
         let lang_display = match lang {
             Some("no_run") | Some("should_panic") | Some("ignore") | Some("compile_fail")
             | Some("edition2015") | Some("edition2018") | Some("edition2021")
@@ -30,19 +41,25 @@ impl<'a> InteractiveState<'a> {
             .saturating_add_signed(CODE_BLOCK_BORDER_OUTDENT);
         let content_col = self.layout.indent; // Code content stays at indent
 
+        // Source listings get a line-number gutter, sized to the widest line number
+        let gutter_width = first_line.map_or(0, |first_line| {
+            (first_line + code.lines().count()).to_string().len() as u16 + 1
+        });
+        let code_col = content_col + gutter_width;
+
         // Calculate code block dimensions accounting for content position
         let available_width = self.layout.area.width.saturating_sub(content_col);
         let max_line_width = code
             .lines()
-            .map(|line| line.len())
+            .map(|line| display_width(line) + gutter_width as usize)
             .max()
             .unwrap_or(0)
             .min((available_width.saturating_sub(4)) as usize); // Leave room for border and padding
 
         // Account for language label in border width: ╭───❬rust❭─╮
         let lang_label = format!("❬{}❭", lang_display);
-        // Count actual display width (number of grapheme clusters, not bytes)
-        let label_display_width = lang_label.chars().count();
+        // Count actual display width, not bytes
+        let label_display_width = display_width(&lang_label);
         let min_border_for_label = label_display_width as u16 + 6; // label + some padding
         let border_width = ((max_line_width + 4).max(min_border_for_label as usize))
             .min(available_width as usize) as u16;
@@ -121,10 +138,18 @@ impl<'a> InteractiveState<'a> {
             .syntax_set()
             .find_syntax_by_token(lang_display)
         {
-            let theme = self.render_context.theme();
-            let mut highlighter = HighlightLines::new(syntax, theme);
+            // Highlighted up front, since writing linked identifiers needs `self` mutably
+            let mut highlighter = HighlightLines::new(syntax, self.render_context.theme());
+            let highlighted = LinesWithEndings::from(code)
+                .map(|line| {
+                    let ranges = highlighter
+                        .highlight_line(line, self.render_context.syntax_set())
+                        .ok();
+                    (line, ranges)
+                })
+                .collect::<Vec<_>>();
 
-            for line in LinesWithEndings::from(code) {
+            for (idx, (line, ranges)) in highlighted.into_iter().enumerate() {
                 if self.layout.pos.y >= self.viewport.scroll_offset
                     && self.layout.pos.y < self.viewport.scroll_offset + self.layout.area.height
                 {
@@ -137,32 +162,23 @@ impl<'a> InteractiveState<'a> {
                         self.layout.area,
                         border_style,
                     );
+                    self.write_line_number(buf, first_line, idx, gutter_width, content_col);
 
-                    let mut col = content_col;
+                    let mut col = code_col;
 
-                    if let Ok(ranges) =
-                        highlighter.highlight_line(line, self.render_context.syntax_set())
-                    {
+                    if let Some(ranges) = ranges {
                         for (style, text) in ranges {
                             let fg = style.foreground;
                             let ratatui_style = Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b));
                             let text = text.trim_end_matches('\n');
 
-                            self.write_text(
-                                buf,
-                                self.layout.pos.y,
-                                col,
-                                text,
-                                self.layout.area,
-                                ratatui_style,
-                            );
-                            col += text.len() as u16;
+                            col = self.write_code_text(buf, col, text, ratatui_style, links);
                         }
                     } else {
                         self.write_text(
                             buf,
                             self.layout.pos.y,
-                            content_col,
+                            code_col,
                             line.trim_end_matches('\n'),
                             self.layout.area,
                             Style::default(),
@@ -183,7 +199,7 @@ impl<'a> InteractiveState<'a> {
                 self.layout.pos.y += 1;
             }
         } else {
-            for line in code.lines() {
+            for (idx, line) in code.lines().enumerate() {
                 if self.layout.pos.y >= self.viewport.scroll_offset
                     && self.layout.pos.y < self.viewport.scroll_offset + self.layout.area.height
                 {
@@ -196,12 +212,13 @@ impl<'a> InteractiveState<'a> {
                         self.layout.area,
                         border_style,
                     );
+                    self.write_line_number(buf, first_line, idx, gutter_width, content_col);
 
                     // Code content
                     self.write_text(
                         buf,
                         self.layout.pos.y,
-                        content_col,
+                        code_col,
                         line,
                         self.layout.area,
                         Style::default(),
@@ -254,4 +271,77 @@ impl<'a> InteractiveState<'a> {
         }
         self.layout.pos.y += 1;
     }
+
+    /// Write the line number for the `idx`th line of a source listing into its gutter
+    fn write_line_number(
+        &self,
+        buf: &mut Buffer,
+        first_line: Option<usize>,
+        idx: usize,
+        gutter_width: u16,
+        col: u16,
+    ) {
+        let Some(first_line) = first_line else {
+            return;
+        };
+
+        let width = gutter_width.saturating_sub(1) as usize;
+        self.write_text(
+            buf,
+            self.layout.pos.y,
+            col,
+            &format!("{:>width$}", first_line + idx),
+            self.layout.area,
+            self.theme.muted_style,
+        );
+    }
+
+    /// Write a run of highlighted code, turning identifiers with known targets into links.
+    /// Returns the column after the written text.
+    fn write_code_text(
+        &mut self,
+        buf: &mut Buffer,
+        mut col: u16,
+        text: &str,
+        style: Style,
+        links: &HashMap<Cow<'a, str>, TuiAction<'a>>,
+    ) -> u16 {
+        for (run, is_ident) in split_identifiers(text) {
+            let width = display_width(run) as u16;
+            let mut run_style = style;
+
+            if let Some(action) = links.get(run).filter(|_| is_ident) {
+                run_style = run_style.add_modifier(Modifier::UNDERLINED);
+
+                let hovered = self.viewport.cursor_pos.is_some_and(|cursor| {
+                    cursor.y == self.layout.pos.y && cursor.x >= col && cursor.x < col + width
+                });
+                let focused = matches!(
+                    self.viewport.keyboard_cursor,
+                    KeyboardCursor::Focused { action_index }
+                        if action_index == self.render_cache.actions.len()
+                );
+                if hovered || focused {
+                    run_style = run_style.add_modifier(Modifier::REVERSED);
+                }
+
+                self.render_cache.actions.push((
+                    Rect::new(col, self.layout.pos.y, width.max(1), 1),
+                    action.clone(),
+                ));
+            }
+
+            self.write_text(
+                buf,
+                self.layout.pos.y,
+                col,
+                run,
+                self.layout.area,
+                run_style,
+            );
+            col += width;
+        }
+
+        col
+    }
 }
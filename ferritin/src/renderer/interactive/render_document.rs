@@ -13,6 +13,9 @@ impl<'a> InteractiveState<'a> {
     /// Render document nodes to buffer, updating action map
     pub(super) fn render_document(&mut self, _area: Rect, buf: &mut Buffer) {
         self.render_cache.actions.clear();
+        self.render_cache.anchor_y = None;
+        self.render_cache.code_blocks.clear();
+        self.render_cache.sections.clear();
 
         // Layout state already initialized in render_frame with area
         // Set initial position and indent
@@ -29,6 +32,12 @@ impl<'a> InteractiveState<'a> {
             .map(|cache| cache.render_width != self.layout.area.width)
             .unwrap_or(true);
 
+        // The outline needs every heading, so it's only rebuilt when the whole document is laid out
+        self.layout.recording_outline = need_height_calc;
+        if need_height_calc {
+            self.render_cache.outline.clear();
+        }
+
         // Use raw pointer to avoid borrow checker issues when calling render_node
         let nodes_ptr = self.document.document.nodes.as_ptr();
         let node_count = self.document.document.nodes.len();
@@ -1,9 +1,10 @@
 use ratatui::{
     Frame,
+    buffer::Buffer,
     layout::{Position, Rect},
 };
 
-use super::{InteractiveState, UiMode};
+use super::{InteractiveState, UiMode, history::SavedPosition};
 use crate::styled_string::NodePath;
 
 impl<'a> InteractiveState<'a> {
@@ -37,28 +38,67 @@ impl<'a> InteractiveState<'a> {
         } else {
             // Normal mode or DevLog mode - both render self.document.document
             // (DevLog has already swapped in its document)
-            // Clear main area with theme background
-            for y in 0..main_area.height {
-                for x in 0..main_area.width {
-                    frame
-                        .buffer_mut()
-                        .cell_mut((x, y))
-                        .unwrap()
-                        .set_style(self.theme.document_bg_style);
+            // Store viewport height for scroll clamping
+            self.viewport.last_viewport_height = main_area.height;
+
+            // The outline sidebar takes the right edge. In split-pane mode the documentation
+            // takes the left half of what remains, its scrollbar divides the panes, and the
+            // source listing takes the rest
+            let outline_width = self.outline_width(main_area.width);
+            let outline_area = Rect {
+                x: frame.area().width.saturating_sub(outline_width),
+                width: outline_width,
+                ..main_area
+            };
+            let docs_area = Rect {
+                width: self.docs_pane_width(main_area.width),
+                ..main_area
+            };
+            let source_x = docs_area.x + docs_area.width + 1;
+            let source_area = Rect {
+                x: source_x,
+                width: outline_area.x.saturating_sub(source_x),
+                ..main_area
+            };
+            if self.source_pane.is_some() {
+                self.render_source_pane(frame.buffer_mut(), source_area);
+            }
+
+            self.render_main_area(frame.buffer_mut(), docs_area);
+
+            // A heading anchor's position is only known once the document has been laid out,
+            // so scroll to it and render again
+            if self.viewport.scroll_to_anchor {
+                self.viewport.scroll_to_anchor = false;
+                if let Some(anchor_y) = self.render_cache.anchor_y {
+                    self.set_scroll_offset(anchor_y);
+                    self.render_main_area(frame.buffer_mut(), docs_area);
+                } else if let Some(anchor) = &self.viewport.target_anchor {
+                    self.ui.debug_message = format!("Heading not found: #{anchor}").into();
                 }
             }
 
-            // Store viewport height for scroll clamping
-            self.viewport.last_viewport_height = main_area.height;
+            // A double-clicked word is read back from what's been rendered under the mouse
+            if let Some(position) = self.viewport.double_click.take() {
+                let source_area = self.source_pane.is_some().then_some(source_area);
+                let areas = std::iter::once(docs_area).chain(source_area);
+                self.look_up_word_at(frame.buffer_mut(), areas, position);
+            }
+
+            self.render_link_hints(frame.buffer_mut(), docs_area);
 
-            // Reset layout state for this frame
-            self.layout.pos = Position::default();
-            self.layout.indent = 0;
-            self.layout.node_path = NodePath::new();
-            self.layout.area = main_area;
+            if outline_width > 0 {
+                self.render_outline(frame.buffer_mut(), outline_area);
+            }
 
-            // Render main document (will update cache if needed)
-            self.render_document(main_area, frame.buffer_mut());
+            // Remember where the reader is, so going back to this page returns here. While a
+            // request is in flight the history may already point at the page being loaded
+            if !self.loading.pending_request && matches!(self.ui_mode, UiMode::Normal) {
+                self.document.history.record_position(SavedPosition {
+                    scroll_offset: self.viewport.scroll_offset,
+                    keyboard_cursor: self.viewport.keyboard_cursor,
+                });
+            }
 
             // Render breadcrumb bar or loading animation
             if self.loading.pending_request {
@@ -71,12 +111,14 @@ impl<'a> InteractiveState<'a> {
                     .render(frame.buffer_mut(), breadcrumb_area, &self.theme);
             }
 
-            // Render status bar
-            self.render_status_bar(frame.buffer_mut(), status_area);
+            // Render status bar, placing the terminal's cursor where a prompt is being edited
+            if let Some(cursor) = self.render_status_bar(frame.buffer_mut(), status_area) {
+                frame.set_cursor_position(cursor);
+            }
 
             // Render scrollbar if we have cached layout information
             if let Some(layout_cache) = self.viewport.cached_layout {
-                self.render_scrollbar(frame.buffer_mut(), main_area, layout_cache.document_height);
+                self.render_scrollbar(frame.buffer_mut(), docs_area, layout_cache.document_height);
             }
 
             // Render theme picker overlay if in theme picker mode
@@ -84,6 +126,44 @@ impl<'a> InteractiveState<'a> {
                 let area = frame.area();
                 self.render_theme_picker(frame.buffer_mut(), area, selected_index);
             }
+
+            // Render bookmarks overlay if in bookmarks mode
+            if let UiMode::Bookmarks { selected_index } = self.ui_mode {
+                let area = frame.area();
+                self.render_bookmarks(frame.buffer_mut(), area, selected_index);
+            }
+
+            // Render command palette overlay if the palette is open
+            if let UiMode::CommandPalette {
+                ref query,
+                selected_index,
+            } = self.ui_mode
+            {
+                let area = frame.area();
+                let query = query.clone();
+                self.render_command_palette(frame.buffer_mut(), area, &query, selected_index);
+            }
         }
     }
+
+    /// Clear the document area with the theme background and render the document into it
+    pub(super) fn render_main_area(&mut self, buf: &mut Buffer, main_area: Rect) {
+        for y in 0..main_area.height {
+            for x in 0..main_area.width {
+                buf.cell_mut((x, y))
+                    .unwrap()
+                    .set_style(self.theme.document_bg_style);
+            }
+        }
+
+        // Reset layout state for this frame
+        self.layout.pos = Position::default();
+        self.layout.indent = 0;
+        self.layout.node_path = NodePath::new();
+        self.layout.area = main_area;
+        self.layout.section_depth = 0;
+
+        // Render main document (will update cache if needed)
+        self.render_document(main_area, buf);
+    }
 }
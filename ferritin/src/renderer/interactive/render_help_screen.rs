@@ -28,10 +28,19 @@ impl<'a> InteractiveState<'a> {
             ("  Ctrl+u, Alt+v, PgUp", "Page up", key_style),
             ("  Home, Alt+<", "Jump to top", key_style),
             ("  Shift+G, End, Alt+>", "Jump to bottom", key_style),
-            ("  ←, Backspace", "Navigate back in history", key_style),
-            ("  →", "Navigate forward in history", key_style),
+            (
+                "  ←, Backspace, Ctrl+o",
+                "Navigate back in history",
+                key_style,
+            ),
+            ("  →, Ctrl+i", "Navigate forward in history", key_style),
             ("", "", bg_style),
             ("Commands:", "", title_style),
+            (
+                "  f",
+                "Show link hints, then type one to follow it",
+                key_style,
+            ),
             ("  g", "Go to item by path", key_style),
             ("  s, /", "Search (scoped to current crate)", key_style),
             (
@@ -39,18 +48,64 @@ impl<'a> InteractiveState<'a> {
                 "  Toggle search scope (current/all crates)",
                 key_style,
             ),
+            (
+                "    ↑, ↓",
+                "  Recall previous go-to / search queries",
+                key_style,
+            ),
+            (
+                "    ←, →, Ctrl+w, Ctrl+u",
+                "  Edit the query as in a shell (readline keys)",
+                key_style,
+            ),
             ("  l", "List available crates", key_style),
             ("  c", "Toggle source code display", key_style),
+            (
+                "  z, Z",
+                "Collapse/expand section under cursor / expand all",
+                key_style,
+            ),
+            ("  v", "Toggle source view beside docs", key_style),
+            ("  O", "Toggle outline sidebar", key_style),
+            (
+                "  Tab",
+                "Switch focus between docs, source and outline",
+                key_style,
+            ),
+            ("  H", "Toggle #[doc(hidden)] items", key_style),
+            (
+                "  V",
+                "Cycle signature verbosity (short/normal/full)",
+                key_style,
+            ),
+            ("  o", "Open current item in browser", key_style),
+            ("  b", "Bookmark current item (again to remove)", key_style),
+            ("  B", "Show bookmarks", key_style),
+            (
+                "  yp, ys, yc",
+                "Copy item path / signature / code block",
+                key_style,
+            ),
             ("  t", "Select theme", key_style),
+            (
+                "  :",
+                "Command palette (type to filter commands)",
+                key_style,
+            ),
             (
                 "  Esc, Ctrl+g",
-                "Cancel input mode / Exit help / Quit",
+                "Cancel loading or input mode / Exit help / Quit",
                 key_style,
             ),
             ("", "", bg_style),
             ("Mouse:", "", title_style),
             ("  m", "Toggle mouse mode (for text selection)", key_style),
             ("  Click", "Navigate to item / Expand block", key_style),
+            (
+                "  Double-click",
+                "Look up the word under the mouse",
+                key_style,
+            ),
             ("  Hover", "Show preview in status bar", key_style),
             ("  Scroll", "Scroll content", key_style),
             ("", "", bg_style),
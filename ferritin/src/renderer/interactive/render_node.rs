@@ -1,7 +1,9 @@
 use ratatui::{buffer::Buffer, layout::Rect, style::Modifier};
 
 use super::{state::InteractiveState, utils::find_paragraph_truncation_point};
-use crate::styled_string::{DocumentNode, HeadingLevel, ShowWhen, TruncationLevel, TuiAction};
+use crate::styled_string::{
+    DocumentNode, HeadingLevel, ShowWhen, TruncationLevel, TuiAction, heading_anchor,
+};
 
 // Truncated block borders are outdented (to the left of content) so that content
 // doesn't shift when expanding/collapsing the block. The border is purely decorative.
@@ -46,9 +48,25 @@ impl<'a> InteractiveState<'a> {
                 // Draw blockquote markers if we're inside a blockquote
                 self.draw_blockquote_markers(buf);
 
+                // Headings targeted by a link anchor are highlighted, and their position
+                // recorded so the viewport can scroll to them
+                let mut modifier = Modifier::BOLD;
+                if let Some(anchor) = &self.viewport.target_anchor
+                    && *anchor == heading_anchor(spans)
+                {
+                    modifier |= Modifier::REVERSED;
+                    self.render_cache.anchor_y.get_or_insert(self.layout.pos.y);
+                }
+
+                let depth = match level {
+                    HeadingLevel::Title => 0,
+                    HeadingLevel::Section => 1,
+                };
+                self.record_outline_entry(spans, depth + self.layout.section_depth);
+
                 // Render heading spans (bold)
                 for span in spans {
-                    self.render_span_with_modifier(span, Modifier::BOLD, buf);
+                    self.render_span_with_modifier(span, modifier, buf);
                 }
 
                 // New line after heading
@@ -129,19 +147,47 @@ impl<'a> InteractiveState<'a> {
             }
 
             DocumentNode::Section { title, nodes } => {
+                let section_start = self.layout.pos.y;
+                let collapsed =
+                    title.is_some() && self.document.collapsed.contains(&self.layout.node_path);
+
                 if let Some(title_spans) = title {
                     // Block element: unconditionally position at indent
                     self.layout.pos.x = self.layout.indent;
+                    self.record_outline_entry(title_spans, 1 + self.layout.section_depth);
 
                     for span in title_spans {
                         self.render_span_with_modifier(span, Modifier::BOLD, buf);
                     }
 
+                    if collapsed {
+                        let marker = format!(" ▸ {} hidden (z to expand)", nodes.len());
+                        self.write_text(
+                            buf,
+                            self.layout.pos.y,
+                            self.layout.pos.x,
+                            &marker,
+                            self.layout.area,
+                            self.theme.muted_style,
+                        );
+                        self.layout.pos.y += 1;
+                        self.render_cache
+                            .sections
+                            .push((section_start..self.layout.pos.y, self.layout.node_path));
+                        return;
+                    }
+
                     // Add blank line after section title
                     self.layout.pos.y += 1;
                     self.layout.pos.y += 1;
                 }
 
+                // Titled sections nest the outline entries inside them
+                let saved_depth = self.layout.section_depth;
+                if title.is_some() {
+                    self.layout.section_depth += 1;
+                }
+
                 for (idx, child_node) in nodes.iter().enumerate() {
                     // Add blank line between consecutive blocks
                     if idx > 0 {
@@ -156,14 +202,30 @@ impl<'a> InteractiveState<'a> {
 
                     self.layout.node_path = saved_path;
                 }
+
+                self.layout.section_depth = saved_depth;
+                if title.is_some() {
+                    self.render_cache
+                        .sections
+                        .push((section_start..self.layout.pos.y, self.layout.node_path));
+                }
                 // Container: children handle their own spacing
             }
 
-            DocumentNode::CodeBlock { lang, code } => {
+            DocumentNode::CodeBlock {
+                lang,
+                code,
+                first_line,
+                links,
+            } => {
                 // Block element: unconditionally position at indent
                 self.layout.pos.x = self.layout.indent;
 
-                self.render_code_block(lang.as_deref(), code, buf);
+                let top = self.layout.pos.y;
+                self.render_code_block(lang.as_deref(), code, *first_line, links, buf);
+                self.render_cache
+                    .code_blocks
+                    .push((top..self.layout.pos.y, self.layout.node_path));
 
                 // Block element: increment y when done
                 self.layout.pos.y += 1;
@@ -343,6 +405,7 @@ impl<'a> InteractiveState<'a> {
                                 DocumentNode::CodeBlock { .. }
                                     | DocumentNode::GeneratedCode { .. }
                                     | DocumentNode::List { .. }
+                                    | DocumentNode::Footnotes { .. }
                             )
                         {
                             rendered_all = false;
@@ -461,6 +524,45 @@ impl<'a> InteractiveState<'a> {
                 }
                 // Transparent container: no additional spacing
             }
+
+            DocumentNode::Footnotes { notes } => {
+                // Block element: a short rule separates the notes from the documentation
+                self.layout.pos.x = self.layout.indent;
+                self.draw_blockquote_markers(buf);
+                self.write_text(
+                    buf,
+                    self.layout.pos.y,
+                    self.layout.pos.x,
+                    &"─".repeat(20),
+                    self.layout.area,
+                    self.theme.muted_style,
+                );
+                self.layout.pos.y += 1;
+
+                for note in notes {
+                    // Laid out like a list item, with the number in place of the bullet
+                    self.layout.pos.x = self.layout.indent;
+                    self.draw_blockquote_markers(buf);
+                    let number_text = format!("{:<4}", format!("[{}]", note.number));
+                    self.write_text(
+                        buf,
+                        self.layout.pos.y,
+                        self.layout.pos.x,
+                        &number_text,
+                        self.layout.area,
+                        self.theme.muted_style,
+                    );
+                    self.layout.pos.x += number_text.len() as u16;
+
+                    let saved_indent = self.layout.indent;
+                    self.layout.indent = self.layout.pos.x;
+                    for content_node in &note.content {
+                        self.render_node(content_node, buf);
+                    }
+                    self.layout.indent = saved_indent;
+                }
+                // Container: children handle their own spacing
+            }
         }
     }
 }
@@ -1,6 +1,7 @@
 use ratatui::{buffer::Buffer, layout::Rect, style::Modifier};
 
 use super::state::InteractiveState;
+use crate::renderer::text_width::{display_width, find_wrap_position};
 use crate::styled_string::Span;
 
 impl<'a> InteractiveState<'a> {
@@ -196,74 +197,3 @@ impl<'a> InteractiveState<'a> {
         }
     }
 }
-
-/// Calculate the display width of text, accounting for tabs rendered as 4 spaces
-fn display_width(text: &str) -> usize {
-    text.chars().map(|ch| if ch == '\t' { 4 } else { 1 }).sum()
-}
-
-/// Find the best position to wrap text within a given width
-/// Returns the position after which to break, or None if no good break point exists
-fn find_wrap_position(text: &str, max_width: usize) -> Option<usize> {
-    if max_width == 0 || text.is_empty() {
-        return None;
-    }
-
-    // Find the byte position that corresponds to max_width display columns (accounting for tabs)
-    let mut display_cols = 0;
-    let mut search_end = 0;
-    for (idx, ch) in text.char_indices() {
-        let char_width = if ch == '\t' { 4 } else { 1 };
-        if display_cols + char_width > max_width {
-            break;
-        }
-        display_cols += char_width;
-        search_end = idx + ch.len_utf8();
-    }
-
-    if search_end == 0 {
-        return None;
-    }
-
-    let search_range = &text[..search_end];
-
-    // First priority: break at whitespace
-    if let Some(pos) = search_range.rfind(char::is_whitespace) {
-        // Avoid breaking if it would leave a very short word (< 3 display cols) on next line
-        // This prevents orphans like "a" or "is" at the start of a line
-        let remaining_width = display_width(&text[pos..]);
-        if pos > 0 && remaining_width > 3 {
-            return Some(pos);
-        }
-        // If the remaining part is short enough, it's ok to break here
-        if remaining_width <= max_width / 2 {
-            return Some(pos);
-        }
-    }
-
-    // Second priority: break after certain punctuation (., ,, ;, :, ), ])
-    // This helps with long sentences without spaces
-    for (i, ch) in search_range.char_indices().rev() {
-        if matches!(ch, '.' | ',' | ';' | ':' | ')' | ']' | '}') {
-            // Break after the punctuation
-            if i + 1 < search_range.len() {
-                return Some(i + 1);
-            }
-        }
-    }
-
-    // Third priority: break at word boundaries (after lowercase before uppercase)
-    // This helps with camelCase or PascalCase identifiers
-    for i in (1..search_range.len()).rev() {
-        let chars: Vec<char> = search_range.chars().collect();
-        if i < chars.len() - 1 {
-            let prev = chars[i - 1];
-            let curr = chars[i];
-            if prev.is_lowercase() && curr.is_uppercase() {
-                return Some(i);
-            }
-        }
-    }
-
-    None
-}
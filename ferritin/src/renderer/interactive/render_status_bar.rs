@@ -1,4 +1,7 @@
-use ratatui::{buffer::Buffer, layout::Rect};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+};
 
 use super::{
     render_document::BASELINE_LEFT_MARGIN,
@@ -6,8 +9,9 @@ use super::{
 };
 
 impl<'a> InteractiveState<'a> {
-    /// Render status bar at the bottom of the screen
-    pub(super) fn render_status_bar(&mut self, buf: &mut Buffer, area: Rect) {
+    /// Render status bar at the bottom of the screen, returning where the cursor of a prompt
+    /// being edited is
+    pub(super) fn render_status_bar(&mut self, buf: &mut Buffer, area: Rect) -> Option<Position> {
         let style = self.theme.status_style;
         let hint_style = self.theme.status_hint_style;
 
@@ -17,16 +21,31 @@ impl<'a> InteractiveState<'a> {
             buf.cell_mut((x, area.y)).unwrap().set_style(style);
         }
 
-        // Determine what to display based on UI mode
+        // Out-of-date documentation is flagged until its rebuild finishes
+        let rebuilding = self
+            .ui
+            .rebuilding
+            .as_ref()
+            .map(|crate_names| format!("rebuilding {crate_names}…"));
+
+        // Determine what to display based on UI mode, and for prompts, the column of the cursor
+        // within the text
+        let mut cursor_column = None;
         let (display_text, hint_text) = match &self.ui_mode {
-            UiMode::Normal | UiMode::Help | UiMode::DevLog { .. } | UiMode::ThemePicker { .. } => {
-                (self.ui.debug_message.clone(), None)
-            }
+            UiMode::Normal
+            | UiMode::Help
+            | UiMode::DevLog { .. }
+            | UiMode::ThemePicker { .. }
+            | UiMode::Bookmarks { .. }
+            | UiMode::CommandPalette { .. } => (self.ui.debug_message.clone(), rebuilding),
 
-            _ if self.loading.pending_request => (self.ui.debug_message.clone(), None),
+            _ if self.loading.pending_request => (self.ui.debug_message.clone(), rebuilding),
 
             UiMode::Input(InputMode::GoTo { buffer }) => {
-                (format!("Go to: {}", buffer).into(), None)
+                let prompt = "Go to: ";
+                cursor_column =
+                    Some(prompt.len() + buffer.text()[..buffer.cursor()].chars().count());
+                (format!("{prompt}{}", buffer.text()).into(), None)
             }
             UiMode::Input(InputMode::Search {
                 buffer, all_crates, ..
@@ -48,17 +67,20 @@ impl<'a> InteractiveState<'a> {
 
                 // Only show toggle hint if there's a crate to toggle to
                 let hint = if current_crate.is_some() {
-                    Some("[tab] toggle scope")
+                    Some("[tab] toggle scope".to_string())
                 } else {
                     None
                 };
 
-                (format!("Search in {}: {}", scope, buffer).into(), hint)
+                let prompt = format!("Search in {scope}: ");
+                cursor_column =
+                    Some(prompt.chars().count() + buffer.text()[..buffer.cursor()].chars().count());
+                (format!("{prompt}{}", buffer.text()).into(), hint)
             }
         };
 
         // Calculate space for hint text (accounting for left margin)
-        let hint_len = hint_text.as_ref().map(|h| h.len()).unwrap_or(0);
+        let hint_len = hint_text.as_ref().map(|h| h.chars().count()).unwrap_or(0);
         let available_width = (area.width as usize).saturating_sub(BASELINE_LEFT_MARGIN as usize);
         let text_max_width = if hint_len > 0 {
             available_width.saturating_sub(hint_len + 2) // +2 for spacing
@@ -66,30 +88,22 @@ impl<'a> InteractiveState<'a> {
             available_width
         };
 
-        // Render main text (truncate if needed)
-        let truncated = if display_text.len() > text_max_width {
-            &display_text[..text_max_width]
-        } else {
-            &display_text
-        };
-
-        let mut col = BASELINE_LEFT_MARGIN;
-        for ch in truncated.chars() {
-            if col >= area.width {
-                break;
-            }
+        // Render main text, truncated to fit, and scrolled along a prompt that's too long so that
+        // its cursor stays in view
+        let skipped = cursor_column.map_or(0, |column| scrolled_columns(column, text_max_width));
+        let text = display_text.chars().skip(skipped).take(text_max_width);
+        for (col, ch) in (BASELINE_LEFT_MARGIN..area.width).zip(text) {
             buf.cell_mut((col, area.y))
                 .unwrap()
                 .set_char(ch)
                 .set_style(style);
-            col += 1;
         }
 
         // Render right-justified hint text if present (within margin-adjusted area)
         if let Some(hint) = hint_text {
             let hint_start = area
                 .width
-                .saturating_sub(hint.len() as u16)
+                .saturating_sub(hint.chars().count() as u16)
                 .max(BASELINE_LEFT_MARGIN);
             let mut hint_col = hint_start;
             for ch in hint.chars() {
@@ -103,5 +117,30 @@ impl<'a> InteractiveState<'a> {
                 hint_col += 1;
             }
         }
+
+        cursor_column.map(|column| Position {
+            x: (BASELINE_LEFT_MARGIN + column.saturating_sub(skipped) as u16)
+                .min(area.width.saturating_sub(1)),
+            y: area.y,
+        })
+    }
+}
+
+/// How many columns of a prompt's text are scrolled out of view on the left, so that the cursor
+/// at `cursor_column` fits in `width` columns, with room for it at the end of the text
+fn scrolled_columns(cursor_column: usize, width: usize) -> usize {
+    (cursor_column + 1).saturating_sub(width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrolls_long_prompts_to_the_cursor() {
+        assert_eq!(scrolled_columns(10, 40), 0);
+        assert_eq!(scrolled_columns(39, 40), 0);
+        assert_eq!(scrolled_columns(40, 40), 1);
+        assert_eq!(scrolled_columns(100, 40), 61);
     }
 }
@@ -4,6 +4,7 @@ use ratatui::{
 };
 
 use super::state::InteractiveState;
+use crate::renderer::text_width::{display_width, truncate_to_width};
 use crate::styled_string::TableCell;
 
 impl<'a> InteractiveState<'a> {
@@ -35,7 +36,11 @@ impl<'a> InteractiveState<'a> {
         // Measure header widths
         if let Some(header_cells) = header {
             for (col_idx, cell) in header_cells.iter().enumerate() {
-                let width = cell.spans.iter().map(|s| s.text.len()).sum::<usize>();
+                let width = cell
+                    .spans
+                    .iter()
+                    .map(|s| display_width(&s.text))
+                    .sum::<usize>();
                 col_widths[col_idx] = col_widths[col_idx].max(width);
             }
         }
@@ -44,7 +49,11 @@ impl<'a> InteractiveState<'a> {
         for row_cells in rows {
             for (col_idx, cell) in row_cells.iter().enumerate() {
                 if col_idx < num_cols {
-                    let width = cell.spans.iter().map(|s| s.text.len()).sum::<usize>();
+                    let width = cell
+                        .spans
+                        .iter()
+                        .map(|s| display_width(&s.text))
+                        .sum::<usize>();
                     col_widths[col_idx] = col_widths[col_idx].max(width);
                 }
             }
@@ -127,11 +136,11 @@ impl<'a> InteractiveState<'a> {
                     // Render cell content (bold for headers)
                     let mut cell_col = col_pos;
                     for span in &cell.spans {
-                        let span_text = if span.text.len() > col_widths[col_idx] {
-                            &span.text[..col_widths[col_idx]]
-                        } else {
-                            &span.text
-                        };
+                        let span_text = truncate_to_width(
+                            &span.text,
+                            (col_pos + col_widths[col_idx] as u16).saturating_sub(cell_col)
+                                as usize,
+                        );
 
                         let mut style = self.style(span.style);
                         style = style.add_modifier(Modifier::BOLD);
@@ -144,7 +153,7 @@ impl<'a> InteractiveState<'a> {
                             self.layout.area,
                             style,
                         );
-                        cell_col += span_text.len() as u16;
+                        cell_col += display_width(span_text) as u16;
                     }
 
                     // Pad to column width
@@ -250,11 +259,11 @@ impl<'a> InteractiveState<'a> {
                     // Render cell content
                     let mut cell_col = col_pos;
                     for span in &cell.spans {
-                        let span_text = if span.text.len() > col_widths[col_idx] {
-                            &span.text[..col_widths[col_idx]]
-                        } else {
-                            &span.text
-                        };
+                        let span_text = truncate_to_width(
+                            &span.text,
+                            (col_pos + col_widths[col_idx] as u16).saturating_sub(cell_col)
+                                as usize,
+                        );
 
                         let style = self.style(span.style);
                         self.write_text(
@@ -265,7 +274,7 @@ impl<'a> InteractiveState<'a> {
                             self.layout.area,
                             style,
                         );
-                        cell_col += span_text.len() as u16;
+                        cell_col += display_width(span_text) as u16;
                     }
 
                     // Pad to column width
@@ -2,25 +2,60 @@
 
 use super::channels::{RequestResponse, UiCommand};
 use super::history::HistoryEntry;
-use crate::commands::{list, search};
+use super::look_up;
+use super::rebuild_thread::queue_stale_crates;
+use crate::commands::{build_failure, list, search};
 use crate::{request::Request, styled_string::Document};
 use crossbeam_channel::{Receiver, Sender};
+use ferritin_common::CrateName;
 
 /// Request thread loop - processes commands from UI thread
+///
+/// Workspace crates served from out-of-date documentation along the way are sent on `stale_tx`
+/// to be rebuilt.
 pub(super) fn request_thread_loop<'a>(
     request: &'a Request,
     cmd_rx: Receiver<UiCommand<'a>>,
     resp_tx: Sender<RequestResponse<'a>>,
+    stale_tx: Sender<CrateName<'static>>,
 ) {
+    let mut over_memory_budget = false;
+    // Where the current page started in the order crates are used, so that the crates used
+    // since are known to be the ones it needs
+    let mut page_mark = request.use_mark();
+
+    // Responses to a request cancelled by the UI are dropped, since the reader has moved on
+    let send = |response: RequestResponse<'a>| {
+        if !request.cancel_token().is_cancelled() {
+            let _ = resp_tx.send(response);
+        }
+    };
+
     for cmd in cmd_rx {
+        // The UI cancels any request in flight before sending the next one
+        request.cancel_token().clear();
+
+        // Toggles and source views reformat the current page rather than starting another
+        if matches!(
+            cmd,
+            UiCommand::Navigate(_)
+                | UiCommand::NavigateToPath(_)
+                | UiCommand::LookUp { .. }
+                | UiCommand::Search { .. }
+                | UiCommand::List
+        ) {
+            page_mark = request.use_mark();
+        }
+
         match cmd {
             UiCommand::Navigate(doc_ref) => {
                 // Format the already-resolved item (e.g., from clicking a link)
+                request.mark_used(doc_ref.crate_docs());
                 let doc_nodes = request.format_item(doc_ref);
                 let doc = Document::from(doc_nodes);
                 let entry = HistoryEntry::Item(doc_ref);
 
-                let _ = resp_tx.send(RequestResponse::Document {
+                send(RequestResponse::Document {
                     doc,
                     entry: Some(entry),
                 });
@@ -33,12 +68,32 @@ pub(super) fn request_thread_loop<'a>(
                     let doc = Document::from(doc_nodes);
                     let entry = HistoryEntry::Item(item);
 
-                    let _ = resp_tx.send(RequestResponse::Document {
+                    send(RequestResponse::Document {
                         doc,
                         entry: Some(entry),
                     });
+                } else if let Some(failure_nodes) = build_failure::for_path(request, &path) {
+                    // The page stays out of history, like any other error
+                    send(RequestResponse::Document {
+                        doc: Document::from(failure_nodes),
+                        entry: None,
+                    });
+                } else {
+                    send(RequestResponse::Error(format!("Not found: {}", path)));
+                }
+            }
+
+            UiCommand::LookUp { text, scope } => {
+                if let Some(item) = look_up::resolve(request, &text, scope) {
+                    request.mark_used(item.crate_docs());
+                    send(RequestResponse::Document {
+                        doc: Document::from(request.format_item(item)),
+                        entry: Some(HistoryEntry::Item(item)),
+                    });
                 } else {
-                    let _ = resp_tx.send(RequestResponse::Error(format!("Not found: {}", path)));
+                    send(RequestResponse::Error(format!(
+                        "Nothing named {text} in scope"
+                    )));
                 }
             }
 
@@ -47,20 +102,29 @@ pub(super) fn request_thread_loop<'a>(
                 crate_name,
                 limit,
             } => {
-                let (search_doc, _is_error) = search::execute(
+                // Always create history entry for searches
+                let entry = HistoryEntry::Search {
+                    query: query.to_string(),
+                    crate_name: crate_name.as_ref().map(|c| c.to_string()),
+                    limit,
+                };
+
+                // Results from the crates searched so far replace each other on the same page,
+                // which the reader can start scrolling before the last crate is searched
+                let (search_doc, _is_error) = search::execute_with_progress(
                     request,
                     query.as_ref(),
                     limit,
                     crate_name.as_ref().map(|c| c.as_ref()),
+                    |partial_doc| {
+                        send(RequestResponse::Document {
+                            doc: partial_doc,
+                            entry: Some(entry.clone()),
+                        });
+                    },
                 );
 
-                // Always create history entry for searches
-                let entry = HistoryEntry::Search {
-                    query: query.into_owned(),
-                    crate_name: crate_name.map(|c| c.into_owned()),
-                };
-
-                let _ = resp_tx.send(RequestResponse::Document {
+                send(RequestResponse::Document {
                     doc: search_doc,
                     entry: Some(entry),
                 });
@@ -70,7 +134,7 @@ pub(super) fn request_thread_loop<'a>(
                 let (list_doc, _is_error, default_crate) = list::execute(request);
                 let entry = HistoryEntry::List { default_crate };
 
-                let _ = resp_tx.send(RequestResponse::Document {
+                send(RequestResponse::Document {
                     doc: list_doc,
                     entry: Some(entry),
                 });
@@ -82,7 +146,53 @@ pub(super) fn request_thread_loop<'a>(
             } => {
                 request.format_context().set_include_source(include_source);
                 if let Some(current_item) = current_item {
-                    let _ = resp_tx.send(RequestResponse::Document {
+                    send(RequestResponse::Document {
+                        doc: Document::from(request.format_item(current_item)),
+                        entry: None,
+                    });
+                }
+            }
+
+            UiCommand::LoadSource(item) => {
+                let (doc, start_line) = match request.format_source_file(item) {
+                    Some((nodes, start_line)) => (Some(Document::from(nodes)), start_line),
+                    None => (None, 0),
+                };
+                send(RequestResponse::Source { doc, start_line });
+            }
+
+            UiCommand::ToggleHidden {
+                show_hidden,
+                current_entry,
+            } => {
+                request.format_context().set_show_hidden(show_hidden);
+                let doc = match current_entry {
+                    Some(HistoryEntry::Item(item)) => {
+                        Some(Document::from(request.format_item(item)))
+                    }
+                    Some(HistoryEntry::Search {
+                        query,
+                        crate_name,
+                        limit,
+                    }) => Some(search::execute(request, &query, limit, crate_name.as_deref()).0),
+                    Some(HistoryEntry::Unresolved { path, .. }) => request
+                        .resolve_path(&path, &mut vec![])
+                        .map(|item| Document::from(request.format_item(item))),
+                    Some(HistoryEntry::List { .. }) | None => None,
+                };
+
+                if let Some(doc) = doc {
+                    send(RequestResponse::Document { doc, entry: None });
+                }
+            }
+
+            UiCommand::SetVerbosity {
+                verbosity,
+                current_item,
+            } => {
+                request.format_context().set_verbosity(verbosity);
+                if let Some(current_item) = current_item {
+                    send(RequestResponse::Document {
                         doc: Document::from(request.format_item(current_item)),
                         entry: None,
                     });
@@ -94,5 +204,15 @@ pub(super) fn request_thread_loop<'a>(
                 break;
             }
         }
+
+        queue_stale_crates(request, &stale_tx, &resp_tx);
+
+        // Once documentation outgrows the memory budget, ask (once) for the session to restart
+        // on the page just sent without the crates it doesn't need, and keep answering until the
+        // UI shuts this session down
+        if !over_memory_budget && request.is_over_memory_budget(page_mark) {
+            over_memory_budget = true;
+            let _ = resp_tx.send(RequestResponse::OverMemoryBudget);
+        }
     }
 }
@@ -2,6 +2,7 @@ use crate::renderer::interactive::UiMode;
 
 use super::InteractiveState;
 use super::channels::RequestResponse;
+use super::history::SavedPosition;
 
 impl<'a> InteractiveState<'a> {
     /// Handle log updates from the log reader (non-blocking)
@@ -22,28 +23,93 @@ impl<'a> InteractiveState<'a> {
     /// Handle a single response from the request thread
     /// Returns true if the UI should exit
     pub fn handle_response(&mut self, response: RequestResponse<'a>) -> bool {
-        self.loading.pending_request = false;
+        // Bookmarks, input history, source listings, the source watcher and background rebuilds
+        // arrive alongside documents rather than answering a request of their own
+        if !matches!(
+            response,
+            RequestResponse::Bookmarks(_)
+                | RequestResponse::InputHistory(_)
+                | RequestResponse::Source { .. }
+                | RequestResponse::Watching(_)
+                | RequestResponse::Rebuilding(_)
+                | RequestResponse::Rebuilt
+                | RequestResponse::RebuildFailed
+        ) {
+            self.loading.pending_request = false;
+        }
         match response {
             RequestResponse::Document { doc, entry } => {
                 self.document.document = doc;
-                self.set_scroll_offset(0);
                 // Invalidate layout cache when document changes
                 self.viewport.cached_layout = None;
-                // Reset keyboard cursor to virtual top when navigating to new document
-                self.reset_keyboard_cursor();
 
                 // Add to history if we got an entry
-                if let Some(new_entry) = entry {
-                    self.document.history.push(new_entry);
+                let is_new_entry = entry.is_some_and(|entry| self.document.history.push(entry));
+
+                // A new page starts at the top. Going back or forward to a page, or reformatting
+                // the current one, returns to where the reader was
+                let position = if is_new_entry {
+                    SavedPosition::default()
+                } else {
+                    self.document.history.current_position()
+                };
+                self.set_scroll_offset(position.scroll_offset);
+                self.viewport.keyboard_cursor = position.keyboard_cursor;
+
+                // Scroll to the heading anchor of the link that led here, if any
+                self.viewport.target_anchor = self.viewport.pending_anchor.take();
+                self.viewport.scroll_to_anchor = self.viewport.target_anchor.is_some();
+
+                self.restore_collapsed_sections();
+
+                // Keep the split-pane source view in step with the item being shown
+                if self.ui.split_source {
+                    self.request_source_pane();
                 }
                 false
             }
 
+            RequestResponse::Source { doc, start_line } => {
+                self.set_source_pane(doc, start_line);
+                false
+            }
+
             RequestResponse::Error(err) => {
+                self.viewport.pending_anchor = None;
                 self.ui.debug_message = err.into();
                 false
             }
 
+            RequestResponse::Bookmarks(bookmarks) => {
+                self.bookmarks = bookmarks;
+                false
+            }
+
+            RequestResponse::InputHistory(input_history) => {
+                self.input_history = input_history;
+                false
+            }
+
+            RequestResponse::Watching(source_watcher) => {
+                self.source_watcher = Some(source_watcher);
+                false
+            }
+
+            RequestResponse::Rebuilding(crate_names) => {
+                let mut rebuilding = self.ui.rebuilding.take().into_iter().collect::<Vec<_>>();
+                rebuilding.extend(crate_names);
+                self.ui.rebuilding = Some(rebuilding.join(", "));
+                false
+            }
+
+            RequestResponse::RebuildFailed => {
+                self.ui.rebuilding = None;
+                false
+            }
+
+            // The event loop restarts the session before these are reached
+            RequestResponse::OverMemoryBudget | RequestResponse::Rebuilt => false,
+
             RequestResponse::ShuttingDown => true,
         }
     }
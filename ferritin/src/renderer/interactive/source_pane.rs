@@ -0,0 +1,203 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{buffer::Buffer, layout::Rect};
+
+use super::{
+    channels::UiCommand,
+    state::{InteractiveState, PaneFocus, SourcePane},
+};
+use crate::styled_string::Document;
+
+/// Rows above the first line of code in a source listing: the "Source:" line, a blank line and
+/// the code block's top border
+const LISTING_HEADER_ROWS: u16 = 3;
+
+/// Lines of code left visible above the item when the source pane opens
+const CONTEXT_ROWS: u16 = 2;
+
+impl<'a> InteractiveState<'a> {
+    /// Show or hide the current item's source beside its documentation
+    pub(super) fn toggle_split_source(&mut self) {
+        self.ui.split_source = !self.ui.split_source;
+        if self.ui.split_source {
+            self.request_source_pane();
+            self.ui.debug_message = "Source pane shown (Tab to switch focus)".into();
+        } else {
+            self.source_pane = None;
+            self.unfocus(PaneFocus::Source);
+            self.ui.debug_message = "Source pane hidden".into();
+        }
+    }
+
+    /// Move keyboard focus to the next visible pane: documentation, source, then outline
+    pub(super) fn cycle_focus(&mut self) {
+        let panes = [
+            (PaneFocus::Documentation, true),
+            (PaneFocus::Source, self.source_pane.is_some()),
+            (PaneFocus::Outline, self.ui.show_outline),
+        ];
+        let current = panes
+            .iter()
+            .position(|(pane, _)| *pane == self.ui.focus)
+            .unwrap_or(0);
+        let next = (1..=panes.len())
+            .map(|step| panes[(current + step) % panes.len()])
+            .find(|(_, visible)| *visible)
+            .map_or(PaneFocus::Documentation, |(pane, _)| pane);
+
+        self.ui.focus = next;
+        self.ui.debug_message = match next {
+            PaneFocus::Documentation => "Focus: documentation".into(),
+            PaneFocus::Source => "Focus: source".into(),
+            PaneFocus::Outline => "Focus: outline".into(),
+        };
+    }
+
+    /// Return focus to the documentation if `pane` had it
+    pub(super) fn unfocus(&mut self, pane: PaneFocus) {
+        if self.ui.focus == pane {
+            self.ui.focus = PaneFocus::Documentation;
+        }
+    }
+
+    /// Ask the request thread for the current item's source, if there is a current item
+    pub(super) fn request_source_pane(&mut self) {
+        match self.document.history.current().and_then(|e| e.item()) {
+            Some(item) => {
+                let _ = self.cmd_tx.send(UiCommand::LoadSource(item));
+            }
+            None => {
+                self.source_pane = None;
+                self.unfocus(PaneFocus::Source);
+            }
+        }
+    }
+
+    /// Show a newly loaded source listing, scrolled to the start of the item
+    pub(super) fn set_source_pane(&mut self, document: Option<Document<'a>>, start_line: usize) {
+        if !self.ui.split_source {
+            return;
+        }
+
+        let Some(document) = document else {
+            self.source_pane = None;
+            self.unfocus(PaneFocus::Source);
+            self.ui.debug_message = "No source available for this item".into();
+            return;
+        };
+
+        let start_row =
+            LISTING_HEADER_ROWS.saturating_add(start_line.min(u16::MAX as usize) as u16);
+        self.source_pane = Some(SourcePane {
+            document,
+            scroll_offset: start_row.saturating_sub(CONTEXT_ROWS),
+            cached_layout: None,
+        });
+    }
+
+    /// Width of the documentation pane, given the width available for content
+    pub(super) fn docs_pane_width(&self, content_width: u16) -> u16 {
+        let width = content_width.saturating_sub(self.outline_width(content_width));
+        if self.source_pane.is_some() {
+            width / 2
+        } else {
+            width
+        }
+    }
+
+    /// Scroll the source pane, clamping to the listing's height once it is known
+    pub(super) fn set_source_scroll_offset(&mut self, offset: u16) {
+        let viewport_height = self.viewport.last_viewport_height;
+        if let Some(pane) = &mut self.source_pane {
+            pane.scroll_offset = match pane.cached_layout {
+                Some(cache) => offset.min(cache.document_height.saturating_sub(viewport_height)),
+                None => offset,
+            };
+        }
+    }
+
+    /// Handle a scrolling key while the source pane has focus
+    ///
+    /// Returns false for keys that aren't about scrolling, which fall through to normal mode.
+    pub(super) fn handle_source_pane_key(&mut self, key: KeyEvent) -> bool {
+        let Some(offset) = self.source_pane.as_ref().map(|pane| pane.scroll_offset) else {
+            return false;
+        };
+        let page_size = self.viewport.last_viewport_height / 2;
+
+        let offset = match (key.code, key.modifiers) {
+            (KeyCode::Char('j'), _)
+            | (KeyCode::Down, _)
+            | (KeyCode::Char('n'), KeyModifiers::CONTROL) => offset.saturating_add(1),
+            (KeyCode::Char('k'), _)
+            | (KeyCode::Up, _)
+            | (KeyCode::Char('p'), KeyModifiers::CONTROL) => offset.saturating_sub(1),
+            (KeyCode::Char('d'), KeyModifiers::CONTROL)
+            | (KeyCode::Char('v'), KeyModifiers::CONTROL)
+            | (KeyCode::PageDown, _) => offset.saturating_add(page_size),
+            (KeyCode::Char('u'), KeyModifiers::CONTROL)
+            | (KeyCode::Char('v'), KeyModifiers::ALT)
+            | (KeyCode::PageUp, _) => offset.saturating_sub(page_size),
+            (KeyCode::Home, _) | (KeyCode::Char('<'), KeyModifiers::ALT) => 0,
+            (KeyCode::Char('G'), KeyModifiers::SHIFT)
+            | (KeyCode::End, _)
+            | (KeyCode::Char('>'), KeyModifiers::ALT) => u16::MAX,
+            _ => return false,
+        };
+
+        self.set_source_scroll_offset(offset);
+        true
+    }
+
+    /// Render the source pane into `area`
+    ///
+    /// Document layout assumes it starts at the buffer's origin, so the listing is rendered into
+    /// a scratch buffer with the source pane's document and scroll state swapped in, then copied
+    /// into place.
+    pub(super) fn render_source_pane(&mut self, buf: &mut Buffer, area: Rect) {
+        if self.source_pane.is_none() {
+            return;
+        }
+
+        let pane_area = Rect::new(0, 0, area.width, area.height);
+        let mut pane_buf = Buffer::empty(pane_area);
+
+        // Keep the documentation pane's links, anchor and code block positions
+        let actions = std::mem::take(&mut self.render_cache.actions);
+        let anchor_y = self.render_cache.anchor_y.take();
+        let code_blocks = std::mem::take(&mut self.render_cache.code_blocks);
+        let sections = std::mem::take(&mut self.render_cache.sections);
+        let outline = std::mem::take(&mut self.render_cache.outline);
+        let target_anchor = self.viewport.target_anchor.take();
+
+        self.swap_source_pane();
+        self.render_main_area(&mut pane_buf, pane_area);
+        self.swap_source_pane();
+
+        self.render_cache.actions = actions;
+        self.render_cache.anchor_y = anchor_y;
+        self.render_cache.code_blocks = code_blocks;
+        self.render_cache.sections = sections;
+        self.render_cache.outline = outline;
+        self.viewport.target_anchor = target_anchor;
+
+        for y in 0..area.height {
+            for x in 0..area.width {
+                if let (Some(cell), Some(target)) = (
+                    pane_buf.cell((x, y)),
+                    buf.cell_mut((area.x + x, area.y + y)),
+                ) {
+                    *target = cell.clone();
+                }
+            }
+        }
+    }
+
+    /// Exchange the displayed document and scroll state with the source pane's
+    fn swap_source_pane(&mut self) {
+        if let Some(pane) = &mut self.source_pane {
+            std::mem::swap(&mut self.document.document, &mut pane.document);
+            std::mem::swap(&mut self.viewport.scroll_offset, &mut pane.scroll_offset);
+            std::mem::swap(&mut self.viewport.cached_layout, &mut pane.cached_layout);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 
 use crate::styled_string::SpanStyle;
 
@@ -7,23 +7,20 @@ use super::state::InteractiveState;
 impl<'a> InteractiveState<'a> {
     /// Convert SpanStyle to ratatui Style
     pub(super) fn style(&self, span_style: SpanStyle) -> Style {
-        match span_style {
-            SpanStyle::Plain => {
-                let fg = self.render_context.color_scheme().default_foreground();
-                Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b))
-            }
+        let color_scheme = self.render_context.color_scheme();
+        let style = match span_style {
             SpanStyle::Punctuation => Style::default(),
             SpanStyle::Strong => Style::default().add_modifier(Modifier::BOLD),
             SpanStyle::Emphasis => Style::default().add_modifier(Modifier::ITALIC),
             SpanStyle::Strikethrough => Style::default().add_modifier(Modifier::CROSSED_OUT),
-            SpanStyle::InlineCode | SpanStyle::InlineRustCode => {
-                let color = self.render_context.color_scheme().color_for(span_style);
-                Style::default().fg(Color::Rgb(color.r, color.g, color.b))
-            }
-            _ => {
-                let color = self.render_context.color_scheme().color_for(span_style);
-                Style::default().fg(Color::Rgb(color.r, color.g, color.b))
-            }
+            SpanStyle::FootnoteReference => Style::default().add_modifier(Modifier::DIM),
+            SpanStyle::Image => Style::default().add_modifier(Modifier::ITALIC),
+            _ => return Style::default().fg(color_scheme.terminal_color_for(span_style)),
+        };
+        // Styles the theme doesn't color can still be given a color in the config file
+        match color_scheme.configured_color_for(span_style) {
+            Some(color) => style.fg(color),
+            None => style,
         }
     }
 }
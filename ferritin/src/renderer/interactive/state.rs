@@ -1,15 +1,22 @@
 use ratatui::layout::{Position, Rect};
 use std::borrow::Cow;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::time::{Instant, SystemTime};
 
 use super::channels::{RequestResponse, UiCommand};
 use super::history::{History, HistoryEntry};
-use super::theme::InteractiveTheme;
+use super::theme::{InteractiveTheme, theme_file_modified};
 use super::utils::supports_cursor_shape;
+use super::{bookmarks::Bookmarks, input_history::InputHistory, line_editor::LineEditor};
 use crate::logging::LogReader;
 use crate::render_context::{RenderContext, ThemeError};
+use crate::renderer::OutputMode;
 use crate::styled_string::{Document, NodePath, TuiAction};
+use crate::verbosity::Verbosity;
+use crate::watch::SourceWatcher;
 use crossbeam_channel::{Receiver, Sender};
+use ferritin_common::CancelToken;
 
 /// UI mode - makes the modal structure of the interface explicit
 #[derive(Debug)]
@@ -33,15 +40,30 @@ pub(super) enum UiMode<'a> {
         /// Theme name to restore on cancel
         saved_theme_name: String,
     },
+    /// Bookmarks modal
+    Bookmarks {
+        /// Index of currently selected bookmark
+        selected_index: usize,
+    },
+    /// Command palette modal (: pressed)
+    CommandPalette {
+        /// Text typed to filter commands
+        query: String,
+        /// Index of currently selected match
+        selected_index: usize,
+    },
 }
 
 /// Input mode with mode-specific state
 #[derive(Debug)]
 pub(super) enum InputMode {
     /// Go-to mode (g pressed) - navigate to an item by path
-    GoTo { buffer: String },
+    GoTo { buffer: LineEditor },
     /// Search mode (s pressed) - search for items
-    Search { buffer: String, all_crates: bool },
+    Search {
+        buffer: LineEditor,
+        all_crates: bool,
+    },
 }
 
 /// Document and navigation state
@@ -49,6 +71,10 @@ pub(super) enum InputMode {
 pub(super) struct DocumentState<'a> {
     pub document: Document<'a>,
     pub history: History<'a>,
+    /// Titled sections of the current document that are collapsed
+    pub collapsed: HashSet<NodePath>,
+    /// Collapsed sections of each item visited this session, by item path
+    pub collapsed_by_item: HashMap<String, HashSet<NodePath>>,
 }
 
 /// Cached document layout information
@@ -93,6 +119,11 @@ pub(super) struct ViewportState {
     pub scroll_offset: u16,
     pub cursor_pos: Option<Position>,
     pub clicked_position: Option<Position>,
+    /// When and where on screen the last click in the content area was, to detect double-clicks
+    pub last_click: Option<(Instant, Position)>,
+    /// Screen position of a double-click, whose word is looked up once the frame under it has
+    /// been rendered
+    pub double_click: Option<Position>,
     pub cached_layout: Option<DocumentLayoutCache>,
     /// Last known viewport height for scroll clamping
     pub last_viewport_height: u16,
@@ -101,12 +132,54 @@ pub(super) struct ViewportState {
     pub scrollbar_dragging: bool,
     /// Keyboard navigation cursor
     pub keyboard_cursor: KeyboardCursor,
+    /// Heading anchor requested by the link whose navigation is in flight
+    pub pending_anchor: Option<String>,
+    /// Heading anchor in the current document to highlight
+    pub target_anchor: Option<String>,
+    /// Whether to scroll to the target anchor once its position is known
+    pub scroll_to_anchor: bool,
+}
+
+/// Which pane keyboard scrolling and selection apply to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PaneFocus {
+    Documentation,
+    Source,
+    Outline,
+}
+
+/// A heading or section title in the current document, for the outline sidebar
+#[derive(Debug, Clone)]
+pub(super) struct OutlineEntry {
+    /// Document row the heading is rendered on
+    pub row: u16,
+    pub title: String,
+    /// Nesting level, 0 for the page title
+    pub depth: u16,
+}
+
+/// Item source shown beside the documentation in split-pane mode
+///
+/// Scrolls independently of the documentation pane.
+#[derive(Debug)]
+pub(super) struct SourcePane<'a> {
+    pub document: Document<'a>,
+    pub scroll_offset: u16,
+    pub cached_layout: Option<DocumentLayoutCache>,
 }
 
 /// Rendering state computed each frame
 #[derive(Debug)]
 pub(super) struct RenderCache<'a> {
     pub actions: Vec<(Rect, TuiAction<'a>)>,
+    /// Document row of the heading matching the target anchor, if rendered this frame
+    pub anchor_y: Option<u16>,
+    /// Document rows spanned by each code block rendered this frame
+    pub code_blocks: Vec<(Range<u16>, NodePath)>,
+    /// Document rows spanned by each titled section rendered this frame, innermost first
+    pub sections: Vec<(Range<u16>, NodePath)>,
+    /// Headings and section titles, collected whenever the whole document is laid out
+    pub outline: Vec<OutlineEntry>,
 }
 
 /// UI display state
@@ -117,6 +190,24 @@ pub(super) struct UiState {
     pub is_hovering: bool,
     pub supports_cursor: bool,
     pub include_source: bool,
+    pub show_hidden: bool,
+    pub verbosity: Verbosity,
+    /// Whether `y` was pressed and the next key chooses what to copy
+    pub yank_pending: bool,
+    /// Letters typed so far while link hints are shown (`f`), `None` when they aren't
+    pub link_hints: Option<String>,
+    /// Whether the item's source is shown in a pane beside its docs
+    pub split_source: bool,
+    /// Whether the outline sidebar is shown
+    pub show_outline: bool,
+    /// Index of the selected outline entry
+    pub outline_selected: usize,
+    /// Which pane keyboard scrolling and selection apply to
+    pub focus: PaneFocus,
+    /// Workspace crates shown from out-of-date documentation while it's rebuilt, comma-separated
+    pub rebuilding: Option<String>,
+    /// How many times the held scrolling key has repeated, until its release
+    pub held_key_repeats: u16,
 }
 
 /// Request/response tracking state
@@ -125,13 +216,28 @@ pub(super) struct LoadingState {
     pub pending_request: bool,
     pub was_loading: bool,
     pub started_at: Instant,
+    /// Shared with the request thread, to stop work on a request the reader has moved on from
+    pub cancel_token: CancelToken,
 }
 
 impl LoadingState {
+    /// Note that a request is in flight, cancelling the one before it if it hasn't finished
     pub fn start(&mut self) {
+        if self.pending_request {
+            self.cancel_token.cancel();
+        }
         self.pending_request = true;
         self.started_at = Instant::now();
     }
+
+    /// Cancel the request in flight, if any, returning whether there was one
+    pub fn cancel(&mut self) -> bool {
+        let was_pending = std::mem::take(&mut self.pending_request);
+        if was_pending {
+            self.cancel_token.cancel();
+        }
+        was_pending
+    }
 }
 
 /// Layout state - cursor position, indentation, and viewport
@@ -145,6 +251,10 @@ pub(super) struct LayoutState {
     /// Stack of x positions where blockquote markers should be drawn
     /// When rendering content, markers are drawn at each of these positions
     pub blockquote_markers: Vec<u16>,
+    /// Number of titled sections enclosing the current node
+    pub section_depth: u16,
+    /// Whether this render lays out the whole document, so outline entries are recorded
+    pub recording_outline: bool,
 }
 
 /// Main interactive state - composes all UI state
@@ -157,6 +267,11 @@ pub(super) struct InteractiveState<'a> {
     pub ui_mode: UiMode<'a>,
     pub ui: UiState,
     pub loading: LoadingState,
+    pub bookmarks: Bookmarks,
+    pub input_history: InputHistory,
+    pub source_pane: Option<SourcePane<'a>>,
+    /// Workspace source watcher, with `--watch`
+    pub source_watcher: Option<SourceWatcher>,
 
     // Thread communication
     pub cmd_tx: Sender<UiCommand<'a>>,
@@ -167,6 +282,8 @@ pub(super) struct InteractiveState<'a> {
     pub render_context: RenderContext,
     pub theme: InteractiveTheme,
     pub current_theme_name: Option<String>,
+    /// Modification time of the current theme, when it was loaded from a .tmTheme file
+    pub theme_modified: Option<SystemTime>,
 }
 
 impl<'a> InteractiveState<'a> {
@@ -184,23 +301,35 @@ impl<'a> InteractiveState<'a> {
             .current_theme_name()
             .as_ref()
             .map(|s| s.to_string());
+        let theme_modified = current_theme_name.as_deref().and_then(theme_file_modified);
         Self {
             document: DocumentState {
                 document: initial_document,
                 history: History::new(initial_entry),
+                collapsed: HashSet::new(),
+                collapsed_by_item: HashMap::new(),
             },
             viewport: ViewportState {
                 scroll_offset: 0,
                 cursor_pos: None,
                 clicked_position: None,
+                last_click: None,
+                double_click: None,
                 cached_layout: None,
                 last_viewport_height: 0,
                 scrollbar_hovered: false,
                 scrollbar_dragging: false,
                 keyboard_cursor: KeyboardCursor::VirtualTop,
+                pending_anchor: None,
+                target_anchor: None,
+                scroll_to_anchor: false,
             },
             render_cache: RenderCache {
                 actions: Vec::new(),
+                anchor_y: None,
+                code_blocks: Vec::new(),
+                sections: Vec::new(),
+                outline: Vec::new(),
             },
             layout: LayoutState {
                 pos: Position::default(),
@@ -208,6 +337,8 @@ impl<'a> InteractiveState<'a> {
                 node_path: NodePath::new(),
                 area: Rect::default(),
                 blockquote_markers: Vec::new(),
+                section_depth: 0,
+                recording_outline: false,
             },
             ui_mode: UiMode::Normal,
             ui: UiState {
@@ -215,23 +346,49 @@ impl<'a> InteractiveState<'a> {
                 debug_message: "ferritin - q:quit ?:help ←/→:history g:go s:search l:list c:code"
                     .into(),
                 is_hovering: false,
-                supports_cursor: supports_cursor_shape(),
+                // Cursor-shape escapes are noise to a screen reader
+                supports_cursor: !matches!(render_context.output_mode(), OutputMode::ScreenReader)
+                    && supports_cursor_shape(),
                 include_source: false,
+                show_hidden: false,
+                verbosity: Verbosity::default(),
+                yank_pending: false,
+                link_hints: None,
+                split_source: false,
+                show_outline: false,
+                outline_selected: 0,
+                focus: PaneFocus::Documentation,
+                rebuilding: None,
+                held_key_repeats: 0,
             },
             loading: LoadingState {
                 pending_request: true,
                 was_loading: false,
                 started_at: Instant::now(),
+                cancel_token: CancelToken::default(),
             },
+            bookmarks: Bookmarks::default(),
+            input_history: InputHistory::default(),
+            source_pane: None,
+            source_watcher: None,
             cmd_tx,
             resp_rx,
             log_reader,
             render_context,
             theme,
             current_theme_name,
+            theme_modified,
         }
     }
 
+    /// Send a command to the request thread, cancelling the request before it if it's still
+    /// in flight
+    pub(super) fn send_request(&mut self, command: UiCommand<'a>) {
+        // Cancelled before sending, so the cancellation can't land on the new command
+        self.loading.start();
+        let _ = self.cmd_tx.send(command);
+    }
+
     pub(super) fn set_debug_message(&mut self, message: impl Into<Cow<'static, str>>) {
         if !self.loading.pending_request {
             self.ui.debug_message = message.into();
@@ -243,9 +400,31 @@ impl<'a> InteractiveState<'a> {
         self.render_context.set_theme_name(theme_name)?;
         self.theme = InteractiveTheme::from_render_context(&self.render_context);
         self.current_theme_name = Some(theme_name.to_string());
+        self.theme_modified = theme_file_modified(theme_name);
         Ok(())
     }
 
+    /// Reload the current theme if it came from a .tmTheme file that has changed since
+    ///
+    /// Returns whether the file changed, so the frame needs redrawing.
+    pub(super) fn reload_theme_if_changed(&mut self) -> bool {
+        let Some(theme_name) = self.current_theme_name.clone() else {
+            return false;
+        };
+        let modified = theme_file_modified(&theme_name);
+        if modified.is_none() || modified == self.theme_modified {
+            return false;
+        }
+
+        // Don't retry a half-written file until it changes again
+        self.theme_modified = modified;
+        self.ui.debug_message = match self.apply_theme(&theme_name) {
+            Ok(()) => format!("Reloaded theme {theme_name}").into(),
+            Err(e) => e.to_string().into(),
+        };
+        true
+    }
+
     /// Set scroll offset with automatic clamping to valid range
     pub(super) fn set_scroll_offset(&mut self, offset: u16) {
         self.viewport.scroll_offset = offset;
@@ -368,9 +547,4 @@ impl<'a> InteractiveState<'a> {
             .find(|(_, (rect, _))| rect.y >= viewport_top && rect.y < viewport_bottom)
             .map(|(idx, _)| idx)
     }
-
-    /// Reset keyboard cursor (called on navigation to new document)
-    pub(super) fn reset_keyboard_cursor(&mut self) {
-        self.viewport.keyboard_cursor = KeyboardCursor::VirtualTop;
-    }
 }
@@ -4,6 +4,7 @@ use crate::{
     styled_string::{Document, DocumentNode, Span, SpanStyle},
 };
 use crossbeam_channel::unbounded as channel;
+use line_editor::LineEditor;
 use ratatui::{Terminal, backend::TestBackend};
 
 /// Helper to create a minimal test state
@@ -56,7 +57,7 @@ fn test_mode_transitions_via_state() {
 
     // Transition to GoTo
     state.ui_mode = UiMode::Input(InputMode::GoTo {
-        buffer: String::new(),
+        buffer: LineEditor::default(),
     });
     assert!(matches!(
         state.ui_mode,
@@ -65,7 +66,7 @@ fn test_mode_transitions_via_state() {
 
     // Transition to Search
     state.ui_mode = UiMode::Input(InputMode::Search {
-        buffer: String::new(),
+        buffer: LineEditor::default(),
         all_crates: false,
     });
     assert!(matches!(
@@ -83,24 +84,24 @@ fn test_input_mode_buffer_manipulation() {
 
     // Enter GoTo mode
     state.ui_mode = UiMode::Input(InputMode::GoTo {
-        buffer: String::from("test"),
+        buffer: LineEditor::from("test"),
     });
 
     // Modify buffer
     if let UiMode::Input(InputMode::GoTo { buffer }) = &mut state.ui_mode {
-        buffer.push_str("_path");
-        assert_eq!(buffer, "test_path");
+        buffer.paste("_path");
+        assert_eq!(buffer.text(), "test_path");
     }
 
     // Enter Search mode
     state.ui_mode = UiMode::Input(InputMode::Search {
-        buffer: String::from("query"),
+        buffer: LineEditor::from("query"),
         all_crates: false,
     });
 
     // Toggle all_crates
     if let UiMode::Input(InputMode::Search { buffer, all_crates }) = &mut state.ui_mode {
-        assert_eq!(buffer, "query");
+        assert_eq!(buffer.text(), "query");
         assert!(!*all_crates);
         *all_crates = true;
         assert!(*all_crates);
@@ -127,6 +128,7 @@ fn test_history_navigation() {
     state.document.history.push(HistoryEntry::Search {
         query: "test".to_string(),
         crate_name: None,
+        limit: 20,
     });
     // Now we can go back (two entries, at index 1)
     assert!(state.document.history.can_go_back());
@@ -145,6 +147,78 @@ fn test_history_navigation() {
     assert!(!state.document.history.can_go_forward());
 }
 
+#[test]
+fn test_history_restores_position() {
+    use history::SavedPosition;
+    use state::KeyboardCursor;
+
+    let mut state = create_test_state();
+    state.document.history.push(HistoryEntry::List {
+        default_crate: None,
+    });
+    let position = SavedPosition {
+        scroll_offset: 42,
+        keyboard_cursor: KeyboardCursor::Focused { action_index: 3 },
+    };
+    state.document.history.record_position(position);
+
+    // A new entry starts at the top
+    assert!(state.document.history.push(HistoryEntry::Search {
+        query: "test".to_string(),
+        crate_name: None,
+        limit: 20,
+    }));
+    assert_eq!(
+        state.document.history.current_position(),
+        SavedPosition::default()
+    );
+
+    // Going back returns to the recorded position, and the response doesn't push again
+    state.document.history.go_back();
+    assert_eq!(state.document.history.current_position(), position);
+    assert!(!state.document.history.push(HistoryEntry::List {
+        default_crate: None,
+    }));
+}
+
+#[test]
+fn test_history_survives_restart() {
+    use history::{History, SavedPosition};
+    use state::KeyboardCursor;
+
+    let mut history = History::new(Some(HistoryEntry::List {
+        default_crate: Some("fixture"),
+    }));
+    history.push(HistoryEntry::Search {
+        query: "test".to_string(),
+        crate_name: None,
+        limit: 20,
+    });
+    let position = SavedPosition {
+        scroll_offset: 7,
+        keyboard_cursor: KeyboardCursor::VirtualTop,
+    };
+    history.record_position(position);
+    history.go_back();
+
+    let mut restored = history.detached();
+    assert!(restored.can_go_forward());
+
+    // Showing the list page again fills its default crate back in, rather than starting a page
+    assert!(!restored.push(HistoryEntry::List {
+        default_crate: Some("fixture"),
+    }));
+    assert_eq!(
+        restored.current(),
+        Some(&HistoryEntry::List {
+            default_crate: Some("fixture"),
+        })
+    );
+
+    restored.go_forward();
+    assert_eq!(restored.current_position(), position);
+}
+
 #[test]
 fn test_rendering_to_test_backend() {
     let mut state = create_test_state();
@@ -186,6 +260,8 @@ fn test_brief_truncation_with_code_block() {
                 DocumentNode::CodeBlock {
                     lang: Some("rust".into()),
                     code: "fn example() {\n    println!(\"Hello\");\n    let x = 42;\n    let y = 100;\n    let z = x + y;\n}\n".into(),
+                    first_line: None,
+                    links: Default::default(),
                 },
                 DocumentNode::paragraph(vec![Span::plain("Third paragraph after code.")]),
             ],
@@ -262,6 +338,8 @@ fn test_brief_with_short_code_block() {
                 DocumentNode::CodeBlock {
                     lang: Some("rust".into()),
                     code: "let x = 42;".into(),
+                    first_line: None,
+                    links: Default::default(),
                 },
             ],
         }],
@@ -496,6 +574,8 @@ fn test_code_block_spacing() {
             DocumentNode::CodeBlock {
                 lang: Some("rust".into()),
                 code: "let x = vec![1, 2, 3];".into(),
+                first_line: None,
+                links: Default::default(),
             },
             DocumentNode::paragraph(vec![Span::plain("More content after the code block.")]),
         ],
@@ -1,4 +1,5 @@
 use ratatui::style::{Color as RatatuiColor, Modifier, Style};
+use std::{fs, path::Path, time::SystemTime};
 use syntect::highlighting::{Color, ThemeSettings};
 
 use crate::render_context::RenderContext;
@@ -290,3 +291,12 @@ fn derive_secondary_accent_fg(settings: &ThemeSettings, accent: Color) -> Color
 fn derive_code_block_border(settings: &ThemeSettings, muted_fg: Color) -> Color {
     settings.guide.unwrap_or(muted_fg)
 }
+
+/// Modification time of `theme_name` if it names a .tmTheme file, for reloading it when edited
+pub(super) fn theme_file_modified(theme_name: &str) -> Option<SystemTime> {
+    let path = Path::new(theme_name);
+    if path.extension().and_then(|s| s.to_str()) != Some("tmTheme") {
+        return None;
+    }
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -1,7 +1,11 @@
 use crate::styled_string::DocumentNode;
-use crossterm::{queue, style::Print};
+use crossterm::{queue, style::Print, terminal::supports_keyboard_enhancement};
 use ratatui::prelude::Backend;
-use std::{env, io};
+use std::{
+    env, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
 /// Detect if the terminal supports mouse cursor shape changes
 pub(super) fn supports_cursor_shape() -> bool {
@@ -14,6 +18,16 @@ pub(super) fn supports_cursor_shape() -> bool {
             .unwrap_or(false)
 }
 
+/// Detect if the terminal speaks the kitty keyboard protocol, which reports key releases and the
+/// modifier combinations legacy escape sequences can't tell apart, like Ctrl+I from Tab
+///
+/// The terminal is asked once, and must be asked before anything else reads its input, since
+/// the answer arrives there.
+pub(super) fn supports_keyboard_protocol() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| supports_keyboard_enhancement().unwrap_or(false))
+}
+
 /// Set the mouse cursor shape (for terminals that support it)
 pub(super) fn set_cursor_shape<B: Backend + io::Write>(backend: &mut B, shape: &str) {
     // OSC 22 sequence: \x1b]22;<shape>\x07
@@ -92,3 +106,64 @@ pub(super) fn estimate_node_lines(node: &DocumentNode, _screen_width: u16) -> u1
         _ => 2,                                             // Default estimate for other nodes
     }
 }
+
+/// Where the interactive ui keeps its `kind` of data (like bookmarks) for the project at
+/// `project_root`: `ferritin/<kind>` in `$XDG_DATA_HOME` (falling back to `~/.local/share`),
+/// rather than the target directory, so that `cargo clean` leaves it be
+pub(super) fn project_data_file(kind: &str, project_root: &Path) -> Option<PathBuf> {
+    let data_dir = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))?;
+    Some(project_data_file_in(&data_dir, kind, project_root))
+}
+
+/// The `kind` data file for the project at `project_root` under `data_dir`
+///
+/// The file is named for a hash of the project's path, which keeps distinct projects apart
+/// however long their paths are, after a readable prefix from the project directory's name.
+fn project_data_file_in(data_dir: &Path, kind: &str, project_root: &Path) -> PathBuf {
+    // FNV-1a, which unlike std's hashers gives the same hash in every ferritin build
+    let hash = project_root
+        .as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    let prefix = project_root
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+        .take(64)
+        .collect::<String>();
+    data_dir
+        .join("ferritin")
+        .join(kind)
+        .join(format!("{prefix}-{hash:016x}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_project_data_files_for_their_project() {
+        let data_dir = Path::new("/data");
+        let file = project_data_file_in(data_dir, "bookmarks", Path::new("/home/user/project"));
+        let name = file.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("project-"), "{name}");
+        assert_eq!(file.parent(), Some(Path::new("/data/ferritin/bookmarks")));
+        assert_ne!(
+            file,
+            project_data_file_in(data_dir, "bookmarks", Path::new("/home/other/project"))
+        );
+
+        let deep = Path::new("/")
+            .join("nested-directory".repeat(8))
+            .join("x".repeat(200));
+        let file = project_data_file_in(data_dir, "input-history", &deep);
+        assert!(file.file_name().unwrap().len() < 100);
+    }
+}
@@ -1,6 +1,8 @@
 use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+use unicode_segmentation::UnicodeSegmentation;
 
 use super::state::InteractiveState;
+use crate::renderer::text_width::grapheme_width;
 
 impl<'a> InteractiveState<'a> {
     /// Write text to buffer at position
@@ -20,16 +22,16 @@ impl<'a> InteractiveState<'a> {
         let screen_row = row - self.viewport.scroll_offset;
         let mut current_col = col;
 
-        for ch in text.chars() {
+        for grapheme in text.graphemes(true) {
             if current_col >= area.width {
                 break; // Past right edge
             }
 
             // Handle tabs: replace with spaces to avoid column counting mismatches
             // Tabs display as multiple spaces in terminals but count as 1 character
-            if ch == '\t' {
+            if grapheme == "\t" {
                 // Write 4 spaces for each tab (Rust convention)
-                for _ in 0..4 {
+                for _ in 0..grapheme_width(grapheme) {
                     if current_col >= area.width {
                         break;
                     }
@@ -39,13 +41,28 @@ impl<'a> InteractiveState<'a> {
                     }
                     current_col += 1;
                 }
-            } else {
-                if let Some(cell) = buf.cell_mut((current_col, screen_row)) {
-                    cell.set_char(ch);
-                    cell.set_style(style);
+                continue;
+            }
+
+            let width = grapheme_width(grapheme) as u16;
+            if width == 0 {
+                continue; // Control characters and lone zero-width characters take no cell
+            }
+            if current_col + width > area.width {
+                break; // A wide character that would be cut off at the right edge
+            }
+
+            if let Some(cell) = buf.cell_mut((current_col, screen_row)) {
+                cell.set_symbol(grapheme);
+                cell.set_style(style);
+            }
+            // Cells covered by a wide character are reset, as ratatui does
+            for covered in current_col + 1..current_col + width {
+                if let Some(cell) = buf.cell_mut((covered, screen_row)) {
+                    cell.reset();
                 }
-                current_col += 1;
             }
+            current_col += width;
         }
     }
 }
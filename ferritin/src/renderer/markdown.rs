@@ -0,0 +1,372 @@
+//! Markdown renderer, for showing documentation in tools that display markdown (editor hovers,
+//! chat tools, static pages).
+//!
+//! Unlike the plain renderer, which is markdown-like but written to be read as-is, this writes
+//! CommonMark: headings with `#`, fenced code for signatures, `[text](url)` for links, and pipe
+//! tables.
+//!
+//! # Layout Model
+//!
+//! Follows the plain renderer: blocks end with a newline, consecutive blocks are separated by
+//! a blank line, and nested content (list items, block quotes) carries a line prefix.
+
+use std::fmt::{Result, Write};
+
+use crate::styled_string::{
+    Document, DocumentNode, HeadingLevel, ListItem, ShowWhen, Span, SpanStyle, TableCell,
+    TruncationLevel,
+};
+
+/// Markdown renderer state
+struct MarkdownRenderer<'w, W: Write> {
+    output: &'w mut W,
+    /// Written at the start of each line: spaces for list items, `> ` for block quotes
+    prefix: String,
+}
+
+/// Render a document as markdown
+pub fn render(document: &Document, output: &mut impl Write) -> Result {
+    let mut renderer = MarkdownRenderer::new(output);
+    renderer.render_block_sequence(&document.nodes)
+}
+
+impl<'w, W: Write> MarkdownRenderer<'w, W> {
+    fn new(output: &'w mut W) -> Self {
+        Self {
+            output,
+            prefix: String::new(),
+        }
+    }
+
+    fn write_prefix(&mut self) -> Result {
+        write!(self.output, "{}", self.prefix)
+    }
+
+    /// A blank line, keeping block quote markers so the quote isn't ended
+    fn blank_line(&mut self) -> Result {
+        writeln!(self.output, "{}", self.prefix.trim_end())
+    }
+
+    /// Render a sequence of block nodes with blank lines between them
+    fn render_block_sequence(&mut self, nodes: &[DocumentNode]) -> Result {
+        for (idx, node) in nodes.iter().enumerate() {
+            if idx > 0 {
+                self.blank_line()?;
+            }
+            self.render_node(node)?;
+        }
+        Ok(())
+    }
+
+    fn render_node(&mut self, node: &DocumentNode) -> Result {
+        match node {
+            DocumentNode::Paragraph { spans } => {
+                self.write_prefix()?;
+                self.render_spans(spans)?;
+                writeln!(self.output)
+            }
+            DocumentNode::Heading { level, spans } => {
+                self.write_prefix()?;
+                match level {
+                    HeadingLevel::Title => write!(self.output, "# ")?,
+                    HeadingLevel::Section => write!(self.output, "## ")?,
+                }
+                self.render_spans(spans)?;
+                writeln!(self.output)
+            }
+            DocumentNode::Section { title, nodes } => {
+                if let Some(title_spans) = title {
+                    self.write_prefix()?;
+                    write!(self.output, "### ")?;
+                    self.render_spans(title_spans)?;
+                    writeln!(self.output)?;
+                    if !nodes.is_empty() {
+                        self.blank_line()?;
+                    }
+                }
+                self.render_block_sequence(nodes)
+            }
+            DocumentNode::List { items } => {
+                for item in items {
+                    self.render_list_item(item)?;
+                }
+                Ok(())
+            }
+            DocumentNode::CodeBlock { lang, code, .. } => {
+                self.render_fenced(lang.as_deref().unwrap_or(""), code)
+            }
+            DocumentNode::GeneratedCode { spans } => {
+                let code: String = spans.iter().map(|span| &*span.text).collect();
+                self.render_fenced("rust", &code)
+            }
+            DocumentNode::HorizontalRule => {
+                self.write_prefix()?;
+                writeln!(self.output, "---")
+            }
+            DocumentNode::BlockQuote { nodes } => {
+                let saved_prefix = self.prefix.clone();
+                self.prefix.push_str("> ");
+                self.render_block_sequence(nodes)?;
+                self.prefix = saved_prefix;
+                Ok(())
+            }
+            DocumentNode::Table { header, rows } => {
+                let col_count = header
+                    .as_ref()
+                    .map_or_else(|| rows.first().map_or(0, |r| r.len()), |h| h.len());
+                // Markdown tables need a header row, so headerless tables get an empty one
+                match header {
+                    Some(header) => self.render_table_row(header)?,
+                    None => {
+                        self.write_prefix()?;
+                        writeln!(self.output, "|{}", "   |".repeat(col_count))?;
+                    }
+                }
+                self.write_prefix()?;
+                writeln!(self.output, "|{}", " --- |".repeat(col_count))?;
+                for row in rows {
+                    self.render_table_row(row)?;
+                }
+                Ok(())
+            }
+            DocumentNode::TruncatedBlock { nodes, level } => match level {
+                // There's nowhere to expand to, so truncated blocks show what fits
+                TruncationLevel::SingleLine | TruncationLevel::Brief => nodes
+                    .first()
+                    .map_or(Ok(()), |first| self.render_node(first)),
+                TruncationLevel::Full => self.render_block_sequence(nodes),
+            },
+            DocumentNode::Conditional { show_when, nodes } => match show_when {
+                ShowWhen::Always | ShowWhen::NonInteractive => self.render_block_sequence(nodes),
+                ShowWhen::Interactive => Ok(()),
+            },
+            DocumentNode::Footnotes { notes } => {
+                for (idx, note) in notes.iter().enumerate() {
+                    if idx > 0 {
+                        self.blank_line()?;
+                    }
+                    self.write_prefix()?;
+                    write!(self.output, "[^{}]: ", note.number)?;
+
+                    // Like a list item, the first node continues the label's line and the
+                    // rest are indented beneath it
+                    let saved_prefix = self.prefix.clone();
+                    let mut content = note.content.iter();
+                    if let Some(first) = content.next() {
+                        self.render_inline_first(first)?;
+                    } else {
+                        writeln!(self.output)?;
+                    }
+                    self.prefix.push_str("    ");
+                    for node in content {
+                        self.blank_line()?;
+                        self.render_node(node)?;
+                    }
+                    self.prefix = saved_prefix;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn render_fenced(&mut self, lang: &str, code: &str) -> Result {
+        // A fence longer than any backtick run in the code, so the code can't close it early
+        let longest_run = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+        let fence = "`".repeat(longest_run.max(2) + 1);
+
+        self.write_prefix()?;
+        writeln!(self.output, "{fence}{lang}")?;
+        for line in code.lines() {
+            self.write_prefix()?;
+            writeln!(self.output, "{line}")?;
+        }
+        self.write_prefix()?;
+        writeln!(self.output, "{fence}")
+    }
+
+    fn render_table_row(&mut self, cells: &[TableCell]) -> Result {
+        self.write_prefix()?;
+        write!(self.output, "|")?;
+        for cell in cells {
+            let mut text = String::new();
+            MarkdownRenderer::new(&mut text).render_spans(&cell.spans)?;
+            // Cells are single lines, and a pipe would end the cell
+            write!(
+                self.output,
+                " {} |",
+                text.replace('\n', " ").replace('|', "\\|")
+            )?;
+        }
+        writeln!(self.output)
+    }
+
+    fn render_spans(&mut self, spans: &[Span]) -> Result {
+        for span in spans {
+            self.render_span(span)?;
+        }
+        Ok(())
+    }
+
+    fn render_span(&mut self, span: &Span) -> Result {
+        if span.style == SpanStyle::Image
+            && let Some(url) = span.url()
+        {
+            return write!(
+                self.output,
+                "![{}]({url})",
+                span.text.replace(['[', ']'], "")
+            );
+        }
+        if span.style == SpanStyle::FootnoteReference {
+            let number = span.text.trim_matches(['[', ']']);
+            return write!(self.output, "[^{number}]");
+        }
+
+        let (open, close) = match span.style {
+            SpanStyle::InlineCode | SpanStyle::InlineRustCode => ("`", "`"),
+            SpanStyle::Strong => ("**", "**"),
+            SpanStyle::Emphasis => ("*", "*"),
+            SpanStyle::Strikethrough => ("~~", "~~"),
+            _ => ("", ""),
+        };
+        let url = span.url();
+        let text = span.text.trim_end_matches('\n');
+
+        if text.is_empty() || (text.trim().is_empty() && open.is_empty()) {
+            return self.write_text(&span.text);
+        }
+        if url.is_some() {
+            write!(self.output, "[")?;
+        }
+        write!(self.output, "{open}")?;
+        self.write_text(text)?;
+        write!(self.output, "{close}")?;
+        if let Some(url) = url {
+            write!(self.output, "]({url})")?;
+        }
+        // Newlines are written outside of the markup so that it stays on one line
+        self.write_text(&span.text[text.len()..])
+    }
+
+    /// Write text, prefixing each line after the first
+    fn write_text(&mut self, text: &str) -> Result {
+        for (idx, line) in text.split('\n').enumerate() {
+            if idx > 0 {
+                writeln!(self.output)?;
+                self.write_prefix()?;
+            }
+            write!(self.output, "{line}")?;
+        }
+        Ok(())
+    }
+
+    fn render_list_item(&mut self, item: &ListItem) -> Result {
+        self.write_prefix()?;
+        write!(self.output, "- ")?;
+
+        let saved_prefix = self.prefix.clone();
+
+        // The first node continues the bullet's line, and the rest line up with it
+        let mut content = item.content.iter();
+        if let Some(first) = content.next() {
+            self.render_inline_first(first)?;
+        } else {
+            writeln!(self.output)?;
+        }
+
+        self.prefix.push_str("  ");
+        for node in content {
+            self.render_node(node)?;
+        }
+
+        self.prefix = saved_prefix;
+        Ok(())
+    }
+
+    /// Render a node that starts on a line that already has its prefix and a bullet
+    fn render_inline_first(&mut self, node: &DocumentNode) -> Result {
+        match node {
+            DocumentNode::Paragraph { spans } => {
+                let saved_prefix = self.prefix.clone();
+                self.prefix.push_str("  ");
+                self.render_spans(spans)?;
+                self.prefix = saved_prefix;
+                writeln!(self.output)
+            }
+            other => {
+                writeln!(self.output)?;
+                let saved_prefix = self.prefix.clone();
+                self.prefix.push_str("  ");
+                self.render_node(other)?;
+                self.prefix = saved_prefix;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::styled_string::Footnote;
+
+    #[test]
+    fn test_render_markdown() {
+        let doc = Document::with_nodes(vec![
+            DocumentNode::heading(
+                HeadingLevel::Title,
+                vec![Span::plain("Item: "), Span::type_name("Vec")],
+            ),
+            DocumentNode::paragraph(vec![
+                Span::plain("A "),
+                Span::inline_code("Vec"),
+                Span::plain(" is "),
+                Span::strong("growable"),
+            ]),
+            DocumentNode::list(vec![
+                ListItem::new(vec![DocumentNode::paragraph(vec![Span::plain("First")])]),
+                ListItem::new(vec![DocumentNode::paragraph(vec![Span::plain("Second")])]),
+            ]),
+            DocumentNode::GeneratedCode {
+                spans: vec![
+                    Span::keyword("pub struct"),
+                    Span::plain(" "),
+                    Span::type_name("Vec"),
+                ],
+            },
+        ]);
+
+        let mut output = String::new();
+        render(&doc, &mut output).unwrap();
+
+        assert_eq!(
+            output,
+            "# Item: Vec\n\
+             \n\
+             A `Vec` is **growable**\n\
+             \n\
+             - First\n\
+             - Second\n\
+             \n\
+             ```rust\n\
+             pub struct Vec\n\
+             ```\n"
+        );
+    }
+
+    #[test]
+    fn test_render_footnotes() {
+        let doc = Document::with_nodes(vec![
+            DocumentNode::paragraph(vec![Span::plain("Noted"), Span::footnote_reference(1)]),
+            DocumentNode::footnotes(vec![Footnote {
+                number: 1,
+                content: vec![DocumentNode::paragraph(vec![Span::plain("The note.")])],
+            }]),
+        ]);
+
+        let mut output = String::new();
+        render(&doc, &mut output).unwrap();
+
+        assert_eq!(output, "Noted[^1]\n\n[^1]: The note.\n");
+    }
+}
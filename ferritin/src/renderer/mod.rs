@@ -4,9 +4,15 @@ use std::{
     io::{self, IsTerminal},
 };
 
+mod base64;
+pub(crate) mod html;
+pub(crate) mod inline_image;
 mod interactive;
+mod markdown;
 mod plain;
+mod screen_reader;
 mod test_mode;
+mod text_width;
 mod tty;
 
 pub use interactive::{HistoryEntry, render_interactive};
@@ -38,6 +44,10 @@ pub enum OutputMode {
     Plain,
     /// Pseudo-XML tags for testing (e.g., <keyword>struct</keyword>)
     TestMode,
+    /// Plain text with structure spelled out in words, for screen readers
+    ScreenReader,
+    /// CommonMark, for tools that display markdown
+    Markdown,
 }
 
 impl OutputMode {
@@ -63,6 +73,8 @@ pub fn render(
         OutputMode::Tty => tty::render(document, render_context, output),
         OutputMode::Plain => plain::render(document, output),
         OutputMode::TestMode => test_mode::render(document, output),
+        OutputMode::ScreenReader => screen_reader::render(document, output),
+        OutputMode::Markdown => markdown::render(document, output),
     }
 }
 
@@ -84,6 +96,7 @@ mod tests {
         let mut tty_output = String::new();
         let mut plain_output = String::new();
         let mut test_output = String::new();
+        let mut screen_reader_output = String::new();
 
         // Test that all modes produce output without panicking
         render(
@@ -104,9 +117,16 @@ mod tests {
             &mut test_output,
         )
         .unwrap();
+        render(
+            &doc,
+            &RenderContext::new().with_output_mode(OutputMode::ScreenReader),
+            &mut screen_reader_output,
+        )
+        .unwrap();
 
         assert!(!tty_output.is_empty());
         assert!(!plain_output.is_empty());
         assert!(!test_output.is_empty());
+        assert!(!screen_reader_output.is_empty());
     }
 }
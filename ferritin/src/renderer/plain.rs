@@ -17,8 +17,10 @@
 
 use std::fmt::{Result, Write};
 
+use super::text_width::display_width;
 use crate::styled_string::{
-    Document, DocumentNode, HeadingLevel, ListItem, ShowWhen, Span, TruncationLevel,
+    Document, DocumentNode, Footnote, HeadingLevel, ListItem, ShowWhen, Span, SpanStyle, TableCell,
+    TruncationLevel,
 };
 
 /// Plain text renderer state
@@ -111,11 +113,18 @@ impl<'w, W: Write> PlainRenderer<'w, W> {
                 }
                 Ok(())
             }
-            DocumentNode::CodeBlock { code, .. } => {
+            DocumentNode::CodeBlock {
+                code, first_line, ..
+            } => {
                 self.write_indent()?;
                 writeln!(self.output, "```")?;
-                for line in code.lines() {
+                let gutter_width = first_line
+                    .map(|first_line| (first_line + code.lines().count()).to_string().len());
+                for (idx, line) in code.lines().enumerate() {
                     self.write_indent()?;
+                    if let (Some(first_line), Some(width)) = (first_line, gutter_width) {
+                        write!(self.output, "{:>width$} | ", first_line + idx)?;
+                    }
                     writeln!(self.output, "{line}")?;
                 }
                 if !code.ends_with('\n') && !code.is_empty() {
@@ -154,20 +163,7 @@ impl<'w, W: Write> PlainRenderer<'w, W> {
                 }
                 Ok(())
             }
-            DocumentNode::Table { header, rows } => {
-                // Placeholder for table rendering
-                let row_count = rows.len();
-                let col_count = header
-                    .as_ref()
-                    .map_or_else(|| rows.first().map_or(0, |r| r.len()), |h| h.len());
-                self.write_indent()?;
-                writeln!(
-                    self.output,
-                    "[Table: {} columns × {} rows]",
-                    col_count, row_count
-                )?;
-                Ok(())
-            }
+            DocumentNode::Table { header, rows } => self.render_table(header.as_deref(), rows),
             DocumentNode::TruncatedBlock { nodes, level } => {
                 // Transparent container - just controls truncation
                 match level {
@@ -229,7 +225,92 @@ impl<'w, W: Write> PlainRenderer<'w, W> {
                 }
                 Ok(())
             }
+            DocumentNode::Footnotes { notes } => {
+                self.write_indent()?;
+                writeln!(self.output, "{}", "─".repeat(20))?;
+                for note in notes {
+                    self.render_footnote(note)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Render a footnote with its number in front, like a list item's bullet
+    fn render_footnote(&mut self, note: &Footnote) -> Result {
+        self.write_indent()?;
+        write!(self.output, "[{}] ", note.number)?;
+
+        let mut content = note.content.iter();
+        match content.next() {
+            Some(DocumentNode::Paragraph { spans }) => {
+                self.render_spans(spans)?;
+                writeln!(self.output)?;
+            }
+            Some(first) => {
+                writeln!(self.output)?;
+                self.render_node(first)?;
+            }
+            None => writeln!(self.output)?,
+        }
+
+        let saved_indent = self.indent.clone();
+        self.indent.push_str("    ");
+        for node in content {
+            self.render_node(node)?;
+        }
+        self.indent = saved_indent;
+        Ok(())
+    }
+
+    /// Render a table as columns padded to line up, with a rule under the header
+    fn render_table(&mut self, header: Option<&[TableCell]>, rows: &[Vec<TableCell>]) -> Result {
+        let cell_text = |cell: &TableCell| {
+            cell.spans
+                .iter()
+                .map(|span| &*span.text)
+                .collect::<String>()
+                .replace('\n', " ")
+        };
+        let header: Option<Vec<String>> = header.map(|cells| cells.iter().map(cell_text).collect());
+        let rows: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| row.iter().map(cell_text).collect())
+            .collect();
+
+        let mut widths: Vec<usize> = vec![];
+        for row in header.iter().chain(&rows) {
+            for (idx, text) in row.iter().enumerate() {
+                let width = display_width(text);
+                match widths.get_mut(idx) {
+                    Some(column_width) => *column_width = (*column_width).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+
+        if let Some(header) = &header {
+            self.render_table_row(header, &widths)?;
+            let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+            self.render_table_row(&rule, &widths)?;
         }
+        for row in &rows {
+            self.render_table_row(row, &widths)?;
+        }
+        Ok(())
+    }
+
+    fn render_table_row(&mut self, cells: &[String], widths: &[usize]) -> Result {
+        let mut line = String::new();
+        for (idx, (text, width)) in cells.iter().zip(widths).enumerate() {
+            if idx > 0 {
+                line.push_str("  ");
+            }
+            line.push_str(text);
+            line.extend(std::iter::repeat_n(' ', width - display_width(text)));
+        }
+        self.write_indent()?;
+        writeln!(self.output, "{}", line.trim_end())
     }
 
     fn render_spans(&mut self, spans: &[Span]) -> Result {
@@ -239,7 +320,10 @@ impl<'w, W: Write> PlainRenderer<'w, W> {
         Ok(())
     }
 
-    fn render_span(&mut self, Span { text, .. }: &Span) -> Result {
+    fn render_span(&mut self, Span { text, style, .. }: &Span) -> Result {
+        if *style == SpanStyle::Image {
+            return write!(self.output, "[image: {text}]");
+        }
         // Handle newlines in span text to maintain indentation
         for (idx, line) in text.split('\n').enumerate() {
             if idx > 0 {
@@ -307,4 +391,24 @@ mod tests {
         assert!(output.contains("  ◦ First"));
         assert!(output.contains("  ◦ Second"));
     }
+
+    #[test]
+    fn test_render_table() {
+        let cell = |text: &'static str| TableCell::from_span(Span::plain(text));
+        let doc = Document::with_nodes(vec![DocumentNode::table(
+            Some(vec![cell("Kind"), cell("Public")]),
+            vec![
+                vec![cell("Struct"), cell("12")],
+                vec![cell("Fn"), cell("3")],
+            ],
+        )]);
+
+        let mut output = String::new();
+        render(&doc, &mut output).unwrap();
+
+        assert_eq!(
+            output,
+            "Kind    Public\n------  ------\nStruct  12\nFn      3\n"
+        );
+    }
 }
@@ -0,0 +1,252 @@
+//! Screen-reader friendly text renderer.
+//!
+//! Like the plain renderer this emits no terminal control codes, but it also drops purely
+//! visual decoration (heading underlines, rules, box-drawing bullets and code fences) and
+//! announces structure in words instead, so that output read aloud line by line still conveys
+//! where headings, lists, code examples and quotes begin and end.
+
+use std::fmt::{Result, Write};
+
+use crate::styled_string::{
+    Document, DocumentNode, HeadingLevel, ListItem, ShowWhen, Span, SpanStyle, TableCell,
+    TruncationLevel,
+};
+
+/// Screen reader renderer state
+struct ScreenReaderRenderer<'w, W: Write> {
+    output: &'w mut W,
+    indent: String,
+}
+
+/// Render a document as text with its structure spelled out
+pub fn render(document: &Document, output: &mut impl Write) -> Result {
+    let mut renderer = ScreenReaderRenderer {
+        output,
+        indent: String::new(),
+    };
+    renderer.render_block_sequence(&document.nodes)
+}
+
+impl<'w, W: Write> ScreenReaderRenderer<'w, W> {
+    fn write_indent(&mut self) -> Result {
+        write!(self.output, "{}", self.indent)
+    }
+
+    /// Write one indented line
+    fn line(&mut self, text: &str) -> Result {
+        self.write_indent()?;
+        writeln!(self.output, "{text}")
+    }
+
+    /// Render a sequence of block nodes with blank lines between them
+    fn render_block_sequence(&mut self, nodes: &[DocumentNode]) -> Result {
+        for (idx, node) in nodes.iter().enumerate() {
+            if idx > 0 {
+                writeln!(self.output)?;
+            }
+            self.render_node(node)?;
+        }
+        Ok(())
+    }
+
+    fn render_node(&mut self, node: &DocumentNode) -> Result {
+        match node {
+            DocumentNode::Paragraph { spans } | DocumentNode::GeneratedCode { spans } => {
+                self.write_indent()?;
+                self.render_spans(spans)?;
+                writeln!(self.output)
+            }
+            DocumentNode::Heading { level, spans } => {
+                self.write_indent()?;
+                match level {
+                    HeadingLevel::Title => write!(self.output, "Heading level 1: ")?,
+                    HeadingLevel::Section => write!(self.output, "Heading level 2: ")?,
+                }
+                self.render_spans(spans)?;
+                writeln!(self.output)
+            }
+            DocumentNode::Section { title, nodes } => {
+                if let Some(title_spans) = title {
+                    self.write_indent()?;
+                    write!(self.output, "Section: ")?;
+                    self.render_spans(title_spans)?;
+                    writeln!(self.output)?;
+                    writeln!(self.output)?;
+                }
+                self.render_block_sequence(nodes)
+            }
+            DocumentNode::List { items } => {
+                let plural = if items.len() == 1 { "" } else { "s" };
+                self.line(&format!("List of {} item{plural}", items.len()))?;
+                for (idx, item) in items.iter().enumerate() {
+                    self.render_list_item(item, idx + 1)?;
+                }
+                self.line("List end")
+            }
+            DocumentNode::CodeBlock {
+                lang,
+                code,
+                first_line,
+                ..
+            } => {
+                match lang.as_deref().filter(|lang| !lang.is_empty()) {
+                    Some(lang) => self.line(&format!("Code example start, {lang}"))?,
+                    None => self.line("Code example start")?,
+                }
+                for (idx, line) in code.lines().enumerate() {
+                    match first_line {
+                        Some(first_line) => {
+                            self.line(&format!("Line {}: {line}", first_line + idx))?
+                        }
+                        None => self.line(line)?,
+                    }
+                }
+                self.line("Code example end")
+            }
+            DocumentNode::HorizontalRule => Ok(()),
+            DocumentNode::BlockQuote { nodes } => {
+                self.line("Quote start")?;
+                self.render_block_sequence(nodes)?;
+                self.line("Quote end")
+            }
+            DocumentNode::Table { header, rows } => self.render_table(header.as_deref(), rows),
+            DocumentNode::TruncatedBlock { nodes, level } => match level {
+                TruncationLevel::SingleLine => {
+                    if let Some(first_node) = nodes.first() {
+                        match first_node {
+                            DocumentNode::Paragraph { spans }
+                            | DocumentNode::Heading { spans, .. } => {
+                                self.write_indent()?;
+                                self.render_spans(spans)?;
+                                writeln!(self.output)?;
+                            }
+                            _ => self.render_node(first_node)?,
+                        }
+                    }
+                    Ok(())
+                }
+                TruncationLevel::Brief => {
+                    if let Some(first_node) = nodes.first() {
+                        self.render_node(first_node)?;
+                        if nodes.len() > 1 {
+                            self.line(&format!("{} more blocks not shown", nodes.len() - 1))?;
+                        }
+                    }
+                    Ok(())
+                }
+                TruncationLevel::Full => self.render_block_sequence(nodes),
+            },
+            DocumentNode::Conditional { show_when, nodes } => match show_when {
+                ShowWhen::Always | ShowWhen::NonInteractive => self.render_block_sequence(nodes),
+                ShowWhen::Interactive => Ok(()),
+            },
+            DocumentNode::Footnotes { notes } => {
+                self.line("Footnotes start")?;
+                for note in notes {
+                    self.write_indent()?;
+                    write!(self.output, "Footnote {}: ", note.number)?;
+                    let saved_indent = self.indent.clone();
+                    if let Some(first) = note.content.first() {
+                        self.render_node(first)?;
+                    }
+                    self.indent.push_str("  ");
+                    for node in note.content.iter().skip(1) {
+                        self.render_node(node)?;
+                    }
+                    self.indent = saved_indent;
+                }
+                self.line("Footnotes end")
+            }
+        }
+    }
+
+    fn render_spans(&mut self, spans: &[Span]) -> Result {
+        for Span { text, style, .. } in spans {
+            if *style == SpanStyle::Image {
+                write!(self.output, "Image: {text}")?;
+                continue;
+            }
+            // Handle newlines in span text to maintain indentation
+            for (idx, line) in text.split('\n').enumerate() {
+                if idx > 0 {
+                    writeln!(self.output)?;
+                    self.write_indent()?;
+                }
+                write!(self.output, "{line}")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn render_list_item(&mut self, item: &ListItem, number: usize) -> Result {
+        self.write_indent()?;
+        write!(self.output, "Item {number}: ")?;
+
+        // Render first node inline with the item number
+        let saved_indent = self.indent.clone();
+        if let Some(first) = item.content.first() {
+            self.render_node(first)?;
+        }
+
+        self.indent.push_str("  ");
+        for node in item.content.iter().skip(1) {
+            self.render_node(node)?;
+        }
+        self.indent = saved_indent;
+        Ok(())
+    }
+
+    /// Render a table row by row, naming each cell by its column header when there is one
+    fn render_table(&mut self, header: Option<&[TableCell]>, rows: &[Vec<TableCell>]) -> Result {
+        let column_count = header.map_or_else(|| rows.first().map_or(0, Vec::len), <[_]>::len);
+        self.line(&format!(
+            "Table with {column_count} columns and {} rows",
+            rows.len()
+        ))?;
+
+        for (row_idx, row) in rows.iter().enumerate() {
+            self.write_indent()?;
+            write!(self.output, "Row {}: ", row_idx + 1)?;
+            for (col_idx, cell) in row.iter().enumerate() {
+                if col_idx > 0 {
+                    write!(self.output, "; ")?;
+                }
+                if let Some(header_cell) = header.and_then(|header| header.get(col_idx)) {
+                    self.render_spans(&header_cell.spans)?;
+                    write!(self.output, ": ")?;
+                }
+                self.render_spans(&cell.spans)?;
+            }
+            writeln!(self.output)?;
+        }
+
+        self.line("Table end")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_announces_structure() {
+        let doc = Document::with_nodes(vec![
+            DocumentNode::heading(
+                HeadingLevel::Title,
+                vec![Span::plain("Item: "), Span::type_name("Vec")],
+            ),
+            DocumentNode::HorizontalRule,
+            DocumentNode::list(vec![ListItem::new(vec![DocumentNode::paragraph(vec![
+                Span::plain("First"),
+            ])])]),
+        ]);
+
+        let mut output = String::new();
+        render(&doc, &mut output).unwrap();
+
+        assert!(output.contains("Heading level 1: Item: Vec"));
+        assert!(output.contains("List of 1 item\nItem 1: First\nList end"));
+        assert!(!output.contains("===="));
+        assert!(!output.contains('─'));
+    }
+}
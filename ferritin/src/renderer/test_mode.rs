@@ -53,12 +53,20 @@ fn render_node(node: &DocumentNode, output: &mut impl Write) -> Result {
             writeln!(output, "</list>")?;
             Ok(())
         }
-        DocumentNode::CodeBlock { lang, code } => {
+        DocumentNode::CodeBlock {
+            lang,
+            code,
+            first_line,
+            ..
+        } => {
             let lang_attr = lang
                 .as_ref()
                 .map(|l| format!(" lang=\"{}\"", l))
                 .unwrap_or_default();
-            writeln!(output, "<code-block{}>", lang_attr)?;
+            let first_line_attr = first_line
+                .map(|n| format!(" first-line=\"{n}\""))
+                .unwrap_or_default();
+            writeln!(output, "<code-block{}{}>", lang_attr, first_line_attr)?;
             write!(output, "{code}")?;
             if !code.ends_with('\n') {
                 writeln!(output)?;
@@ -155,6 +163,16 @@ fn render_node(node: &DocumentNode, output: &mut impl Write) -> Result {
             writeln!(output, "</conditional>")?;
             Ok(())
         }
+        DocumentNode::Footnotes { notes } => {
+            writeln!(output, "<footnotes>")?;
+            for note in notes {
+                write!(output, "  <footnote number=\"{}\">", note.number)?;
+                render_nodes(&note.content, output)?;
+                writeln!(output, "</footnote>")?;
+            }
+            writeln!(output, "</footnotes>")?;
+            Ok(())
+        }
     }
 }
 
@@ -186,6 +204,8 @@ fn render_span(span: &Span, output: &mut impl Write) -> Result {
         SpanStyle::Strong => "strong",
         SpanStyle::Emphasis => "emphasis",
         SpanStyle::Strikethrough => "strikethrough",
+        SpanStyle::FootnoteReference => "footnote-reference",
+        SpanStyle::Image => "image",
     };
 
     write!(output, "<{tag}>{}</{tag}>", span.text)?;
@@ -240,6 +260,10 @@ fn count_chars_in_node(node: &DocumentNode) -> usize {
         }
         DocumentNode::TruncatedBlock { nodes, .. } => count_chars_in_nodes(nodes),
         DocumentNode::Conditional { nodes, .. } => count_chars_in_nodes(nodes),
+        DocumentNode::Footnotes { notes } => notes
+            .iter()
+            .map(|note| count_chars_in_nodes(&note.content))
+            .sum(),
     }
 }
 
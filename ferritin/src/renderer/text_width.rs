@@ -0,0 +1,127 @@
+//! Display width of text, for wrapping paragraphs and sizing table columns
+//!
+//! Text is measured in terminal columns per grapheme cluster rather than in bytes or chars, so
+//! CJK characters and most emoji take two columns, and combining characters stay with the
+//! character they modify. Tabs count as four columns, as the renderers draw them.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns a tab is drawn as
+const TAB_WIDTH: usize = 4;
+
+/// The columns a single grapheme cluster takes
+pub(crate) fn grapheme_width(grapheme: &str) -> usize {
+    if grapheme == "\t" {
+        TAB_WIDTH
+    } else {
+        grapheme.width()
+    }
+}
+
+/// The columns `text` takes on a single line
+pub(crate) fn display_width(text: &str) -> usize {
+    text.graphemes(true).map(grapheme_width).sum()
+}
+
+/// The byte length of the longest prefix of `text` that fits in `max_width` columns, never
+/// splitting a grapheme cluster
+pub(crate) fn fitting_prefix_len(text: &str, max_width: usize) -> usize {
+    let mut width = 0;
+    for (idx, grapheme) in text.grapheme_indices(true) {
+        width += grapheme_width(grapheme);
+        if width > max_width {
+            return idx;
+        }
+    }
+    text.len()
+}
+
+/// Cut `text` down to at most `max_width` columns
+pub(crate) fn truncate_to_width(text: &str, max_width: usize) -> &str {
+    &text[..fitting_prefix_len(text, max_width)]
+}
+
+/// Find the best position to wrap text within a given width
+/// Returns the byte position after which to break, or None if no good break point exists
+pub(crate) fn find_wrap_position(text: &str, max_width: usize) -> Option<usize> {
+    if max_width == 0 || text.is_empty() {
+        return None;
+    }
+
+    let search_end = fitting_prefix_len(text, max_width);
+    if search_end == 0 {
+        return None;
+    }
+
+    let search_range = &text[..search_end];
+
+    // First priority: break at whitespace
+    if let Some(pos) = search_range.rfind(char::is_whitespace) {
+        // Avoid breaking if it would leave a very short word (< 3 columns) on next line
+        // This prevents orphans like "a" or "is" at the start of a line
+        let remaining_width = display_width(&text[pos..]);
+        if pos > 0 && remaining_width > 3 {
+            return Some(pos);
+        }
+        // If the remaining part is short enough, it's ok to break here
+        if remaining_width <= max_width / 2 {
+            return Some(pos);
+        }
+    }
+
+    // Second priority: break after certain punctuation (., ,, ;, :, ), ])
+    // This helps with long sentences without spaces
+    for (i, ch) in search_range.char_indices().rev() {
+        if matches!(ch, '.' | ',' | ';' | ':' | ')' | ']' | '}') {
+            // Break after the punctuation
+            if i + 1 < search_range.len() {
+                return Some(i + 1);
+            }
+        }
+    }
+
+    // Third priority: break at word boundaries (after lowercase before uppercase)
+    // This helps with camelCase or PascalCase identifiers
+    let chars: Vec<(usize, char)> = search_range.char_indices().collect();
+    for pair in chars.windows(2).rev() {
+        let [(_, prev), (i, curr)] = pair else {
+            continue;
+        };
+        if prev.is_lowercase() && curr.is_uppercase() {
+            return Some(*i);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("a\tb"), 6);
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        // A wide character that would straddle the limit is left out entirely
+        assert_eq!(truncate_to_width("日本語", 5), "日本");
+        // Combining marks stay with their base character
+        assert_eq!(truncate_to_width("e\u{301}e\u{301}", 1), "e\u{301}");
+        assert_eq!(truncate_to_width("short", 40), "short");
+    }
+
+    #[test]
+    fn test_find_wrap_position() {
+        assert_eq!(find_wrap_position("hello wonderful world", 12), Some(5));
+        // Wide characters fill the line in half as many characters
+        assert_eq!(find_wrap_position("日本語 日本語 日本語", 10), Some(9));
+        assert_eq!(find_wrap_position("fooBarBaz", 7), Some(6));
+    }
+}
@@ -12,11 +12,16 @@
 //! - First list item content is inline with bullet, rest indented
 //! - Maintains indentation for nested content
 
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::{Result, Write};
 
+use super::inline_image::kitty_image;
+use super::text_width::{display_width, find_wrap_position, fitting_prefix_len, truncate_to_width};
 use crate::render_context::RenderContext;
 use crate::styled_string::{
-    Document, DocumentNode, HeadingLevel, ShowWhen, Span, SpanStyle, TruncationLevel,
+    Document, DocumentNode, HeadingLevel, ShowWhen, Span, SpanStyle, TruncationLevel, TuiAction,
+    split_identifiers,
 };
 use ratatui::{
     style::{Color, Modifier, Style},
@@ -61,8 +66,9 @@ impl RenderBudget {
                 }
 
                 // Check budget
-                if text.len() <= *remaining {
-                    *remaining -= text.len();
+                let width = display_width(text);
+                if width <= *remaining {
+                    *remaining -= width;
                     None
                 } else {
                     let truncated = truncate_at_word_boundary(text, *remaining);
@@ -102,76 +108,20 @@ impl RenderBudget {
 }
 
 /// Truncate at word boundary
-fn truncate_at_word_boundary(text: &str, max_chars: usize) -> &str {
-    if text.len() <= max_chars {
+fn truncate_at_word_boundary(text: &str, max_width: usize) -> &str {
+    let fitting = truncate_to_width(text, max_width);
+    if fitting.len() == text.len() {
         return text;
     }
 
-    // Find last whitespace before max_chars
-    if let Some(pos) = text[..max_chars].rfind(char::is_whitespace) {
+    // Find last whitespace before max_width
+    if let Some(pos) = fitting.rfind(char::is_whitespace) {
         &text[..pos]
     } else {
-        &text[..max_chars]
+        fitting
     }
 }
 
-/// Find the best position to wrap text within a given width
-/// Returns the position after which to break, or None if no good break point exists
-fn find_wrap_position(text: &str, max_width: usize) -> Option<usize> {
-    if max_width == 0 || text.is_empty() {
-        return None;
-    }
-
-    // Find the byte position that corresponds to max_width characters (char-boundary-safe)
-    let search_end = text
-        .char_indices()
-        .take(max_width)
-        .last()
-        .map(|(idx, ch)| idx + ch.len_utf8())
-        .unwrap_or(0);
-
-    let search_range = &text[..search_end];
-
-    // First priority: break at whitespace
-    if let Some(pos) = search_range.rfind(char::is_whitespace) {
-        // Avoid breaking if it would leave a very short word (< 3 chars) on next line
-        // This prevents orphans like "a" or "is" at the start of a line
-        if pos > 0 && text.len() - pos > 3 {
-            return Some(pos);
-        }
-        // If the remaining part is short enough, it's ok to break here
-        if text.len() - pos <= max_width / 2 {
-            return Some(pos);
-        }
-    }
-
-    // Second priority: break after certain punctuation (., ,, ;, :, ), ])
-    // This helps with long sentences without spaces
-    for (i, ch) in search_range.char_indices().rev() {
-        if matches!(ch, '.' | ',' | ';' | ':' | ')' | ']' | '}') {
-            // Break after the punctuation
-            if i + 1 < search_range.len() {
-                return Some(i + 1);
-            }
-        }
-    }
-
-    // Third priority: break at word boundaries (after lowercase before uppercase)
-    // This helps with camelCase or PascalCase identifiers
-    for i in (1..search_range.len()).rev() {
-        let chars: Vec<char> = search_range.chars().collect();
-        if i < chars.len() - 1 {
-            let prev = chars[i - 1];
-            let curr = chars[i];
-            if prev.is_lowercase() && curr.is_uppercase() {
-                return Some(i);
-            }
-        }
-    }
-
-    None
-}
-
 /// Find the prefix up to the Nth newline
 fn find_nth_newline_prefix(text: &str, n: usize) -> &str {
     let mut count = 0;
@@ -250,15 +200,16 @@ fn write_styled_span(span: &RatatuiSpan, output: &mut impl Write) -> Result {
     // Build ANSI escape sequence
     let mut codes = Vec::new();
 
-    if let Some(fg) = style.fg
-        && let Color::Rgb(r, g, b) = fg
-    {
-        codes.push(format!("38;2;{};{};{}", r, g, b));
+    if let Some(fg) = style.fg {
+        codes.push(ansi_foreground(fg));
     }
 
     if style.add_modifier.contains(Modifier::BOLD) {
         codes.push("1".to_string());
     }
+    if style.add_modifier.contains(Modifier::DIM) {
+        codes.push("2".to_string());
+    }
     if style.add_modifier.contains(Modifier::ITALIC) {
         codes.push("3".to_string());
     }
@@ -282,6 +233,34 @@ fn write_styled_span(span: &RatatuiSpan, output: &mut impl Write) -> Result {
     Ok(())
 }
 
+/// SGR parameters selecting `color` as the foreground
+///
+/// Named colors use the basic ANSI codes, so 16-color palettes follow the terminal's own colors.
+fn ansi_foreground(color: Color) -> String {
+    let code = match color {
+        Color::Reset => 39,
+        Color::Black => 30,
+        Color::Red => 31,
+        Color::Green => 32,
+        Color::Yellow => 33,
+        Color::Blue => 34,
+        Color::Magenta => 35,
+        Color::Cyan => 36,
+        Color::Gray => 37,
+        Color::DarkGray => 90,
+        Color::LightRed => 91,
+        Color::LightGreen => 92,
+        Color::LightYellow => 93,
+        Color::LightBlue => 94,
+        Color::LightMagenta => 95,
+        Color::LightCyan => 96,
+        Color::White => 97,
+        Color::Indexed(index) => return format!("38;5;{index}"),
+        Color::Rgb(r, g, b) => return format!("38;2;{r};{g};{b}"),
+    };
+    code.to_string()
+}
+
 /// Build ratatui Lines from document nodes with blank lines between blocks
 pub(super) fn build_lines<'a>(
     nodes: &'a [DocumentNode],
@@ -303,6 +282,24 @@ pub(super) fn build_lines<'a>(
     lines
 }
 
+/// The escape sequences drawing each image in a paragraph that holds nothing but images
+///
+/// Returns `None` for paragraphs with other text, or when any of the images can't be drawn, so
+/// that the paragraph is shown as alt text instead.
+fn inline_images(spans: &[Span], max_columns: usize) -> Option<Vec<String>> {
+    let mut images = vec![];
+    for span in spans {
+        match (&span.style, &span.action) {
+            (SpanStyle::Image, Some(TuiAction::OpenUrl(url))) => {
+                images.push(kitty_image(url, max_columns)?);
+            }
+            _ if span.text.trim().is_empty() => {}
+            _ => return None,
+        }
+    }
+    (!images.is_empty()).then_some(images)
+}
+
 /// Build lines for a single node
 fn build_node_lines<'a>(
     node: &'a DocumentNode,
@@ -317,6 +314,17 @@ fn build_node_lines<'a>(
 
     match node {
         DocumentNode::Paragraph { spans } => {
+            if render_context.inline_images()
+                && matches!(budget, RenderBudget::Unlimited)
+                && let Some(images) = inline_images(
+                    spans,
+                    render_context.terminal_width().saturating_sub(indent),
+                )
+            {
+                lines.extend(images.into_iter().map(Line::from));
+                return;
+            }
+
             // Start a new line for paragraph
             let start_idx = lines.len();
             let terminal_width = render_context.terminal_width() as usize;
@@ -361,7 +369,7 @@ fn build_node_lines<'a>(
                             continue;
                         }
 
-                        if remaining.len() <= available_width {
+                        if display_width(remaining) <= available_width {
                             // Fits on current line
                             let span_to_add = RatatuiSpan::styled(make_text(remaining), style);
                             if lines.len() == start_idx {
@@ -374,7 +382,7 @@ fn build_node_lines<'a>(
                                 // Continuing current line
                                 lines.last_mut().unwrap().spans.push(span_to_add);
                             }
-                            current_line_len += remaining.len();
+                            current_line_len += display_width(remaining);
                             break;
                         } else {
                             // Need to wrap - find best break point
@@ -397,7 +405,7 @@ fn build_node_lines<'a>(
                                 // Look for the next break point beyond the available width
                                 if let Some(next_space) = remaining.find(char::is_whitespace) {
                                     // Check if the word will fit on the current line
-                                    if next_space <= available_width {
+                                    if display_width(&remaining[..next_space]) <= available_width {
                                         // Word fits on current line, write it
                                         let (chunk, rest) = remaining.split_at(next_space);
                                         let span_to_add =
@@ -419,7 +427,7 @@ fn build_node_lines<'a>(
                                 } else {
                                     // No whitespace at all in remaining text
                                     // If it fits, write it; otherwise we need to hard-break
-                                    if remaining.len() <= available_width {
+                                    if display_width(remaining) <= available_width {
                                         let span_to_add =
                                             RatatuiSpan::styled(make_text(remaining), style);
                                         if lines.len() == start_idx {
@@ -429,7 +437,7 @@ fn build_node_lines<'a>(
                                         } else {
                                             lines.last_mut().unwrap().spans.push(span_to_add);
                                         }
-                                        current_line_len += remaining.len();
+                                        current_line_len += display_width(remaining);
                                         break;
                                     } else {
                                         // Doesn't fit even on a new line - need to hard-break mid-word
@@ -438,8 +446,11 @@ fn build_node_lines<'a>(
                                             // Already on a fresh line, must hard-break
                                             let max_fit =
                                                 terminal_width.saturating_sub(indent).max(1);
-                                            let (chunk, rest) =
-                                                remaining.split_at(max_fit.min(remaining.len()));
+                                            // At least one grapheme, so that this makes progress
+                                            let split = fitting_prefix_len(remaining, max_fit).max(
+                                                remaining.chars().next().map_or(0, char::len_utf8),
+                                            );
+                                            let (chunk, rest) = remaining.split_at(split);
                                             let span_to_add =
                                                 RatatuiSpan::styled(make_text(chunk), style);
                                             lines.push(Line::from(vec![span_to_add]));
@@ -537,12 +548,23 @@ fn build_node_lines<'a>(
                 }
             }
         }
-        DocumentNode::CodeBlock { lang, code } => {
+        DocumentNode::CodeBlock {
+            lang,
+            code,
+            first_line,
+            links,
+        } => {
             if matches!(budget, RenderBudget::Characters { .. }) {
                 return;
             }
 
-            lines.extend(render_code_block(lang.as_deref(), code, render_context));
+            lines.extend(render_code_block(
+                lang.as_deref(),
+                code,
+                *first_line,
+                links,
+                render_context,
+            ));
         }
         DocumentNode::GeneratedCode { spans } => {
             let code_spans: Vec<_> = spans
@@ -646,6 +668,7 @@ fn build_node_lines<'a>(
                             DocumentNode::CodeBlock { .. }
                                 | DocumentNode::GeneratedCode { .. }
                                 | DocumentNode::List { .. }
+                                | DocumentNode::Footnotes { .. }
                         )
                     {
                         rendered_all = false;
@@ -719,6 +742,39 @@ fn build_node_lines<'a>(
                 }
             }
         }
+        DocumentNode::Footnotes { notes } => {
+            if matches!(budget, RenderBudget::Characters { .. }) {
+                return;
+            }
+
+            lines.push(Line::from(RatatuiSpan::styled(
+                "─".repeat(20),
+                Style::default().fg(Color::DarkGray),
+            )));
+            for note in notes {
+                let start_idx = lines.len();
+                for node in &note.content {
+                    let mut note_budget = budget.clone();
+                    build_node_lines(node, render_context, &mut note_budget, lines, 4);
+                }
+
+                // Number the first line, and indent the rest to line up with it
+                let number = format!("{:<4}", format!("[{}]", note.number));
+                for (line_idx, line) in lines[start_idx..].iter_mut().enumerate() {
+                    if line_idx == 0 {
+                        line.spans.insert(
+                            0,
+                            RatatuiSpan::styled(
+                                number.clone(),
+                                span_style_to_ratatui(SpanStyle::FootnoteReference, render_context),
+                            ),
+                        );
+                    } else {
+                        line.spans.insert(0, RatatuiSpan::raw("    "));
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -751,7 +807,11 @@ fn render_table<'a>(
     // Measure header widths
     if let Some(header_cells) = header {
         for (col_idx, cell) in header_cells.iter().enumerate() {
-            let width = cell.spans.iter().map(|s| s.text.len()).sum::<usize>();
+            let width = cell
+                .spans
+                .iter()
+                .map(|s| display_width(&s.text))
+                .sum::<usize>();
             col_widths[col_idx] = col_widths[col_idx].max(width);
         }
     }
@@ -760,7 +820,11 @@ fn render_table<'a>(
     for row_cells in rows {
         for (col_idx, cell) in row_cells.iter().enumerate() {
             if col_idx < num_cols {
-                let width = cell.spans.iter().map(|s| s.text.len()).sum::<usize>();
+                let width = cell
+                    .spans
+                    .iter()
+                    .map(|s| display_width(&s.text))
+                    .sum::<usize>();
                 col_widths[col_idx] = col_widths[col_idx].max(width);
             }
         }
@@ -792,20 +856,7 @@ fn render_table<'a>(
         let mut header_spans = vec![RatatuiSpan::styled("│", border_style)];
 
         for (col_idx, cell) in header_cells.iter().enumerate() {
-            let mut cell_text = String::new();
-            for span in &cell.spans {
-                let span_text = if span.text.len() > col_widths[col_idx] {
-                    &span.text[..col_widths[col_idx]]
-                } else {
-                    &span.text
-                };
-                cell_text.push_str(span_text);
-            }
-
-            // Pad to column width
-            while cell_text.len() < col_widths[col_idx] {
-                cell_text.push(' ');
-            }
+            let cell_text = table_cell_text(&cell.spans, col_widths[col_idx]);
 
             let mut style = span_style_to_ratatui(
                 cell.spans
@@ -845,20 +896,7 @@ fn render_table<'a>(
                 break;
             }
 
-            let mut cell_text = String::new();
-            for span in &cell.spans {
-                let span_text = if span.text.len() > col_widths[col_idx] {
-                    &span.text[..col_widths[col_idx]]
-                } else {
-                    &span.text
-                };
-                cell_text.push_str(span_text);
-            }
-
-            // Pad to column width
-            while cell_text.len() < col_widths[col_idx] {
-                cell_text.push(' ');
-            }
+            let cell_text = table_cell_text(&cell.spans, col_widths[col_idx]);
 
             let style = span_style_to_ratatui(
                 cell.spans
@@ -898,6 +936,8 @@ fn render_table<'a>(
 fn render_code_block<'a>(
     lang: Option<&str>,
     code: &'a str,
+    first_line: Option<usize>,
+    links: &HashMap<Cow<'_, str>, TuiAction<'_>>,
     render_context: &RenderContext,
 ) -> Vec<Line<'a>> {
     let mut lines = Vec::new();
@@ -921,10 +961,24 @@ fn render_code_block<'a>(
                 let mut line_spans = Vec::new();
                 for (style, text) in ranges {
                     let fg = style.foreground;
-                    line_spans.push(RatatuiSpan::styled(
-                        text.trim_end_matches('\n'),
-                        Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b)),
-                    ));
+                    let style = Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b));
+                    let text = text.trim_end_matches('\n');
+
+                    if links.is_empty() {
+                        line_spans.push(RatatuiSpan::styled(text, style));
+                        continue;
+                    }
+
+                    // Link identifiers that resolved to documented items
+                    for (run, is_ident) in split_identifiers(text) {
+                        match links.get(run).and_then(|action| action.url()) {
+                            Some(url) if is_ident => line_spans.push(RatatuiSpan::styled(
+                                wrap_with_osc8(run, &url),
+                                style.add_modifier(Modifier::UNDERLINED),
+                            )),
+                            _ => line_spans.push(RatatuiSpan::styled(run, style)),
+                        }
+                    }
                 }
                 lines.push(Line::from(line_spans));
             } else {
@@ -937,6 +991,18 @@ fn render_code_block<'a>(
         }
     }
 
+    // Prefix each line with its line number for source listings
+    if let Some(first_line) = first_line {
+        let width = (first_line + lines.len()).to_string().len();
+        let gutter_style = span_style_to_ratatui(SpanStyle::Comment, render_context);
+        for (idx, line) in lines.iter_mut().enumerate() {
+            line.spans.insert(
+                0,
+                RatatuiSpan::styled(format!("{:>width$} │ ", first_line + idx), gutter_style),
+            );
+        }
+    }
+
     lines.push(Line::from(""));
     lines
 }
@@ -981,6 +1047,26 @@ fn convert_span_bold<'a>(span: &'a Span, render_context: &RenderContext) -> Rata
     RatatuiSpan::styled(text, style)
 }
 
+/// Build a table cell's text, truncated and padded to `width`, with OSC8 hyperlinks around
+/// linked spans
+fn table_cell_text(spans: &[Span], width: usize) -> String {
+    let mut text = String::new();
+    let mut used = 0;
+    for span in spans {
+        let chunk = truncate_to_width(&span.text, width - used);
+        used += display_width(chunk);
+
+        match span.url() {
+            Some(url) if !chunk.is_empty() => text.push_str(&wrap_with_osc8(chunk, &url)),
+            _ => text.push_str(chunk),
+        }
+    }
+
+    // Pad to column width
+    text.push_str(&" ".repeat(width - used));
+    text
+}
+
 /// Wrap text with OSC8 hyperlink escape codes
 fn wrap_with_osc8(text: &str, url: &str) -> String {
     format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
@@ -988,23 +1074,20 @@ fn wrap_with_osc8(text: &str, url: &str) -> String {
 
 /// Convert SpanStyle to ratatui Style
 fn span_style_to_ratatui(span_style: SpanStyle, render_context: &RenderContext) -> Style {
-    match span_style {
-        SpanStyle::Plain => {
-            let fg = render_context.color_scheme().default_foreground();
-            Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b))
-        }
+    let color_scheme = render_context.color_scheme();
+    let style = match span_style {
         SpanStyle::Punctuation => Style::default(),
         SpanStyle::Strong => Style::default().add_modifier(Modifier::BOLD),
         SpanStyle::Emphasis => Style::default().add_modifier(Modifier::ITALIC),
         SpanStyle::Strikethrough => Style::default().add_modifier(Modifier::CROSSED_OUT),
-        SpanStyle::InlineCode | SpanStyle::InlineRustCode => {
-            let color = render_context.color_scheme().color_for(span_style);
-            Style::default().fg(Color::Rgb(color.r, color.g, color.b))
-        }
-        _ => {
-            let color = render_context.color_scheme().color_for(span_style);
-            Style::default().fg(Color::Rgb(color.r, color.g, color.b))
-        }
+        SpanStyle::FootnoteReference => Style::default().add_modifier(Modifier::DIM),
+        SpanStyle::Image => Style::default().add_modifier(Modifier::ITALIC),
+        _ => return Style::default().fg(color_scheme.terminal_color_for(span_style)),
+    };
+    // Styles the theme doesn't color can still be given a color in the config file
+    match color_scheme.configured_color_for(span_style) {
+        Some(color) => style.fg(color),
+        None => style,
     }
 }
 
@@ -1,11 +1,18 @@
 use ferritin_common::{
-    Navigator,
-    sources::{DocsRsSource, LocalSource, StdSource},
+    CancelToken, DocRef, Navigator,
+    cfg::TargetCfg,
+    parse_version_req,
+    search::Ranking,
+    sources::{BuildOptions, DocsRsSource, JsonFileSource, LocalSource, StdSource},
+    toolchain::DEFAULT_TOOLCHAIN,
 };
+use semver::{Version, VersionReq};
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::sync::OnceLock;
 
+use rustdoc_types::Item;
+
 use crate::format_context::FormatContext;
 
 /// Wrapper around Navigator that adds formatting capabilities
@@ -13,6 +20,23 @@ pub(crate) struct Request {
     inner: OnceLock<Navigator>,
     manifest_path: PathBuf,
     format_context: FormatContext,
+    /// Regenerate workspace documentation when populating, because sources changed
+    rebuild_workspace_docs: bool,
+    build_options: BuildOptions,
+    /// Rustdoc JSON files (or directories of them) to read instead of a cargo workspace
+    json_files: Vec<PathBuf>,
+    /// Bytes of loaded documentation past which an interactive session restarts, dropping the
+    /// least recently used crates that the current page doesn't need
+    memory_budget: Option<usize>,
+    /// How search results are ranked
+    ranking: Ranking,
+    /// Crates to load when populating, most important first, as `(name, version, estimated
+    /// memory)`, with the memory each took in the session before a restart
+    preload: Vec<(String, Option<Version>, usize)>,
+    /// The cfg options of the documented platform, detected on first use
+    target_cfg: OnceLock<Option<TargetCfg>>,
+    /// Stops builds, loads and searches for a request the reader has moved on from
+    cancel_token: CancelToken,
 }
 
 impl Deref for Request {
@@ -29,9 +53,17 @@ impl Request {
     /// Create a new request with Navigator and formatting configuration
     pub(crate) fn new(navigator: Navigator, format_context: FormatContext) -> Self {
         Self {
+            cancel_token: navigator.cancel_token().clone(),
             inner: OnceLock::from(navigator),
             manifest_path: PathBuf::new(), // Not used in eager mode
             format_context,
+            rebuild_workspace_docs: false,
+            build_options: BuildOptions::default(),
+            json_files: vec![],
+            memory_budget: None,
+            ranking: Ranking::default(),
+            preload: vec![],
+            target_cfg: OnceLock::new(),
         }
     }
 
@@ -41,16 +73,164 @@ impl Request {
             inner: OnceLock::new(),
             manifest_path,
             format_context,
+            rebuild_workspace_docs: false,
+            build_options: BuildOptions::default(),
+            json_files: vec![],
+            memory_budget: None,
+            ranking: Ranking::default(),
+            preload: vec![],
+            target_cfg: OnceLock::new(),
+            cancel_token: CancelToken::default(),
+        }
+    }
+
+    /// Build workspace documentation with these options when populating
+    pub(crate) fn with_build_options(mut self, build_options: BuildOptions) -> Self {
+        self.build_options = build_options;
+        self
+    }
+
+    /// Read these rustdoc JSON files or directories when populating, instead of looking for a
+    /// cargo workspace
+    pub(crate) fn with_json_files(mut self, json_files: Vec<PathBuf>) -> Self {
+        self.json_files = json_files;
+        self
+    }
+
+    /// Restart interactive sessions once loaded documentation is estimated to take more than
+    /// this many bytes
+    pub(crate) fn with_memory_budget(mut self, memory_budget: Option<usize>) -> Self {
+        self.memory_budget = memory_budget;
+        self
+    }
+
+    /// Rank search results with these parameters once populated
+    pub(crate) fn with_ranking(mut self, ranking: Ranking) -> Self {
+        self.ranking = ranking;
+        self
+    }
+
+    /// A fresh lazy request configured like this one, for the next interactive session
+    ///
+    /// After the workspace's sources change, its documentation is rebuilt when populating.
+    /// Otherwise the session is restarting to free memory or to pick up documentation rebuilt in
+    /// the background, so the crates in use are loaded again, most recently used first, as long
+    /// as they fit in half of the memory budget by what they took before. The rest are evicted,
+    /// though their search indexes stay on disk.
+    pub(crate) fn restarted(&self, sources_changed: bool) -> Self {
+        let format_context = FormatContext::new()
+            .with_show_hidden(self.format_context.show_hidden())
+            .with_hide_unavailable(self.format_context.hide_unavailable())
+            .with_rank_debug(self.format_context.rank_debug());
+
+        let preload = match self.inner.get() {
+            Some(navigator) if !sources_changed => navigator
+                .recently_used_crates()
+                .into_iter()
+                .map(|data| {
+                    (
+                        data.name().to_string(),
+                        data.version().cloned(),
+                        data.estimated_memory(),
+                    )
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        Self {
+            inner: OnceLock::new(),
+            manifest_path: self.manifest_path.clone(),
+            format_context,
+            rebuild_workspace_docs: sources_changed,
+            build_options: self.build_options.clone(),
+            json_files: self.json_files.clone(),
+            memory_budget: self.memory_budget,
+            ranking: self.ranking,
+            preload,
+            target_cfg: OnceLock::new(),
+            cancel_token: CancelToken::default(),
         }
     }
 
+    /// The token that cancels work on the current request, available before populating
+    pub(crate) fn cancel_token(&self) -> &CancelToken {
+        &self.cancel_token
+    }
+
+    /// The toolchain std docs are read from and installed for
+    pub(crate) fn toolchain(&self) -> &str {
+        std_toolchain(&self.build_options, self.local_source())
+    }
+
+    /// Whether loaded documentation is estimated to exceed the memory budget, and evicting the
+    /// crates the current page doesn't need (those not used since `page_mark`, from
+    /// [`Navigator::use_mark`]) would free more than half of it
+    ///
+    /// A restarted session reloads no more than half the budget's worth of crates besides those
+    /// of its page, so it's never over the budget this way again before the reader moves on.
+    pub(crate) fn is_over_memory_budget(&self, page_mark: u64) -> bool {
+        let Some(budget) = self.memory_budget else {
+            return false;
+        };
+        if self.estimated_memory() <= budget {
+            return false;
+        }
+
+        let evictable: usize = self
+            .loaded_crates()
+            .filter(|data| !self.used_since(data, page_mark))
+            .map(|data| data.estimated_memory())
+            .sum();
+        evictable > budget / 2
+    }
+
     /// Populate the Navigator with sources (if not already populated)
     /// This is the slow operation that loads all documentation sources
     pub(crate) fn populate(&self) {
         let manifest_path = &self.manifest_path;
         self.inner.get_or_init(|| {
+            let json_file_source = if self.json_files.is_empty() {
+                None
+            } else {
+                log::info!("Reading rustdoc JSON files");
+                JsonFileSource::load(&self.json_files)
+                    .map_err(|e| log::error!("Failed to read rustdoc JSON files: {e}"))
+                    .ok()
+            };
+
+            let local_source = if self.json_files.is_empty() {
+                log::info!(
+                    "Looking for a cargo workspace from {}",
+                    manifest_path.display()
+                );
+                let build_options = self
+                    .build_options
+                    .clone()
+                    .with_cancel_token(self.cancel_token.clone());
+                LocalSource::load_with_options(manifest_path, build_options).ok()
+            } else {
+                None
+            };
+            if let Some(local_source) = &local_source {
+                log::info!(
+                    "Found cargo workspace at {}",
+                    local_source.manifest_path().display()
+                );
+                if self.rebuild_workspace_docs {
+                    log::info!("Sources changed, rebuilding workspace documentation");
+                    if let Err(e) = local_source.rebuild_workspace_docs() {
+                        log::error!("Failed to rebuild documentation: {e}");
+                    }
+                }
+            }
+
+            // After the workspace, whose toolchain the std docs come from
             log::info!("Checking for std documentation from rustup");
-            let std_source = StdSource::from_rustup();
+            let std_source = StdSource::from_toolchain(std_toolchain(
+                &self.build_options,
+                local_source.as_ref(),
+            ));
             if let Some(std_source) = &std_source {
                 log::info!(
                     "Found std docs for {} at {}",
@@ -59,17 +239,6 @@ impl Request {
                 );
             }
 
-            log::info!(
-                "Looking for a cargo workspace from {}",
-                manifest_path.display()
-            );
-            let local_source = LocalSource::load(manifest_path).ok();
-            if let Some(local_source) = &local_source {
-                log::info!(
-                    "Found cargo workspace at {}",
-                    local_source.manifest_path().display()
-                );
-            }
             log::info!("Building a docs.rs client");
             let docsrs_source = DocsRsSource::from_default_cache();
             if let Some(docsrs_source) = &docsrs_source {
@@ -79,11 +248,49 @@ impl Request {
                 );
             }
 
-            Navigator::default()
+            let navigator = Navigator::default()
+                .with_cancel_token(self.cancel_token.clone())
                 .with_std_source(std_source)
                 .with_local_source(local_source)
+                .with_json_file_source(json_file_source)
                 .with_docsrs_source(docsrs_source)
+                .with_ranking(self.ranking);
+
+            // Crates are sized by what they took before, so that preloading stays within the
+            // bound `is_over_memory_budget` relies on
+            let mut preload_budget = self.memory_budget.map_or(usize::MAX, |budget| budget / 2);
+            for (name, version, estimated_memory) in &self.preload {
+                let Some(remaining) = preload_budget.checked_sub(*estimated_memory) else {
+                    continue;
+                };
+                preload_budget = remaining;
+                let version_req = version.as_ref().map_or(VersionReq::STAR, |version| {
+                    parse_version_req(&version.to_string())
+                });
+                log::info!("Reloading {name}");
+                navigator.load_crate(name, &version_req);
+            }
+
+            navigator
+        });
+    }
+
+    /// Whether an item exists on the platform being documented: the `--target` triple, or the
+    /// host
+    ///
+    /// Items without `#[doc(cfg(…))]`, or whose platform can't be determined, count as available.
+    pub(crate) fn is_available(&self, item: DocRef<'_, Item>) -> bool {
+        let target_cfg = self.target_cfg.get_or_init(|| {
+            let target = self
+                .local_source()
+                .and_then(|local_source| local_source.build_options().target());
+            TargetCfg::detect(target)
         });
+
+        match (target_cfg, item.cfg()) {
+            (Some(target_cfg), Some(cfg)) => target_cfg.is_available(&cfg),
+            _ => true,
+        }
     }
 
     /// Get the formatting context
@@ -91,3 +298,15 @@ impl Request {
         &self.format_context
     }
 }
+
+/// The toolchain std docs come from: the one the workspace is documented with, or outside of a
+/// workspace, the one chosen with `--toolchain`
+pub(crate) fn std_toolchain<'a>(
+    build_options: &'a BuildOptions,
+    local_source: Option<&'a LocalSource>,
+) -> &'a str {
+    local_source.map_or_else(
+        || build_options.toolchain().unwrap_or(DEFAULT_TOOLCHAIN),
+        LocalSource::toolchain,
+    )
+}
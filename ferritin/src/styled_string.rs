@@ -1,7 +1,16 @@
+//! The document model that commands build and renderers draw
+//!
+//! Documents serialize with serde, so they can be cached, sent over the daemon protocol, or
+//! drawn by renderers outside ferritin. A [`DocRef`] only means something alongside the navigator
+//! it borrows from, so links to items serialize as their discriminated path
+//! (`std::vec::struct@Vec`) and deserialize as [`TuiAction::NavigateToPath`], resolved when
+//! followed.
+
 use ferritin_common::DocRef;
 use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
 use rustdoc_types::Item;
-use std::borrow::Cow;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{borrow::Cow, collections::HashMap};
 
 /// Interactive action that can be attached to a span
 #[derive(Debug, Clone)]
@@ -11,6 +20,8 @@ pub enum TuiAction<'a> {
         doc_ref: DocRef<'a, Item>,
         /// Optional docs.rs URL for renderers that need it (e.g., TTY mode OSC8 links)
         url: Option<Cow<'a, str>>,
+        /// Optional heading anchor to scroll to once the item is shown (e.g. `guarantees`)
+        anchor: Option<Cow<'a, str>>,
     },
     /// Navigate to an item by path (resolves lazily)
     /// Uses Cow to borrow from JSON when possible, avoiding allocation
@@ -18,6 +29,8 @@ pub enum TuiAction<'a> {
         path: Cow<'a, str>,
         /// Optional docs.rs URL for renderers that need it (e.g., TTY mode OSC8 links)
         url: Option<Cow<'a, str>>,
+        /// Optional heading anchor to scroll to once the item is shown (e.g. `guarantees`)
+        anchor: Option<Cow<'a, str>>,
     },
     /// Expand a truncated block (identified by index path into document tree)
     ExpandBlock(NodePath),
@@ -33,18 +46,22 @@ impl<'a> TuiAction<'a> {
     /// Returns Cow to avoid allocations when URL is already borrowed.
     pub fn url(&self) -> Option<Cow<'a, str>> {
         match self {
-            TuiAction::Navigate { doc_ref, url } => {
+            TuiAction::Navigate {
+                doc_ref,
+                url,
+                anchor,
+            } => {
                 url.clone().or_else(|| {
                     // Generate URL from DocRef
-                    Some(Cow::Owned(crate::generate_docsrs_url::generate_docsrs_url(
-                        *doc_ref,
-                    )))
+                    let url = crate::generate_docsrs_url::generate_docsrs_url(*doc_ref);
+                    Some(Cow::Owned(with_fragment(url, anchor.as_deref())))
                 })
             }
-            TuiAction::NavigateToPath { path, url } => {
+            TuiAction::NavigateToPath { path, url, anchor } => {
                 url.clone().or_else(|| {
                     // Generate a heuristic URL from the path
-                    Some(Cow::Owned(generate_url_from_path(path)))
+                    let url = generate_url_from_path(path);
+                    Some(Cow::Owned(with_fragment(url, anchor.as_deref())))
                 })
             }
             TuiAction::ExpandBlock(_) => None,
@@ -52,6 +69,117 @@ impl<'a> TuiAction<'a> {
             TuiAction::SelectTheme(_) => None,
         }
     }
+
+    /// The heading anchor a navigation action should scroll to, if any
+    pub fn anchor(&self) -> Option<&str> {
+        match self {
+            TuiAction::Navigate { anchor, .. } | TuiAction::NavigateToPath { anchor, .. } => {
+                anchor.as_deref()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// How a [`TuiAction`] is serialized, with items referred to by path
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum SerializedAction<'a> {
+    Navigate {
+        path: Cow<'a, str>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        url: Option<Cow<'a, str>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        anchor: Option<Cow<'a, str>>,
+    },
+    ExpandBlock {
+        node_path: NodePath,
+    },
+    OpenUrl {
+        url: Cow<'a, str>,
+    },
+    SelectTheme {
+        theme: Cow<'a, str>,
+    },
+}
+
+impl Serialize for TuiAction<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let action = match self {
+            TuiAction::Navigate {
+                doc_ref, anchor, ..
+            } => SerializedAction::Navigate {
+                path: Cow::Owned(stable_path(*doc_ref)),
+                url: self.url(),
+                anchor: anchor.clone(),
+            },
+            TuiAction::NavigateToPath { path, anchor, .. } => SerializedAction::Navigate {
+                path: path.clone(),
+                url: self.url(),
+                anchor: anchor.clone(),
+            },
+            TuiAction::ExpandBlock(node_path) => SerializedAction::ExpandBlock {
+                node_path: *node_path,
+            },
+            TuiAction::OpenUrl(url) => SerializedAction::OpenUrl { url: url.clone() },
+            TuiAction::SelectTheme(theme) => SerializedAction::SelectTheme {
+                theme: theme.clone(),
+            },
+        };
+        action.serialize(serializer)
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for TuiAction<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match SerializedAction::deserialize(deserializer)? {
+            SerializedAction::Navigate { path, url, anchor } => {
+                TuiAction::NavigateToPath { path, url, anchor }
+            }
+            SerializedAction::ExpandBlock { node_path } => TuiAction::ExpandBlock(node_path),
+            SerializedAction::OpenUrl { url } => TuiAction::OpenUrl(url),
+            SerializedAction::SelectTheme { theme } => TuiAction::SelectTheme(theme),
+        })
+    }
+}
+
+/// A path that resolves back to the same item, for referring to it outside this process
+fn stable_path(doc_ref: DocRef<'_, Item>) -> String {
+    doc_ref
+        .discriminated_path()
+        .or_else(|| doc_ref.path().map(|path| path.to_string()))
+        .unwrap_or_else(|| {
+            let crate_name = doc_ref.crate_docs().name();
+            match doc_ref.name() {
+                Some(name) => format!("{crate_name}::{name}"),
+                None => crate_name.to_string(),
+            }
+        })
+}
+
+/// Append a `#fragment` to a generated URL
+fn with_fragment(url: String, anchor: Option<&str>) -> String {
+    match anchor {
+        Some(anchor) => format!("{url}#{anchor}"),
+        None => url,
+    }
+}
+
+/// Compute the anchor for a heading the way rustdoc does: lowercase alphanumerics, with
+/// whitespace turned into `-` and other punctuation dropped
+pub(crate) fn heading_anchor(spans: &[Span<'_>]) -> String {
+    spans
+        .iter()
+        .flat_map(|span| span.text.chars())
+        .filter_map(|c| match c {
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            c if c.is_whitespace() => Some('-'),
+            _ => None,
+        })
+        .flat_map(char::to_lowercase)
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
 }
 
 /// Generate a heuristic docs.rs URL from a path string
@@ -76,6 +204,16 @@ fn generate_url_from_path(path: &str) -> String {
         return format!("{}/{}/index.html", base, crate_name);
     }
 
+    // Primitives and keywords have fixed pages, e.g. `std::prim@str` → `std/primitive.str.html`
+    if let [_, last, ..] = parts[..] {
+        if let Some(name) = last.strip_prefix("prim@") {
+            return format!("{}/{}/primitive.{}.html", base, crate_name, name);
+        }
+        if let Some(name) = last.strip_prefix("keyword@") {
+            return format!("{}/{}/keyword.{}.html", base, crate_name, name);
+        }
+    }
+
     // Generate search URL for the full path
     let module_path = if parts.len() > 2 {
         parts[1..parts.len() - 1].join("/")
@@ -99,7 +237,7 @@ fn generate_url_from_path(path: &str) -> String {
 
 /// Path to a node in the document tree using indices
 /// Example: [2, 3, 1] means nodes[2].children[3].children[1]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NodePath {
     indices: [u16; 8], // 8 levels deep should be enough
     len: u8,
@@ -135,13 +273,14 @@ pub enum LinkTarget<'a> {
 }
 
 /// A semantic content tree for Rust documentation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Document<'a> {
     pub nodes: Vec<DocumentNode<'a>>,
 }
 
 /// Condition for when to show content (used by Conditional node)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ShowWhen {
     /// Always show (default)
     Always,
@@ -152,7 +291,8 @@ pub enum ShowWhen {
 }
 
 /// A node in the documentation tree
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum DocumentNode<'a> {
     /// Block-level paragraph
     Paragraph { spans: Vec<Span<'a>> },
@@ -176,6 +316,11 @@ pub enum DocumentNode<'a> {
     CodeBlock {
         lang: Option<Cow<'a, str>>,
         code: Cow<'a, str>,
+        /// Line number of the first line, for blocks rendered with a line-number gutter
+        first_line: Option<usize>,
+        /// Navigation targets for identifiers that appear in the code
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        links: HashMap<Cow<'a, str>, TuiAction<'a>>,
     },
 
     /// Generated code with pre-styled spans (for signatures, etc.)
@@ -206,29 +351,65 @@ pub enum DocumentNode<'a> {
         show_when: ShowWhen,
         nodes: Vec<DocumentNode<'a>>,
     },
+
+    /// Footnote definitions, gathered at the end of the documentation that references them
+    Footnotes { notes: Vec<Footnote<'a>> },
+}
+
+/// Split code into runs of identifier and non-identifier text, so that renderers can attach
+/// code block links to identifiers. Each run is paired with whether it is an identifier.
+pub(crate) fn split_identifiers(text: &str) -> Vec<(&str, bool)> {
+    let mut runs = vec![];
+    let mut start = 0;
+    let mut in_ident = false;
+
+    for (index, c) in text.char_indices() {
+        let is_ident = c.is_alphanumeric() || c == '_';
+        if index > start && is_ident != in_ident {
+            runs.push((&text[start..index], in_ident));
+            start = index;
+        }
+        in_ident = is_ident;
+    }
+
+    if start < text.len() {
+        runs.push((&text[start..], in_ident));
+    }
+
+    runs
 }
 
 /// A single cell in a table
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableCell<'a> {
     pub spans: Vec<Span<'a>>,
 }
 
 /// A single item in a list
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListItem<'a> {
     pub content: Vec<DocumentNode<'a>>,
 }
 
+/// A footnote definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Footnote<'a> {
+    /// The number it's referenced by, counting references in the order they appear
+    pub number: usize,
+    pub content: Vec<DocumentNode<'a>>,
+}
+
 /// Heading level for semantic structure
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum HeadingLevel {
     Title,   // Top-level item name: "Item: Vec"
     Section, // Section header: "Fields:", "Methods:"
 }
 
 /// Truncation level hint for renderers
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TruncationLevel {
     /// Single-line summary (for listings)
     SingleLine,
@@ -239,10 +420,11 @@ pub enum TruncationLevel {
 }
 
 /// A styled text span with semantic meaning
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Span<'a> {
     pub text: Cow<'a, str>,
     pub style: SpanStyle,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub action: Option<TuiAction<'a>>,
 }
 
@@ -253,7 +435,8 @@ impl<'a> Span<'a> {
 }
 
 /// Semantic styling categories for Rust code elements
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SpanStyle {
     // Rust code semantic elements
     Keyword,      // struct, enum, pub, fn, const, etc.
@@ -274,9 +457,11 @@ pub enum SpanStyle {
     InlineCode,     // Generic inline code from markdown backticks
 
     // Markdown semantic styles
-    Strong,        // **bold** - semantic emphasis
-    Emphasis,      // *italic* - semantic emphasis
-    Strikethrough, // ~~strikethrough~~ - from GFM
+    Strong,            // **bold** - semantic emphasis
+    Emphasis,          // *italic* - semantic emphasis
+    Strikethrough,     // ~~strikethrough~~ - from GFM
+    FootnoteReference, // [1] - a reference to a footnote, by number
+    Image,             // ![alt](url) - an image's alt text, linked to the image
 }
 
 impl<'a> Span<'a> {
@@ -402,6 +587,25 @@ impl<'a> Span<'a> {
         }
     }
 
+    /// A reference to footnote `number`, shown as `[number]`
+    pub fn footnote_reference(number: usize) -> Self {
+        Self {
+            text: format!("[{number}]").into(),
+            style: SpanStyle::FootnoteReference,
+            action: None,
+        }
+    }
+
+    /// An image, shown as its alt text with a link to the image
+    pub fn image(alt: impl Into<Cow<'a, str>>, url: impl Into<Cow<'a, str>>) -> Self {
+        let alt = alt.into();
+        Self {
+            text: if alt.is_empty() { "image".into() } else { alt },
+            style: SpanStyle::Image,
+            action: Some(TuiAction::OpenUrl(url.into())),
+        }
+    }
+
     /// Chainable method to attach an action to this span
     pub fn with_action(mut self, action: TuiAction<'a>) -> Self {
         self.action = Some(action);
@@ -414,6 +618,7 @@ impl<'a> Span<'a> {
             self.action = Some(TuiAction::Navigate {
                 doc_ref: target,
                 url: None,
+                anchor: None,
             });
         }
         self
@@ -424,6 +629,7 @@ impl<'a> Span<'a> {
         self.action = Some(TuiAction::NavigateToPath {
             path: path.into(),
             url: None,
+            anchor: None,
         });
         self
     }
@@ -437,6 +643,14 @@ impl<'a> Document<'a> {
     pub fn with_nodes(nodes: Vec<DocumentNode<'a>>) -> Self {
         Self { nodes }
     }
+
+    /// Give every truncated block in the document the same level, for output that can't be
+    /// expanded interactively
+    pub fn set_truncation(&mut self, level: TruncationLevel) {
+        for node in &mut self.nodes {
+            node.set_truncation(level);
+        }
+    }
 }
 
 impl<'a> Default for Document<'a> {
@@ -465,6 +679,38 @@ impl<'a> ListItem<'a> {
 }
 
 impl<'a> DocumentNode<'a> {
+    fn set_truncation(&mut self, truncation: TruncationLevel) {
+        let children = match self {
+            DocumentNode::TruncatedBlock { nodes, level } => {
+                *level = truncation;
+                nodes
+            }
+            DocumentNode::Section { nodes, .. }
+            | DocumentNode::BlockQuote { nodes }
+            | DocumentNode::Conditional { nodes, .. } => nodes,
+            DocumentNode::List { items } => {
+                for item in items {
+                    for node in &mut item.content {
+                        node.set_truncation(truncation);
+                    }
+                }
+                return;
+            }
+            DocumentNode::Footnotes { notes } => {
+                for note in notes {
+                    for node in &mut note.content {
+                        node.set_truncation(truncation);
+                    }
+                }
+                return;
+            }
+            _ => return,
+        };
+        for node in children {
+            node.set_truncation(truncation);
+        }
+    }
+
     /// Convenience constructor for a paragraph
     pub fn paragraph(spans: Vec<Span<'a>>) -> Self {
         DocumentNode::Paragraph { spans }
@@ -501,6 +747,23 @@ impl<'a> DocumentNode<'a> {
         DocumentNode::CodeBlock {
             lang: lang.map(Into::into),
             code: code.into(),
+            first_line: None,
+            links: HashMap::new(),
+        }
+    }
+
+    /// Convenience constructor for a source listing, shown with line numbers starting at
+    /// `first_line`
+    pub fn source_code(
+        lang: Option<impl Into<Cow<'a, str>>>,
+        code: impl Into<Cow<'a, str>>,
+        first_line: usize,
+    ) -> Self {
+        DocumentNode::CodeBlock {
+            lang: lang.map(Into::into),
+            code: code.into(),
+            first_line: Some(first_line),
+            links: HashMap::new(),
         }
     }
 
@@ -524,6 +787,11 @@ impl<'a> DocumentNode<'a> {
         DocumentNode::Table { header, rows }
     }
 
+    /// Convenience constructor for footnote definitions
+    pub fn footnotes(notes: Vec<Footnote<'a>>) -> Self {
+        DocumentNode::Footnotes { notes }
+    }
+
     /// Convenience constructor for a truncated block
     pub fn truncated_block(nodes: Vec<DocumentNode<'a>>, level: TruncationLevel) -> Self {
         DocumentNode::TruncatedBlock { nodes, level }
@@ -567,6 +835,66 @@ const _: () = {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_serde_round_trip() {
+        let document = Document::with_nodes(vec![
+            DocumentNode::heading(
+                HeadingLevel::Title,
+                vec![Span::type_name("Vec").with_path("std::vec::Vec")],
+            ),
+            DocumentNode::horizontal_rule(),
+        ]);
+
+        let json = serde_json::to_value(&document).unwrap();
+        assert_eq!(json["nodes"][0]["type"], "heading");
+        assert_eq!(json["nodes"][0]["level"], "title");
+        let span = &json["nodes"][0]["spans"][0];
+        assert_eq!(span["style"], "type_name");
+        assert_eq!(span["action"]["action"], "navigate");
+        assert_eq!(span["action"]["path"], "std::vec::Vec");
+        assert_eq!(json["nodes"][1]["type"], "horizontal_rule");
+
+        let document: Document<'static> = serde_json::from_value(json).unwrap();
+        let DocumentNode::Heading { spans, .. } = &document.nodes[0] else {
+            panic!("Expected heading node");
+        };
+        assert!(matches!(
+            &spans[0].action,
+            Some(TuiAction::NavigateToPath { path, .. }) if path == "std::vec::Vec"
+        ));
+    }
+
+    #[test]
+    fn test_set_truncation() {
+        let mut document =
+            Document::with_nodes(vec![DocumentNode::list(vec![ListItem::new(vec![
+                DocumentNode::truncated_block(
+                    vec![DocumentNode::truncated_block(
+                        vec![DocumentNode::paragraph(vec![Span::plain("nested")])],
+                        TruncationLevel::Brief,
+                    )],
+                    TruncationLevel::SingleLine,
+                ),
+            ])])]);
+
+        document.set_truncation(TruncationLevel::Full);
+
+        let DocumentNode::List { items } = &document.nodes[0] else {
+            panic!("Expected list node");
+        };
+        let DocumentNode::TruncatedBlock { nodes, level } = &items[0].content[0] else {
+            panic!("Expected truncated block");
+        };
+        assert_eq!(*level, TruncationLevel::Full);
+        assert!(matches!(
+            &nodes[0],
+            DocumentNode::TruncatedBlock {
+                level: TruncationLevel::Full,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn test_span_creation() {
         let span = Span::keyword("struct");
@@ -625,11 +953,41 @@ mod tests {
     fn test_code_block() {
         let code = DocumentNode::code_block(Some("rust".to_string()), "fn main() {}".to_string());
 
-        if let DocumentNode::CodeBlock { lang, code } = code {
+        if let DocumentNode::CodeBlock { lang, code, .. } = code {
             assert_eq!(lang, Some("rust".into()));
             assert_eq!(code, "fn main() {}");
         } else {
             panic!("Expected code block");
         }
     }
+
+    #[test]
+    fn test_split_identifiers() {
+        let runs = split_identifiers("let v: Vec<u8> = vec![];");
+        assert_eq!(
+            runs,
+            vec![
+                ("let", true),
+                (" ", false),
+                ("v", true),
+                (": ", false),
+                ("Vec", true),
+                ("<", false),
+                ("u8", true),
+                ("> = ", false),
+                ("vec", true),
+                ("![];", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heading_anchor() {
+        let spans = vec![
+            Span::plain("Capacity and "),
+            Span::inline_code("reallocation"),
+        ];
+        assert_eq!(heading_anchor(&spans), "capacity-and-reallocation");
+        assert_eq!(heading_anchor(&[Span::plain("What's new?")]), "whats-new");
+    }
 }
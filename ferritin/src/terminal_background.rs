@@ -0,0 +1,157 @@
+//! Detect whether the terminal has a light or dark background, to pick a readable default theme,
+//! and query the terminal for other capabilities
+
+use std::io::IsTerminal;
+
+/// Brightness of the terminal's background
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Background {
+    Light,
+    Dark,
+}
+
+impl Background {
+    /// Detect the terminal background from `COLORFGBG`, falling back to asking the terminal
+    ///
+    /// Returns `None` when stdout isn't a terminal or the terminal doesn't say.
+    pub(crate) fn detect() -> Option<Self> {
+        if !std::io::stdout().is_terminal() {
+            return None;
+        }
+
+        std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|value| Self::from_colorfgbg(&value))
+            .or_else(|| Self::from_osc11_reply(&query::reply("\x1b]11;?\x1b\\")?))
+    }
+
+    /// Parse `COLORFGBG`, set by some terminals as `fg;bg` or `fg;default;bg` ANSI color indices
+    fn from_colorfgbg(value: &str) -> Option<Self> {
+        let background: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+        Some(match background {
+            7 | 9..=15 => Background::Light,
+            _ => Background::Dark,
+        })
+    }
+
+    /// Parse a reply to an OSC 11 background color query, like `rgb:ffff/ffff/dddd`
+    fn from_osc11_reply(reply: &str) -> Option<Self> {
+        let rgb = reply.split_once("rgb:")?.1;
+        let mut channels = rgb.splitn(3, '/').map(|channel| {
+            let digits: String = channel
+                .chars()
+                .take_while(|c| c.is_ascii_hexdigit())
+                .collect();
+            let max = 16_u32.checked_pow(digits.len() as u32)? - 1;
+            let value = u32::from_str_radix(&digits, 16).ok()?;
+            Some(value as f32 / max as f32)
+        });
+        let (r, g, b) = (channels.next()??, channels.next()??, channels.next()??);
+
+        let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        Some(if luminance > 0.5 {
+            Background::Light
+        } else {
+            Background::Dark
+        })
+    }
+}
+
+/// Send `request`, an escape sequence query, to the terminal, and return its reply
+///
+/// Returns `None` when there's no terminal or it doesn't reply in time.
+pub(crate) fn query_terminal(request: &str) -> Option<String> {
+    query::reply(request)
+}
+
+#[cfg(unix)]
+mod query {
+    use crossterm::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled};
+    use std::{
+        fs::OpenOptions,
+        io::{Read, Write},
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    /// How long to wait for the terminal to answer before assuming it won't
+    const TIMEOUT: Duration = Duration::from_millis(100);
+
+    /// Send a query to the terminal
+    ///
+    /// The query is followed by a primary device attributes request, which every terminal
+    /// answers, so reading stops promptly even when the query is unsupported.
+    pub(super) fn reply(request: &str) -> Option<String> {
+        let mut tty = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")
+            .ok()?;
+        let mut reader = tty.try_clone().ok()?;
+
+        let was_raw = is_raw_mode_enabled().unwrap_or(false);
+        if !was_raw {
+            enable_raw_mode().ok()?;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let reply = tty
+            .write_all(format!("{request}\x1b[c").as_bytes())
+            .and_then(|()| tty.flush())
+            .ok()
+            .and_then(|()| {
+                thread::spawn(move || {
+                    let mut reply = Vec::new();
+                    let mut byte = [0];
+                    // The device attributes reply ends in `c`, after any reply to the query
+                    while reader.read_exact(&mut byte).is_ok() {
+                        reply.push(byte[0]);
+                        if byte[0] == b'c' && reply.contains(&b'[') {
+                            break;
+                        }
+                    }
+                    let _ = tx.send(reply);
+                });
+                rx.recv_timeout(TIMEOUT).ok()
+            });
+
+        if !was_raw {
+            let _ = disable_raw_mode();
+        }
+
+        Some(String::from_utf8_lossy(&reply?).into_owned())
+    }
+}
+
+#[cfg(not(unix))]
+mod query {
+    pub(super) fn reply(_request: &str) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_terminal_background_reports() {
+        assert_eq!(Background::from_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(
+            Background::from_colorfgbg("0;default;15"),
+            Some(Background::Light)
+        );
+        assert_eq!(Background::from_colorfgbg("default"), None);
+
+        assert_eq!(
+            Background::from_osc11_reply("\x1b]11;rgb:ffff/ffff/dddd\x1b\\\x1b[?62;c"),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            Background::from_osc11_reply("\x1b]11;rgb:30/34/46\x07"),
+            Some(Background::Dark)
+        );
+        assert_eq!(Background::from_osc11_reply("\x1b[?62;c"), None);
+    }
+}
@@ -140,6 +140,18 @@ test_all_modes!(
 
 test_all_modes!(get_generic_enum, Commands::get("crate::GenericEnum"));
 
+test_all_modes!(
+    get_const_generic_struct,
+    Commands::get("crate::ConstGenericBuffer")
+);
+
+test_all_modes!(
+    get_generic_associated_type,
+    Commands::get("crate::LendingIterator")
+);
+
+test_all_modes!(get_long_signature, Commands::get("crate::copy_transformed"));
+
 test_all_modes!(nonexistent_item, Commands::get("crate::DoesNotExist"));
 
 test_all_modes!(recursive_module_listing, Commands::get("crate").recursive());
@@ -156,6 +168,8 @@ test_all_modes!(
 
 test_all_modes!(list_crates, Commands::list());
 
+test_all_modes!(theme_sample, Commands::themes());
+
 test_all_modes!(search, Commands::search("trigger line-based truncation"));
 
 test_all_modes!(search_2, Commands::search("generic struct"));
@@ -181,3 +195,84 @@ test_all_modes!(
     get_markdown_test,
     Commands::get("fixture-crate::markdown_test")
 );
+
+test_all_modes!(
+    primitive_and_keyword_links,
+    Commands::get("crate::link_resolution_tests::parse_digits")
+);
+
+test_all_modes!(
+    url_for_method,
+    Commands::url("crate::TestStruct::get_field")
+);
+
+#[test]
+fn batch_answers_paths_and_searches() {
+    use crate::commands::batch::{self, BatchFormat};
+
+    let request = create_test_state();
+    let input = "crate::TestStruct\n\ngeneric struct\n";
+    let mut output = vec![];
+    let any_failed = batch::run(
+        &request,
+        input.as_bytes(),
+        &mut output,
+        BatchFormat::Json,
+        &RenderContext::new().with_output_mode(OutputMode::Plain),
+    )
+    .unwrap();
+    assert!(!any_failed);
+
+    let answers: Vec<serde_json::Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(answers.len(), 2);
+    assert_eq!(answers[0]["command"], "get");
+    assert_eq!(answers[0]["query"], "crate::TestStruct");
+    assert!(
+        answers[0]["output"]
+            .as_str()
+            .unwrap()
+            .contains("TestStruct")
+    );
+    assert_eq!(answers[1]["command"], "search");
+}
+
+#[test]
+fn exists_distinguishes_found_and_missing_items() {
+    use crate::commands::exists::{Existence, check};
+
+    let request = create_test_state();
+    assert_eq!(check(&request, "crate::TestStruct"), Existence::Found);
+    assert_eq!(check(&request, "crate::DoesNotExist"), Existence::Missing);
+    assert_eq!(check(&request, "no_such_crate::Thing"), Existence::Missing);
+    assert_eq!(Existence::Missing.exit_code(), 1);
+}
+
+#[test]
+fn renderer_renders_items_as_markdown() {
+    use crate::{OutputFormat, RenderOptions, Renderer};
+
+    let mut renderer = Renderer::new(
+        Navigator::default().with_local_source(LocalSource::load(&get_fixture_crate_path()).ok()),
+    )
+    .with_options(RenderOptions::default().with_format(OutputFormat::Markdown));
+    let item = renderer
+        .navigator()
+        .resolve_path("crate::TestStruct", &mut vec![])
+        .unwrap();
+    let output = renderer.render_item(item);
+    assert!(output.contains("```rust\n"));
+    assert!(output.contains("TestStruct"));
+    assert!(!output.contains('\x1b'));
+    assert!(!output.contains("Source:"));
+
+    renderer.set_options(renderer.options().clone().with_include_source(true));
+    let item = renderer
+        .navigator()
+        .resolve_path("crate::TestStruct", &mut vec![])
+        .unwrap();
+    assert!(renderer.render_item(item).contains("Source:"));
+}
@@ -1,16 +1,55 @@
 use clap::ValueEnum;
 
-/// Controls the verbosity level of documentation display
+/// Controls how much of each signature is shown
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
 pub(crate) enum Verbosity {
-    Minimal,
-    Brief,
+    /// Leave out where clauses and lifetime parameters
+    Short,
     #[default]
+    Normal,
+    /// Show everything, including the auto trait and blanket impls in full
     Full,
 }
 
 impl Verbosity {
+    pub(crate) fn is_short(self) -> bool {
+        matches!(self, Self::Short)
+    }
+
     pub(crate) fn is_full(self) -> bool {
         matches!(self, Self::Full)
     }
+
+    /// The next level, wrapping around, for cycling through them in interactive mode
+    pub(crate) fn next(self) -> Self {
+        match self {
+            Self::Short => Self::Normal,
+            Self::Normal => Self::Full,
+            Self::Full => Self::Short,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Short => "short",
+            Self::Normal => "normal",
+            Self::Full => "full",
+        }
+    }
+
+    pub(crate) fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Short,
+            2 => Self::Full,
+            _ => Self::Normal,
+        }
+    }
+
+    pub(crate) fn as_u8(self) -> u8 {
+        match self {
+            Self::Short => 0,
+            Self::Normal => 1,
+            Self::Full => 2,
+        }
+    }
 }
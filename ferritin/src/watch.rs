@@ -0,0 +1,111 @@
+//! Watch a workspace's sources for `--watch`, so documentation can be rebuilt as code is edited
+
+use crossbeam_channel::Receiver;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{path::Path, time::Duration};
+
+/// How long the sources must be quiet before a burst of changes (an editor saving several
+/// files, a `git checkout`) is considered finished
+const SETTLE_TIME: Duration = Duration::from_millis(300);
+
+/// Signals changes to Rust sources and manifests under a project root
+pub(crate) struct SourceWatcher {
+    // Kept alive for as long as changes should be reported
+    _watcher: RecommendedWatcher,
+    changes: Receiver<()>,
+}
+
+impl std::fmt::Debug for SourceWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceWatcher").finish_non_exhaustive()
+    }
+}
+
+impl SourceWatcher {
+    /// Watch `project_root` recursively, ignoring build output in `target_dir`
+    pub(crate) fn new(project_root: &Path, target_dir: &Path) -> notify::Result<Self> {
+        let (change_tx, changes) = crossbeam_channel::unbounded();
+        let target_dir = target_dir.to_path_buf();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event
+                && is_source_change(&event, &target_dir)
+            {
+                let _ = change_tx.send(());
+            }
+        })?;
+        watcher.watch(project_root, RecursiveMode::Recursive)?;
+        log::info!("Watching {} for changes", project_root.display());
+
+        Ok(Self {
+            _watcher: watcher,
+            changes,
+        })
+    }
+
+    /// Receives a message for every change; call [`Self::settle`] after the first
+    pub(crate) fn changes(&self) -> &Receiver<()> {
+        &self.changes
+    }
+
+    /// Wait until changes stop arriving
+    pub(crate) fn settle(&self) {
+        while self.changes.recv_timeout(SETTLE_TIME).is_ok() {}
+    }
+
+    /// Block until the sources change and settle, returning false if watching stopped
+    pub(crate) fn wait(&self) -> bool {
+        if self.changes.recv().is_err() {
+            return false;
+        }
+        self.settle();
+        true
+    }
+}
+
+fn is_source_change(event: &Event, target_dir: &Path) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event.paths.iter().any(|path| {
+        !path.starts_with(target_dir)
+            && (path.extension().is_some_and(|extension| extension == "rs")
+                || path.file_name().is_some_and(|name| name == "Cargo.toml"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, ModifyKind};
+    use std::path::PathBuf;
+
+    #[test]
+    fn only_source_changes_outside_target_count() {
+        let target_dir = PathBuf::from("/project/target");
+        let modified = |path: &str| {
+            Event::new(EventKind::Modify(ModifyKind::Any)).add_path(PathBuf::from(path))
+        };
+
+        assert!(is_source_change(
+            &modified("/project/src/lib.rs"),
+            &target_dir
+        ));
+        assert!(is_source_change(
+            &modified("/project/Cargo.toml"),
+            &target_dir
+        ));
+        assert!(!is_source_change(
+            &modified("/project/README.md"),
+            &target_dir
+        ));
+        assert!(!is_source_change(
+            &modified("/project/target/doc/project.json"),
+            &target_dir
+        ));
+        assert!(!is_source_change(
+            &Event::new(EventKind::Access(AccessKind::Any))
+                .add_path(PathBuf::from("/project/src/lib.rs")),
+            &target_dir
+        ));
+    }
+}
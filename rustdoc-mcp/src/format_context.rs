@@ -18,6 +18,8 @@ pub(crate) struct FormatContext {
     verbosity: Verbosity,
     /// Filter items by type
     filters: Vec<Filter>,
+    /// Whether to leave `#[doc(hidden)]` items out of listings
+    exclude_hidden: bool,
 }
 
 impl Default for FormatContext {
@@ -27,6 +29,7 @@ impl Default for FormatContext {
             recursive: false,
             verbosity: Verbosity::Brief,
             filters: Filter::VARIANTS.into(),
+            exclude_hidden: true,
         }
     }
 }
@@ -39,6 +42,7 @@ impl FormatContext {
             recursive: item.recursive(),
             verbosity: item.verbosity(),
             filters: item.filters().to_vec(),
+            exclude_hidden: item.exclude_hidden(),
         }
     }
 
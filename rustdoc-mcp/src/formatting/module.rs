@@ -33,6 +33,7 @@ impl Request {
     ) {
         for child in item.child_items() {
             if let Some(item_name) = child.name()
+                && !(context.exclude_hidden() && child.is_hidden())
                 && context.filter_match_kind(child.kind())
             {
                 let path = path.as_deref().map_or_else(
@@ -26,6 +26,7 @@ use strum::VariantArray;
 /// - `recursive` works with `filter` to recursively show only filtered item types
 /// - `include_source` adds source code snippets to any item that has them
 /// - `verbosity` controls detail level for all output (minimal/brief/full)
+/// - `exclude_hidden` leaves `#[doc(hidden)]` items out of module listings, unless set to false
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema, Args)]
 #[serde(rename = "get_item")]
 pub struct GetItem {
@@ -53,6 +54,11 @@ pub struct GetItem {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[arg(long, value_enum)]
     pub verbosity: Option<Verbosity>,
+
+    /// Leave `#[doc(hidden)]` items out of module listings (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub exclude_hidden: Option<bool>,
 }
 
 impl GetItem {
@@ -71,6 +77,11 @@ impl GetItem {
         self.verbosity.unwrap_or_default()
     }
 
+    /// Get exclude_hidden with default
+    pub(crate) fn exclude_hidden(&self) -> bool {
+        self.exclude_hidden.unwrap_or(true)
+    }
+
     /// Get filters with default
     pub(crate) fn filters(&self) -> &[Filter] {
         self.filter.as_deref().unwrap_or(Filter::VARIANTS)
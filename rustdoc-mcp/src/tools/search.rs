@@ -110,8 +110,12 @@ impl Tool<RustdocTools> for Search {
                     prev_score = result.score;
                     let path = path.join("::");
                     let normalized_score = 100.0 * result.score / top_score;
+                    let alias = item
+                        .alias_matching(&self.query)
+                        .map(|alias| format!(" (alias: {alias})"))
+                        .unwrap_or_default();
                     output.write_fmt(format_args!(
-                        "• {path} ({:?}) - score: {normalized_score:.0}\n",
+                        "• {path}{alias} ({:?}) - score: {normalized_score:.0}\n",
                         item.kind()
                     ));
 
@@ -126,6 +126,7 @@ impl TestTrait for TestStruct {
 }
 
 /// A public function
+#[doc(alias = "transmogrify")]
 pub fn test_function(input: &str) -> String {
     format!("processed: {}", input)
 }
@@ -139,6 +140,21 @@ where
     transform(data)
 }
 
+/// A function whose signature is too long for one line
+pub fn copy_transformed<K, F>(
+    source: &mut impl std::io::Read,
+    destination: &mut impl std::io::Write,
+    transform: F,
+    buffer_size: usize,
+) -> std::io::Result<u64>
+where
+    K: Clone + Send + Sync + std::fmt::Debug + std::fmt::Display + std::hash::Hash + Ord + 'static,
+    F: FnMut(&[u8]) -> Vec<u8>,
+{
+    let _ = (source, destination, transform, buffer_size);
+    Ok(0)
+}
+
 /// An async function
 pub async fn async_function(delay: u64) -> Result<String, Box<dyn std::error::Error>> {
     Ok(format!("waited {delay} ms"))
@@ -260,6 +276,37 @@ where
         U: Into<T>;
 }
 
+/// A fixed-capacity buffer, for testing const generics with defaults
+pub struct ConstGenericBuffer<T, const N: usize = 4> {
+    items: [Option<T>; N],
+}
+
+impl<T, const N: usize> ConstGenericBuffer<T, N> {
+    /// Copy the items into a buffer of another capacity
+    pub fn resize<const M: usize>(self) -> ConstGenericBuffer<T, M> {
+        let mut items = self.items.into_iter();
+        ConstGenericBuffer {
+            items: std::array::from_fn(|_| items.next().flatten()),
+        }
+    }
+
+    /// Extend the buffer from anything iterable
+    pub fn extend_from(&mut self, items: impl IntoIterator<Item = T>) {
+        let _ = items;
+    }
+}
+
+/// A lending iterator, for testing generic associated types
+pub trait LendingIterator {
+    /// The item, borrowed from the iterator
+    type Item<'a>: std::fmt::Debug
+    where
+        Self: 'a;
+
+    /// Advance the iterator
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+}
+
 /// Module for testing intra-doc link resolution
 pub mod link_resolution_tests {
     pub use super::TestStruct as RenamedTestStruct;
@@ -287,6 +334,14 @@ pub mod link_resolution_tests {
         }
     }
 
+    /// Reads a [`u32`] from a [`str`], with a [`match`] on each digit
+    pub fn parse_digits(input: &str) -> u32 {
+        input.bytes().fold(0, |value, byte| match byte {
+            b'0'..=b'9' => value * 10 + u32::from(byte - b'0'),
+            _ => value,
+        })
+    }
+
     /// Nested module for testing scoped resolution
     pub mod nested {
         pub use super::super::TestTrait;